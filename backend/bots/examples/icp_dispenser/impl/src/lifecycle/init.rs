@@ -1,4 +1,5 @@
 use crate::lifecycle::{init_env, init_state};
+use crate::memory::{get_log_overflow_data_memory, get_log_overflow_index_memory};
 use crate::Data;
 use canister_tracing_macros::trace;
 use ic_cdk_macros::init;
@@ -10,6 +11,7 @@ use utils::env::Environment;
 #[trace]
 fn init(args: Args) {
     canister_logger::init(args.test_mode);
+    canister_logger::init_log_overflow(get_log_overflow_index_memory(), get_log_overflow_data_memory());
 
     let env = init_env();
 
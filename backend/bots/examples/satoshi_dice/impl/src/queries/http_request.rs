@@ -1,7 +1,8 @@
 use crate::{read_state, RuntimeState};
+use canister_logger::LogQuery;
 use http_request::{build_json_response, encode_logs, extract_route, get_document, Route};
 use ic_cdk_macros::query;
-use types::{HttpRequest, HttpResponse, TimestampMillis};
+use types::{HttpRequest, HttpResponse};
 
 #[query]
 fn http_request(request: HttpRequest) -> HttpResponse {
@@ -9,12 +10,12 @@ fn http_request(request: HttpRequest) -> HttpResponse {
         get_document(requested_avatar_id, &state.data.avatar, "avatar")
     }
 
-    fn get_logs_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_logs(), since.unwrap_or(0))
+    fn get_logs_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::query_logs(&query))
     }
 
-    fn get_traces_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_traces(), since.unwrap_or(0))
+    fn get_traces_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::query_traces(&query))
     }
 
     fn get_metrics_impl(state: &RuntimeState) -> HttpResponse {
@@ -29,8 +30,8 @@ fn http_request(request: HttpRequest) -> HttpResponse {
 
     match extract_route(&request.url) {
         Route::Avatar(requested_avatar_id) => read_state(|state| get_avatar_impl(requested_avatar_id, state)),
-        Route::Logs(since) => get_logs_impl(since),
-        Route::Traces(since) => get_traces_impl(since),
+        Route::Logs(query) => get_logs_impl(query),
+        Route::Traces(query) => get_traces_impl(query),
         Route::Metrics => read_state(get_metrics_impl),
         Route::Other(path, _) if path == "admins" => read_state(get_admins),
         _ => HttpResponse::not_found(),
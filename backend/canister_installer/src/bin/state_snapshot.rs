@@ -0,0 +1,57 @@
+use canister_agent_utils::get_dfx_identity;
+use canister_installer::{export_state_snapshot, restore_state_snapshot};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use types::CanisterId;
+
+#[tokio::main]
+async fn main() {
+    let opts = Opts::parse();
+    let identity = get_dfx_identity(&opts.controller);
+
+    match opts.command {
+        Command::Export { canister_id, output } => {
+            export_state_snapshot(identity, opts.url, opts.group_index, canister_id, output).await
+        }
+        Command::Restore { canister_id, snapshot } => {
+            restore_state_snapshot(identity, opts.url, opts.group_index, canister_id, snapshot).await
+        }
+    }
+}
+
+#[derive(Parser)]
+struct Opts {
+    #[arg(long)]
+    url: String,
+
+    #[arg(long)]
+    controller: String,
+
+    #[arg(long)]
+    group_index: CanisterId,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Export the state of a group or community canister to a file
+    Export {
+        #[arg(long)]
+        canister_id: CanisterId,
+
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Restore a previously exported state. The canister is reinstalled, wiping its current state,
+    /// before the snapshot is restored into it, which is only allowed within an hour of the
+    /// canister having been exported.
+    Restore {
+        #[arg(long)]
+        canister_id: CanisterId,
+
+        #[arg(long)]
+        snapshot: PathBuf,
+    },
+}
@@ -4,14 +4,34 @@ use ic_agent::{Agent, Identity};
 use ic_utils::interfaces::ManagementCanister;
 use types::{BuildVersion, CanisterWasm, Cycles};
 
+mod seed;
+mod state_snapshots;
+
+pub use seed::{seed_fixture_data, SeedConfig};
+pub use state_snapshots::{export_state_snapshot, restore_state_snapshot};
+
 const T: Cycles = 1_000_000_000_000;
 
-pub async fn install_service_canisters(identity: Box<dyn Identity>, url: String, canister_ids: CanisterIds, test_mode: bool) {
+pub async fn install_service_canisters(
+    identity: Box<dyn Identity>,
+    url: String,
+    canister_ids: CanisterIds,
+    test_mode: bool,
+    skip_internet_identity_check: bool,
+) {
     let principal = identity.sender().unwrap();
     let agent = build_ic_agent(url, identity).await;
     let management_canister = ManagementCanister::create(&agent);
 
-    install_service_canisters_impl(principal, &canister_ids, &agent, &management_canister, test_mode).await;
+    install_service_canisters_impl(
+        principal,
+        &canister_ids,
+        &agent,
+        &management_canister,
+        test_mode,
+        skip_internet_identity_check,
+    )
+    .await;
 }
 
 async fn install_service_canisters_impl(
@@ -20,6 +40,7 @@ async fn install_service_canisters_impl(
     agent: &Agent,
     management_canister: &ManagementCanister<'_>,
     test_mode: bool,
+    skip_internet_identity_check: bool,
 ) {
     let controllers = vec![principal];
     futures::future::join_all(vec![
@@ -61,11 +82,14 @@ async fn install_service_canisters_impl(
         group_index_canister_id: canister_ids.group_index,
         notifications_index_canister_id: canister_ids.notifications_index,
         proposals_bot_canister_id: canister_ids.proposals_bot,
+        online_users_canister_id: canister_ids.online_users,
         storage_index_canister_id: canister_ids.storage_index,
         cycles_dispenser_canister_id: canister_ids.cycles_dispenser,
         internet_identity_canister_id: canister_ids.nns_internet_identity,
+        registry_canister_id: canister_ids.registry,
         wasm_version: version,
         test_mode,
+        skip_internet_identity_check,
     };
 
     let group_index_canister_wasm = get_canister_wasm(CanisterName::GroupIndex, version);
@@ -1,5 +1,5 @@
 use canister_agent_utils::{get_dfx_identity, CanisterIds};
-use canister_installer::install_service_canisters;
+use canister_installer::{install_service_canisters, seed_fixture_data, SeedConfig};
 use clap::Parser;
 use types::CanisterId;
 
@@ -29,9 +29,41 @@ async fn main() {
         nns_sns_wasm: opts.nns_sns_wasm,
     };
 
+    if opts.seed.is_some() && !opts.test_mode {
+        panic!("Fixture data can only be seeded in test mode");
+    }
+
+    if opts.skip_internet_identity_check && !opts.test_mode {
+        panic!("The Internet Identity check can only be skipped in test mode");
+    }
+
+    if opts.seed.is_some() && !opts.skip_internet_identity_check {
+        panic!("Seeding fixture data requires --skip-internet-identity-check");
+    }
+
     let identity = get_dfx_identity(&opts.controller);
 
-    install_service_canisters(identity, opts.url, canister_ids, opts.test_mode).await;
+    install_service_canisters(
+        identity,
+        opts.url.clone(),
+        canister_ids.clone(),
+        opts.test_mode,
+        opts.skip_internet_identity_check,
+    )
+    .await;
+
+    if let Some(seed) = opts.seed {
+        let config = SeedConfig {
+            seed,
+            users: opts.seed_users,
+            groups: opts.seed_groups,
+            communities: opts.seed_communities,
+            channels_per_community: opts.seed_channels_per_community,
+            messages_per_chat: opts.seed_messages_per_chat,
+        };
+
+        seed_fixture_data(get_dfx_identity(&opts.controller), opts.url, canister_ids, config).await;
+    }
 }
 
 #[derive(Parser)]
@@ -42,6 +74,10 @@ struct Opts {
     #[arg(long, action = clap::ArgAction::Set)]
     test_mode: bool,
 
+    /// Only for local devnets. Lets users register with keys not derived from Internet Identity
+    #[arg(long)]
+    skip_internet_identity_check: bool,
+
     #[arg(long)]
     controller: String,
 
@@ -101,4 +137,23 @@ struct Opts {
 
     #[arg(long)]
     nns_sns_wasm: CanisterId,
+
+    /// If set, the canisters are seeded with fixture data generated deterministically from this seed
+    #[arg(long)]
+    seed: Option<u64>,
+
+    #[arg(long, default_value_t = 10)]
+    seed_users: usize,
+
+    #[arg(long, default_value_t = 3)]
+    seed_groups: usize,
+
+    #[arg(long, default_value_t = 2)]
+    seed_communities: usize,
+
+    #[arg(long, default_value_t = 2)]
+    seed_channels_per_community: usize,
+
+    #[arg(long, default_value_t = 20)]
+    seed_messages_per_chat: usize,
 }
@@ -0,0 +1,453 @@
+use candid::{Decode, Encode, Principal};
+use canister_agent_utils::{build_ic_agent, CanisterIds};
+use ic_agent::identity::BasicIdentity;
+use ic_agent::{Agent, Identity};
+use ic_ledger_types::{AccountIdentifier, Memo, Tokens, TransferArgs, TransferResult, DEFAULT_FEE, DEFAULT_SUBACCOUNT};
+use rand::distributions::Alphanumeric;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use std::time::Duration;
+use types::{
+    ChannelId, ChatId, CommunityId, Cryptocurrency, DiamondMembershipPlanDuration, FileContent, MessageContentInitial,
+    MessageId, MessageIndex, PollConfig, PollContent, PollVotes, Reaction, Rules, TextContent, TotalVotes, UserId,
+    VoteOperation,
+};
+
+// The DER prefix of an Ed25519 SubjectPublicKeyInfo, the raw 32 byte public key follows it
+const ED25519_DER_PREFIX: [u8; 12] = [48, 42, 48, 5, 6, 3, 43, 101, 112, 3, 33, 0];
+const REACTIONS: [&str; 5] = ["👍", "❤️", "😂", "🎉", "🔥"];
+const WORDS: [&str; 12] = [
+    "hello",
+    "chat",
+    "open",
+    "canister",
+    "cycles",
+    "diamond",
+    "proposal",
+    "community",
+    "channel",
+    "thread",
+    "token",
+    "poll",
+];
+
+pub struct SeedConfig {
+    pub seed: u64,
+    pub users: usize,
+    pub groups: usize,
+    pub communities: usize,
+    pub channels_per_community: usize,
+    pub messages_per_chat: usize,
+}
+
+struct SeededUser {
+    agent: Agent,
+    user_id: UserId,
+    username: String,
+}
+
+// Populates a freshly installed (test mode) deployment with users, groups, communities, channels
+// and a mix of messages, polls, reactions and files. The same seed always produces the same
+// principals, names and content, so a local devnet can be rebuilt into a known state.
+//
+// The first seeded user is upgraded to Diamond, using ICP sent from the controller, and creates
+// every group and community. The remaining users join them and fill them with content.
+pub async fn seed_fixture_data(controller: Box<dyn Identity>, url: String, canister_ids: CanisterIds, config: SeedConfig) {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let prefix: String = (0..4).map(|_| rng.sample(Alphanumeric) as char).collect();
+
+    let mut users = Vec::with_capacity(config.users);
+    for i in 0..config.users {
+        let username = format!("{prefix}_user{i}");
+        let user = register_user(&url, canister_ids.local_user_index, username, &mut rng).await;
+        users.push(user);
+    }
+    println!("Seeded {} users", users.len());
+
+    let (creator, members) = match users.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+
+    if config.groups > 0 || config.communities > 0 {
+        let controller_agent = build_ic_agent(url.clone(), controller).await;
+        upgrade_to_diamond(&controller_agent, creator, &canister_ids).await;
+    }
+
+    for i in 0..config.groups {
+        let chat_id = create_group(creator, format!("{prefix} group {i}"), &mut rng).await;
+        for member in members {
+            join_group(member, canister_ids.local_user_index, chat_id).await;
+        }
+        seed_group_messages(chat_id, &users, config.messages_per_chat, &mut rng).await;
+    }
+    println!("Seeded {} groups", config.groups);
+
+    for i in 0..config.communities {
+        let community_id = create_community(creator, format!("{prefix} community {i}"), &mut rng).await;
+        for j in 0..config.channels_per_community {
+            let channel_id = create_channel(creator, community_id, format!("channel {j}"), &mut rng).await;
+            for member in members {
+                join_channel(member, canister_ids.local_user_index, community_id, channel_id).await;
+            }
+            seed_channel_messages(community_id, channel_id, &users, config.messages_per_chat, &mut rng).await;
+        }
+    }
+    println!("Seeded {} communities", config.communities);
+}
+
+async fn register_user(url: &str, local_user_index: Principal, username: String, rng: &mut StdRng) -> SeededUser {
+    use local_user_index_canister::register_user::{Args, Response};
+
+    let key_pair = Ed25519KeyPair::from_seed_unchecked(&rng.gen::<[u8; 32]>()).unwrap();
+    let public_key = [ED25519_DER_PREFIX.as_slice(), key_pair.public_key().as_ref()].concat();
+    let agent = build_ic_agent(url.to_string(), Box::new(BasicIdentity::from_key_pair(key_pair))).await;
+
+    let args = Args {
+        username: username.clone(),
+        display_name: None,
+        referral_code: None,
+        public_key,
+    };
+
+    match local_user_index_canister_client::register_user(&agent, &local_user_index, &args)
+        .await
+        .unwrap()
+    {
+        Response::Success(result) => SeededUser {
+            agent,
+            user_id: result.user_id,
+            username,
+        },
+        response => panic!("Failed to register user '{username}': {response:?}"),
+    }
+}
+
+async fn upgrade_to_diamond(controller_agent: &Agent, user: &SeededUser, canister_ids: &CanisterIds) {
+    use user_index_canister::pay_for_diamond_membership::{Args, Response};
+
+    let duration = DiamondMembershipPlanDuration::OneMonth;
+    let transfer_args = TransferArgs {
+        memo: Memo(0),
+        amount: Tokens::from_e8s(duration.icp_price_e8s() + DEFAULT_FEE.e8s()),
+        fee: DEFAULT_FEE,
+        from_subaccount: None,
+        to: AccountIdentifier::new(&user.user_id.into(), &DEFAULT_SUBACCOUNT),
+        created_at_time: None,
+    };
+    let response = controller_agent
+        .update(&canister_ids.nns_ledger, "transfer")
+        .with_arg(Encode!(&transfer_args).unwrap())
+        .call_and_wait()
+        .await
+        .unwrap();
+    if let Err(error) = Decode!(response.as_slice(), TransferResult).unwrap() {
+        panic!("Failed to transfer ICP to seeded user, does the controller have an ICP balance? {error:?}");
+    }
+
+    let args = Args {
+        duration,
+        token: Cryptocurrency::InternetComputer,
+        ledger: None,
+        expected_price_e8s: duration.icp_price_e8s(),
+        recurring: false,
+    };
+    match user_index_canister_client::pay_for_diamond_membership(&user.agent, &canister_ids.user_index, &args)
+        .await
+        .unwrap()
+    {
+        Response::Success(_) => {}
+        response => panic!("Failed to pay for Diamond membership: {response:?}"),
+    }
+}
+
+async fn create_group(creator: &SeededUser, name: String, rng: &mut StdRng) -> ChatId {
+    use user_canister::create_group::{Args, Response};
+
+    let args = Args {
+        is_public: true,
+        name,
+        description: random_text(rng, 12),
+        rules: Rules::default(),
+        avatar: None,
+        history_visible_to_new_joiners: true,
+        permissions: None,
+        events_ttl: None,
+        gate: None,
+    };
+
+    // The user canister learns about the Diamond membership asynchronously, so retry until it has
+    for _ in 0..10 {
+        match user_canister_client::create_group(&creator.agent, &creator.user_id.into(), &args)
+            .await
+            .unwrap()
+        {
+            Response::Success(result) => return result.chat_id,
+            Response::UnauthorizedToCreatePublicGroup => tokio::time::sleep(Duration::from_secs(1)).await,
+            response => panic!("Failed to create group: {response:?}"),
+        }
+    }
+    panic!("Failed to create group: the creator is still not a Diamond member");
+}
+
+async fn create_community(creator: &SeededUser, name: String, rng: &mut StdRng) -> CommunityId {
+    use user_canister::create_community::{Args, Response};
+
+    let args = Args {
+        is_public: true,
+        name,
+        description: random_text(rng, 12),
+        rules: Rules::default(),
+        avatar: None,
+        banner: None,
+        history_visible_to_new_joiners: true,
+        permissions: None,
+        gate: None,
+        default_channels: vec!["General".to_string()],
+        default_channel_rules: None,
+        primary_language: "en".to_string(),
+    };
+
+    for _ in 0..10 {
+        match user_canister_client::create_community(&creator.agent, &creator.user_id.into(), &args)
+            .await
+            .unwrap()
+        {
+            Response::Success(result) => return result.community_id,
+            Response::Unauthorized => tokio::time::sleep(Duration::from_secs(1)).await,
+            response => panic!("Failed to create community: {response:?}"),
+        }
+    }
+    panic!("Failed to create community: the creator is still not a Diamond member");
+}
+
+async fn create_channel(creator: &SeededUser, community_id: CommunityId, name: String, rng: &mut StdRng) -> ChannelId {
+    use community_canister::create_channel::{Args, Response};
+
+    let args = Args {
+        is_public: true,
+        name,
+        description: random_text(rng, 8),
+        rules: Rules::default(),
+        subtype: None,
+        avatar: None,
+        history_visible_to_new_joiners: true,
+        permissions: None,
+        events_ttl: None,
+        gate: None,
+    };
+
+    match community_canister_client::create_channel(&creator.agent, &community_id.into(), &args)
+        .await
+        .unwrap()
+    {
+        Response::Success(result) => result.channel_id,
+        response => panic!("Failed to create channel: {response:?}"),
+    }
+}
+
+async fn join_group(user: &SeededUser, local_user_index: Principal, chat_id: ChatId) {
+    use local_user_index_canister::join_group::{Args, Response};
+
+    let args = Args {
+        chat_id,
+        invite_code: None,
+        correlation_id: 0,
+    };
+
+    match local_user_index_canister_client::join_group(&user.agent, &local_user_index, &args)
+        .await
+        .unwrap()
+    {
+        Response::Success(_) | Response::AlreadyInGroup | Response::AlreadyInGroupV2(_) => {}
+        response => panic!("Failed to join group: {response:?}"),
+    }
+}
+
+async fn join_channel(user: &SeededUser, local_user_index: Principal, community_id: CommunityId, channel_id: ChannelId) {
+    use local_user_index_canister::join_channel::{Args, Response};
+
+    let args = Args {
+        community_id,
+        channel_id,
+        invite_code: None,
+    };
+
+    match local_user_index_canister_client::join_channel(&user.agent, &local_user_index, &args)
+        .await
+        .unwrap()
+    {
+        Response::Success(_) | Response::SuccessJoinedCommunity(_) | Response::AlreadyInChannel(_) => {}
+        response => panic!("Failed to join channel: {response:?}"),
+    }
+}
+
+async fn seed_group_messages(chat_id: ChatId, users: &[SeededUser], count: usize, rng: &mut StdRng) {
+    for _ in 0..count {
+        let sender = &users[rng.gen_range(0..users.len())];
+        let message_id: MessageId = rng.gen();
+        let args = group_canister::send_message_v2::Args {
+            thread_root_message_index: None,
+            message_id,
+            content: random_content(rng),
+            sender_name: sender.username.clone(),
+            sender_display_name: None,
+            replies_to: None,
+            mentioned: Vec::new(),
+            forwarding: false,
+            rules_accepted: None,
+            correlation_id: 0,
+        };
+        let is_poll = matches!(args.content, MessageContentInitial::Poll(_));
+
+        let message_index = match group_canister_client::send_message_v2(&sender.agent, &chat_id.into(), &args)
+            .await
+            .unwrap()
+        {
+            group_canister::send_message_v2::Response::Success(result) => result.message_index,
+            response => panic!("Failed to send group message: {response:?}"),
+        };
+
+        let reactors: Vec<_> = users.iter().filter(|_| rng.gen_bool(0.3)).collect();
+        for user in reactors {
+            let args = group_canister::add_reaction::Args {
+                thread_root_message_index: None,
+                message_id,
+                reaction: random_reaction(rng),
+                username: user.username.clone(),
+                display_name: None,
+                correlation_id: 0,
+            };
+            group_canister_client::add_reaction(&user.agent, &chat_id.into(), &args)
+                .await
+                .unwrap();
+        }
+
+        if is_poll {
+            let voters: Vec<_> = users.iter().filter(|_| rng.gen_bool(0.5)).collect();
+            for user in voters {
+                let args = group_canister::register_poll_vote::Args {
+                    thread_root_message_index: None,
+                    message_index,
+                    poll_option: rng.gen_range(0..2),
+                    operation: VoteOperation::RegisterVote,
+                    correlation_id: 0,
+                };
+                group_canister_client::register_poll_vote(&user.agent, &chat_id.into(), &args)
+                    .await
+                    .unwrap();
+            }
+        }
+    }
+}
+
+async fn seed_channel_messages(
+    community_id: CommunityId,
+    channel_id: ChannelId,
+    users: &[SeededUser],
+    count: usize,
+    rng: &mut StdRng,
+) {
+    for _ in 0..count {
+        let sender = &users[rng.gen_range(0..users.len())];
+        let message_id: MessageId = rng.gen();
+        let args = community_canister::send_message::Args {
+            channel_id,
+            thread_root_message_index: None,
+            message_id,
+            content: random_content(rng),
+            sender_name: sender.username.clone(),
+            sender_display_name: None,
+            replies_to: None,
+            mentioned: Vec::new(),
+            forwarding: false,
+            community_rules_accepted: None,
+            channel_rules_accepted: None,
+        };
+        let is_poll = matches!(args.content, MessageContentInitial::Poll(_));
+
+        let message_index: MessageIndex =
+            match community_canister_client::send_message(&sender.agent, &community_id.into(), &args)
+                .await
+                .unwrap()
+            {
+                community_canister::send_message::Response::Success(result) => result.message_index,
+                response => panic!("Failed to send channel message: {response:?}"),
+            };
+
+        let reactors: Vec<_> = users.iter().filter(|_| rng.gen_bool(0.3)).collect();
+        for user in reactors {
+            let args = community_canister::add_reaction::Args {
+                channel_id,
+                thread_root_message_index: None,
+                message_id,
+                reaction: random_reaction(rng),
+                username: user.username.clone(),
+                display_name: None,
+            };
+            community_canister_client::add_reaction(&user.agent, &community_id.into(), &args)
+                .await
+                .unwrap();
+        }
+
+        if is_poll {
+            let voters: Vec<_> = users.iter().filter(|_| rng.gen_bool(0.5)).collect();
+            for user in voters {
+                let args = community_canister::register_poll_vote::Args {
+                    channel_id,
+                    thread_root_message_index: None,
+                    message_index,
+                    poll_option: rng.gen_range(0..2),
+                    operation: VoteOperation::RegisterVote,
+                };
+                community_canister_client::register_poll_vote(&user.agent, &community_id.into(), &args)
+                    .await
+                    .unwrap();
+            }
+        }
+    }
+}
+
+// Mostly text messages with the occasional poll or file. Seeded files only carry their metadata,
+// they have no blob in a storage bucket.
+fn random_content(rng: &mut StdRng) -> MessageContentInitial {
+    match rng.gen_range(0..10) {
+        0 => MessageContentInitial::Poll(PollContent {
+            config: PollConfig {
+                text: Some(random_text(rng, 6)),
+                options: vec!["Yes".to_string(), "No".to_string()],
+                end_date: None,
+                anonymous: false,
+                show_votes_before_end_date: true,
+                allow_multiple_votes_per_user: false,
+            },
+            votes: PollVotes {
+                total: TotalVotes::Visible(Default::default()),
+                user: Vec::new(),
+            },
+            ended: false,
+        }),
+        1 => MessageContentInitial::File(FileContent {
+            name: format!("{}.txt", WORDS[rng.gen_range(0..WORDS.len())]),
+            caption: Some(random_text(rng, 4)),
+            mime_type: "text/plain".to_string(),
+            file_size: rng.gen_range(100..100_000),
+            blob_reference: None,
+        }),
+        _ => MessageContentInitial::Text(TextContent {
+            text: random_text(rng, rng.gen_range(2..20)),
+        }),
+    }
+}
+
+fn random_text(rng: &mut StdRng, words: usize) -> String {
+    (0..words)
+        .map(|_| WORDS[rng.gen_range(0..WORDS.len())])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn random_reaction(rng: &mut StdRng) -> Reaction {
+    Reaction::new(REACTIONS[rng.gen_range(0..REACTIONS.len())].to_string())
+}
@@ -0,0 +1,82 @@
+use canister_agent_utils::{build_ic_agent, read_file};
+use ic_agent::Identity;
+use serde_bytes::ByteBuf;
+use std::path::PathBuf;
+use types::CanisterId;
+
+const PAGE_SIZE: u32 = 1_000_000;
+const CHUNK_SIZE: usize = 1_000_000;
+
+// Exports the state of a group or community canister, in the same format that is written to
+// stable memory during upgrades, and writes it to `output_path`
+pub async fn export_state_snapshot(
+    identity: Box<dyn Identity>,
+    url: String,
+    group_index_canister_id: CanisterId,
+    canister_id: CanisterId,
+    output_path: PathBuf,
+) {
+    let agent = build_ic_agent(url, identity).await;
+
+    let bytes = group_index_canister_client::download_state_snapshot(&agent, &group_index_canister_id, canister_id, PAGE_SIZE)
+        .await
+        .unwrap_or_else(|error| panic!("Failed to export state: {error}"));
+
+    std::fs::write(&output_path, &bytes).unwrap();
+
+    println!(
+        "Exported {} bytes of state from {canister_id} to {}",
+        bytes.len(),
+        output_path.display()
+    );
+}
+
+// Restores a snapshot taken by `export_state_snapshot` into the original canister, which is first
+// reinstalled as a placeholder, wiping its current state
+pub async fn restore_state_snapshot(
+    identity: Box<dyn Identity>,
+    url: String,
+    group_index_canister_id: CanisterId,
+    canister_id: CanisterId,
+    snapshot_path: PathBuf,
+) {
+    let agent = build_ic_agent(url, identity).await;
+    let snapshot = read_file(snapshot_path);
+
+    let start_args = group_index_canister::start_state_restore::Args { canister_id };
+    match group_index_canister_client::start_state_restore(&agent, &group_index_canister_id, &start_args)
+        .await
+        .unwrap()
+    {
+        group_index_canister::start_state_restore::Response::Success => {}
+        response => panic!("Failed to start state restore: {response:?}"),
+    }
+
+    let mut from = 0;
+    for chunk in snapshot.chunks(CHUNK_SIZE) {
+        let args = group_index_canister::push_state_restore_chunk::Args {
+            canister_id,
+            total_bytes: snapshot.len() as u64,
+            from: from as u64,
+            bytes: ByteBuf::from(chunk.to_vec()),
+        };
+        match group_index_canister_client::push_state_restore_chunk(&agent, &group_index_canister_id, &args)
+            .await
+            .unwrap()
+        {
+            group_index_canister::push_state_restore_chunk::Response::Success => from += chunk.len(),
+            response => panic!("Failed to push state restore chunk: {response:?}"),
+        }
+    }
+
+    let finish_args = group_index_canister::finish_state_restore::Args { canister_id };
+    match group_index_canister_client::finish_state_restore(&agent, &group_index_canister_id, &finish_args)
+        .await
+        .unwrap()
+    {
+        group_index_canister::finish_state_restore::Response::Success => {
+            println!("Restored state of {canister_id}");
+        }
+        response => panic!("Failed to finish state restore: {response:?}"),
+    }
+}
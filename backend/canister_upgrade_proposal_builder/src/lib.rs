@@ -1,14 +1,29 @@
 use candid::Encode;
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
+use sha256::sha256;
 use sns_governance_canister::types::{proposal, ExecuteGenericNervousSystemFunction, Proposal};
 use std::error::Error;
 use std::fs;
-use types::{BuildVersion, CanisterWasm, UpgradeCanisterWasmArgs};
+use types::{BuildVersion, CanisterId, CanisterWasm, RollbackCanisterWasmArgs, UpgradeCanisterWasmArgs, UpgradeRolloutPolicy};
 
 /// Builds the binary encoded candid representation of an ExecuteGenericNervousSystemFunction proposal
-/// for upgrading a canister WASM
+/// for upgrading a canister WASM or for rolling it back to a previous WASM
 #[derive(Parser, Debug)]
 pub struct Config {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Upgrade the canisters to a new WASM
+    Upgrade(UpgradeConfig),
+    /// Roll the canisters back to a WASM previously held by their index canister
+    Rollback(RollbackConfig),
+}
+
+#[derive(Args, Debug)]
+pub struct ProposalConfig {
     /// Title of the proposal
     #[arg(long)]
     pub title: String,
@@ -24,6 +39,12 @@ pub struct Config {
     /// Custom function_id of the proposal
     #[arg(long)]
     pub function_id: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct UpgradeConfig {
+    #[command(flatten)]
+    pub proposal: ProposalConfig,
 
     /// Path to the wasm module
     #[arg(long)]
@@ -32,29 +53,95 @@ pub struct Config {
     /// Version of the wasm module
     #[arg(long)]
     pub version: BuildVersion,
+
+    /// Reference the wasm by its hash rather than including it in the proposal. The wasm must first
+    /// have been uploaded to the index canister in chunks (see canister_upgrader's upload-wasm command)
+    #[arg(long)]
+    pub uploaded: bool,
+
+    /// Canisters to upgrade first, as a stage of their own, when performing a staged rollout
+    #[arg(long, value_delimiter = ',')]
+    pub canaries: Vec<CanisterId>,
+
+    /// Cumulative percentages of canisters to upgrade in each stage of a staged rollout, eg. 1,10,50
+    #[arg(long, value_delimiter = ',')]
+    pub rollout_stages: Vec<u8>,
+
+    /// How long to wait after each stage of a staged rollout before checking the health signals
+    #[arg(long, default_value_t = 60)]
+    pub soak_minutes: u64,
+
+    /// The rollout is halted if more than this many upgrades fail within a single stage
+    #[arg(long, default_value_t = 0)]
+    pub max_failures_per_stage: u32,
+
+    /// The rollout is halted if more than this many upgraded canisters request a cycles top up within a single stage
+    #[arg(long, default_value_t = 10)]
+    pub max_low_cycles_notifications_per_stage: u32,
+}
+
+#[derive(Args, Debug)]
+pub struct RollbackConfig {
+    #[command(flatten)]
+    pub proposal: ProposalConfig,
+
+    /// Version to roll back to, defaults to the most recent previous version
+    #[arg(long)]
+    pub version: Option<BuildVersion>,
 }
 
 pub fn build(config: Config) -> Result<Vec<u8>, Box<dyn Error>> {
-    let proposal = create_proposal(config)?;
+    let proposal = match config.command {
+        Command::Upgrade(config) => create_upgrade_proposal(config)?,
+        Command::Rollback(config) => create_rollback_proposal(config)?,
+    };
 
     Ok(Encode!(&proposal)?)
 }
 
-fn create_proposal(config: Config) -> Result<Proposal, Box<dyn Error>> {
+fn create_upgrade_proposal(config: UpgradeConfig) -> Result<Proposal, Box<dyn Error>> {
     let wasm_module = fs::read(config.wasm_path)?;
 
+    let rollout = if config.canaries.is_empty() && config.rollout_stages.is_empty() {
+        None
+    } else {
+        Some(UpgradeRolloutPolicy {
+            canaries: config.canaries,
+            stage_percentages: config.rollout_stages,
+            soak_duration: config.soak_minutes * 60 * 1000,
+            max_failures_per_stage: config.max_failures_per_stage,
+            max_low_cycles_notifications_per_stage: config.max_low_cycles_notifications_per_stage,
+        })
+    };
+
+    let (module, uploaded_wasm_hash) =
+        if config.uploaded { (Vec::new(), Some(sha256(&wasm_module))) } else { (wasm_module, None) };
+
     let args = UpgradeCanisterWasmArgs {
         wasm: CanisterWasm {
             version: config.version,
-            module: wasm_module,
+            module,
         },
         filter: None,
         use_for_new_canisters: None,
+        rollout,
+        uploaded_wasm_hash,
+    };
+
+    Ok(create_proposal(config.proposal, Encode!(&args)?))
+}
+
+fn create_rollback_proposal(config: RollbackConfig) -> Result<Proposal, Box<dyn Error>> {
+    let args = RollbackCanisterWasmArgs {
+        version: config.version,
+        filter: None,
     };
 
-    let payload = Encode!(&args)?;
+    Ok(create_proposal(config.proposal, Encode!(&args)?))
+}
 
-    let proposal = Proposal {
+fn create_proposal(config: ProposalConfig, payload: Vec<u8>) -> Proposal {
+    Proposal {
         title: config.title,
         summary: config.summary,
         url: config.url,
@@ -64,7 +151,5 @@ fn create_proposal(config: Config) -> Result<Proposal, Box<dyn Error>> {
                 payload,
             },
         )),
-    };
-
-    Ok(proposal)
+    }
 }
@@ -1,11 +1,23 @@
 use candid::CandidType;
 use canister_agent_utils::{build_ic_agent, get_canister_wasm, CanisterName};
-use ic_agent::Identity;
+use ic_agent::{Agent, Identity};
+use ic_test_state_machine_client::StateMachine;
 use ic_utils::call::AsyncCall;
 use ic_utils::interfaces::management_canister::builders::InstallMode;
 use ic_utils::interfaces::management_canister::CanisterStatus;
 use ic_utils::interfaces::ManagementCanister;
-use types::{BuildVersion, CanisterId, CanisterWasm, UpgradeCanisterWasmArgs};
+use sha256::sha256;
+use std::path::PathBuf;
+use types::{
+    BuildVersion, CanisterId, CanisterWasm, CommitWasmUploadArgs, Hash, PushWasmChunkArgs, RollbackCanisterWasmArgs,
+    StartWasmUploadArgs, UpgradeCanisterWasmArgs,
+};
+use upgrade_dry_run::StableMemoryLayout;
+
+// Wasms larger than this wouldn't fit within a single ingress message so are uploaded to the index
+// canister in chunks and then referenced by their hash
+const MAX_INLINE_WASM_SIZE: usize = 1_800_000;
+const WASM_CHUNK_SIZE: usize = 1_000_000;
 
 pub async fn upgrade_group_index_canister(
     identity: Box<dyn Identity>,
@@ -205,14 +217,14 @@ pub async fn upgrade_local_group_index_canister(
 ) {
     let agent = build_ic_agent(url, identity).await;
     let canister_wasm = get_canister_wasm(CanisterName::LocalGroupIndex, version);
-    let args = UpgradeCanisterWasmArgs {
-        wasm: CanisterWasm {
-            version,
-            module: canister_wasm.module,
-        },
-        filter: None,
-        use_for_new_canisters: None,
-    };
+    let args = build_upgrade_args(
+        &agent,
+        IndexCanister::GroupIndex,
+        &group_index_canister_id,
+        version,
+        canister_wasm.module,
+    )
+    .await;
 
     let response =
         group_index_canister_client::upgrade_local_group_index_canister_wasm(&agent, &group_index_canister_id, &args)
@@ -236,14 +248,14 @@ pub async fn upgrade_group_canister(
 ) {
     let agent = build_ic_agent(url, identity).await;
     let canister_wasm = get_canister_wasm(CanisterName::Group, version);
-    let args = UpgradeCanisterWasmArgs {
-        wasm: CanisterWasm {
-            version,
-            module: canister_wasm.module,
-        },
-        filter: None,
-        use_for_new_canisters: None,
-    };
+    let args = build_upgrade_args(
+        &agent,
+        IndexCanister::GroupIndex,
+        &group_index_canister_id,
+        version,
+        canister_wasm.module,
+    )
+    .await;
 
     let response = group_index_canister_client::upgrade_group_canister_wasm(&agent, &group_index_canister_id, &args)
         .await
@@ -263,14 +275,14 @@ pub async fn upgrade_community_canister(
 ) {
     let agent = build_ic_agent(url, identity).await;
     let canister_wasm = get_canister_wasm(CanisterName::Community, version);
-    let args = UpgradeCanisterWasmArgs {
-        wasm: CanisterWasm {
-            version,
-            module: canister_wasm.module,
-        },
-        filter: None,
-        use_for_new_canisters: None,
-    };
+    let args = build_upgrade_args(
+        &agent,
+        IndexCanister::GroupIndex,
+        &group_index_canister_id,
+        version,
+        canister_wasm.module,
+    )
+    .await;
 
     let response = group_index_canister_client::upgrade_community_canister_wasm(&agent, &group_index_canister_id, &args)
         .await
@@ -293,14 +305,14 @@ pub async fn upgrade_user_canister(
 ) {
     let agent = build_ic_agent(url, identity).await;
     let canister_wasm = get_canister_wasm(CanisterName::User, version);
-    let args = UpgradeCanisterWasmArgs {
-        wasm: CanisterWasm {
-            version,
-            module: canister_wasm.module,
-        },
-        filter: None,
-        use_for_new_canisters: None,
-    };
+    let args = build_upgrade_args(
+        &agent,
+        IndexCanister::UserIndex,
+        &user_index_canister_id,
+        version,
+        canister_wasm.module,
+    )
+    .await;
 
     let response = user_index_canister_client::upgrade_user_canister_wasm(&agent, &user_index_canister_id, &args)
         .await
@@ -320,14 +332,14 @@ pub async fn upgrade_local_user_index_canister(
 ) {
     let agent = build_ic_agent(url, identity).await;
     let canister_wasm = get_canister_wasm(CanisterName::LocalUserIndex, version);
-    let args = UpgradeCanisterWasmArgs {
-        wasm: CanisterWasm {
-            version,
-            module: canister_wasm.module,
-        },
-        filter: None,
-        use_for_new_canisters: None,
-    };
+    let args = build_upgrade_args(
+        &agent,
+        IndexCanister::UserIndex,
+        &user_index_canister_id,
+        version,
+        canister_wasm.module,
+    )
+    .await;
 
     let response = user_index_canister_client::upgrade_local_user_index_canister_wasm(&agent, &user_index_canister_id, &args)
         .await
@@ -350,14 +362,14 @@ pub async fn upgrade_notifications_canister(
 ) {
     let agent = build_ic_agent(url, identity).await;
     let canister_wasm = get_canister_wasm(CanisterName::Notifications, version);
-    let args = UpgradeCanisterWasmArgs {
-        wasm: CanisterWasm {
-            version,
-            module: canister_wasm.module,
-        },
-        filter: None,
-        use_for_new_canisters: None,
-    };
+    let args = build_upgrade_args(
+        &agent,
+        IndexCanister::NotificationsIndex,
+        &notifications_index_canister_id,
+        version,
+        canister_wasm.module,
+    )
+    .await;
 
     let response = notifications_index_canister_client::upgrade_notifications_canister_wasm(
         &agent,
@@ -391,6 +403,8 @@ pub async fn upgrade_storage_bucket_canister(
         },
         filter: None,
         use_for_new_canisters: None,
+        rollout: None,
+        uploaded_wasm_hash: None,
     };
 
     let response = storage_index_canister_client::upgrade_bucket_canister_wasm(&agent, &storage_index_canister_id, &args)
@@ -406,6 +420,151 @@ pub async fn upgrade_storage_bucket_canister(
     println!("Storage bucket canister wasm upgraded to version {version}");
 }
 
+// Uploads the wasm to the index canister in chunks and commits it, returning its hash. The upgrade
+// itself can then be proposed by referencing the hash rather than including the wasm.
+pub async fn upload_canister_wasm(
+    identity: Box<dyn Identity>,
+    url: String,
+    canister_name: CanisterName,
+    index_canister_id: CanisterId,
+    version: BuildVersion,
+) -> Hash {
+    let agent = build_ic_agent(url, identity).await;
+    let index = match canister_name {
+        CanisterName::User | CanisterName::LocalUserIndex => IndexCanister::UserIndex,
+        CanisterName::Group | CanisterName::Community | CanisterName::LocalGroupIndex => IndexCanister::GroupIndex,
+        CanisterName::Notifications => IndexCanister::NotificationsIndex,
+        _ => panic!("Uploading the {canister_name} canister wasm in chunks is not supported"),
+    };
+    let canister_wasm = get_canister_wasm(canister_name.clone(), version);
+    let hash = upload_wasm_in_chunks(&agent, index, &index_canister_id, version, &canister_wasm.module).await;
+
+    let hash_string: String = hash.iter().map(|b| format!("{b:02x}")).collect();
+    println!("{canister_name} canister wasm uploaded. Version: {version}. Hash: {hash_string}");
+    hash
+}
+
+// Runs the new build's `post_upgrade` against a snapshot of the canister's state in a local
+// StateMachine and prints the report. Returns true if the snapshot is compatible with the new build.
+pub fn dry_run_canister_upgrade(
+    canister_name: CanisterName,
+    version: BuildVersion,
+    snapshot: &[u8],
+    state_machine_path: PathBuf,
+) -> bool {
+    let canister_wasm = get_canister_wasm(canister_name.clone(), version);
+    let layout = stable_memory_layout(&canister_name);
+    let mut env = StateMachine::new(state_machine_path.to_str().unwrap(), false);
+
+    let report = upgrade_dry_run::dry_run_upgrade(&mut env, snapshot, layout, &canister_wasm.module, version);
+
+    println!("{canister_name} canister upgrade dry run for version {version}");
+    print!("{report}");
+    report.is_compatible()
+}
+
+// Downloads the current state of a group or community canister so that it can be used for a dry run
+pub async fn download_state_snapshot(
+    identity: Box<dyn Identity>,
+    url: String,
+    group_index_canister_id: CanisterId,
+    canister_id: CanisterId,
+) -> Vec<u8> {
+    let agent = build_ic_agent(url, identity).await;
+
+    group_index_canister_client::download_state_snapshot(&agent, &group_index_canister_id, canister_id, 1_000_000)
+        .await
+        .unwrap_or_else(|error| panic!("Failed to download state of {canister_id}: {error}"))
+}
+
+// The user and cycles_dispenser canisters write their state directly to stable memory rather than
+// via a MemoryManager
+fn stable_memory_layout(canister_name: &CanisterName) -> StableMemoryLayout {
+    match canister_name {
+        CanisterName::User | CanisterName::CyclesDispenser => StableMemoryLayout::Raw,
+        _ => StableMemoryLayout::MemoryManager,
+    }
+}
+
+// Re-queues the canisters to be upgraded to a wasm previously held by their index canister. If
+// `version` is not set, the most recent previous version is used.
+pub async fn rollback_canister_wasm(
+    identity: Box<dyn Identity>,
+    url: String,
+    canister_name: CanisterName,
+    index_canister_id: CanisterId,
+    version: Option<BuildVersion>,
+) {
+    let agent = build_ic_agent(url, identity).await;
+    let args = RollbackCanisterWasmArgs { version, filter: None };
+
+    let version = match canister_name {
+        CanisterName::User => {
+            use user_index_canister::rollback_user_canister_wasm::Response;
+            match user_index_canister_client::rollback_user_canister_wasm(&agent, &index_canister_id, &args)
+                .await
+                .unwrap()
+            {
+                Response::Success(version) => version,
+                response => panic!("{response:?}"),
+            }
+        }
+        CanisterName::LocalUserIndex => {
+            use user_index_canister::rollback_local_user_index_canister_wasm::Response;
+            match user_index_canister_client::rollback_local_user_index_canister_wasm(&agent, &index_canister_id, &args)
+                .await
+                .unwrap()
+            {
+                Response::Success(version) => version,
+                response => panic!("{response:?}"),
+            }
+        }
+        CanisterName::Group => {
+            use group_index_canister::rollback_group_canister_wasm::Response;
+            match group_index_canister_client::rollback_group_canister_wasm(&agent, &index_canister_id, &args)
+                .await
+                .unwrap()
+            {
+                Response::Success(version) => version,
+                response => panic!("{response:?}"),
+            }
+        }
+        CanisterName::Community => {
+            use group_index_canister::rollback_community_canister_wasm::Response;
+            match group_index_canister_client::rollback_community_canister_wasm(&agent, &index_canister_id, &args)
+                .await
+                .unwrap()
+            {
+                Response::Success(version) => version,
+                response => panic!("{response:?}"),
+            }
+        }
+        CanisterName::LocalGroupIndex => {
+            use group_index_canister::rollback_local_group_index_canister_wasm::Response;
+            match group_index_canister_client::rollback_local_group_index_canister_wasm(&agent, &index_canister_id, &args)
+                .await
+                .unwrap()
+            {
+                Response::Success(version) => version,
+                response => panic!("{response:?}"),
+            }
+        }
+        CanisterName::Notifications => {
+            use notifications_index_canister::rollback_notifications_canister_wasm::Response;
+            match notifications_index_canister_client::rollback_notifications_canister_wasm(&agent, &index_canister_id, &args)
+                .await
+                .unwrap()
+            {
+                Response::Success(version) => version,
+                response => panic!("{response:?}"),
+            }
+        }
+        _ => panic!("Rollback is not supported for the {canister_name} canister"),
+    };
+
+    println!("{canister_name} canister wasm rolled back to version {version}");
+}
+
 async fn upgrade_top_level_canister<A: CandidType + Send + Sync>(
     identity: Box<dyn Identity>,
     url: String,
@@ -468,3 +627,104 @@ async fn upgrade_wasm<A: CandidType + Send + Sync>(
         .expect("Failed to start canister");
     println!("Canister started");
 }
+
+#[derive(Clone, Copy)]
+enum IndexCanister {
+    UserIndex,
+    GroupIndex,
+    NotificationsIndex,
+}
+
+async fn build_upgrade_args(
+    agent: &Agent,
+    index: IndexCanister,
+    index_canister_id: &CanisterId,
+    version: BuildVersion,
+    module: Vec<u8>,
+) -> UpgradeCanisterWasmArgs {
+    let (module, uploaded_wasm_hash) = if module.len() > MAX_INLINE_WASM_SIZE {
+        let hash = upload_wasm_in_chunks(agent, index, index_canister_id, version, &module).await;
+        (Vec::new(), Some(hash))
+    } else {
+        (module, None)
+    };
+
+    UpgradeCanisterWasmArgs {
+        wasm: CanisterWasm { version, module },
+        filter: None,
+        use_for_new_canisters: None,
+        rollout: None,
+        uploaded_wasm_hash,
+    }
+}
+
+macro_rules! upload_wasm {
+    ($client:ident, $canister:ident, $agent:expr, $canister_id:expr, $version:expr, $module:expr) => {{
+        let sha256 = sha256($module);
+        let start_args = StartWasmUploadArgs {
+            version: $version,
+            total_size: $module.len() as u64,
+            sha256,
+        };
+        match $client::start_wasm_upload($agent, $canister_id, &start_args).await.unwrap() {
+            $canister::start_wasm_upload::Response::Success => {}
+            response => panic!("{response:?}"),
+        }
+
+        for (index, chunk) in $module.chunks(WASM_CHUNK_SIZE).enumerate() {
+            let chunk_args = PushWasmChunkArgs {
+                sha256,
+                index: index as u32,
+                bytes: chunk.to_vec(),
+            };
+            match $client::push_wasm_chunk($agent, $canister_id, &chunk_args).await.unwrap() {
+                $canister::push_wasm_chunk::Response::Success => {}
+                response => panic!("{response:?}"),
+            }
+        }
+
+        match $client::commit_wasm_upload($agent, $canister_id, &CommitWasmUploadArgs { sha256 })
+            .await
+            .unwrap()
+        {
+            $canister::commit_wasm_upload::Response::Success(_) => {}
+            response => panic!("{response:?}"),
+        }
+        sha256
+    }};
+}
+
+async fn upload_wasm_in_chunks(
+    agent: &Agent,
+    index: IndexCanister,
+    index_canister_id: &CanisterId,
+    version: BuildVersion,
+    module: &[u8],
+) -> Hash {
+    match index {
+        IndexCanister::UserIndex => upload_wasm!(
+            user_index_canister_client,
+            user_index_canister,
+            agent,
+            index_canister_id,
+            version,
+            module
+        ),
+        IndexCanister::GroupIndex => upload_wasm!(
+            group_index_canister_client,
+            group_index_canister,
+            agent,
+            index_canister_id,
+            version,
+            module
+        ),
+        IndexCanister::NotificationsIndex => upload_wasm!(
+            notifications_index_canister_client,
+            notifications_index_canister,
+            agent,
+            index_canister_id,
+            version,
+            module
+        ),
+    }
+}
@@ -1,52 +1,82 @@
-use canister_agent_utils::{get_dfx_identity, CanisterName};
+use canister_agent_utils::{get_dfx_identity, read_file, CanisterName};
 use canister_upgrader::*;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 use types::{BuildVersion, CanisterId};
 
 #[tokio::main]
 async fn main() {
-    let opts = Opts::parse();
+    let mut opts = Opts::parse();
+
+    match opts.command.take() {
+        Some(Command::Rollback { canister, version }) => {
+            let identity = get_dfx_identity(&opts.controller);
+            let index_canister_id = index_canister_id(&opts, &canister);
+            rollback_canister_wasm(identity, opts.url, canister, index_canister_id, version).await;
+            return;
+        }
+        Some(Command::UploadWasm { canister, version }) => {
+            let identity = get_dfx_identity(&opts.controller);
+            let index_canister_id = index_canister_id(&opts, &canister);
+            upload_canister_wasm(identity, opts.url, canister, index_canister_id, version).await;
+            return;
+        }
+        Some(Command::DryRun {
+            canister,
+            version,
+            snapshot,
+            canister_id,
+            state_machine,
+        }) => {
+            let snapshot = match (snapshot, canister_id) {
+                (Some(path), _) => read_file(path),
+                (None, Some(canister_id)) if matches!(canister, CanisterName::Group | CanisterName::Community) => {
+                    let identity = get_dfx_identity(&opts.controller);
+                    download_state_snapshot(identity, opts.url, opts.group_index, canister_id).await
+                }
+                (None, Some(_)) => panic!("Only group and community snapshots can be downloaded, use '--snapshot' instead"),
+                (None, None) => panic!("Either '--snapshot' or '--canister-id' must be set"),
+            };
+            let compatible = dry_run_canister_upgrade(canister, version, &snapshot, state_machine);
+            std::process::exit(if compatible { 0 } else { 1 });
+        }
+        None => {}
+    }
 
     let identity = get_dfx_identity(&opts.controller);
+    let (canister_to_upgrade, version) = (opts.canister_to_upgrade.unwrap(), opts.version.unwrap());
 
-    match opts.canister_to_upgrade {
-        CanisterName::Community => upgrade_community_canister(identity, opts.url, opts.group_index, opts.version).await,
+    match canister_to_upgrade {
+        CanisterName::Community => upgrade_community_canister(identity, opts.url, opts.group_index, version).await,
         CanisterName::CyclesDispenser => {
-            upgrade_cycles_dispenser_canister(identity, opts.url, opts.cycles_dispenser, opts.version).await
+            upgrade_cycles_dispenser_canister(identity, opts.url, opts.cycles_dispenser, version).await
         }
-        CanisterName::ExchangeBot => upgrade_exchange_bot_canister(identity, opts.url, opts.exchange_bot, opts.version).await,
-        CanisterName::Group => upgrade_group_canister(identity, opts.url, opts.group_index, opts.version).await,
+        CanisterName::ExchangeBot => upgrade_exchange_bot_canister(identity, opts.url, opts.exchange_bot, version).await,
+        CanisterName::Group => upgrade_group_canister(identity, opts.url, opts.group_index, version).await,
         CanisterName::LocalGroupIndex => {
-            upgrade_local_group_index_canister(identity, opts.url, opts.group_index, opts.version).await
+            upgrade_local_group_index_canister(identity, opts.url, opts.group_index, version).await
         }
-        CanisterName::GroupIndex => upgrade_group_index_canister(identity, opts.url, opts.group_index, opts.version).await,
-        CanisterName::MarketMaker => upgrade_market_maker_canister(identity, opts.url, opts.market_maker, opts.version).await,
+        CanisterName::GroupIndex => upgrade_group_index_canister(identity, opts.url, opts.group_index, version).await,
+        CanisterName::MarketMaker => upgrade_market_maker_canister(identity, opts.url, opts.market_maker, version).await,
         CanisterName::NotificationsIndex => {
-            upgrade_notifications_index_canister(identity, opts.url, opts.notifications_index, opts.version).await
+            upgrade_notifications_index_canister(identity, opts.url, opts.notifications_index, version).await
         }
         CanisterName::Notifications => {
-            upgrade_notifications_canister(identity, opts.url, opts.notifications_index, opts.version).await
-        }
-        CanisterName::OnlineUsers => upgrade_online_users_canister(identity, opts.url, opts.online_users, opts.version).await,
-        CanisterName::ProposalsBot => {
-            upgrade_proposals_bot_canister(identity, opts.url, opts.proposals_bot, opts.version).await
-        }
-        CanisterName::Registry => upgrade_registry_canister(identity, opts.url, opts.registry, opts.version).await,
-        CanisterName::StorageBucket => {
-            upgrade_storage_bucket_canister(identity, opts.url, opts.storage_index, opts.version).await
-        }
-        CanisterName::StorageIndex => {
-            upgrade_storage_index_canister(identity, opts.url, opts.storage_index, opts.version).await
-        }
-        CanisterName::User => upgrade_user_canister(identity, opts.url, opts.user_index, opts.version).await,
-        CanisterName::LocalUserIndex => {
-            upgrade_local_user_index_canister(identity, opts.url, opts.user_index, opts.version).await
+            upgrade_notifications_canister(identity, opts.url, opts.notifications_index, version).await
         }
-        CanisterName::UserIndex => upgrade_user_index_canister(identity, opts.url, opts.user_index, opts.version).await,
+        CanisterName::OnlineUsers => upgrade_online_users_canister(identity, opts.url, opts.online_users, version).await,
+        CanisterName::ProposalsBot => upgrade_proposals_bot_canister(identity, opts.url, opts.proposals_bot, version).await,
+        CanisterName::Registry => upgrade_registry_canister(identity, opts.url, opts.registry, version).await,
+        CanisterName::StorageBucket => upgrade_storage_bucket_canister(identity, opts.url, opts.storage_index, version).await,
+        CanisterName::StorageIndex => upgrade_storage_index_canister(identity, opts.url, opts.storage_index, version).await,
+        CanisterName::User => upgrade_user_canister(identity, opts.url, opts.user_index, version).await,
+        CanisterName::LocalUserIndex => upgrade_local_user_index_canister(identity, opts.url, opts.user_index, version).await,
+        CanisterName::UserIndex => upgrade_user_index_canister(identity, opts.url, opts.user_index, version).await,
     };
 }
 
 #[derive(Parser)]
+#[command(subcommand_negates_reqs = true)]
 struct Opts {
     #[arg(long)]
     url: String,
@@ -84,9 +114,61 @@ struct Opts {
     #[arg(long)]
     exchange_bot: CanisterId,
 
-    #[arg(long)]
-    canister_to_upgrade: CanisterName,
+    #[arg(long, required = true)]
+    canister_to_upgrade: Option<CanisterName>,
 
-    #[arg(long)]
-    version: BuildVersion,
+    #[arg(long, required = true)]
+    version: Option<BuildVersion>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Rolls the canisters back to a wasm previously used by their index canister
+    Rollback {
+        #[arg(long)]
+        canister: CanisterName,
+
+        /// Defaults to the most recent previous version
+        #[arg(long)]
+        version: Option<BuildVersion>,
+    },
+    /// Uploads the wasm to its index canister in chunks so that an upgrade proposal can reference it by hash
+    UploadWasm {
+        #[arg(long)]
+        canister: CanisterName,
+
+        #[arg(long)]
+        version: BuildVersion,
+    },
+    /// Checks that the new build can deserialize a snapshot of the canister's state in `post_upgrade`
+    DryRun {
+        #[arg(long)]
+        canister: CanisterName,
+
+        #[arg(long)]
+        version: BuildVersion,
+
+        /// Path to the canister's state, as serialized by `pre_upgrade`
+        #[arg(long)]
+        snapshot: Option<PathBuf>,
+
+        /// Group or community canister whose current state should be downloaded and used as the snapshot
+        #[arg(long)]
+        canister_id: Option<CanisterId>,
+
+        /// Path to the ic-test-state-machine binary used to run the new build
+        #[arg(long)]
+        state_machine: PathBuf,
+    },
+}
+
+fn index_canister_id(opts: &Opts, canister: &CanisterName) -> CanisterId {
+    match canister {
+        CanisterName::User | CanisterName::LocalUserIndex => opts.user_index,
+        CanisterName::Group | CanisterName::Community | CanisterName::LocalGroupIndex => opts.group_index,
+        _ => opts.notifications_index,
+    }
 }
@@ -22,22 +22,30 @@ fn main() {
     generate_candid_method!(community, summary, query);
     generate_candid_method!(community, summary_updates, query);
     generate_candid_method!(community, thread_previews, query);
+    generate_candid_method!(community, typing_indicators, query);
 
     generate_candid_method!(community, add_members_to_channel, update);
     generate_candid_method!(community, add_reaction, update);
+    generate_candid_method!(community, add_referral_campaign, update);
     generate_candid_method!(community, block_user, update);
     generate_candid_method!(community, change_channel_role, update);
     generate_candid_method!(community, change_role, update);
     generate_candid_method!(community, claim_prize, update);
     generate_candid_method!(community, create_channel, update);
+    generate_candid_method!(community, create_channel_category, update);
+    generate_candid_method!(community, create_custom_role, update);
     generate_candid_method!(community, create_user_group, update);
     generate_candid_method!(community, decline_invitation, update);
     generate_candid_method!(community, delete_channel, update);
+    generate_candid_method!(community, delete_channel_categories, update);
+    generate_candid_method!(community, delete_custom_roles, update);
     generate_candid_method!(community, delete_messages, update);
     generate_candid_method!(community, delete_user_groups, update);
     generate_candid_method!(community, disable_invite_code, update);
     generate_candid_method!(community, edit_message, update);
     generate_candid_method!(community, enable_invite_code, update);
+    generate_candid_method!(community, follow_announcement_channel, update);
+    generate_candid_method!(community, follow_proposal_votes, update);
     generate_candid_method!(community, follow_thread, update);
     generate_candid_method!(community, import_group, update);
     generate_candid_method!(community, leave_channel, update);
@@ -48,16 +56,22 @@ fn main() {
     generate_candid_method!(community, remove_member_from_channel, update);
     generate_candid_method!(community, remove_member, update);
     generate_candid_method!(community, remove_reaction, update);
+    generate_candid_method!(community, reorder_channel_categories, update);
     generate_candid_method!(community, reset_invite_code, update);
     generate_candid_method!(community, send_message, update);
+    generate_candid_method!(community, set_channel_user_group_permissions, update);
     generate_candid_method!(community, set_member_display_name, update);
+    generate_candid_method!(community, set_typing, update);
     generate_candid_method!(community, toggle_mute_notifications, update);
     generate_candid_method!(community, unblock_user, update);
     generate_candid_method!(community, undelete_messages, update);
+    generate_candid_method!(community, unfollow_announcement_channel, update);
     generate_candid_method!(community, unfollow_thread, update);
     generate_candid_method!(community, unpin_message, update);
     generate_candid_method!(community, update_channel, update);
+    generate_candid_method!(community, update_channel_category, update);
     generate_candid_method!(community, update_community, update);
+    generate_candid_method!(community, update_custom_role, update);
     generate_candid_method!(community, update_user_group, update);
 
     candid::export_service!();
@@ -19,3 +19,4 @@ pub mod selected_updates_v2;
 pub mod summary;
 pub mod summary_updates;
 pub mod thread_previews;
+pub mod typing_indicators;
@@ -0,0 +1,25 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, TimestampMillis, TypingIndicator};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    // Only indicators which have changed since this time are returned
+    pub updated_since: Option<TimestampMillis>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    SuccessNoUpdates(TimestampMillis),
+    UserNotInCommunity,
+    ChannelNotFound,
+    UserNotInChannel,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub timestamp: TimestampMillis,
+    pub indicators: Vec<TypingIndicator>,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub from: u64,
+    pub page_size: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    NotStarted,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub bytes: ByteBuf,
+    pub total_bytes: u64,
+}
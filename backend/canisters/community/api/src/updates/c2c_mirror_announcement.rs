@@ -0,0 +1,46 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, EventIndex, MessageContentInitial, MessageId, UserId};
+
+// Sent by the community which owns an announcement channel to each community following it
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub action: AnnouncementAction,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum AnnouncementAction {
+    Send(AnnouncementMessage),
+    Edit(AnnouncementEdit),
+    Delete(AnnouncementDeletion),
+    // The announcement channel has been deleted so it can no longer be followed
+    ChannelDeleted,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AnnouncementMessage {
+    pub message_id: MessageId,
+    pub event_index: EventIndex,
+    pub sender: UserId,
+    pub content: MessageContentInitial,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AnnouncementEdit {
+    pub message_id: MessageId,
+    pub sender: UserId,
+    pub content: MessageContentInitial,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AnnouncementDeletion {
+    pub message_ids: Vec<MessageId>,
+    pub deleted_by: UserId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NotFollowing,
+}
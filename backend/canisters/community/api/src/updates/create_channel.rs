@@ -17,6 +17,10 @@ pub struct Args {
     pub permissions: Option<GroupPermissions>,
     pub events_ttl: Option<Milliseconds>,
     pub gate: Option<AccessGate>,
+    #[serde(default)]
+    pub category_id: Option<u32>,
+    #[serde(default)]
+    pub is_announcement: Option<bool>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -31,6 +35,7 @@ pub enum Response {
     AvatarTooBig(FieldTooLongResult),
     MaxChannelsCreated(u32),
     NameTaken,
+    CategoryNotFound,
     UserSuspended,
     NotAuthorized,
     CommunityFrozen,
@@ -0,0 +1,28 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, FieldTooLongResult, FieldTooShortResult, GroupPermissions};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub name: String,
+    pub collapsed_by_default: bool,
+    pub default_permissions: Option<GroupPermissions>,
+    pub channel_ids: Vec<ChannelId>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    NameTooShort(FieldTooShortResult),
+    NameTooLong(FieldTooLongResult),
+    NameInvalid,
+    NameTaken,
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub category_id: u32,
+}
@@ -0,0 +1,27 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CommunityPermission, FieldTooLongResult, FieldTooShortResult, GroupPermission};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub name: String,
+    pub community_permissions: Vec<CommunityPermission>,
+    pub channel_permissions: Vec<GroupPermission>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    NameTooShort(FieldTooShortResult),
+    NameTooLong(FieldTooLongResult),
+    NameInvalid,
+    NameTaken,
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub role_id: u32,
+}
@@ -0,0 +1,27 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, CommunityId};
+
+// Mirrors new messages from an announcement channel in another community into `channel_id`
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub source_community_id: CommunityId,
+    pub source_channel_id: ChannelId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    AlreadyFollowing,
+    SourceChannelNotFound,
+    NotAnnouncementChannel,
+    TooManyFollowers,
+    ChannelNotFound,
+    UserNotInChannel,
+    UserNotInCommunity,
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+    InternalError(String),
+}
@@ -0,0 +1,24 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, UserId};
+
+// Automatically votes on the channel's proposals in the same way as `followee` does, passing in
+// `None` stops following
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub followee: Option<UserId>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    FolloweeNotInChannel,
+    CannotFollowSelf,
+    CircularFollow,
+    ChannelNotFound,
+    UserNotInChannel,
+    UserNotInCommunity,
+    UserSuspended,
+    CommunityFrozen,
+}
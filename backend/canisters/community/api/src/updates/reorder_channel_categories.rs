@@ -0,0 +1,16 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Any categories not included keep their relative order and are placed at the end
+    pub category_ids: Vec<u32>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+}
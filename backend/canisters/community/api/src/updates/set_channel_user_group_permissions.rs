@@ -0,0 +1,25 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, GroupPermission};
+
+// Replaces the channel's permission override for the user group, passing in empty lists of permissions
+// removes the override
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub user_group_id: u32,
+    pub allow: Vec<GroupPermission>,
+    pub deny: Vec<GroupPermission>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    ChannelNotFound,
+    UserGroupNotFound,
+    UserNotInChannel,
+    UserNotInCommunity,
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+}
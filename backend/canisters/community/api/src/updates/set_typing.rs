@@ -0,0 +1,21 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, MessageIndex, TypingActivity};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    // Set to None once the user stops typing or recording
+    pub activity: Option<TypingActivity>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    UserNotInCommunity,
+    ChannelNotFound,
+    UserNotInChannel,
+    UserSuspended,
+    CommunityFrozen,
+}
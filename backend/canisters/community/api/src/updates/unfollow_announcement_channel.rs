@@ -0,0 +1,20 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, CommunityId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub source_community_id: CommunityId,
+    pub source_channel_id: ChannelId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NotFollowing,
+    UserNotInChannel,
+    UserNotInCommunity,
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+}
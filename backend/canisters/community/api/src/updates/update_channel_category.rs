@@ -0,0 +1,28 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, FieldTooLongResult, FieldTooShortResult, GroupPermissions, OptionUpdate};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub category_id: u32,
+    pub name: Option<String>,
+    pub collapsed_by_default: Option<bool>,
+    pub default_permissions: OptionUpdate<GroupPermissions>,
+    // The full ordered list of channels in the category, channels are removed from any other category
+    pub channel_ids: Option<Vec<ChannelId>>,
+    // If true, the category's default permissions are applied to each of its channels
+    pub apply_default_permissions: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CategoryNotFound,
+    NameTooShort(FieldTooShortResult),
+    NameTooLong(FieldTooLongResult),
+    NameInvalid,
+    NameTaken,
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+}
@@ -0,0 +1,28 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CommunityPermission, FieldTooLongResult, FieldTooShortResult, GroupPermission, UserId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub role_id: u32,
+    pub name: Option<String>,
+    pub community_permissions: Option<Vec<CommunityPermission>>,
+    pub channel_permissions: Option<Vec<GroupPermission>>,
+    pub users_to_add: Vec<UserId>,
+    pub users_to_remove: Vec<UserId>,
+    pub user_groups_to_add: Vec<u32>,
+    pub user_groups_to_remove: Vec<u32>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    RoleNotFound,
+    NameTooShort(FieldTooShortResult),
+    NameTooLong(FieldTooLongResult),
+    NameInvalid,
+    NameTaken,
+    NotAuthorized,
+    CommunityFrozen,
+    UserSuspended,
+}
@@ -6,6 +6,9 @@ use community_canister::*;
 // Updates
 generate_c2c_call!(c2c_create_proposals_channel);
 generate_c2c_call!(c2c_delete_community);
+generate_c2c_call!(c2c_export_state);
+generate_c2c_call!(c2c_finish_state_restore);
+generate_c2c_call!(c2c_follow_announcement_channel);
 generate_c2c_call!(c2c_freeze_community);
 generate_c2c_call!(c2c_import_proposals_group);
 generate_c2c_call!(c2c_invite_users);
@@ -13,6 +16,8 @@ generate_c2c_call!(c2c_invite_users_to_channel);
 generate_c2c_call!(c2c_join_channel);
 generate_c2c_call!(c2c_join_community);
 generate_c2c_call!(c2c_leave_community);
+generate_c2c_call!(c2c_restore_state_chunk);
+generate_c2c_call!(c2c_send_proposal_reminder);
 generate_c2c_call!(c2c_tip_message);
 generate_c2c_call!(c2c_unfreeze_community);
 generate_c2c_call!(c2c_update_proposals);
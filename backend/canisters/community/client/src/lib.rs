@@ -0,0 +1,10 @@
+use canister_client::generate_update_call;
+use community_canister::*;
+
+// Queries
+
+// Updates
+generate_update_call!(add_reaction);
+generate_update_call!(create_channel);
+generate_update_call!(register_poll_vote);
+generate_update_call!(send_message);
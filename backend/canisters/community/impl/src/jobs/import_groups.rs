@@ -7,13 +7,13 @@ use crate::timer_job_types::{FinalizeGroupImportJob, ProcessGroupImportChannelMe
 use crate::updates::c2c_join_channel::join_channel_unchecked;
 use crate::{mutate_state, RuntimeState};
 use group_canister::c2c_export_group::{Args, Response};
-use group_chat_core::GroupChatCore;
+use group_chat_core::{GroupChatCore, ProposalVoteFollows};
 use ic_cdk_timers::TimerId;
 use std::cell::Cell;
 use std::collections::HashMap;
 use std::time::Duration;
 use tracing::{info, trace};
-use types::{ChannelId, ChannelLatestMessageIndex, ChatId, Empty, UserId};
+use types::{ChannelId, ChannelLatestMessageIndex, ChatId, Empty, Timestamped, UserId};
 use utils::consts::OPENCHAT_BOT_USER_ID;
 
 const PAGE_SIZE: u32 = 19 * 102 * 1024; // Roughly 1.9MB (1.9 * 1024 * 1024)
@@ -116,6 +116,9 @@ pub(crate) fn finalize_group_import(group_id: ChatId) {
                 id: channel_id,
                 chat,
                 date_imported: None, // This is only set once everything is complete
+                user_group_overrides: Timestamped::default(),
+                is_announcement: false,
+                proposal_vote_follows: ProposalVoteFollows::default(),
             });
 
             state.data.timer_jobs.enqueue_job(
@@ -222,6 +225,7 @@ pub(crate) async fn process_channel_members(group_id: ChatId, channel_id: Channe
     }
 
     mutate_state(|state| {
+        state.data.sync_channel_permissions_for_channel(&channel_id);
         state.data.events.push_event(
             CommunityEventInternal::GroupImported(Box::new(GroupImportedInternal {
                 group_id,
@@ -1,5 +1,7 @@
 use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
-use crate::model::channels::Channels;
+use crate::model::announcements::{AnnouncementFollower, Announcements, FollowedAnnouncementChannel};
+use crate::model::channel_categories::ChannelCategories;
+use crate::model::channels::{Channel, Channels};
 use crate::model::groups_being_imported::{GroupBeingImportedSummary, GroupsBeingImported};
 use crate::model::members::CommunityMembers;
 use crate::timer_job_types::{RemoveExpiredEventsJob, TimerJob};
@@ -8,6 +10,8 @@ use candid::Principal;
 use canister_state_macros::canister_state;
 use canister_timer_jobs::TimerJobs;
 use chat_events::ChatMetricsInternal;
+use community_canister::c2c_mirror_announcement::{self, AnnouncementAction};
+use community_canister::c2c_unfollow_announcement_channel;
 use fire_and_forget_handler::FireAndForgetHandler;
 use group_chat_core::AccessRulesInternal;
 use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
@@ -17,12 +21,14 @@ use notifications_canister::c2c_push_notification;
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::ops::Deref;
 use types::{
     AccessGate, BuildVersion, CanisterId, ChannelId, ChatMetrics, CommunityCanisterCommunitySummary, CommunityMembership,
-    CommunityPermissions, Cryptocurrency, Cycles, Document, Empty, FrozenGroupInfo, Milliseconds, Notification, Rules,
-    TimestampMillis, Timestamped, UserId,
+    CommunityPermission, CommunityPermissions, Cryptocurrency, Cycles, Document, Empty, FrozenGroupInfo, GroupPermission,
+    Milliseconds, Notification, Rules, TimestampMillis, Timestamped, UserId,
 };
+use utils::canister::StateSnapshots;
 use utils::env::Environment;
 use utils::regular_jobs::RegularJobs;
 
@@ -101,6 +107,7 @@ impl RuntimeState {
                     .as_ref()
                     .map_or(false, |version| version.value >= self.data.rules.text.version),
                 display_name: m.display_name().value.clone(),
+                custom_roles: data.members.custom_role_ids(&m.user_id),
             };
 
             // Return all the channels that the user is a member of
@@ -142,10 +149,27 @@ impl RuntimeState {
             channels,
             membership,
             user_groups: data.members.iter_user_groups().map(|u| u.into()).collect(),
+            custom_roles: data.members.iter_custom_roles().map(|r| r.into()).collect(),
+            channel_categories: data.channel_categories.iter().map(|c| c.into()).collect(),
             metrics: data.cached_chat_metrics.value.clone(),
         }
     }
 
+    // Replaces the state with one restored from a snapshot. The ids of the canisters this community
+    // interacts with are kept, since the snapshot may have been taken from a community on another subnet.
+    pub fn restore_data(&mut self, mut data: Data) {
+        data.user_index_canister_id = self.data.user_index_canister_id;
+        data.local_user_index_canister_id = self.data.local_user_index_canister_id;
+        data.group_index_canister_id = self.data.group_index_canister_id;
+        data.local_group_index_canister_id = self.data.local_group_index_canister_id;
+        data.notifications_canister_id = self.data.notifications_canister_id;
+        data.proposals_bot_user_id = self.data.proposals_bot_user_id;
+        data.test_mode = self.data.test_mode;
+
+        self.data.timer_jobs.cancel_jobs(|_| true);
+        self.data = data;
+    }
+
     pub fn run_event_expiry_job(&mut self) {
         let now = self.env.now();
         let mut next_event_expiry = None;
@@ -183,6 +207,7 @@ impl RuntimeState {
             frozen: self.data.is_frozen(),
             groups_being_imported: self.data.groups_being_imported.summaries(),
             instruction_counts: self.data.instruction_counts_log.iter().collect(),
+            timer_jobs: self.data.timer_jobs.len() as u32,
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 group_index: self.data.group_index_canister_id,
@@ -220,6 +245,10 @@ struct Data {
     date_created: TimestampMillis,
     members: CommunityMembers,
     channels: Channels,
+    #[serde(default)]
+    channel_categories: ChannelCategories,
+    #[serde(default)]
+    announcements: Announcements,
     events: CommunityEvents,
     invited_users: InvitedUsers,
     invite_code: Option<u64>,
@@ -235,6 +264,8 @@ struct Data {
     next_event_expiry: Option<TimestampMillis>,
     test_mode: bool,
     cached_chat_metrics: Timestamped<ChatMetrics>,
+    #[serde(skip)]
+    state_snapshots: StateSnapshots,
 }
 
 impl Data {
@@ -286,6 +317,8 @@ impl Data {
             date_created: now,
             members,
             channels,
+            channel_categories: ChannelCategories::default(),
+            announcements: Announcements::default(),
             events,
             invited_users: InvitedUsers::default(),
             invite_code: None,
@@ -299,6 +332,7 @@ impl Data {
             next_event_expiry: None,
             test_mode,
             cached_chat_metrics: Timestamped::default(),
+            state_snapshots: StateSnapshots::default(),
         }
     }
 
@@ -326,6 +360,77 @@ impl Data {
         self.cached_chat_metrics = Timestamped::new(metrics.hydrate(), now);
     }
 
+    // Members are permitted if either their role or any of their custom roles grants the permission
+    pub fn is_permitted(&self, member: &CommunityMemberInternal, permission: CommunityPermission) -> bool {
+        member.role.is_permitted(self.permissions.role_for(permission))
+            || self.members.has_custom_permission(&member.user_id, permission)
+    }
+
+    // Channel permission checks happen within `GroupChatCore` so the permissions granted by custom
+    // roles and by each channel's user group overrides are copied onto each of the user's channel
+    // memberships whenever they change
+    pub fn sync_channel_permissions(&mut self, user_ids: impl IntoIterator<Item = UserId>) {
+        let user_groups_by_user = self.members.user_group_ids_by_user();
+        let no_user_groups = HashSet::new();
+
+        for user_id in user_ids {
+            if let Some(member) = self.members.get_by_user_id(&user_id) {
+                let user_groups = user_groups_by_user.get(&user_id).unwrap_or(&no_user_groups);
+                let custom_permissions = self.members.custom_channel_permissions(&user_id, user_groups);
+
+                for channel_id in member.channels.iter() {
+                    if let Some(channel) = self.channels.get_mut(channel_id) {
+                        sync_channel_member_permissions(channel, user_id, &custom_permissions, user_groups);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn sync_channel_permissions_for_channel(&mut self, channel_id: &ChannelId) {
+        if let Some(channel) = self.channels.get_mut(channel_id) {
+            let user_groups_by_user = self.members.user_group_ids_by_user();
+            let no_user_groups = HashSet::new();
+            let user_ids: Vec<_> = channel.chat.members.iter().map(|m| m.user_id).collect();
+
+            for user_id in user_ids {
+                let user_groups = user_groups_by_user.get(&user_id).unwrap_or(&no_user_groups);
+                let custom_permissions = self.members.custom_channel_permissions(&user_id, user_groups);
+                sync_channel_member_permissions(channel, user_id, &custom_permissions, user_groups);
+            }
+        }
+    }
+
+    // Handing out a custom role must not let a member grant permissions beyond those which they
+    // already hold, either within the community or within any of its channels (since a custom
+    // role's channel permissions apply in every channel). Owners are exempt since they are the
+    // only ones able to define the roles in the first place.
+    pub fn can_grant_custom_role_permissions<'a>(
+        &self,
+        member: &CommunityMemberInternal,
+        community_permissions: impl IntoIterator<Item = &'a CommunityPermission>,
+        channel_permissions: impl IntoIterator<Item = &'a GroupPermission>,
+    ) -> bool {
+        if !member.role.is_permitted(self.permissions.change_roles) {
+            return false;
+        }
+        if member.role.is_owner() {
+            return true;
+        }
+
+        let channel_permissions: HashSet<_> = channel_permissions.into_iter().copied().collect();
+
+        community_permissions
+            .into_iter()
+            .all(|p| member.role.is_permitted(self.permissions.role_for(*p)))
+            && (channel_permissions.is_empty()
+                || self.channels.iter().all(|c| {
+                    c.chat.members.get(&member.user_id).map_or(false, |m| {
+                        channel_permissions.iter().all(|p| m.has_permission(*p, &c.chat.permissions))
+                    })
+                }))
+    }
+
     pub fn check_rules(&self, member: &CommunityMemberInternal) -> bool {
         !self.rules.enabled
             || member.is_bot
@@ -344,6 +449,14 @@ impl Data {
             .record(function_id, instructions_count, wasm_version, now);
     }
 
+    // Per endpoint counts are only recorded in test mode (they are used by the load tests) so that
+    // the log doesn't grow with every message in production
+    pub fn record_endpoint_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        if self.test_mode {
+            self.record_instructions_count(function_id, now);
+        }
+    }
+
     pub fn mark_community_updated_in_user_canister(&self, user_id: UserId) {
         self.fire_and_forget_handler.send(
             user_id.into(),
@@ -352,6 +465,39 @@ impl Data {
         );
     }
 
+    pub fn mirror_announcement(&self, channel_id: ChannelId, action: AnnouncementAction) {
+        for follower in self.announcements.followers(&channel_id) {
+            self.send_announcement_action(channel_id, follower, action.clone());
+        }
+    }
+
+    // Called once the channel has been removed from `announcements`, so the followers are passed in
+    pub fn notify_announcement_channel_deleted(&self, channel_id: ChannelId, followers: Vec<AnnouncementFollower>) {
+        for follower in followers.iter() {
+            self.send_announcement_action(channel_id, follower, AnnouncementAction::ChannelDeleted);
+        }
+    }
+
+    // Followers look up the channel to mirror into by the id of the source channel
+    fn send_announcement_action(&self, channel_id: ChannelId, follower: &AnnouncementFollower, action: AnnouncementAction) {
+        self.fire_and_forget_handler.send(
+            follower.community_id.into(),
+            "c2c_mirror_announcement_msgpack".to_string(),
+            serialize_then_unwrap(c2c_mirror_announcement::Args { channel_id, action }),
+        );
+    }
+
+    pub fn notify_announcement_channel_unfollowed(&self, followed: &FollowedAnnouncementChannel) {
+        self.fire_and_forget_handler.send(
+            followed.source_community_id.into(),
+            "c2c_unfollow_announcement_channel_msgpack".to_string(),
+            serialize_then_unwrap(c2c_unfollow_announcement_channel::Args {
+                channel_id: followed.source_channel_id,
+                follower_channel_id: followed.channel_id,
+            }),
+        );
+    }
+
     fn is_invite_code_valid(&self, invite_code: Option<u64>) -> bool {
         if self.invite_code_enabled {
             if let Some(provided_code) = invite_code {
@@ -365,6 +511,18 @@ impl Data {
     }
 }
 
+fn sync_channel_member_permissions(
+    channel: &mut Channel,
+    user_id: UserId,
+    custom_permissions: &HashSet<GroupPermission>,
+    user_groups: &HashSet<u32>,
+) {
+    let (mut granted, denied) = channel.user_group_permissions(user_groups);
+    granted.extend(custom_permissions.iter().copied());
+
+    channel.chat.members.set_permission_overrides(&user_id, granted, denied);
+}
+
 fn run_regular_jobs() {
     mutate_state(|state| state.regular_jobs.run(state.env.deref(), &mut state.data));
 }
@@ -386,6 +544,7 @@ pub struct Metrics {
     pub frozen: bool,
     pub groups_being_imported: Vec<GroupBeingImportedSummary>,
     pub instruction_counts: Vec<InstructionCountEntry>,
+    pub timer_jobs: u32,
     pub canister_ids: CanisterIds,
 }
 
@@ -1,4 +1,5 @@
 use crate::lifecycle::{init_env, init_state};
+use crate::memory::{get_log_overflow_data_memory, get_log_overflow_index_memory};
 use crate::updates::import_group::commit_group_to_import;
 use crate::{mutate_state, Data};
 use canister_tracing_macros::trace;
@@ -12,6 +13,7 @@ use utils::env::Environment;
 #[trace]
 fn init(args: Args) {
     canister_logger::init(args.test_mode);
+    canister_logger::init_log_overflow(get_log_overflow_index_memory(), get_log_overflow_data_memory());
 
     let mut env = init_env();
 
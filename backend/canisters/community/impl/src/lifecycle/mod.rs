@@ -11,7 +11,7 @@ mod inspect_message;
 mod post_upgrade;
 mod pre_upgrade;
 
-const UPGRADE_BUFFER_SIZE: usize = 1024 * 1024; // 1MB
+pub const UPGRADE_BUFFER_SIZE: usize = 1024 * 1024; // 1MB
 
 fn init_env() -> Box<CanisterEnv> {
     ic_cdk_timers::set_timer(Duration::ZERO, reseed_rng);
@@ -1,6 +1,6 @@
 use crate::jobs::import_groups::finalize_group_import;
 use crate::lifecycle::{init_env, init_state, UPGRADE_BUFFER_SIZE};
-use crate::memory::get_upgrades_memory;
+use crate::memory::{get_log_overflow_data_memory, get_log_overflow_index_memory, get_upgrades_memory};
 use crate::{read_state, Data};
 use canister_logger::LogEntry;
 use canister_tracing_macros::trace;
@@ -20,6 +20,7 @@ fn post_upgrade(args: Args) {
 
     let (data, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>) = serializer::deserialize(reader).unwrap();
 
+    canister_logger::init_log_overflow(get_log_overflow_index_memory(), get_log_overflow_data_memory());
     canister_logger::init_with_logs(data.test_mode, logs, traces);
 
     init_state(env, data, args.wasm_version);
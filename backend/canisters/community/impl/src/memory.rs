@@ -6,6 +6,8 @@ use ic_stable_structures::{
 const UPGRADES: MemoryId = MemoryId::new(0);
 const INSTRUCTION_COUNTS_INDEX: MemoryId = MemoryId::new(1);
 const INSTRUCTION_COUNTS_DATA: MemoryId = MemoryId::new(2);
+const LOG_OVERFLOW_INDEX: MemoryId = MemoryId::new(3);
+const LOG_OVERFLOW_DATA: MemoryId = MemoryId::new(4);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -26,6 +28,14 @@ pub fn get_instruction_counts_data_memory() -> Memory {
     get_memory(INSTRUCTION_COUNTS_DATA)
 }
 
+pub fn get_log_overflow_index_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_INDEX)
+}
+
+pub fn get_log_overflow_data_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_DATA)
+}
+
 fn get_memory(id: MemoryId) -> Memory {
     MEMORY_MANAGER.with(|m| m.get(id))
 }
@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use types::{BlobReference, ChannelId, CommunityId, MessageContentInitial, TimestampMillis};
+
+const MAX_FOLLOWERS_PER_CHANNEL: usize = 1000;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Announcements {
+    // The channels in other communities which mirror each of this community's announcement channels
+    followers: HashMap<ChannelId, Vec<AnnouncementFollower>>,
+    // The announcement channels in other communities which are mirrored into this community's channels
+    following: Vec<FollowedAnnouncementChannel>,
+    // The ids of the files within mirrored messages. These files belong to the source community so
+    // they must not be deleted when the mirrored messages are deleted from this community
+    #[serde(default)]
+    mirrored_files: HashSet<u128>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AnnouncementFollower {
+    pub community_id: CommunityId,
+    pub channel_id: ChannelId,
+    pub since: TimestampMillis,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FollowedAnnouncementChannel {
+    pub source_community_id: CommunityId,
+    pub source_channel_id: ChannelId,
+    pub channel_id: ChannelId,
+    pub since: TimestampMillis,
+}
+
+impl Announcements {
+    pub fn add_follower(
+        &mut self,
+        channel_id: ChannelId,
+        community_id: CommunityId,
+        follower_channel_id: ChannelId,
+        now: TimestampMillis,
+    ) -> AddFollowerResult {
+        let followers = self.followers.entry(channel_id).or_default();
+        if followers
+            .iter()
+            .any(|f| f.community_id == community_id && f.channel_id == follower_channel_id)
+        {
+            AddFollowerResult::AlreadyFollowing
+        } else if followers.len() >= MAX_FOLLOWERS_PER_CHANNEL {
+            AddFollowerResult::TooManyFollowers
+        } else {
+            followers.push(AnnouncementFollower {
+                community_id,
+                channel_id: follower_channel_id,
+                since: now,
+            });
+            AddFollowerResult::Success
+        }
+    }
+
+    pub fn remove_follower(
+        &mut self,
+        channel_id: ChannelId,
+        community_id: CommunityId,
+        follower_channel_id: ChannelId,
+    ) -> bool {
+        if let Some(followers) = self.followers.get_mut(&channel_id) {
+            let original_len = followers.len();
+            followers.retain(|f| f.community_id != community_id || f.channel_id != follower_channel_id);
+            let removed = followers.len() != original_len;
+
+            if followers.is_empty() {
+                self.followers.remove(&channel_id);
+            }
+            removed
+        } else {
+            false
+        }
+    }
+
+    pub fn followers(&self, channel_id: &ChannelId) -> &[AnnouncementFollower] {
+        self.followers.get(channel_id).map_or(&[], |f| f.as_slice())
+    }
+
+    pub fn follow(
+        &mut self,
+        source_community_id: CommunityId,
+        source_channel_id: ChannelId,
+        channel_id: ChannelId,
+        now: TimestampMillis,
+    ) -> bool {
+        if self.followed_into(source_community_id, source_channel_id).is_some() {
+            false
+        } else {
+            self.following.push(FollowedAnnouncementChannel {
+                source_community_id,
+                source_channel_id,
+                channel_id,
+                since: now,
+            });
+            true
+        }
+    }
+
+    pub fn unfollow(
+        &mut self,
+        source_community_id: CommunityId,
+        source_channel_id: ChannelId,
+    ) -> Option<FollowedAnnouncementChannel> {
+        let index = self
+            .following
+            .iter()
+            .position(|f| f.source_community_id == source_community_id && f.source_channel_id == source_channel_id)?;
+
+        Some(self.following.remove(index))
+    }
+
+    // Returns the local channel into which the given announcement channel is mirrored
+    pub fn followed_into(&self, source_community_id: CommunityId, source_channel_id: ChannelId) -> Option<ChannelId> {
+        self.following
+            .iter()
+            .find(|f| f.source_community_id == source_community_id && f.source_channel_id == source_channel_id)
+            .map(|f| f.channel_id)
+    }
+
+    // Removes all announcement links involving the channel, returning the communities mirroring it
+    // and the announcement channels in other communities which were being mirrored into it so
+    // that each of them can be notified
+    pub fn remove_channel(&mut self, channel_id: &ChannelId) -> RemovedAnnouncementLinks {
+        let followers = self.followers.remove(channel_id).unwrap_or_default();
+
+        let (following, retained) = std::mem::take(&mut self.following)
+            .into_iter()
+            .partition(|f| f.channel_id == *channel_id);
+
+        self.following = retained;
+
+        RemovedAnnouncementLinks { followers, following }
+    }
+
+    pub fn add_mirrored_files(&mut self, files: Vec<BlobReference>) {
+        self.mirrored_files.extend(files.into_iter().map(|f| f.blob_id));
+    }
+
+    // Removes any files belonging to mirrored messages from `files`, leaving only those files
+    // which are owned by this community
+    pub fn retain_owned_files(&mut self, files: &mut Vec<BlobReference>) {
+        files.retain(|f| !self.mirrored_files.remove(&f.blob_id));
+    }
+}
+
+pub enum AddFollowerResult {
+    Success,
+    AlreadyFollowing,
+    TooManyFollowers,
+}
+
+pub struct RemovedAnnouncementLinks {
+    pub followers: Vec<AnnouncementFollower>,
+    pub following: Vec<FollowedAnnouncementChannel>,
+}
+
+// Only content which makes sense outside of the source community is mirrored, so eg. polls, prizes
+// and crypto transfers are excluded
+pub fn can_be_mirrored(content: &MessageContentInitial) -> bool {
+    matches!(
+        content,
+        MessageContentInitial::Text(_)
+            | MessageContentInitial::Image(_)
+            | MessageContentInitial::Video(_)
+            | MessageContentInitial::Audio(_)
+            | MessageContentInitial::File(_)
+            | MessageContentInitial::Giphy(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::CanisterId;
+
+    fn community(id: u8) -> CommunityId {
+        CanisterId::from_slice(&[id]).into()
+    }
+
+    #[test]
+    fn deleting_channel_removes_links_in_both_directions() {
+        let mut announcements = Announcements::default();
+        let announcement_channel: ChannelId = 1;
+        let mirror_channel: ChannelId = 2;
+
+        assert!(matches!(
+            announcements.add_follower(announcement_channel, community(1), 10, 1),
+            AddFollowerResult::Success
+        ));
+        assert!(matches!(
+            announcements.add_follower(announcement_channel, community(1), 10, 1),
+            AddFollowerResult::AlreadyFollowing
+        ));
+        assert!(matches!(
+            announcements.add_follower(announcement_channel, community(2), 20, 1),
+            AddFollowerResult::Success
+        ));
+        assert!(announcements.follow(community(3), 30, mirror_channel, 1));
+        assert!(!announcements.follow(community(3), 30, 3, 1));
+
+        assert_eq!(announcements.followers(&announcement_channel).len(), 2);
+        assert_eq!(announcements.followed_into(community(3), 30), Some(mirror_channel));
+
+        assert!(announcements.remove_follower(announcement_channel, community(1), 10));
+        assert_eq!(announcements.followers(&announcement_channel).len(), 1);
+
+        let removed = announcements.remove_channel(&announcement_channel);
+        assert_eq!(removed.followers.len(), 1);
+        assert!(announcements.followers(&announcement_channel).is_empty());
+
+        let removed = announcements.remove_channel(&mirror_channel);
+        assert_eq!(removed.following.len(), 1);
+        assert!(announcements.followed_into(community(3), 30).is_none());
+    }
+
+    #[test]
+    fn followers_per_channel_are_capped() {
+        let mut announcements = Announcements::default();
+
+        for i in 0..MAX_FOLLOWERS_PER_CHANNEL {
+            assert!(matches!(
+                announcements.add_follower(1, community(1), i as ChannelId, 1),
+                AddFollowerResult::Success
+            ));
+        }
+
+        assert!(matches!(
+            announcements.add_follower(1, community(2), 1, 1),
+            AddFollowerResult::TooManyFollowers
+        ));
+    }
+
+    #[test]
+    fn mirrored_files_are_not_deleted() {
+        let mut announcements = Announcements::default();
+        let file = |blob_id| BlobReference {
+            canister_id: CanisterId::from_slice(&[1]),
+            blob_id,
+        };
+
+        announcements.add_mirrored_files(vec![file(1)]);
+
+        let mut files = vec![file(1), file(2)];
+        announcements.retain_owned_files(&mut files);
+        assert_eq!(files, vec![file(2)]);
+    }
+}
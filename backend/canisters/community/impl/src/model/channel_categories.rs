@@ -0,0 +1,308 @@
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use types::{ChannelCategory as ChannelCategorySummary, ChannelId, GroupPermissions, TimestampMillis, Timestamped};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ChannelCategories {
+    // Categories are stored in the order in which they should be displayed
+    categories: Vec<ChannelCategory>,
+    deleted: BTreeMap<TimestampMillis, Vec<u32>>,
+    order_last_updated: TimestampMillis,
+    last_updated: TimestampMillis,
+}
+
+impl ChannelCategories {
+    pub fn create<R: RngCore>(
+        &mut self,
+        name: String,
+        collapsed_by_default: bool,
+        default_permissions: Option<GroupPermissions>,
+        channels: Vec<ChannelId>,
+        rng: &mut R,
+        now: TimestampMillis,
+    ) -> Option<u32> {
+        if self.name_taken(&name, None) {
+            None
+        } else {
+            let id = self.generate_id(rng);
+            let channels = self.take_channels(channels, now);
+
+            self.categories.push(ChannelCategory {
+                id,
+                name: Timestamped::new(name, now),
+                collapsed_by_default: Timestamped::new(collapsed_by_default, now),
+                channels: Timestamped::new(channels, now),
+                default_permissions: Timestamped::new(default_permissions, now),
+            });
+            self.order_last_updated = now;
+            self.last_updated = now;
+
+            Some(id)
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        id: u32,
+        name: Option<String>,
+        collapsed_by_default: Option<bool>,
+        default_permissions: Option<Option<GroupPermissions>>,
+        channels: Option<Vec<ChannelId>>,
+        now: TimestampMillis,
+    ) -> UpdateChannelCategoryResult {
+        if !self.categories.iter().any(|c| c.id == id) {
+            return UpdateChannelCategoryResult::NotFound;
+        }
+        if name.as_ref().map_or(false, |n| self.name_taken(n, Some(id))) {
+            return UpdateChannelCategoryResult::NameTaken;
+        }
+
+        // Any channels moved into this category must first be removed from their previous category
+        let channels = channels.map(|c| self.take_channels(c, now));
+
+        let category = self.categories.iter_mut().find(|c| c.id == id).unwrap();
+        if let Some(name) = name {
+            category.name = Timestamped::new(name, now);
+        }
+        if let Some(collapsed) = collapsed_by_default {
+            category.collapsed_by_default = Timestamped::new(collapsed, now);
+        }
+        if let Some(permissions) = default_permissions {
+            category.default_permissions = Timestamped::new(permissions, now);
+        }
+        if let Some(channels) = channels {
+            category.channels = Timestamped::new(channels, now);
+        }
+        self.last_updated = now;
+        UpdateChannelCategoryResult::Success
+    }
+
+    // Categories not included in `ids` keep their relative order and are placed after those which are
+    pub fn reorder(&mut self, ids: Vec<u32>, now: TimestampMillis) {
+        let mut categories = Vec::with_capacity(self.categories.len());
+        for id in ids {
+            if let Some(index) = self.categories.iter().position(|c| c.id == id) {
+                categories.push(self.categories.remove(index));
+            }
+        }
+        categories.append(&mut self.categories);
+
+        self.categories = categories;
+        self.order_last_updated = now;
+        self.last_updated = now;
+    }
+
+    pub fn delete(&mut self, id: u32, now: TimestampMillis) -> bool {
+        let original_len = self.categories.len();
+        self.categories.retain(|c| c.id != id);
+
+        if self.categories.len() != original_len {
+            self.deleted.entry(now).or_default().push(id);
+            self.order_last_updated = now;
+            self.last_updated = now;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn remove_channel(&mut self, channel_id: &ChannelId, now: TimestampMillis) {
+        for category in self.categories.iter_mut() {
+            if category.channels.update(|c| remove_channel(c, channel_id), now) {
+                self.last_updated = now;
+            }
+        }
+    }
+
+    pub fn add_channel(&mut self, id: u32, channel_id: ChannelId, now: TimestampMillis) -> bool {
+        self.remove_channel(&channel_id, now);
+
+        if let Some(category) = self.categories.iter_mut().find(|c| c.id == id) {
+            category.channels.update(
+                |c| {
+                    c.push(channel_id);
+                    true
+                },
+                now,
+            );
+            self.last_updated = now;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn get(&self, id: u32) -> Option<&ChannelCategory> {
+        self.categories.iter().find(|c| c.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ChannelCategory> {
+        self.categories.iter()
+    }
+
+    pub fn ordering(&self) -> Vec<u32> {
+        self.categories.iter().map(|c| c.id).collect()
+    }
+
+    pub fn order_last_updated(&self) -> TimestampMillis {
+        self.order_last_updated
+    }
+
+    pub fn last_updated(&self) -> TimestampMillis {
+        self.last_updated
+    }
+
+    pub fn deleted_since(&self, since: TimestampMillis) -> Vec<u32> {
+        self.deleted
+            .iter()
+            .rev()
+            .take_while(|(&k, _)| k > since)
+            .flat_map(|(_, v)| v)
+            .copied()
+            .collect()
+    }
+
+    fn take_channels(&mut self, channels: Vec<ChannelId>, now: TimestampMillis) -> Vec<ChannelId> {
+        let mut unique = HashSet::new();
+        let channels: Vec<_> = channels.into_iter().filter(|c| unique.insert(*c)).collect();
+
+        for channel_id in channels.iter() {
+            self.remove_channel(channel_id, now);
+        }
+        channels
+    }
+
+    fn name_taken(&self, name: &str, excluding: Option<u32>) -> bool {
+        let name_upper = name.to_uppercase();
+        self.categories
+            .iter()
+            .any(|c| Some(c.id) != excluding && c.name.to_uppercase() == name_upper)
+    }
+
+    fn generate_id<R: RngCore>(&self, rng: &mut R) -> u32 {
+        let ids: HashSet<_> = self.categories.iter().map(|c| c.id).collect();
+
+        loop {
+            let id: u32 = rng.gen();
+            if !ids.contains(&id) {
+                return id;
+            }
+        }
+    }
+}
+
+fn remove_channel(channels: &mut Vec<ChannelId>, channel_id: &ChannelId) -> bool {
+    let original_len = channels.len();
+    channels.retain(|c| c != channel_id);
+    channels.len() != original_len
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChannelCategory {
+    pub id: u32,
+    pub name: Timestamped<String>,
+    pub collapsed_by_default: Timestamped<bool>,
+    pub channels: Timestamped<Vec<ChannelId>>,
+    pub default_permissions: Timestamped<Option<GroupPermissions>>,
+}
+
+impl ChannelCategory {
+    pub fn last_updated(&self) -> TimestampMillis {
+        [
+            self.name.timestamp,
+            self.collapsed_by_default.timestamp,
+            self.channels.timestamp,
+            self.default_permissions.timestamp,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default()
+    }
+}
+
+pub enum UpdateChannelCategoryResult {
+    Success,
+    NotFound,
+    NameTaken,
+}
+
+impl From<&ChannelCategory> for ChannelCategorySummary {
+    fn from(value: &ChannelCategory) -> Self {
+        ChannelCategorySummary {
+            category_id: value.id,
+            name: value.name.value.clone(),
+            collapsed_by_default: value.collapsed_by_default.value,
+            channels: value.channels.value.clone(),
+            default_permissions: value.default_permissions.value.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn channels_belong_to_at_most_one_category() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut categories = ChannelCategories::default();
+        let channel1: ChannelId = 1;
+        let channel2: ChannelId = 2;
+
+        let general = categories
+            .create(
+                "General".to_string(),
+                false,
+                None,
+                vec![channel1, channel2, channel1],
+                &mut rng,
+                1,
+            )
+            .unwrap();
+        let governance = categories
+            .create("Governance".to_string(), true, None, vec![channel2], &mut rng, 2)
+            .unwrap();
+
+        assert_eq!(categories.get(general).unwrap().channels.value, vec![channel1]);
+        assert_eq!(categories.get(governance).unwrap().channels.value, vec![channel2]);
+        assert_eq!(categories.get(general).unwrap().last_updated(), 2);
+
+        assert!(categories.add_channel(governance, channel1, 3));
+        assert!(categories.get(general).unwrap().channels.is_empty());
+        assert_eq!(categories.get(governance).unwrap().channels.value, vec![channel2, channel1]);
+
+        categories.remove_channel(&channel2, 4);
+        assert_eq!(categories.get(governance).unwrap().channels.value, vec![channel1]);
+    }
+
+    #[test]
+    fn reorder_and_delete_update_ordering() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut categories = ChannelCategories::default();
+
+        let a = categories
+            .create("A".to_string(), false, None, Vec::new(), &mut rng, 1)
+            .unwrap();
+        let b = categories
+            .create("B".to_string(), false, None, Vec::new(), &mut rng, 1)
+            .unwrap();
+        let c = categories
+            .create("C".to_string(), false, None, Vec::new(), &mut rng, 1)
+            .unwrap();
+        assert!(categories
+            .create("a".to_string(), false, None, Vec::new(), &mut rng, 1)
+            .is_none());
+
+        categories.reorder(vec![c, a], 2);
+        assert_eq!(categories.ordering(), vec![c, a, b]);
+        assert_eq!(categories.order_last_updated(), 2);
+
+        assert!(categories.delete(a, 3));
+        assert_eq!(categories.ordering(), vec![c, b]);
+        assert_eq!(categories.order_last_updated(), 3);
+        assert_eq!(categories.deleted_since(2), vec![a]);
+    }
+}
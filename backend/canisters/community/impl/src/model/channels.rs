@@ -1,14 +1,14 @@
 use chat_events::Reader;
-use group_chat_core::{GroupChatCore, GroupMemberInternal, LeaveResult};
+use group_chat_core::{GroupChatCore, GroupMemberInternal, LeaveResult, ProposalVoteFollows};
 use search::*;
 use serde::{Deserialize, Serialize};
 use std::cmp::Reverse;
 use std::collections::hash_map::Entry::Vacant;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use types::{
     ChannelId, ChannelMatch, ChannelMembership, ChannelMembershipUpdates, CommunityCanisterChannelSummary,
-    CommunityCanisterChannelSummaryUpdates, GroupPermissionRole, GroupPermissions, Rules, TimestampMillis, Timestamped, UserId,
-    MAX_THREADS_IN_SUMMARY,
+    CommunityCanisterChannelSummaryUpdates, GroupPermission, GroupPermissionRole, GroupPermissions, Rules, TimestampMillis,
+    Timestamped, UserGroupPermissionOverride, UserId, MAX_THREADS_IN_SUMMARY,
 };
 
 use super::members::CommunityMembers;
@@ -23,6 +23,14 @@ pub struct Channel {
     pub id: ChannelId,
     pub chat: GroupChatCore,
     pub date_imported: Option<TimestampMillis>,
+    #[serde(default)]
+    pub user_group_overrides: Timestamped<Vec<UserGroupPermissionOverride>>,
+    // Only designated roles can post in announcement channels, their messages are mirrored into
+    // the channels of any other communities which follow them
+    #[serde(default)]
+    pub is_announcement: bool,
+    #[serde(default)]
+    pub proposal_vote_follows: ProposalVoteFollows,
 }
 
 impl Channels {
@@ -142,6 +150,12 @@ impl Channels {
         (matches, total)
     }
 
+    pub fn remove_user_group_overrides(&mut self, user_group_id: u32, now: TimestampMillis) {
+        for channel in self.channels.values_mut() {
+            channel.set_user_group_override(user_group_id, HashSet::new(), HashSet::new(), now);
+        }
+    }
+
     pub fn is_name_taken(&self, name: &str) -> bool {
         let lowercase_name = name.to_lowercase();
 
@@ -181,9 +195,58 @@ impl Channel {
                 now,
             ),
             date_imported: None,
+            user_group_overrides: Timestamped::default(),
+            is_announcement: false,
+            proposal_vote_follows: ProposalVoteFollows::default(),
         }
     }
 
+    // Replaces the override for the user group, passing in no permissions removes the override
+    pub fn set_user_group_override(
+        &mut self,
+        user_group_id: u32,
+        allow: HashSet<GroupPermission>,
+        deny: HashSet<GroupPermission>,
+        now: TimestampMillis,
+    ) -> bool {
+        self.user_group_overrides.update(
+            |overrides| {
+                let original_len = overrides.len();
+                overrides.retain(|o| o.user_group_id != user_group_id);
+                let removed = overrides.len() != original_len;
+
+                if allow.is_empty() && deny.is_empty() {
+                    removed
+                } else {
+                    overrides.push(UserGroupPermissionOverride {
+                        user_group_id,
+                        allow: allow.into_iter().collect(),
+                        deny: deny.into_iter().collect(),
+                    });
+                    true
+                }
+            },
+            now,
+        )
+    }
+
+    // Returns the permissions (allowed, denied) for a member of the given user groups
+    pub fn user_group_permissions(&self, user_groups: &HashSet<u32>) -> (HashSet<GroupPermission>, HashSet<GroupPermission>) {
+        let mut allowed = HashSet::new();
+        let mut denied = HashSet::new();
+
+        for o in self
+            .user_group_overrides
+            .iter()
+            .filter(|o| user_groups.contains(&o.user_group_id))
+        {
+            allowed.extend(o.allow.iter().copied());
+            denied.extend(o.deny.iter().copied());
+        }
+
+        (allowed, denied)
+    }
+
     pub fn summary(
         &self,
         user_id: Option<UserId>,
@@ -259,12 +322,16 @@ impl Channel {
             date_last_pinned: chat.date_last_pinned,
             events_ttl: chat.events.get_events_time_to_live().value,
             gate: chat.gate.value.clone(),
+            user_group_permission_overrides: self.user_group_overrides.value.clone(),
+            is_announcement: self.is_announcement,
             membership,
         })
     }
 
     pub fn has_updates_since(&self, user_id: Option<UserId>, since: TimestampMillis) -> bool {
-        self.chat.has_updates_since(user_id, since) || self.date_imported.unwrap_or_default() > since
+        self.chat.has_updates_since(user_id, since)
+            || self.date_imported.unwrap_or_default() > since
+            || self.user_group_overrides.timestamp > since
     }
 
     pub fn summary_updates(
@@ -341,6 +408,7 @@ impl Channel {
             date_last_pinned: updates_from_events.date_last_pinned,
             events_ttl: updates_from_events.events_ttl,
             gate: updates_from_events.gate,
+            user_group_permission_overrides: self.user_group_overrides.if_set_after(since).cloned(),
             membership,
         })
     }
@@ -0,0 +1,286 @@
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::cmp::max;
+use std::collections::{BTreeMap, HashSet};
+use types::{CommunityPermission, CustomRoleDetails, GroupPermission, TimestampMillis, Timestamped, UserId};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct CustomRoles {
+    roles: Vec<CustomRole>,
+    deleted: BTreeMap<TimestampMillis, Vec<u32>>,
+    last_updated: TimestampMillis,
+}
+
+impl CustomRoles {
+    pub fn create<R: RngCore>(
+        &mut self,
+        name: String,
+        community_permissions: HashSet<CommunityPermission>,
+        channel_permissions: HashSet<GroupPermission>,
+        rng: &mut R,
+        now: TimestampMillis,
+    ) -> Option<u32> {
+        if self.name_taken(&name, None) {
+            None
+        } else {
+            let id = self.generate_id(rng);
+
+            self.roles.push(CustomRole {
+                id,
+                name: Timestamped::new(name, now),
+                community_permissions: Timestamped::new(community_permissions, now),
+                channel_permissions: Timestamped::new(channel_permissions, now),
+                users: Timestamped::new(HashSet::new(), now),
+                user_groups: Timestamped::new(HashSet::new(), now),
+            });
+            self.last_updated = now;
+
+            Some(id)
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        id: u32,
+        name: Option<String>,
+        community_permissions: Option<HashSet<CommunityPermission>>,
+        channel_permissions: Option<HashSet<GroupPermission>>,
+        now: TimestampMillis,
+    ) -> UpdateCustomRoleResult {
+        if name.as_ref().map_or(false, |n| self.name_taken(n, Some(id))) {
+            return UpdateCustomRoleResult::NameTaken;
+        }
+
+        if let Some(role) = self.roles.iter_mut().find(|r| r.id == id) {
+            if let Some(name) = name {
+                role.name = Timestamped::new(name, now);
+            }
+            if let Some(permissions) = community_permissions {
+                role.community_permissions = Timestamped::new(permissions, now);
+            }
+            if let Some(permissions) = channel_permissions {
+                role.channel_permissions = Timestamped::new(permissions, now);
+            }
+            self.last_updated = now;
+            UpdateCustomRoleResult::Success
+        } else {
+            UpdateCustomRoleResult::NotFound
+        }
+    }
+
+    pub fn assign(
+        &mut self,
+        id: u32,
+        users_to_add: Vec<UserId>,
+        users_to_remove: Vec<UserId>,
+        user_groups_to_add: Vec<u32>,
+        user_groups_to_remove: Vec<u32>,
+        now: TimestampMillis,
+    ) -> bool {
+        if let Some(role) = self.roles.iter_mut().find(|r| r.id == id) {
+            if !users_to_add.is_empty() || !users_to_remove.is_empty() {
+                for user_id in users_to_remove {
+                    role.users.value.remove(&user_id);
+                }
+                role.users.value.extend(users_to_add);
+                role.users.timestamp = now;
+            }
+            if !user_groups_to_add.is_empty() || !user_groups_to_remove.is_empty() {
+                for user_group_id in user_groups_to_remove {
+                    role.user_groups.value.remove(&user_group_id);
+                }
+                role.user_groups.value.extend(user_groups_to_add);
+                role.user_groups.timestamp = now;
+            }
+            self.last_updated = now;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn delete(&mut self, id: u32, now: TimestampMillis) -> Option<CustomRole> {
+        let index = self.roles.iter().position(|r| r.id == id)?;
+        let role = self.roles.remove(index);
+
+        self.deleted.entry(now).or_default().push(id);
+        self.last_updated = now;
+        Some(role)
+    }
+
+    pub fn get(&self, id: u32) -> Option<&CustomRole> {
+        self.roles.iter().find(|r| r.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CustomRole> {
+        self.roles.iter()
+    }
+
+    // Returns the roles assigned to the user either directly or via one of their user groups
+    pub fn roles_for_user<'a>(
+        &'a self,
+        user_id: &'a UserId,
+        user_groups: &'a HashSet<u32>,
+    ) -> impl Iterator<Item = &'a CustomRole> + 'a {
+        self.roles
+            .iter()
+            .filter(|r| r.users.contains(user_id) || r.user_groups.iter().any(|g| user_groups.contains(g)))
+    }
+
+    pub fn remove_user_from_all(&mut self, user_id: &UserId, now: TimestampMillis) {
+        for role in self.roles.iter_mut() {
+            if role.users.update(|u| u.remove(user_id), now) {
+                self.last_updated = now;
+            }
+        }
+    }
+
+    pub fn remove_user_group_from_all(&mut self, user_group_id: u32, now: TimestampMillis) {
+        for role in self.roles.iter_mut() {
+            if role.user_groups.update(|g| g.remove(&user_group_id), now) {
+                self.last_updated = now;
+            }
+        }
+    }
+
+    pub fn last_updated(&self) -> TimestampMillis {
+        self.last_updated
+    }
+
+    pub fn deleted_since(&self, since: TimestampMillis) -> Vec<u32> {
+        self.deleted
+            .iter()
+            .rev()
+            .take_while(|(&k, _)| k > since)
+            .flat_map(|(_, v)| v)
+            .copied()
+            .collect()
+    }
+
+    fn name_taken(&self, name: &str, excluding: Option<u32>) -> bool {
+        let name_upper = name.to_uppercase();
+        self.roles
+            .iter()
+            .any(|r| Some(r.id) != excluding && r.name.to_uppercase() == name_upper)
+    }
+
+    fn generate_id<R: RngCore>(&self, rng: &mut R) -> u32 {
+        let ids: HashSet<_> = self.roles.iter().map(|r| r.id).collect();
+
+        loop {
+            let id: u32 = rng.gen();
+            if !ids.contains(&id) {
+                return id;
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CustomRole {
+    pub id: u32,
+    pub name: Timestamped<String>,
+    pub community_permissions: Timestamped<HashSet<CommunityPermission>>,
+    pub channel_permissions: Timestamped<HashSet<GroupPermission>>,
+    pub users: Timestamped<HashSet<UserId>>,
+    pub user_groups: Timestamped<HashSet<u32>>,
+}
+
+impl CustomRole {
+    pub fn last_updated(&self) -> TimestampMillis {
+        [
+            self.name.timestamp,
+            self.community_permissions.timestamp,
+            self.channel_permissions.timestamp,
+            self.users.timestamp,
+            self.user_groups.timestamp,
+        ]
+        .into_iter()
+        .fold(0, max)
+    }
+}
+
+pub enum UpdateCustomRoleResult {
+    Success,
+    NotFound,
+    NameTaken,
+}
+
+impl From<&CustomRole> for CustomRoleDetails {
+    fn from(value: &CustomRole) -> Self {
+        CustomRoleDetails {
+            role_id: value.id,
+            name: value.name.value.clone(),
+            community_permissions: value.community_permissions.iter().copied().collect(),
+            channel_permissions: value.channel_permissions.iter().copied().collect(),
+            users: value.users.iter().copied().collect(),
+            user_groups: value.user_groups.iter().copied().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use types::CanisterId;
+
+    fn user(id: u8) -> UserId {
+        UserId::new(CanisterId::from_slice(&[id]))
+    }
+
+    #[test]
+    fn roles_assigned_directly_or_via_user_groups() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut roles = CustomRoles::default();
+
+        let host = roles
+            .create(
+                "Event host".to_string(),
+                HashSet::new(),
+                HashSet::from([GroupPermission::PinMessages]),
+                &mut rng,
+                1,
+            )
+            .unwrap();
+        let translator = roles
+            .create(
+                "Translator".to_string(),
+                HashSet::new(),
+                HashSet::from([GroupPermission::SendMessages]),
+                &mut rng,
+                1,
+            )
+            .unwrap();
+
+        assert!(roles
+            .create("event HOST".to_string(), HashSet::new(), HashSet::new(), &mut rng, 1)
+            .is_none());
+
+        assert!(roles.assign(host, vec![user(1)], Vec::new(), Vec::new(), Vec::new(), 2));
+        assert!(roles.assign(translator, Vec::new(), Vec::new(), vec![10], Vec::new(), 2));
+
+        let no_groups = HashSet::new();
+        let in_group = HashSet::from([10]);
+
+        assert_eq!(role_ids(&roles, user(1), &no_groups), vec![host]);
+        assert_eq!(role_ids(&roles, user(2), &in_group), vec![translator]);
+        let mut both = vec![host, translator];
+        both.sort();
+        assert_eq!(role_ids(&roles, user(1), &in_group), both);
+
+        roles.remove_user_group_from_all(10, 3);
+        assert!(role_ids(&roles, user(2), &in_group).is_empty());
+
+        assert!(roles.delete(host, 4).is_some());
+        assert!(role_ids(&roles, user(1), &no_groups).is_empty());
+        assert_eq!(roles.deleted_since(3), vec![host]);
+    }
+
+    fn role_ids(roles: &CustomRoles, user_id: UserId, user_groups: &HashSet<u32>) -> Vec<u32> {
+        let mut ids: Vec<_> = roles.roles_for_user(&user_id, user_groups).map(|r| r.id).collect();
+        ids.sort();
+        ids
+    }
+}
@@ -1,10 +1,14 @@
+use crate::model::custom_roles::{CustomRole, CustomRoles, UpdateCustomRoleResult};
 use crate::model::user_groups::{UserGroup, UserGroups};
 use candid::Principal;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Entry::Vacant;
 use std::collections::{HashMap, HashSet};
-use types::{ChannelId, CommunityMember, CommunityPermissions, CommunityRole, TimestampMillis, Timestamped, UserId, Version};
+use types::{
+    ChannelId, CommunityMember, CommunityPermission, CommunityPermissions, CommunityRole, GroupPermission, TimestampMillis,
+    Timestamped, UserId, Version,
+};
 
 const MAX_MEMBERS_PER_COMMUNITY: u32 = 100_000;
 
@@ -13,6 +17,8 @@ pub struct CommunityMembers {
     members: HashMap<UserId, CommunityMemberInternal>,
     display_names_last_updated: TimestampMillis,
     user_groups: UserGroups,
+    #[serde(default)]
+    custom_roles: CustomRoles,
     // This includes the userIds of community members and also users invited to the community
     principal_to_user_id_map: HashMap<Principal, UserId>,
     blocked: HashSet<UserId>,
@@ -43,6 +49,7 @@ impl CommunityMembers {
             members: vec![(creator_user_id, member)].into_iter().collect(),
             display_names_last_updated: now,
             user_groups: UserGroups::default(),
+            custom_roles: CustomRoles::default(),
             principal_to_user_id_map: vec![(creator_principal, creator_user_id)].into_iter().collect(),
             blocked: HashSet::new(),
             admin_count: 0,
@@ -95,6 +102,7 @@ impl CommunityMembers {
                 }
 
                 self.user_groups.remove_user_from_all(&member.user_id, now);
+                self.custom_roles.remove_user_from_all(&member.user_id, now);
 
                 return Some(member);
             }
@@ -200,7 +208,12 @@ impl CommunityMembers {
     }
 
     pub fn delete_user_group(&mut self, user_group_id: u32, now: TimestampMillis) -> bool {
-        self.user_groups.delete(user_group_id, now)
+        if self.user_groups.delete(user_group_id, now) {
+            self.custom_roles.remove_user_group_from_all(user_group_id, now);
+            true
+        } else {
+            false
+        }
     }
 
     pub fn get_user_group(&self, user_group_id: u32) -> Option<&UserGroup> {
@@ -219,6 +232,136 @@ impl CommunityMembers {
         self.user_groups.last_updated()
     }
 
+    pub fn create_custom_role<R: RngCore>(
+        &mut self,
+        name: String,
+        community_permissions: HashSet<CommunityPermission>,
+        channel_permissions: HashSet<GroupPermission>,
+        rng: &mut R,
+        now: TimestampMillis,
+    ) -> Option<u32> {
+        self.custom_roles
+            .create(name, community_permissions, channel_permissions, rng, now)
+    }
+
+    pub fn update_custom_role(
+        &mut self,
+        role_id: u32,
+        name: Option<String>,
+        community_permissions: Option<HashSet<CommunityPermission>>,
+        channel_permissions: Option<HashSet<GroupPermission>>,
+        now: TimestampMillis,
+    ) -> UpdateCustomRoleResult {
+        self.custom_roles
+            .update(role_id, name, community_permissions, channel_permissions, now)
+    }
+
+    pub fn assign_custom_role(
+        &mut self,
+        role_id: u32,
+        mut users_to_add: Vec<UserId>,
+        users_to_remove: Vec<UserId>,
+        mut user_groups_to_add: Vec<u32>,
+        user_groups_to_remove: Vec<u32>,
+        now: TimestampMillis,
+    ) -> bool {
+        users_to_add.retain(|u| self.members.contains_key(u));
+        user_groups_to_add.retain(|g| self.user_groups.get(*g).is_some());
+
+        self.custom_roles.assign(
+            role_id,
+            users_to_add,
+            users_to_remove,
+            user_groups_to_add,
+            user_groups_to_remove,
+            now,
+        )
+    }
+
+    pub fn delete_custom_role(&mut self, role_id: u32, now: TimestampMillis) -> Option<CustomRole> {
+        self.custom_roles.delete(role_id, now)
+    }
+
+    pub fn get_custom_role(&self, role_id: u32) -> Option<&CustomRole> {
+        self.custom_roles.get(role_id)
+    }
+
+    pub fn iter_custom_roles(&self) -> impl Iterator<Item = &CustomRole> {
+        self.custom_roles.iter()
+    }
+
+    pub fn custom_roles_deleted_since(&self, since: TimestampMillis) -> Vec<u32> {
+        self.custom_roles.deleted_since(since)
+    }
+
+    pub fn custom_roles_last_updated(&self) -> TimestampMillis {
+        self.custom_roles.last_updated()
+    }
+
+    // The users whose permissions depend on the given role, either because the role has been
+    // assigned to them directly or to one of their user groups
+    pub fn users_with_custom_role(&self, role: &CustomRole) -> HashSet<UserId> {
+        role.users
+            .iter()
+            .copied()
+            .chain(
+                role.user_groups
+                    .iter()
+                    .filter_map(|g| self.user_groups.get(*g))
+                    .flat_map(|g| g.members.iter().copied()),
+            )
+            .collect()
+    }
+
+    pub fn custom_role_ids(&self, user_id: &UserId) -> Vec<u32> {
+        let user_groups = self.user_group_ids(user_id);
+        self.custom_roles
+            .roles_for_user(user_id, &user_groups)
+            .map(|r| r.id)
+            .collect()
+    }
+
+    pub fn has_custom_permission(&self, user_id: &UserId, permission: CommunityPermission) -> bool {
+        let user_groups = self.user_group_ids(user_id);
+        self.custom_roles
+            .roles_for_user(user_id, &user_groups)
+            .any(|r| r.community_permissions.contains(&permission))
+    }
+
+    pub fn custom_channel_permissions(&self, user_id: &UserId, user_groups: &HashSet<u32>) -> HashSet<GroupPermission> {
+        self.custom_roles
+            .roles_for_user(user_id, user_groups)
+            .flat_map(|r| r.channel_permissions.iter().copied())
+            .collect()
+    }
+
+    // The custom roles which have been assigned to the given user group
+    pub fn custom_roles_for_user_group(&self, user_group_id: u32) -> impl Iterator<Item = &CustomRole> {
+        self.custom_roles
+            .iter()
+            .filter(move |r| r.user_groups.contains(&user_group_id))
+    }
+
+    pub fn user_group_ids(&self, user_id: &UserId) -> HashSet<u32> {
+        self.user_groups
+            .iter()
+            .filter(|g| g.members.contains(user_id))
+            .map(|g| g.id)
+            .collect()
+    }
+
+    // Builds the user group memberships of every user in a single pass over the user groups, so
+    // that syncing the permissions of many users doesn't scan every group once per user
+    pub fn user_group_ids_by_user(&self) -> HashMap<UserId, HashSet<u32>> {
+        let mut map: HashMap<UserId, HashSet<u32>> = HashMap::new();
+        for group in self.user_groups.iter() {
+            for user_id in group.members.iter() {
+                map.entry(*user_id).or_default().insert(group.id);
+            }
+        }
+        map
+    }
+
     pub fn display_names_last_updated(&self) -> TimestampMillis {
         self.display_names_last_updated
     }
@@ -1,4 +1,7 @@
+pub mod announcements;
+pub mod channel_categories;
 pub mod channels;
+pub mod custom_roles;
 pub mod events;
 pub mod groups_being_imported;
 pub mod invited_users;
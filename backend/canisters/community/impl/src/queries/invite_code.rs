@@ -2,6 +2,7 @@ use crate::read_state;
 use crate::RuntimeState;
 use community_canister::invite_code::{Response::*, *};
 use ic_cdk_macros::query;
+use types::CommunityPermission;
 
 #[query]
 fn invite_code(_: Args) -> Response {
@@ -12,7 +13,7 @@ fn invite_code_impl(state: &RuntimeState) -> Response {
     let caller = state.env.caller();
 
     if let Some(member) = state.data.members.get(caller) {
-        if member.role.can_invite_users(&state.data.permissions) {
+        if state.data.is_permitted(member, CommunityPermission::InviteUsers) {
             Success(SuccessResult {
                 code: if state.data.invite_code_enabled { state.data.invite_code } else { None },
             })
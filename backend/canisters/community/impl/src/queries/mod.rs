@@ -17,3 +17,4 @@ mod selected_updates;
 mod summary;
 mod summary_updates;
 mod thread_previews;
+mod typing_indicators;
@@ -58,6 +58,8 @@ fn summary_updates_impl(args: Args, state: &RuntimeState) -> Response {
         && state.data.events.latest_event_timestamp() <= args.updates_since
         && state.data.cached_chat_metrics.timestamp <= args.updates_since
         && state.data.members.user_groups_last_updated() <= args.updates_since
+        && state.data.members.custom_roles_last_updated() <= args.updates_since
+        && state.data.channel_categories.last_updated() <= args.updates_since
         && !member.map_or(false, |m| m.has_summary_updates_since(args.updates_since))
     {
         return SuccessNoUpdates;
@@ -108,6 +110,9 @@ fn summary_updates_impl(args: Args, state: &RuntimeState) -> Response {
                 Some(display_name) => OptionUpdate::SetToSome(display_name.clone()),
                 None => OptionUpdate::SetToNone,
             }),
+        custom_roles: (state.data.members.custom_roles_last_updated() > args.updates_since
+            || state.data.members.user_groups_last_updated() > args.updates_since)
+            .then(|| state.data.members.custom_role_ids(&m.user_id)),
     });
 
     Success(CommunityCanisterCommunitySummaryUpdates {
@@ -136,6 +141,24 @@ fn summary_updates_impl(args: Args, state: &RuntimeState) -> Response {
             .map(|u| u.into())
             .collect(),
         user_groups_deleted: state.data.members.user_groups_deleted_since(args.updates_since),
+        custom_roles: state
+            .data
+            .members
+            .iter_custom_roles()
+            .filter(|r| r.last_updated() > args.updates_since)
+            .map(|r| r.into())
+            .collect(),
+        custom_roles_deleted: state.data.members.custom_roles_deleted_since(args.updates_since),
+        channel_categories: state
+            .data
+            .channel_categories
+            .iter()
+            .filter(|c| c.last_updated() > args.updates_since)
+            .map(|c| c.into())
+            .collect(),
+        channel_categories_deleted: state.data.channel_categories.deleted_since(args.updates_since),
+        channel_category_order: (state.data.channel_categories.order_last_updated() > args.updates_since)
+            .then(|| state.data.channel_categories.ordering()),
         metrics: state.data.cached_chat_metrics.if_set_after(args.updates_since).cloned(),
     })
 }
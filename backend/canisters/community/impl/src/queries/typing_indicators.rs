@@ -0,0 +1,34 @@
+use crate::{read_state, RuntimeState};
+use community_canister::typing_indicators::{Response::*, *};
+use group_chat_core::TypingIndicatorsResult;
+use ic_cdk_macros::query;
+
+#[query]
+fn typing_indicators(args: Args) -> Response {
+    read_state(|state| typing_indicators_impl(args, state))
+}
+
+fn typing_indicators_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+
+    if let Some(member) = state.data.members.get(caller) {
+        let user_id = member.user_id;
+
+        if let Some(channel) = state.data.channels.get(&args.channel_id) {
+            let now = state.env.now();
+
+            match channel.chat.typing_indicators(user_id, args.updated_since, now) {
+                TypingIndicatorsResult::Success(indicators) => Success(SuccessResult {
+                    timestamp: now,
+                    indicators,
+                }),
+                TypingIndicatorsResult::SuccessNoUpdates => SuccessNoUpdates(now),
+                TypingIndicatorsResult::UserNotInGroup => UserNotInChannel,
+            }
+        } else {
+            ChannelNotFound
+        }
+    } else {
+        UserNotInCommunity
+    }
+}
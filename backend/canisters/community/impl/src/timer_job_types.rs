@@ -2,10 +2,11 @@ use crate::activity_notifications::handle_activity_notification;
 use crate::jobs::import_groups::{finalize_group_import, mark_import_complete, process_channel_members};
 use crate::{mutate_state, read_state};
 use canister_timer_jobs::Job;
+use community_canister::c2c_mirror_announcement::{AnnouncementAction, AnnouncementDeletion};
 use ledger_utils::process_transaction;
 use serde::{Deserialize, Serialize};
 use tracing::error;
-use types::{BlobReference, CanisterId, ChannelId, ChatId, MessageId, MessageIndex, PendingCryptoTransaction};
+use types::{BlobReference, CanisterId, ChannelId, ChatId, MessageId, MessageIndex, PendingCryptoTransaction, UserId};
 use utils::time::MINUTE_IN_MS;
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -31,6 +32,17 @@ pub struct HardDeleteMessageContentJob {
 #[derive(Serialize, Deserialize, Clone)]
 pub struct DeleteFileReferencesJob {
     pub files: Vec<BlobReference>,
+    // Set if the message was mirrored into other communities, which must then delete their copies
+    // since they reference the same files
+    #[serde(default)]
+    pub mirrored_announcement: Option<MirroredAnnouncement>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MirroredAnnouncement {
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+    pub sender: UserId,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -98,7 +110,8 @@ impl Job for HardDeleteMessageContentJob {
                     .events
                     .remove_deleted_message_content(self.thread_root_message_index, self.message_id)
             }) {
-                let files_to_delete = content.blob_references();
+                let mut files_to_delete = content.blob_references();
+                state.data.announcements.retain_owned_files(&mut files_to_delete);
                 if !files_to_delete.is_empty() {
                     // If there was already a job queued up to delete these files, cancel it
                     state.data.timer_jobs.cancel_jobs(|job| {
@@ -117,6 +130,18 @@ impl Job for HardDeleteMessageContentJob {
 
 impl Job for DeleteFileReferencesJob {
     fn execute(&self) {
+        if let Some(mirrored) = &self.mirrored_announcement {
+            read_state(|state| {
+                state.data.mirror_announcement(
+                    mirrored.channel_id,
+                    AnnouncementAction::Delete(AnnouncementDeletion {
+                        message_ids: vec![mirrored.message_id],
+                        deleted_by: mirrored.sender,
+                    }),
+                )
+            });
+        }
+
         ic_cdk::spawn(storage_bucket_client::delete_files(self.files.clone()));
     }
 }
@@ -94,7 +94,7 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response>
                 Err(UserLimitReached(limit))
             } else if let Some(channel_member) = channel.chat.members.get(&user_id) {
                 let permissions = &channel.chat.permissions;
-                if !channel_member.role.can_add_members(permissions) {
+                if !channel_member.can_add_members(permissions) {
                     return Err(NotAuthorized);
                 }
 
@@ -207,6 +207,7 @@ fn commit(
         });
 
         state.push_notification(users_added.clone(), notification);
+        state.data.sync_channel_permissions(users_added.clone());
 
         handle_activity_notification(state);
 
@@ -5,6 +5,7 @@ use canister_tracing_macros::trace;
 use chat_events::Reader;
 use community_canister::add_reaction::{Response::*, *};
 use group_chat_core::{AddRemoveReactionResult, GroupChatCore};
+use instruction_counts_log::InstructionCountFunctionId;
 use types::{ChannelReactionAddedNotification, EventIndex, EventWrapper, Message, Notification, UserId};
 
 #[update_candid_and_msgpack]
@@ -12,7 +13,13 @@ use types::{ChannelReactionAddedNotification, EventIndex, EventWrapper, Message,
 fn add_reaction(args: Args) -> Response {
     run_regular_jobs();
 
-    mutate_state(|state| add_reaction_impl(args, state))
+    mutate_state(|state| {
+        let response = add_reaction_impl(args, state);
+        state
+            .data
+            .record_endpoint_instructions_count(InstructionCountFunctionId::AddReaction, state.env.now());
+        response
+    })
 }
 
 fn add_reaction_impl(args: Args, state: &mut RuntimeState) -> Response {
@@ -0,0 +1,61 @@
+use crate::{read_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::add_referral_campaign::{Response::*, *};
+use ic_cdk_macros::update;
+use types::{CanisterId, UserId};
+use user_index_canister::c2c_add_referral_campaign::Response as C2cResponse;
+
+#[update]
+#[trace]
+async fn add_referral_campaign(args: Args) -> Response {
+    run_regular_jobs();
+
+    let (owner, user_index_canister_id) = match read_state(prepare) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    let c2c_args = user_index_canister::c2c_add_referral_campaign::Args {
+        owner,
+        name: args.name,
+        start: args.start,
+        end: args.end,
+        token: args.token,
+        rewards: args.rewards,
+        second_tier_rewards: args.second_tier_rewards,
+        budget_e8s: args.budget_e8s,
+        codes: args.codes,
+    };
+
+    match user_index_canister_c2c_client::c2c_add_referral_campaign(user_index_canister_id, &c2c_args).await {
+        Ok(C2cResponse::Success) => Success,
+        Ok(C2cResponse::AlreadyExists) => AlreadyExists,
+        Ok(C2cResponse::InvalidDates) => InvalidDates,
+        Ok(C2cResponse::TokenNotSupported) => TokenNotSupported,
+        Ok(C2cResponse::CodeAlreadyInUse(code)) => CodeAlreadyInUse(code),
+        Ok(C2cResponse::InsufficientFunds(balance)) => InsufficientFunds(balance),
+        Ok(C2cResponse::TransferFailed(error)) => TransferFailed(error),
+        Ok(C2cResponse::InternalError(error)) => InternalError(error),
+        Ok(C2cResponse::NotAuthorized) => InternalError("User index rejected the community".to_string()),
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
+
+fn prepare(state: &RuntimeState) -> Result<(UserId, CanisterId), Response> {
+    if state.data.is_frozen() {
+        return Err(CommunityFrozen);
+    }
+
+    let caller = state.env.caller();
+    if let Some(member) = state.data.members.get(caller) {
+        if member.suspended.value {
+            Err(UserSuspended)
+        } else if !member.role.is_owner() {
+            Err(UserNotCommunityOwner)
+        } else {
+            Ok((member.user_id, state.data.user_index_canister_id))
+        }
+    } else {
+        Err(UserNotInCommunity)
+    }
+}
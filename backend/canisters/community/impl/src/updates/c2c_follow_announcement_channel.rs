@@ -0,0 +1,69 @@
+use crate::model::announcements::AddFollowerResult;
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use community_canister::c2c_follow_announcement_channel::{Response::*, *};
+use types::{CanisterId, CommunityId};
+
+#[update_msgpack]
+#[trace]
+async fn c2c_follow_announcement_channel(args: Args) -> Response {
+    run_regular_jobs();
+
+    let PrepareResult {
+        caller,
+        group_index_canister_id,
+    } = match read_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    // Only other communities can follow announcement channels
+    match group_index_canister_c2c_client::is_community(group_index_canister_id, caller).await {
+        Ok(true) => mutate_state(|state| commit(args, caller, state)),
+        Ok(false) => NotAuthorized,
+        Err(error) => InternalError(error),
+    }
+}
+
+struct PrepareResult {
+    caller: CommunityId,
+    group_index_canister_id: CanisterId,
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    if state.data.is_frozen() {
+        return Err(CommunityFrozen);
+    }
+
+    if let Some(channel) = state.data.channels.get(&args.channel_id) {
+        // Only public announcement channels can be followed since their messages are copied into
+        // communities whose members may not be able to see the source channel
+        if !channel.is_announcement || !channel.chat.is_public {
+            Err(NotAnnouncementChannel)
+        } else {
+            Ok(PrepareResult {
+                caller: state.env.caller().into(),
+                group_index_canister_id: state.data.group_index_canister_id,
+            })
+        }
+    } else {
+        Err(ChannelNotFound)
+    }
+}
+
+fn commit(args: Args, caller: CommunityId, state: &mut RuntimeState) -> Response {
+    if state.data.channels.get(&args.channel_id).is_none() {
+        return ChannelNotFound;
+    }
+
+    let now = state.env.now();
+    match state
+        .data
+        .announcements
+        .add_follower(args.channel_id, caller, args.follower_channel_id, now)
+    {
+        AddFollowerResult::Success | AddFollowerResult::AlreadyFollowing => Success,
+        AddFollowerResult::TooManyFollowers => TooManyFollowers,
+    }
+}
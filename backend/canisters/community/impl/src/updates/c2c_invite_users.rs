@@ -6,7 +6,7 @@ use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
 use community_canister::c2c_invite_users::{Response::*, *};
-use types::UsersInvited;
+use types::{CommunityPermission, UsersInvited};
 
 const MAX_INVITES: usize = 100;
 
@@ -31,7 +31,7 @@ pub(crate) fn invite_users_to_community_impl(args: Args, state: &mut RuntimeStat
         }
 
         // The original caller must be authorized to invite other users
-        if !state.data.is_public && !member.role.can_invite_users(&state.data.permissions) {
+        if !state.data.is_public && !state.data.is_permitted(member, CommunityPermission::InviteUsers) {
             return NotAuthorized;
         }
 
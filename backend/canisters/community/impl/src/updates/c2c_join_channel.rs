@@ -11,6 +11,7 @@ use chat_events::ChatEventInternal;
 use community_canister::c2c_join_channel::{Response::*, *};
 use gated_groups::{check_if_passes_gate, CheckIfPassesGateResult};
 use group_chat_core::AddResult;
+use instruction_counts_log::InstructionCountFunctionId;
 use types::{AccessGate, CanisterId, ChannelId, MemberJoined, TimestampMillis, UserId};
 
 #[update_msgpack(guard = "caller_is_user_index_or_local_user_index")]
@@ -64,7 +65,13 @@ pub(crate) async fn join_channel_impl(channel_id: ChannelId, user_principal: Pri
         Err(response) => return response,
     };
 
-    mutate_state(|state| commit(channel_id, user_principal, state))
+    mutate_state(|state| {
+        let response = commit(channel_id, user_principal, state);
+        state
+            .data
+            .record_endpoint_instructions_count(InstructionCountFunctionId::JoinChannel, state.env.now());
+        response
+    })
 }
 
 fn is_permitted_to_join(
@@ -119,9 +126,11 @@ fn commit(channel_id: ChannelId, user_principal: Principal, state: &mut RuntimeS
             let now = state.env.now();
             match join_channel_unchecked(channel, member, state.data.is_public, now) {
                 AddResult::Success(_) => {
+                    let user_id = member.user_id;
                     let summary = channel
-                        .summary(Some(member.user_id), true, state.data.is_public, &state.data.members, now)
+                        .summary(Some(user_id), true, state.data.is_public, &state.data.members, now)
                         .unwrap();
+                    state.data.sync_channel_permissions([user_id]);
                     handle_activity_notification(state);
                     Success(Box::new(summary))
                 }
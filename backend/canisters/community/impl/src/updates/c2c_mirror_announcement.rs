@@ -0,0 +1,87 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use chat_events::{
+    ChatInternal, DeleteUndeleteMessagesArgs, EditMessageArgs, MessageContentInternal, PushMessageArgs, ReplyContextInternal,
+};
+use community_canister::c2c_mirror_announcement::{Response::*, *};
+use types::{CommunityId, EventIndex};
+
+#[update_msgpack]
+#[trace]
+fn c2c_mirror_announcement(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| c2c_mirror_announcement_impl(args, state))
+}
+
+fn c2c_mirror_announcement_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let source_community_id: CommunityId = state.env.caller().into();
+
+    let channel_id = match state.data.announcements.followed_into(source_community_id, args.channel_id) {
+        Some(c) => c,
+        None => return NotFollowing,
+    };
+    let channel = match state.data.channels.get_mut(&channel_id) {
+        Some(c) => c,
+        None => return NotFollowing,
+    };
+    let now = state.env.now();
+
+    match args.action {
+        AnnouncementAction::Send(message) => {
+            let content: MessageContentInternal = message.content.into();
+            state.data.announcements.add_mirrored_files(content.blob_references());
+
+            // Mirrored messages are marked as forwarded and reply to the original message so
+            // that they link back to the announcement channel they came from
+            channel.chat.events.push_message(PushMessageArgs {
+                sender: message.sender,
+                thread_root_message_index: None,
+                message_id: message.message_id,
+                content,
+                mentioned: Vec::new(),
+                replies_to: Some(ReplyContextInternal {
+                    chat_if_other: Some((ChatInternal::Channel(source_community_id, args.channel_id), None)),
+                    event_index: message.event_index,
+                }),
+                forwarded: true,
+                correlation_id: 0,
+                now,
+            });
+        }
+        AnnouncementAction::Edit(edit) => {
+            state
+                .data
+                .announcements
+                .add_mirrored_files(MessageContentInternal::from(edit.content.clone()).blob_references());
+
+            channel.chat.events.edit_message(EditMessageArgs {
+                sender: edit.sender,
+                min_visible_event_index: EventIndex::default(),
+                thread_root_message_index: None,
+                message_id: edit.message_id,
+                content: edit.content,
+                now,
+            });
+        }
+        AnnouncementAction::Delete(deletion) => {
+            channel.chat.events.delete_messages(DeleteUndeleteMessagesArgs {
+                caller: deletion.deleted_by,
+                is_admin: true,
+                min_visible_event_index: EventIndex::default(),
+                thread_root_message_index: None,
+                message_ids: deletion.message_ids,
+                now,
+            });
+        }
+        AnnouncementAction::ChannelDeleted => {
+            // The mirrored messages are kept but nothing more will be received from the source
+            state.data.announcements.unfollow(source_community_id, args.channel_id);
+        }
+    }
+
+    handle_activity_notification(state);
+    Success
+}
@@ -0,0 +1,86 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::guards::caller_is_proposals_bot;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use community_canister::c2c_send_proposal_reminder::{Response::*, *};
+use group_chat_core::SendMessageResult;
+use itertools::Itertools;
+use types::{ChannelMessageNotification, MessageContentInitial, Notification, TextContent, UserId};
+
+#[update_msgpack(guard = "caller_is_proposals_bot")]
+#[trace]
+fn c2c_send_proposal_reminder(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| c2c_send_proposal_reminder_impl(args, state))
+}
+
+fn c2c_send_proposal_reminder_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let proposals_bot_user_id = state.data.proposals_bot_user_id;
+    let channel = match state.data.channels.get_mut(&args.channel_id) {
+        Some(c) => c,
+        None => return ChannelNotFound,
+    };
+
+    let recipients = match channel.chat.proposal_reminder_recipients(args.thread_root_message_index) {
+        Some(users) => users,
+        None => return ProposalNotFound,
+    };
+
+    let now = state.env.now();
+    match channel.chat.send_message(
+        proposals_bot_user_id,
+        Some(args.thread_root_message_index),
+        args.message_id,
+        MessageContentInitial::Text(TextContent { text: args.text }),
+        None,
+        Vec::new(),
+        false,
+        None,
+        proposals_bot_user_id,
+        now,
+    ) {
+        SendMessageResult::Success(result) => {
+            let content = &result.message_event.event.content;
+            let notification = Notification::ChannelMessage(ChannelMessageNotification {
+                community_id: state.env.canister_id().into(),
+                channel_id: args.channel_id,
+                thread_root_message_index: Some(args.thread_root_message_index),
+                message_index: result.message_event.event.message_index,
+                event_index: result.message_event.index,
+                community_name: state.data.name.clone(),
+                channel_name: channel.chat.name.clone(),
+                sender: proposals_bot_user_id,
+                sender_name: "ProposalsBot".to_string(),
+                sender_display_name: None,
+                message_type: content.message_type(),
+                message_text: content.notification_text(&[], &[]),
+                image_url: None,
+                community_avatar_id: state.data.avatar.as_ref().map(|d| d.id),
+                channel_avatar_id: channel.chat.avatar.as_ref().map(|d| d.id),
+                crypto_transfer: None,
+            });
+
+            // As well as the thread's followers, reminders are pushed to members with a history of voting
+            // who are yet to vote on this proposal
+            let users_to_notify: Vec<UserId> = result
+                .users_to_notify
+                .into_iter()
+                .chain(recipients)
+                .unique()
+                .filter(|u| state.data.members.get_by_user_id(u).map_or(false, |m| !m.suspended.value))
+                .collect();
+
+            state.push_notification(users_to_notify, notification);
+            handle_activity_notification(state);
+            Success
+        }
+        SendMessageResult::ThreadMessageNotFound => ProposalNotFound,
+        _ => NotAuthorized,
+    }
+}
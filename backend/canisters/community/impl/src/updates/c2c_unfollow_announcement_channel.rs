@@ -0,0 +1,26 @@
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use community_canister::c2c_unfollow_announcement_channel::{Response::*, *};
+
+#[update_msgpack]
+#[trace]
+fn c2c_unfollow_announcement_channel(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| c2c_unfollow_announcement_channel_impl(args, state))
+}
+
+fn c2c_unfollow_announcement_channel_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let caller = state.env.caller().into();
+
+    if state
+        .data
+        .announcements
+        .remove_follower(args.channel_id, caller, args.follower_channel_id)
+    {
+        Success
+    } else {
+        NotFollowing
+    }
+}
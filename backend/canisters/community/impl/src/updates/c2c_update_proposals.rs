@@ -2,7 +2,10 @@ use crate::activity_notifications::handle_activity_notification;
 use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
+use chat_events::{MessageContentInternal, PushMessageArgs, ReplyContextInternal};
 use community_canister::c2c_update_proposals::{Response::*, *};
+use rand::Rng;
+use types::TextContent;
 
 #[update_msgpack]
 #[trace]
@@ -25,10 +28,28 @@ fn c2c_update_proposals_impl(args: Args, state: &mut RuntimeState) -> Response {
                 return UserNotInChannel;
             }
 
-            channel
-                .chat
-                .events
-                .update_proposals(member.user_id, args.proposals, state.env.now());
+            let now = state.env.now();
+            let closed = channel.chat.events.update_proposals(member.user_id, args.proposals, now);
+
+            // Post a summary of how members voted on each proposal which has just closed
+            for proposal in closed.into_iter().filter(|p| !p.votes.is_empty()) {
+                channel.chat.events.push_message(PushMessageArgs {
+                    sender: member.user_id,
+                    thread_root_message_index: None,
+                    message_id: state.env.rng().gen(),
+                    content: MessageContentInternal::Text(TextContent {
+                        text: proposal.vote_summary(),
+                    }),
+                    mentioned: Vec::new(),
+                    replies_to: Some(ReplyContextInternal {
+                        chat_if_other: None,
+                        event_index: proposal.event_index,
+                    }),
+                    forwarded: false,
+                    correlation_id: 0,
+                    now,
+                });
+            }
 
             handle_activity_notification(state);
 
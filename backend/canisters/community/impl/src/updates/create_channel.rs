@@ -8,10 +8,10 @@ use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
 use community_canister::c2c_join_community;
 use community_canister::create_channel::{Response::*, *};
-use group_chat_core::GroupChatCore;
+use group_chat_core::{GroupChatCore, ProposalVoteFollows};
 use ic_cdk_macros::update;
 use rand::Rng;
-use types::ChannelId;
+use types::{ChannelId, CommunityPermission, GroupPermissionRole, Timestamped};
 use utils::document_validation::validate_avatar;
 use utils::text_validation::{
     validate_description, validate_group_name, validate_rules, NameValidationError, RulesValidationError,
@@ -62,6 +62,17 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
     }
 
     let caller = state.env.caller();
+    let required_permission = if args.is_public {
+        CommunityPermission::CreatePublicChannel
+    } else {
+        CommunityPermission::CreatePrivateChannel
+    };
+    let is_authorized = state
+        .data
+        .members
+        .get(caller)
+        .map_or(false, |m| state.data.is_permitted(m, required_permission));
+
     if let Some(member) = state.data.members.get_mut(caller) {
         if member.suspended.value {
             return UserSuspended;
@@ -70,12 +81,6 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
         let subtype = is_proposals_channel.then_some(args.subtype).flatten();
 
         if !is_proposals_channel {
-            let is_authorized = if args.is_public {
-                member.role.can_create_public_channel(&state.data.permissions)
-            } else {
-                member.role.can_create_private_channel(&state.data.permissions)
-            };
-
             if !is_authorized {
                 return NotAuthorized;
             } else if let Err(error) = validate_group_name(&args.name, args.is_public, subtype.as_ref()) {
@@ -98,9 +103,28 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
             AvatarTooBig(error)
         } else if state.data.channels.is_name_taken(&args.name) {
             NameTaken
+        } else if args
+            .category_id
+            .map_or(false, |id| state.data.channel_categories.get(id).is_none())
+        {
+            CategoryNotFound
         } else {
             let now = state.env.now();
             let channel_id: ChannelId = state.env.rng().gen();
+            // Channels created without explicit permissions inherit the defaults of their category
+            let mut permissions = args
+                .permissions
+                .or_else(|| {
+                    args.category_id
+                        .and_then(|id| state.data.channel_categories.get(id))
+                        .and_then(|c| c.default_permissions.value.clone())
+                })
+                .unwrap_or_default();
+            let is_announcement = args.is_announcement.unwrap_or_default();
+            if is_announcement {
+                restrict_to_admins(&mut permissions.send_messages);
+                restrict_to_admins(&mut permissions.reply_in_thread);
+            }
             let chat = GroupChatCore::new(
                 member.user_id,
                 args.is_public,
@@ -110,7 +134,7 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
                 subtype,
                 args.avatar,
                 args.history_visible_to_new_joiners,
-                args.permissions.unwrap_or_default(),
+                permissions,
                 args.gate,
                 args.events_ttl,
                 member.is_bot,
@@ -123,6 +147,9 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
                 id: channel_id,
                 chat,
                 date_imported: None,
+                user_group_overrides: Timestamped::default(),
+                is_announcement,
+                proposal_vote_follows: ProposalVoteFollows::default(),
             };
 
             if args.is_public && channel.chat.gate.is_none() {
@@ -132,6 +159,11 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
             }
 
             state.data.channels.add(channel);
+            state.data.sync_channel_permissions_for_channel(&channel_id);
+
+            if let Some(category_id) = args.category_id {
+                state.data.channel_categories.add_channel(category_id, channel_id, now);
+            }
 
             handle_activity_notification(state);
             Success(SuccessResult { channel_id })
@@ -140,3 +172,9 @@ fn create_channel_impl(args: Args, is_proposals_channel: bool, state: &mut Runti
         NotAuthorized
     }
 }
+
+pub(crate) fn restrict_to_admins(role: &mut GroupPermissionRole) {
+    if matches!(role, GroupPermissionRole::Members) {
+        *role = GroupPermissionRole::Admins;
+    }
+}
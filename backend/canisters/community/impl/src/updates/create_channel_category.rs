@@ -0,0 +1,57 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::create_channel_category::{Response::*, *};
+use ic_cdk_macros::update;
+use types::CommunityPermission;
+use utils::text_validation::{validate_channel_category_name, UsernameValidationError};
+
+#[update]
+#[trace]
+fn create_channel_category(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| create_channel_category_impl(args, state))
+}
+
+fn create_channel_category_impl(mut args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    if let Some(member) = state.data.members.get(caller) {
+        if member.suspended.value {
+            return UserSuspended;
+        }
+
+        if !state.data.is_permitted(member, CommunityPermission::ManageChannelCategories) {
+            NotAuthorized
+        } else if let Err(error) = validate_channel_category_name(&args.name) {
+            match error {
+                UsernameValidationError::TooShort(s) => NameTooShort(s),
+                UsernameValidationError::TooLong(l) => NameTooLong(l),
+                UsernameValidationError::Invalid => NameInvalid,
+            }
+        } else {
+            let now = state.env.now();
+            args.channel_ids.retain(|c| state.data.channels.get(c).is_some());
+
+            if let Some(category_id) = state.data.channel_categories.create(
+                args.name,
+                args.collapsed_by_default,
+                args.default_permissions,
+                args.channel_ids,
+                state.env.rng(),
+                now,
+            ) {
+                handle_activity_notification(state);
+                Success(SuccessResult { category_id })
+            } else {
+                NameTaken
+            }
+        }
+    } else {
+        NotAuthorized
+    }
+}
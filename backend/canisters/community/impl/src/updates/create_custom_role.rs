@@ -0,0 +1,55 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::create_custom_role::{Response::*, *};
+use ic_cdk_macros::update;
+use utils::text_validation::{validate_custom_role_name, UsernameValidationError};
+
+#[update]
+#[trace]
+fn create_custom_role(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| create_custom_role_impl(args, state))
+}
+
+fn create_custom_role_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    if let Some(member) = state.data.members.get(caller) {
+        if member.suspended.value {
+            return UserSuspended;
+        }
+
+        if !member.role.can_change_permissions() {
+            NotAuthorized
+        } else if let Err(error) = validate_custom_role_name(&args.name) {
+            match error {
+                UsernameValidationError::TooShort(s) => NameTooShort(s),
+                UsernameValidationError::TooLong(l) => NameTooLong(l),
+                UsernameValidationError::Invalid => NameInvalid,
+            }
+        } else {
+            let now = state.env.now();
+            let rng = state.env.rng();
+
+            if let Some(role_id) = state.data.members.create_custom_role(
+                args.name,
+                args.community_permissions.into_iter().collect(),
+                args.channel_permissions.into_iter().collect(),
+                rng,
+                now,
+            ) {
+                handle_activity_notification(state);
+                Success(SuccessResult { role_id })
+            } else {
+                NameTaken
+            }
+        }
+    } else {
+        NotAuthorized
+    }
+}
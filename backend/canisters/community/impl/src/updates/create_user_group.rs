@@ -3,6 +3,7 @@ use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use community_canister::create_user_group::{Response::*, *};
 use ic_cdk_macros::update;
+use types::CommunityPermission;
 use utils::text_validation::{validate_user_group_name, UsernameValidationError};
 
 #[update]
@@ -19,12 +20,12 @@ fn create_user_group_impl(args: Args, state: &mut RuntimeState) -> Response {
     }
 
     let caller = state.env.caller();
-    if let Some(member) = state.data.members.get_mut(caller) {
+    if let Some(member) = state.data.members.get(caller) {
         if member.suspended.value {
             return UserSuspended;
         }
 
-        if !member.role.can_manage_user_groups(&state.data.permissions) {
+        if !state.data.is_permitted(member, CommunityPermission::ManageUserGroups) {
             NotAuthorized
         } else if let Err(error) = validate_user_group_name(&args.name) {
             match error {
@@ -33,6 +33,15 @@ fn delete_channel_impl(channel_id: ChannelId, state: &mut RuntimeState) -> Respo
                 if channel_member.role.can_delete_group() {
                     let now = state.env.now();
                     let channel = state.data.channels.delete(channel_id).expect("Channel should exist");
+                    state.data.channel_categories.remove_channel(&channel_id, now);
+
+                    let announcement_links = state.data.announcements.remove_channel(&channel_id);
+                    for followed in announcement_links.following {
+                        state.data.notify_announcement_channel_unfollowed(&followed);
+                    }
+                    state
+                        .data
+                        .notify_announcement_channel_deleted(channel_id, announcement_links.followers);
 
                     state.data.events.push_event(
                         CommunityEventInternal::ChannelDeleted(Box::new(ChannelDeleted {
@@ -0,0 +1,43 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::delete_custom_roles::{Response::*, *};
+use ic_cdk_macros::update;
+use std::collections::HashSet;
+
+#[update]
+#[trace]
+fn delete_custom_roles(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| delete_custom_roles_impl(args, state))
+}
+
+fn delete_custom_roles_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => UserSuspended,
+        Some(m) if m.role.can_change_permissions() => {
+            let now = state.env.now();
+
+            let mut updated = false;
+            let mut affected_users = HashSet::new();
+            for role_id in args.role_ids {
+                if let Some(role) = state.data.members.delete_custom_role(role_id, now) {
+                    affected_users.extend(state.data.members.users_with_custom_role(&role));
+                    updated = true;
+                }
+            }
+            if updated {
+                state.data.sync_channel_permissions(affected_users);
+                handle_activity_notification(state);
+            }
+            Success
+        }
+        _ => NotAuthorized,
+    }
+}
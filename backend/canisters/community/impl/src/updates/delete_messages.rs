@@ -4,6 +4,7 @@ use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState, TimerJob};
 use candid::Principal;
 use canister_tracing_macros::trace;
 use chat_events::DeleteMessageResult;
+use community_canister::c2c_mirror_announcement::{AnnouncementAction, AnnouncementDeletion};
 use community_canister::delete_messages::{Response::*, *};
 use group_chat_core::DeleteMessagesResult;
 use ic_cdk_macros::update;
@@ -66,6 +67,8 @@ fn delete_messages_impl(user_id: UserId, args: Args, state: &mut RuntimeState) -
 
     let now = state.env.now();
     if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
+        let mirror_deletions = channel.is_announcement && args.thread_root_message_index.is_none();
+
         match channel.chat.delete_messages(
             user_id,
             args.thread_root_message_index,
@@ -74,14 +77,23 @@ fn delete_messages_impl(user_id: UserId, args: Args, state: &mut RuntimeState) -
             now,
         ) {
             DeleteMessagesResult::Success(results) => {
+                let deleted: Vec<_> = results
+                    .into_iter()
+                    .filter_map(|(message_id, result)| {
+                        if let DeleteMessageResult::Success(sender) = result {
+                            Some((message_id, sender))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+
                 let remove_deleted_message_content_at = now + (5 * MINUTE_IN_MS);
-                for message_id in results.into_iter().filter_map(|(message_id, result)| {
-                    if let DeleteMessageResult::Success(sender) = result {
-                        (sender == user_id).then_some(message_id)
-                    } else {
-                        None
-                    }
-                }) {
+                for message_id in deleted
+                    .iter()
+                    .filter(|(_, sender)| *sender == user_id)
+                    .map(|(message_id, _)| *message_id)
+                {
                     // After 5 minutes hard delete those messages where the deleter was the message sender
                     state.data.timer_jobs.enqueue_job(
                         TimerJob::HardDeleteMessageContent(HardDeleteMessageContentJob {
@@ -94,6 +106,16 @@ fn delete_messages_impl(user_id: UserId, args: Args, state: &mut RuntimeState) -
                     );
                 }
 
+                if mirror_deletions && !deleted.is_empty() {
+                    state.data.mirror_announcement(
+                        args.channel_id,
+                        AnnouncementAction::Delete(AnnouncementDeletion {
+                            message_ids: deleted.into_iter().map(|(message_id, _)| message_id).collect(),
+                            deleted_by: user_id,
+                        }),
+                    );
+                }
+
                 handle_activity_notification(state);
 
                 Success
@@ -3,6 +3,8 @@ use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use community_canister::delete_user_groups::{Response::*, *};
 use ic_cdk_macros::update;
+use std::collections::HashSet;
+use types::CommunityPermission;
 
 #[update]
 #[trace]
@@ -20,16 +22,27 @@ fn delete_user_groups_impl(args: Args, state: &mut RuntimeState) -> Response {
     let caller = state.env.caller();
     match state.data.members.get(caller) {
         Some(m) if m.suspended.value => UserSuspended,
-        Some(m) if m.role.can_manage_user_groups(&state.data.permissions) => {
+        Some(m) if state.data.is_permitted(m, CommunityPermission::ManageUserGroups) => {
             let now = state.env.now();
 
             let mut updated = false;
+            let mut affected_users = HashSet::new();
             for user_group_id in args.user_group_ids {
+                let members = state
+                    .data
+                    .members
+                    .get_user_group(user_group_id)
+                    .map(|g| g.members.value.clone())
+                    .unwrap_or_default();
+
                 if state.data.members.delete_user_group(user_group_id, now) {
+                    state.data.channels.remove_user_group_overrides(user_group_id, now);
+                    affected_users.extend(members);
                     updated = true;
                 }
             }
             if updated {
+                state.data.sync_channel_permissions(affected_users);
                 handle_activity_notification(state);
             }
             Success
@@ -5,7 +5,7 @@ use crate::{
 use canister_tracing_macros::trace;
 use community_canister::disable_invite_code::{Response::*, *};
 use ic_cdk_macros::update;
-use types::{GroupInviteCodeChange, GroupInviteCodeChanged};
+use types::{CommunityPermission, GroupInviteCodeChange, GroupInviteCodeChanged};
 
 #[update]
 #[trace]
@@ -26,7 +26,7 @@ fn disable_invite_code_impl(state: &mut RuntimeState) -> Response {
             return UserSuspended;
         }
 
-        if member.role.can_invite_users(&state.data.permissions) {
+        if state.data.is_permitted(member, CommunityPermission::InviteUsers) {
             state.data.invite_code_enabled = false;
 
             let now = state.env.now();
@@ -1,6 +1,8 @@
+use crate::model::announcements::can_be_mirrored;
 use crate::{activity_notifications::handle_activity_notification, mutate_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use chat_events::{EditMessageArgs, EditMessageResult};
+use community_canister::c2c_mirror_announcement::{AnnouncementAction, AnnouncementEdit};
 use community_canister::edit_message::{Response::*, *};
 use ic_cdk_macros::update;
 
@@ -28,6 +30,10 @@ fn edit_message_impl(args: Args, state: &mut RuntimeState) -> Response {
         if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
             let sender = member.user_id;
             if let Some(channel_member) = channel.chat.members.get(&sender) {
+                let content_to_mirror =
+                    (channel.is_announcement && args.thread_root_message_index.is_none() && can_be_mirrored(&args.content))
+                        .then(|| args.content.clone());
+
                 match channel.chat.events.edit_message(EditMessageArgs {
                     sender,
                     min_visible_event_index: channel_member.min_visible_event_index(),
@@ -37,6 +43,16 @@ fn edit_message_impl(args: Args, state: &mut RuntimeState) -> Response {
                     now,
                 }) {
                     EditMessageResult::Success => {
+                        if let Some(content) = content_to_mirror {
+                            state.data.mirror_announcement(
+                                args.channel_id,
+                                AnnouncementAction::Edit(AnnouncementEdit {
+                                    message_id: args.message_id,
+                                    sender,
+                                    content,
+                                }),
+                            );
+                        }
                         handle_activity_notification(state);
                         Success
                     }
@@ -8,7 +8,7 @@ use community_canister::reset_invite_code;
 use ic_cdk_macros::update;
 use rand::rngs::StdRng;
 use rand::{RngCore, SeedableRng};
-use types::{GroupInviteCodeChange, GroupInviteCodeChanged};
+use types::{CommunityPermission, GroupInviteCodeChange, GroupInviteCodeChanged};
 use utils::canister;
 
 #[update]
@@ -97,7 +97,7 @@ fn prepare(state: &RuntimeState) -> Result<PrepareResult, Response> {
             return Err(UserSuspended);
         }
 
-        if participant.role.can_invite_users(&state.data.permissions) {
+        if state.data.is_permitted(participant, CommunityPermission::InviteUsers) {
             return Ok(PrepareResult {
                 caller,
                 code: state.data.invite_code,
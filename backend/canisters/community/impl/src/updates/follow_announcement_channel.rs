@@ -0,0 +1,79 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, read_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::c2c_follow_announcement_channel;
+use community_canister::follow_announcement_channel::{Response::*, *};
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+async fn follow_announcement_channel(args: Args) -> Response {
+    run_regular_jobs();
+
+    if let Err(response) = read_state(|state| prepare(&args, state)) {
+        return response;
+    }
+
+    let c2c_args = c2c_follow_announcement_channel::Args {
+        channel_id: args.source_channel_id,
+        follower_channel_id: args.channel_id,
+    };
+    match community_canister_c2c_client::c2c_follow_announcement_channel(args.source_community_id.into(), &c2c_args).await {
+        Ok(c2c_follow_announcement_channel::Response::Success) => mutate_state(|state| commit(args, state)),
+        Ok(c2c_follow_announcement_channel::Response::ChannelNotFound) => SourceChannelNotFound,
+        Ok(c2c_follow_announcement_channel::Response::NotAnnouncementChannel) => NotAnnouncementChannel,
+        Ok(c2c_follow_announcement_channel::Response::TooManyFollowers) => TooManyFollowers,
+        Ok(c2c_follow_announcement_channel::Response::NotAuthorized) => InternalError("Not authorized".to_string()),
+        Ok(c2c_follow_announcement_channel::Response::CommunityFrozen) => InternalError("Source community frozen".to_string()),
+        Ok(c2c_follow_announcement_channel::Response::InternalError(error)) => InternalError(error),
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<(), Response> {
+    if state.data.is_frozen() {
+        return Err(CommunityFrozen);
+    }
+
+    let caller = state.env.caller();
+    let user_id = match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => return Err(UserSuspended),
+        Some(m) => m.user_id,
+        None => return Err(UserNotInCommunity),
+    };
+
+    if state
+        .data
+        .announcements
+        .followed_into(args.source_community_id, args.source_channel_id)
+        .is_some()
+    {
+        return Err(AlreadyFollowing);
+    }
+
+    if let Some(channel) = state.data.channels.get(&args.channel_id) {
+        match channel.chat.members.get(&user_id) {
+            Some(m) if m.suspended.value => Err(UserSuspended),
+            Some(m) if m.role.can_change_permissions() => Ok(()),
+            Some(_) => Err(NotAuthorized),
+            None => Err(UserNotInChannel),
+        }
+    } else {
+        Err(ChannelNotFound)
+    }
+}
+
+fn commit(args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+
+    if state
+        .data
+        .announcements
+        .follow(args.source_community_id, args.source_channel_id, args.channel_id, now)
+    {
+        handle_activity_notification(state);
+        Success
+    } else {
+        AlreadyFollowing
+    }
+}
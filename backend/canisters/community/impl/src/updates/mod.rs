@@ -1,28 +1,42 @@
 pub mod add_members_to_channel;
 pub mod add_reaction;
+pub mod add_referral_campaign;
 pub mod c2c_delete_community;
+pub mod c2c_export_state;
+pub mod c2c_finish_state_restore;
+pub mod c2c_follow_announcement_channel;
 pub mod c2c_freeze_community;
 pub mod c2c_invite_users;
 pub mod c2c_invite_users_to_channel;
 pub mod c2c_join_channel;
 pub mod c2c_join_community;
 pub mod c2c_leave_community;
+pub mod c2c_mirror_announcement;
+pub mod c2c_restore_state_chunk;
+pub mod c2c_send_proposal_reminder;
 pub mod c2c_set_user_suspended;
 pub mod c2c_tip_message;
+pub mod c2c_unfollow_announcement_channel;
 pub mod c2c_unfreeze_community;
 pub mod c2c_update_proposals;
 pub mod change_channel_role;
 pub mod change_role;
 pub mod claim_prize;
 pub mod create_channel;
+pub mod create_channel_category;
+pub mod create_custom_role;
 pub mod create_user_group;
 pub mod decline_invitation;
 pub mod delete_channel;
+pub mod delete_channel_categories;
+pub mod delete_custom_roles;
 pub mod delete_messages;
 pub mod delete_user_groups;
 pub mod disable_invite_code;
 pub mod edit_message;
 pub mod enable_invite_code;
+pub mod follow_announcement_channel;
+pub mod follow_proposal_votes;
 pub mod follow_thread;
 pub mod import_group;
 pub mod leave_channel;
@@ -33,13 +47,19 @@ pub mod register_proposal_vote_v2;
 pub mod remove_member;
 pub mod remove_member_from_channel;
 pub mod remove_reaction;
+pub mod reorder_channel_categories;
 pub mod send_message;
+pub mod set_channel_user_group_permissions;
 pub mod set_member_display_name;
+pub mod set_typing;
 pub mod toggle_mute_notifications;
 pub mod unblock_user;
 pub mod undelete_messages;
+pub mod unfollow_announcement_channel;
 pub mod unfollow_thread;
 pub mod update_channel;
+pub mod update_channel_category;
 pub mod update_community;
+pub mod update_custom_role;
 pub mod update_user_group;
 pub mod wallet_receive;
@@ -0,0 +1,32 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::reorder_channel_categories::{Response::*, *};
+use ic_cdk_macros::update;
+use types::CommunityPermission;
+
+#[update]
+#[trace]
+fn reorder_channel_categories(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| reorder_channel_categories_impl(args, state))
+}
+
+fn reorder_channel_categories_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => UserSuspended,
+        Some(m) if state.data.is_permitted(m, CommunityPermission::ManageChannelCategories) => {
+            let now = state.env.now();
+            state.data.channel_categories.reorder(args.category_ids, now);
+            handle_activity_notification(state);
+            Success
+        }
+        _ => NotAuthorized,
+    }
+}
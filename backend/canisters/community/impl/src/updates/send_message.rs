@@ -1,13 +1,18 @@
 use crate::activity_notifications::handle_activity_notification;
+use crate::model::announcements::can_be_mirrored;
 use crate::model::members::CommunityMembers;
 use crate::model::user_groups::UserGroup;
-use crate::timer_job_types::{DeleteFileReferencesJob, EndPollJob, RefundPrizeJob, RemoveExpiredEventsJob, TimerJob};
+use crate::timer_job_types::{
+    DeleteFileReferencesJob, EndPollJob, MirroredAnnouncement, RefundPrizeJob, RemoveExpiredEventsJob, TimerJob,
+};
 use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_api_macros::update_candid_and_msgpack;
 use canister_timer_jobs::TimerJobs;
 use canister_tracing_macros::trace;
+use community_canister::c2c_mirror_announcement::{AnnouncementAction, AnnouncementMessage};
 use community_canister::send_message::{Response::*, *};
 use group_chat_core::SendMessageResult;
+use instruction_counts_log::InstructionCountFunctionId;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex_lite::Regex;
@@ -22,7 +27,13 @@ use types::{
 fn send_message(args: Args) -> Response {
     run_regular_jobs();
 
-    mutate_state(|state| send_message_impl(args, state))
+    mutate_state(|state| {
+        let response = send_message_impl(args, state);
+        state
+            .data
+            .record_endpoint_instructions_count(InstructionCountFunctionId::SendMessage, state.env.now());
+        response
+    })
 }
 
 fn send_message_impl(args: Args, state: &mut RuntimeState) -> Response {
@@ -53,6 +64,9 @@ fn send_message_impl(args: Args, state: &mut RuntimeState) -> Response {
 
     if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
         let user_id = member.user_id;
+        let content_to_mirror =
+            (channel.is_announcement && args.thread_root_message_index.is_none() && can_be_mirrored(&args.content))
+                .then(|| args.content.clone());
 
         let user_groups_mentioned = extract_user_groups_mentioned(&args.content, &state.data.members);
         let mentioned: Vec<_> = args
@@ -93,6 +107,7 @@ fn send_message_impl(args: Args, state: &mut RuntimeState) -> Response {
                     args.thread_root_message_index,
                     &result.message_event,
                     is_next_event_to_expire,
+                    content_to_mirror.is_some(),
                     now,
                     &mut state.data.timer_jobs,
                 );
@@ -131,6 +146,18 @@ fn send_message_impl(args: Args, state: &mut RuntimeState) -> Response {
                 });
                 state.push_notification(users_to_notify, notification);
 
+                if let Some(content) = content_to_mirror {
+                    state.data.mirror_announcement(
+                        args.channel_id,
+                        AnnouncementAction::Send(AnnouncementMessage {
+                            message_id: args.message_id,
+                            event_index,
+                            sender: user_id,
+                            content,
+                        }),
+                    );
+                }
+
                 handle_activity_notification(state);
 
                 Success(SuccessResult {
@@ -160,6 +187,7 @@ fn register_timer_jobs(
     thread_root_message_index: Option<MessageIndex>,
     message_event: &EventWrapper<Message>,
     is_next_event_to_expire: bool,
+    is_mirrored: bool,
     now: TimestampMillis,
     timer_jobs: &mut TimerJobs<TimerJob>,
 ) {
@@ -180,7 +208,19 @@ fn register_timer_jobs(
     let files = message_event.event.content.blob_references();
     if !files.is_empty() {
         if let Some(expiry) = message_event.expires_at {
-            timer_jobs.enqueue_job(TimerJob::DeleteFileReferences(DeleteFileReferencesJob { files }), expiry, now);
+            let mirrored_announcement = is_mirrored.then(|| MirroredAnnouncement {
+                channel_id,
+                message_id: message_event.event.message_id,
+                sender: message_event.event.sender,
+            });
+            timer_jobs.enqueue_job(
+                TimerJob::DeleteFileReferences(DeleteFileReferencesJob {
+                    files,
+                    mirrored_announcement,
+                }),
+                expiry,
+                now,
+            );
         }
     }
 
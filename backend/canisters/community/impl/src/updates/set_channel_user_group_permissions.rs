@@ -0,0 +1,52 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::set_channel_user_group_permissions::{Response::*, *};
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn set_channel_user_group_permissions(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| set_channel_user_group_permissions_impl(args, state))
+}
+
+fn set_channel_user_group_permissions_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    let user_id = match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => return UserSuspended,
+        Some(m) => m.user_id,
+        None => return UserNotInCommunity,
+    };
+
+    if state.data.members.get_user_group(args.user_group_id).is_none() {
+        return UserGroupNotFound;
+    }
+
+    if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
+        match channel.chat.members.get(&user_id) {
+            Some(m) if m.suspended.value => UserSuspended,
+            Some(m) if m.role.can_change_permissions() => {
+                let now = state.env.now();
+                let allow = args.allow.into_iter().collect();
+                let deny = args.deny.into_iter().collect();
+
+                if channel.set_user_group_override(args.user_group_id, allow, deny, now) {
+                    // Apply the override to the channel's members straight away
+                    state.data.sync_channel_permissions_for_channel(&args.channel_id);
+                    handle_activity_notification(state);
+                }
+                Success
+            }
+            Some(_) => NotAuthorized,
+            None => UserNotInChannel,
+        }
+    } else {
+        ChannelNotFound
+    }
+}
@@ -0,0 +1,55 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::unfollow_announcement_channel::{Response::*, *};
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn unfollow_announcement_channel(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| unfollow_announcement_channel_impl(args, state))
+}
+
+fn unfollow_announcement_channel_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    let user_id = match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => return UserSuspended,
+        Some(m) => m.user_id,
+        None => return UserNotInCommunity,
+    };
+
+    let channel_id = match state
+        .data
+        .announcements
+        .followed_into(args.source_community_id, args.source_channel_id)
+    {
+        Some(c) => c,
+        None => return NotFollowing,
+    };
+
+    if let Some(channel) = state.data.channels.get(&channel_id) {
+        match channel.chat.members.get(&user_id) {
+            Some(m) if m.suspended.value => return UserSuspended,
+            Some(m) if m.role.can_change_permissions() => {}
+            Some(_) => return NotAuthorized,
+            None => return UserNotInChannel,
+        }
+    }
+
+    if let Some(followed) = state
+        .data
+        .announcements
+        .unfollow(args.source_community_id, args.source_channel_id)
+    {
+        state.data.notify_announcement_channel_unfollowed(&followed);
+    }
+
+    handle_activity_notification(state);
+    Success
+}
@@ -4,6 +4,7 @@ use canister_tracing_macros::trace;
 use community_canister::update_channel::{Response::*, *};
 use group_chat_core::UpdateResult;
 use ic_cdk_macros::update;
+use types::{GroupPermissionRole, OptionalGroupPermissions};
 
 #[update]
 #[trace]
@@ -30,6 +31,10 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> Response {
         let caller = state.env.caller();
 
         if let Some(member) = state.data.members.get(caller) {
+            if channel.is_announcement && opens_to_members(args.permissions.as_ref()) {
+                return NotAuthorized;
+            }
+
             let now = state.env.now();
 
             match channel.chat.update(
@@ -49,6 +54,7 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> Response {
                         for m in state.data.members.iter_mut() {
                             join_channel_unchecked(channel, m, true, now);
                         }
+                        state.data.sync_channel_permissions_for_channel(&args.channel_id);
                     }
 
                     handle_activity_notification(state);
@@ -75,6 +81,16 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> Response {
     }
 }
 
+// Announcement channels are read-only, so posting can't be opened up to all members
+fn opens_to_members(permissions: Option<&OptionalGroupPermissions>) -> bool {
+    permissions.map_or(false, |p| {
+        [p.send_messages.as_ref(), p.reply_in_thread.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|r| matches!(r, GroupPermissionRole::Members))
+    })
+}
+
 fn clean_args(args: &mut Args) {
     args.name = args.name.as_ref().map(|name| name.trim().to_string());
     args.description = args.description.as_ref().map(|desc| desc.trim().to_string());
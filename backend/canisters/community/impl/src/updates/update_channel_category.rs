@@ -0,0 +1,84 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::model::channel_categories::UpdateChannelCategoryResult;
+use crate::updates::create_channel::restrict_to_admins;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::update_channel_category::{Response::*, *};
+use ic_cdk_macros::update;
+use types::CommunityPermission;
+use utils::text_validation::{validate_channel_category_name, UsernameValidationError};
+
+#[update]
+#[trace]
+fn update_channel_category(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| update_channel_category_impl(args, state))
+}
+
+fn update_channel_category_impl(mut args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    let user_id = match state.data.members.get(caller) {
+        Some(m) if m.suspended.value => return UserSuspended,
+        Some(m) if state.data.is_permitted(m, CommunityPermission::ManageChannelCategories) => m.user_id,
+        _ => return NotAuthorized,
+    };
+
+    if let Err(error) = args.name.as_ref().map_or(Ok(()), |n| validate_channel_category_name(n)) {
+        return match error {
+            UsernameValidationError::TooShort(s) => NameTooShort(s),
+            UsernameValidationError::TooLong(l) => NameTooLong(l),
+            UsernameValidationError::Invalid => NameInvalid,
+        };
+    }
+
+    let now = state.env.now();
+    if let Some(channel_ids) = args.channel_ids.as_mut() {
+        channel_ids.retain(|c| state.data.channels.get(c).is_some());
+    }
+
+    match state.data.channel_categories.update(
+        args.category_id,
+        args.name,
+        args.collapsed_by_default,
+        args.default_permissions.expand(),
+        args.channel_ids,
+        now,
+    ) {
+        UpdateChannelCategoryResult::Success => {
+            if args.apply_default_permissions {
+                let category = state.data.channel_categories.get(args.category_id).unwrap();
+                if let Some(permissions) = category.default_permissions.value.as_ref() {
+                    for channel_id in category.channels.iter() {
+                        if let Some(channel) = state.data.channels.get_mut(channel_id) {
+                            // Channels in which the caller can't change the permissions are left as they are
+                            if !channel
+                                .chat
+                                .members
+                                .get(&user_id)
+                                .map_or(false, |m| !m.suspended.value && m.role.can_change_permissions())
+                            {
+                                continue;
+                            }
+
+                            let mut permissions = permissions.clone();
+                            if channel.is_announcement {
+                                restrict_to_admins(&mut permissions.send_messages);
+                                restrict_to_admins(&mut permissions.reply_in_thread);
+                            }
+                            channel.chat.set_permissions(permissions, user_id, now);
+                        }
+                    }
+                }
+            }
+            handle_activity_notification(state);
+            Success
+        }
+        UpdateChannelCategoryResult::NotFound => CategoryNotFound,
+        UpdateChannelCategoryResult::NameTaken => NameTaken,
+    }
+}
@@ -7,9 +7,9 @@ use group_index_canister::{c2c_make_community_private, c2c_update_community};
 use ic_cdk_macros::update;
 use tracing::error;
 use types::{
-    AccessGate, AvatarChanged, BannerChanged, CanisterId, CommunityId, CommunityPermissions, CommunityPermissionsChanged,
-    Document, GroupDescriptionChanged, GroupGateUpdated, GroupNameChanged, GroupRulesChanged, GroupVisibilityChanged,
-    OptionalCommunityPermissions, PrimaryLanguageChanged, Timestamped, UserId,
+    AccessGate, AvatarChanged, BannerChanged, CanisterId, CommunityId, CommunityPermission, CommunityPermissions,
+    CommunityPermissionsChanged, Document, GroupDescriptionChanged, GroupGateUpdated, GroupNameChanged, GroupRulesChanged,
+    GroupVisibilityChanged, OptionalCommunityPermissions, PrimaryLanguageChanged, Timestamped, UserId,
 };
 use utils::document_validation::{validate_avatar, validate_banner};
 use utils::text_validation::{
@@ -159,8 +159,7 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response>
             return Err(UserSuspended);
         }
 
-        let permissions = &state.data.permissions;
-        if !member.role.can_update_details(permissions)
+        if !state.data.is_permitted(member, CommunityPermission::UpdateDetails)
             || (args.permissions.is_some() && !member.role.can_change_permissions())
             || (args.public.is_some() && !member.role.can_change_community_visibility())
         {
@@ -355,5 +354,6 @@ fn merge_permissions(new: OptionalCommunityPermissions, old: &CommunityPermissio
         create_public_channel: new.create_public_channel.unwrap_or(old.create_public_channel),
         create_private_channel: new.create_private_channel.unwrap_or(old.create_private_channel),
         manage_user_groups: new.manage_user_groups.unwrap_or(old.manage_user_groups),
+        manage_channel_categories: new.manage_channel_categories.unwrap_or(old.manage_channel_categories),
     }
 }
@@ -0,0 +1,119 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::model::custom_roles::UpdateCustomRoleResult;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use community_canister::update_custom_role::{Response::*, *};
+use ic_cdk_macros::update;
+use std::collections::HashSet;
+use utils::text_validation::{validate_custom_role_name, UsernameValidationError};
+
+#[update]
+#[trace]
+fn update_custom_role(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| update_custom_role_impl(args, state))
+}
+
+fn update_custom_role_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return CommunityFrozen;
+    }
+
+    let caller = state.env.caller();
+    let member = match state.data.members.get(caller) {
+        Some(m) => m,
+        None => return NotAuthorized,
+    };
+
+    if member.suspended.value {
+        return UserSuspended;
+    }
+
+    // Changing what a role grants requires the same rights as changing the community permissions,
+    // whereas assigning it only requires the right to change members' roles
+    let is_definition_change =
+        args.name.is_some() || args.community_permissions.is_some() || args.channel_permissions.is_some();
+    let is_assignment_change = !args.users_to_add.is_empty()
+        || !args.users_to_remove.is_empty()
+        || !args.user_groups_to_add.is_empty()
+        || !args.user_groups_to_remove.is_empty();
+
+    if is_definition_change && !member.role.can_change_permissions() {
+        return NotAuthorized;
+    }
+
+    if is_assignment_change {
+        let role = match state.data.members.get_custom_role(args.role_id) {
+            Some(r) => r,
+            None => return RoleNotFound,
+        };
+        let community_permissions: Vec<_> = args
+            .community_permissions
+            .clone()
+            .unwrap_or_else(|| role.community_permissions.iter().copied().collect());
+        let channel_permissions: Vec<_> = args
+            .channel_permissions
+            .clone()
+            .unwrap_or_else(|| role.channel_permissions.iter().copied().collect());
+
+        if !state
+            .data
+            .can_grant_custom_role_permissions(member, &community_permissions, &channel_permissions)
+        {
+            return NotAuthorized;
+        }
+    }
+
+    if let Err(error) = args.name.as_ref().map_or(Ok(()), |n| validate_custom_role_name(n)) {
+        return match error {
+            UsernameValidationError::TooShort(s) => NameTooShort(s),
+            UsernameValidationError::TooLong(l) => NameTooLong(l),
+            UsernameValidationError::Invalid => NameInvalid,
+        };
+    }
+
+    let now = state.env.now();
+
+    let users_before = match state.data.members.get_custom_role(args.role_id) {
+        Some(role) => state.data.members.users_with_custom_role(role),
+        None => return RoleNotFound,
+    };
+
+    match state.data.members.update_custom_role(
+        args.role_id,
+        args.name,
+        args.community_permissions.map(HashSet::from_iter),
+        args.channel_permissions.map(HashSet::from_iter),
+        now,
+    ) {
+        UpdateCustomRoleResult::Success => {}
+        UpdateCustomRoleResult::NotFound => return RoleNotFound,
+        UpdateCustomRoleResult::NameTaken => return NameTaken,
+    }
+
+    if is_assignment_change {
+        state.data.members.assign_custom_role(
+            args.role_id,
+            args.users_to_add,
+            args.users_to_remove,
+            args.user_groups_to_add,
+            args.user_groups_to_remove,
+            now,
+        );
+    }
+
+    let users_after = state
+        .data
+        .members
+        .get_custom_role(args.role_id)
+        .map(|role| state.data.members.users_with_custom_role(role))
+        .unwrap_or_default();
+
+    state
+        .data
+        .sync_channel_permissions(users_before.union(&users_after).copied().collect::<Vec<_>>());
+
+    handle_activity_notification(state);
+    Success
+}
@@ -3,6 +3,7 @@ use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use community_canister::update_user_group::{Response::*, *};
 use ic_cdk_macros::update;
+use types::CommunityPermission;
 use utils::text_validation::{validate_user_group_name, UsernameValidationError};
 
 #[update]
@@ -19,12 +20,38 @@ fn update_user_group_impl(args: Args, state: &mut RuntimeState) -> Response {
     }
 
     let caller = state.env.caller();
-    if let Some(member) = state.data.members.get_mut(caller) {
+    if let Some(member) = state.data.members.get(caller) {
         if member.suspended.value {
             return UserSuspended;
         }
 
-        if !member.role.can_manage_user_groups(&state.data.permissions) {
+        // Changing the members of a group which has custom roles assigned to it hands those roles
+        // out, so it requires the same rights as assigning the roles directly
+        let is_membership_change = !args.users_to_add.is_empty() || !args.users_to_remove.is_empty();
+        let grants_custom_roles = is_membership_change
+            && state
+                .data
+                .members
+                .custom_roles_for_user_group(args.user_group_id)
+                .next()
+                .is_some();
+
+        if !state.data.is_permitted(member, CommunityPermission::ManageUserGroups)
+            || (grants_custom_roles
+                && !state.data.can_grant_custom_role_permissions(
+                    member,
+                    state
+                        .data
+                        .members
+                        .custom_roles_for_user_group(args.user_group_id)
+                        .flat_map(|r| r.community_permissions.iter()),
+                    state
+                        .data
+                        .members
+                        .custom_roles_for_user_group(args.user_group_id)
+                        .flat_map(|r| r.channel_permissions.iter()),
+                ))
+        {
             NotAuthorized
         } else if let Err(error) = args.name.as_ref().map_or(Ok(()), |n| validate_user_group_name(n)) {
             match error {
@@ -34,12 +61,14 @@ fn update_user_group_impl(args: Args, state: &mut RuntimeState) -> Response {
             }
         } else {
             let now = state.env.now();
+            let users_changed: Vec<_> = args.users_to_add.iter().chain(args.users_to_remove.iter()).copied().collect();
 
             if state
                 .data
                 .members
                 .update_user_group(args.user_group_id, args.name, args.users_to_add, args.users_to_remove, now)
             {
+                state.data.sync_channel_permissions(users_changed);
                 handle_activity_notification(state);
                 Success
             } else {
@@ -1,3 +1,7 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, Cycles, Milliseconds, TimestampMillis};
+
 mod lifecycle;
 mod queries;
 mod updates;
@@ -5,3 +9,53 @@ mod updates;
 pub use lifecycle::*;
 pub use queries::*;
 pub use updates::*;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct TopUpBudget {
+    // The most which can be sent in a single top up, capped by the global `max_top_up_amount`
+    pub max_top_up_amount: Option<Cycles>,
+    // The most which can be sent in total over any 24 hour period
+    pub daily_cap: Option<Cycles>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum BudgetTarget {
+    Canister(CanisterId),
+    // Budgets set for a class are shared between all canisters assigned to that class
+    Class(String),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ExternalCanisterGroup {
+    pub canisters: ExternalCanisters,
+    // Canisters are topped up once their balance falls below this amount
+    pub min_cycles_balance: Cycles,
+    pub top_up_amount: Cycles,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum ExternalCanisters {
+    // All canisters belonging to the SNS, as reported by its root canister
+    SnsRoot(CanisterId),
+    // An ad-hoc set of canisters, the dispenser must be one of their controllers in order to read their balances
+    Canisters(Vec<CanisterId>),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TopUpDecision {
+    pub group: String,
+    pub canister_id: CanisterId,
+    pub cycles_balance: Option<Cycles>,
+    pub balance_observed_at: Option<TimestampMillis>,
+    pub top_up_amount: Option<Cycles>,
+    pub reason: TopUpReason,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum TopUpReason {
+    BelowThreshold(Cycles),
+    AboveThreshold(Cycles),
+    BalanceUnknown,
+    Throttled(Milliseconds),
+    DispenserBalanceTooLow,
+}
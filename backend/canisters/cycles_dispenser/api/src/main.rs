@@ -2,8 +2,15 @@ use candid_gen::generate_candid_method;
 
 #[allow(deprecated)]
 fn main() {
+    generate_candid_method!(cycles_dispenser, top_up_dry_run, query);
+
     generate_candid_method!(cycles_dispenser, add_canister, update);
     generate_candid_method!(cycles_dispenser, c2c_request_cycles, update);
+    generate_candid_method!(cycles_dispenser, register_bot, update);
+    generate_candid_method!(cycles_dispenser, set_canister_class, update);
+    generate_candid_method!(cycles_dispenser, set_external_canister_group, update);
+    generate_candid_method!(cycles_dispenser, set_ops_group, update);
+    generate_candid_method!(cycles_dispenser, set_top_up_budget, update);
     generate_candid_method!(cycles_dispenser, update_config, update);
 
     candid::export_service!();
@@ -1 +1 @@
-
+pub mod top_up_dry_run;
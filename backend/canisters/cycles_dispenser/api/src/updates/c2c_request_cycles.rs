@@ -13,6 +13,7 @@ pub enum Response {
     TopUpInProgress,
     Throttled(Milliseconds),
     CyclesBalanceTooLow,
+    DailyCapReached,
     NotAuthorized,
     InternalError(String),
 }
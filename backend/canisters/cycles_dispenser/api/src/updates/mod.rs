@@ -1,3 +1,8 @@
 pub mod add_canister;
 pub mod c2c_request_cycles;
+pub mod register_bot;
+pub mod set_canister_class;
+pub mod set_external_canister_group;
+pub mod set_ops_group;
+pub mod set_top_up_budget;
 pub mod update_config;
@@ -0,0 +1,35 @@
+use candid::CandidType;
+use human_readable::{HumanReadablePrincipal, ToHumanReadable};
+use serde::{Deserialize, Serialize};
+use types::CanisterId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub user_index_canister_id: CanisterId,
+    pub username: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    AlreadyRegistered,
+    RegistrationFailed(String),
+    InternalError(String),
+}
+
+#[derive(Serialize)]
+pub struct HumanReadableArgs {
+    user_index_canister_id: HumanReadablePrincipal,
+    username: String,
+}
+
+impl ToHumanReadable for Args {
+    type Target = HumanReadableArgs;
+
+    fn to_human_readable(&self) -> Self::Target {
+        HumanReadableArgs {
+            user_index_canister_id: self.user_index_canister_id.into(),
+            username: self.username.clone(),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use candid::CandidType;
+use human_readable::{HumanReadablePrincipal, ToHumanReadable};
+use serde::{Deserialize, Serialize};
+use types::CanisterId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub canister_id: CanisterId,
+    pub class: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CanisterNotFound,
+}
+
+#[derive(Serialize)]
+pub struct HumanReadableArgs {
+    canister_id: HumanReadablePrincipal,
+    class: Option<String>,
+}
+
+impl ToHumanReadable for Args {
+    type Target = HumanReadableArgs;
+
+    fn to_human_readable(&self) -> Self::Target {
+        HumanReadableArgs {
+            canister_id: self.canister_id.into(),
+            class: self.class.clone(),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::ExternalCanisterGroup;
+use candid::CandidType;
+use human_readable::HumanReadable;
+use serde::{Deserialize, Serialize};
+use types::CanisterId;
+
+#[derive(CandidType, Serialize, Deserialize, HumanReadable, Clone, Debug)]
+pub struct Args {
+    pub name: String,
+    // Setting this to None removes the group
+    pub group: Option<ExternalCanisterGroup>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NameInvalid,
+    // The dispenser can only read the balances of canisters which it controls, SNS canisters are
+    // exempt since their balances are read via the SNS root canister
+    NotController(Vec<CanisterId>),
+}
@@ -0,0 +1,19 @@
+use candid::CandidType;
+use human_readable::HumanReadable;
+use serde::{Deserialize, Serialize};
+use types::ChatId;
+
+#[derive(CandidType, Serialize, Deserialize, HumanReadable, Clone, Debug)]
+pub struct Args {
+    // Setting this to None stops alerts from being posted
+    pub group_id: Option<ChatId>,
+    pub invite_code: Option<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    BotNotRegistered,
+    JoinGroupFailed(String),
+    InternalError(String),
+}
@@ -0,0 +1,17 @@
+use crate::{BudgetTarget, TopUpBudget};
+use candid::CandidType;
+use human_readable::HumanReadable;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, HumanReadable, Clone, Debug)]
+pub struct Args {
+    pub target: BudgetTarget,
+    // Setting this to None removes the budget
+    pub budget: Option<TopUpBudget>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CanisterNotFound,
+}
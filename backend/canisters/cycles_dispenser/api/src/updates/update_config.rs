@@ -10,6 +10,10 @@ pub struct Args {
     pub min_interval: Option<Milliseconds>,
     pub min_cycles_balance: Option<Cycles>,
     pub icp_burn_amount: Option<Tokens>,
+    #[serde(default)]
+    pub anomaly_multiplier: Option<u32>,
+    #[serde(default)]
+    pub min_days_until_empty: Option<u32>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -1,7 +1,7 @@
 mod burn_icp_into_cycles;
-mod top_up_sns_canisters;
+pub mod top_up_external_canisters;
 
 pub(crate) fn start() {
     burn_icp_into_cycles::start_job();
-    top_up_sns_canisters::start_job();
+    top_up_external_canisters::start_job();
 }
@@ -0,0 +1,141 @@
+use crate::jobs::top_up_external_canisters::get_sns_canisters_summary::CanisterSummary;
+use crate::{mutate_state, read_state};
+use candid::Nat;
+use cycles_dispenser_canister::ExternalCanisters;
+use ic_cdk::api::management_canister::main::{canister_status, CanisterIdRecord};
+use std::time::Duration;
+use tracing::error;
+use types::{CanisterId, Cycles, Empty};
+use utils::canister::deposit_cycles;
+
+const INTERVAL: Duration = Duration::from_secs(60 * 60); // 1 hour
+
+pub fn start_job() {
+    ic_cdk_timers::set_timer_interval(INTERVAL, run);
+
+    // Run the job now so that there is never a gap of more than 1 hour.
+    ic_cdk_timers::set_timer(Duration::ZERO, run);
+}
+
+fn run() {
+    let sources = read_state(|state| state.data.external_canister_groups.sources());
+    if !sources.is_empty() {
+        ic_cdk::spawn(run_async(sources));
+    }
+}
+
+async fn run_async(sources: Vec<(String, ExternalCanisters)>) {
+    for (name, canisters) in sources {
+        let balances = match canisters {
+            ExternalCanisters::SnsRoot(root_canister_id) => get_sns_balances(root_canister_id).await,
+            ExternalCanisters::Canisters(canister_ids) => get_balances(canister_ids).await,
+        };
+
+        mutate_state(|state| {
+            let now = state.env.now();
+            state.data.external_canister_groups.record_balances(&name, balances, now);
+        });
+    }
+
+    let to_top_up: Vec<_> = read_state(|state| {
+        let available = state.env.cycles_balance().saturating_sub(state.data.min_cycles_balance);
+        state
+            .data
+            .external_canister_groups
+            .evaluate(None, state.data.min_interval, available, state.env.now())
+            .into_iter()
+            .filter_map(|d| d.top_up_amount.map(|amount| (d.group, d.canister_id, amount)))
+            .collect()
+    });
+
+    for (group, canister_id, amount) in to_top_up {
+        if deposit_cycles(canister_id, amount).await.is_ok() {
+            mutate_state(|state| {
+                let now = state.env.now();
+                state
+                    .data
+                    .external_canister_groups
+                    .record_top_up(&group, canister_id, amount, now);
+            });
+        }
+    }
+}
+
+async fn get_sns_balances(root_canister_id: CanisterId) -> Vec<(CanisterId, Cycles)> {
+    match get_sns_canisters_summary(root_canister_id, &Empty {}).await {
+        Ok(response) => vec![
+            response.root,
+            response.governance,
+            response.ledger,
+            response.swap,
+            response.index,
+        ]
+        .into_iter()
+        .flatten()
+        .chain(response.archives)
+        .filter_map(|s| extract_balance(&s))
+        .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+// The dispenser can only read the balances of canisters which it controls, which is checked when
+// the group is set. Canisters whose balance can't be read are omitted.
+pub async fn get_balances(canister_ids: Vec<CanisterId>) -> Vec<(CanisterId, Cycles)> {
+    let mut balances = Vec::new();
+    for canister_id in canister_ids {
+        match canister_status(CanisterIdRecord { canister_id }).await {
+            Ok((response,)) => balances.push((canister_id, to_cycles(response.cycles))),
+            Err((code, msg)) => {
+                error!(
+                    %canister_id,
+                    error_code = code as u8,
+                    error_message = msg.as_str(),
+                    "Error calling canister_status"
+                );
+            }
+        }
+    }
+    balances
+}
+
+fn extract_balance(summary: &CanisterSummary) -> Option<(CanisterId, Cycles)> {
+    let canister_id = summary.canister_id?;
+    let status = summary.status.as_ref()?;
+    Some((canister_id, to_cycles(status.cycles.clone())))
+}
+
+fn to_cycles(cycles: Nat) -> Cycles {
+    cycles.0.try_into().unwrap_or(Cycles::MAX)
+}
+
+canister_client::generate_candid_c2c_call!(get_sns_canisters_summary);
+
+pub mod get_sns_canisters_summary {
+    use candid::{CandidType, Nat, Principal};
+    use serde::Deserialize;
+    use types::Empty;
+
+    pub type Args = Empty;
+
+    #[derive(CandidType, Deserialize, Debug)]
+    pub struct Response {
+        pub root: Option<CanisterSummary>,
+        pub governance: Option<CanisterSummary>,
+        pub ledger: Option<CanisterSummary>,
+        pub swap: Option<CanisterSummary>,
+        pub archives: Vec<CanisterSummary>,
+        pub index: Option<CanisterSummary>,
+    }
+
+    #[derive(CandidType, Deserialize, Debug)]
+    pub struct CanisterSummary {
+        pub canister_id: Option<Principal>,
+        pub status: Option<CanisterStatusResult>,
+    }
+
+    #[derive(CandidType, Deserialize, Debug)]
+    pub struct CanisterStatusResult {
+        pub cycles: Nat,
+    }
+}
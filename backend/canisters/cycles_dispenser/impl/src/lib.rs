@@ -1,14 +1,24 @@
+use crate::model::alerts::{Alert, Alerts, RaisedAlert};
 use crate::model::canisters::{CanisterMetrics, Canisters};
+use crate::model::external_canister_groups::{ExternalCanisterGroupMetrics, ExternalCanisterGroups};
+use crate::model::policies::TopUpPolicies;
 use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
+use cycles_dispenser_canister::{BudgetTarget, ExternalCanisterGroup, ExternalCanisters, TopUpBudget};
+use group_canister::send_message_v2::Args as SendMessageArgs;
 use ic_ledger_types::{BlockIndex, Tokens};
 use ledger_utils::default_ledger_account;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::HashSet;
-use types::{BuildVersion, CanisterId, Cycles, Milliseconds, TimestampMillis, Timestamped};
+use tracing::{error, warn};
+use types::{
+    BuildVersion, CanisterId, ChatId, Cycles, MessageContentInitial, Milliseconds, TextContent, TimestampMillis, Timestamped,
+};
 use utils::env::Environment;
 use utils::memory;
+use utils::time::DAY_IN_MS;
 
 mod guards;
 mod jobs;
@@ -17,6 +27,10 @@ mod model;
 mod queries;
 mod updates;
 
+const FORECAST_WINDOW_DAYS: u64 = 7;
+const LEGACY_SNS_GROUP_NAME: &str = "OpenChat SNS";
+const LEGACY_SNS_MIN_CYCLES_BALANCE: Cycles = 100_000_000_000_000; // 100T
+
 thread_local! {
     static WASM_VERSION: RefCell<Timestamped<BuildVersion>> = RefCell::default();
 }
@@ -37,6 +51,51 @@ impl State {
         self.data.governance_principals.contains(&self.env.caller())
     }
 
+    // Logs the alert and, if an ops group has been set, posts it to that group. The ops group can
+    // only be set once the dispenser has registered as a bot and joined the group.
+    pub fn raise_alert(&mut self, alert: Alert) {
+        let now = self.env.now();
+        let text = alert.text();
+        if !self.data.alerts.raise(alert, now) {
+            return;
+        }
+
+        warn!(text, "Cycles dispenser alert raised");
+
+        if let Some(group_id) = self.data.ops_group {
+            let args = SendMessageArgs {
+                message_id: self.env.rng().gen(),
+                thread_root_message_index: None,
+                content: MessageContentInitial::Text(TextContent { text }),
+                sender_name: "CyclesDispenser".to_string(),
+                sender_display_name: None,
+                replies_to: None,
+                mentioned: Vec::new(),
+                forwarding: false,
+                rules_accepted: None,
+                correlation_id: 0,
+            };
+            ic_cdk::spawn(send_alert(group_id, args));
+        }
+    }
+
+    // The number of days until the dispenser falls below `min_cycles_balance` if it continues
+    // topping up canisters at the rate seen over the last week
+    pub fn days_until_empty(&self) -> Option<u64> {
+        let rate = self.daily_top_up_rate();
+        (rate > 0).then(|| {
+            let available = self.env.cycles_balance().saturating_sub(self.data.min_cycles_balance);
+            (available / rate) as u64
+        })
+    }
+
+    // The average amount sent per day over the last week, including top ups of external canisters
+    pub fn daily_top_up_rate(&self) -> Cycles {
+        let since = self.env.now().saturating_sub(FORECAST_WINDOW_DAYS * DAY_IN_MS);
+        let total = self.data.canisters.top_ups_since(since) + self.data.external_canister_groups.top_ups_since(since);
+        total / FORECAST_WINDOW_DAYS as Cycles
+    }
+
     pub fn metrics(&self) -> Metrics {
         Metrics {
             memory_used: memory::used(),
@@ -47,21 +106,42 @@ impl State {
             governance_principals: self.data.governance_principals.iter().copied().collect(),
             canisters: self.data.canisters.metrics(),
             icp_account: default_ledger_account(self.env.canister_id()).to_string(),
-            sns_root_canister: self.data.sns_root_canister,
+            external_canister_groups: self.data.external_canister_groups.metrics(),
             max_top_up_amount: self.data.max_top_up_amount,
             min_interval: self.data.min_interval,
             min_cycles_balance: self.data.min_cycles_balance,
             icp_burn_amount: self.data.icp_burn_amount,
             ledger_canister: self.data.ledger_canister,
             cycles_minting_canister: self.data.cycles_minting_canister,
+            top_up_budgets: self.data.top_up_policies.metrics(),
+            top_up_rate_per_day: self.daily_top_up_rate(),
+            days_until_empty: self.days_until_empty(),
+            registered_as_bot: self.data.registered_as_bot,
+            ops_group: self.data.ops_group,
+            anomaly_multiplier: self.data.anomaly_multiplier,
+            min_days_until_empty: self.data.min_days_until_empty,
+            recent_alerts: self.data.alerts.recent(),
         }
     }
 }
 
+async fn send_alert(group_id: ChatId, args: SendMessageArgs) {
+    match group_canister_c2c_client::send_message_v2(group_id.into(), &args).await {
+        Ok(group_canister::send_message_v2::Response::Success(_)) => {}
+        Ok(response) => error!(?response, text = ?args.content, "Failed to send alert to ops group"),
+        Err(error) => error!(?error, text = ?args.content, "Failed to send alert to ops group"),
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 struct Data {
     pub governance_principals: HashSet<Principal>,
     pub canisters: Canisters,
+    #[serde(default)]
+    pub external_canister_groups: ExternalCanisterGroups,
+    // Only read from the previous version's state, it is moved into `external_canister_groups` in
+    // post_upgrade
+    #[serde(default, skip_serializing)]
     pub sns_root_canister: Option<CanisterId>,
     pub max_top_up_amount: Cycles,
     pub min_interval: Milliseconds,
@@ -71,6 +151,26 @@ struct Data {
     pub cycles_minting_canister: CanisterId,
     pub cycles_top_up_pending_notification: Option<BlockIndex>,
     pub test_mode: bool,
+    #[serde(default)]
+    pub top_up_policies: TopUpPolicies,
+    #[serde(default)]
+    pub alerts: Alerts,
+    #[serde(default)]
+    pub registered_as_bot: bool,
+    #[serde(default)]
+    pub ops_group: Option<ChatId>,
+    #[serde(default = "default_anomaly_multiplier")]
+    pub anomaly_multiplier: u32,
+    #[serde(default = "default_min_days_until_empty")]
+    pub min_days_until_empty: u32,
+}
+
+fn default_anomaly_multiplier() -> u32 {
+    10
+}
+
+fn default_min_days_until_empty() -> u32 {
+    7
 }
 
 impl Data {
@@ -90,6 +190,7 @@ impl Data {
         Data {
             governance_principals: governance_principals.into_iter().collect(),
             canisters: Canisters::new(canisters, now),
+            external_canister_groups: ExternalCanisterGroups::default(),
             sns_root_canister: None,
             max_top_up_amount,
             min_interval,
@@ -99,6 +200,29 @@ impl Data {
             cycles_minting_canister,
             cycles_top_up_pending_notification: None,
             test_mode,
+            top_up_policies: TopUpPolicies::default(),
+            alerts: Alerts::default(),
+            registered_as_bot: false,
+            ops_group: None,
+            anomaly_multiplier: default_anomaly_multiplier(),
+            min_days_until_empty: default_min_days_until_empty(),
+        }
+    }
+
+    // Converts the SNS root canister from before external canister groups were supported into a
+    // group, keeping the thresholds which the previous SNS top up job used
+    pub fn migrate_sns_root_canister(&mut self) {
+        if let Some(root_canister_id) = self.sns_root_canister.take() {
+            if !self.external_canister_groups.exists(LEGACY_SNS_GROUP_NAME) {
+                self.external_canister_groups.set(
+                    LEGACY_SNS_GROUP_NAME.to_string(),
+                    Some(ExternalCanisterGroup {
+                        canisters: ExternalCanisters::SnsRoot(root_canister_id),
+                        min_cycles_balance: LEGACY_SNS_MIN_CYCLES_BALANCE,
+                        top_up_amount: self.max_top_up_amount,
+                    }),
+                );
+            }
         }
     }
 }
@@ -113,11 +237,19 @@ pub struct Metrics {
     pub governance_principals: Vec<Principal>,
     pub canisters: Vec<CanisterMetrics>,
     pub icp_account: String,
-    pub sns_root_canister: Option<CanisterId>,
+    pub external_canister_groups: Vec<ExternalCanisterGroupMetrics>,
     pub max_top_up_amount: Cycles,
     pub min_interval: Milliseconds,
     pub min_cycles_balance: Cycles,
     pub icp_burn_amount: Tokens,
     pub ledger_canister: CanisterId,
     pub cycles_minting_canister: CanisterId,
+    pub top_up_budgets: Vec<(BudgetTarget, TopUpBudget)>,
+    pub top_up_rate_per_day: Cycles,
+    pub days_until_empty: Option<u64>,
+    pub registered_as_bot: bool,
+    pub ops_group: Option<ChatId>,
+    pub anomaly_multiplier: u32,
+    pub min_days_until_empty: u32,
+    pub recent_alerts: Vec<RaisedAlert>,
 }
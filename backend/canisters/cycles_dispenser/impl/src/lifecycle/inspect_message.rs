@@ -18,7 +18,13 @@ fn inspect_message() {
 
 fn is_valid(method_name: &str, state: &State) -> bool {
     match method_name {
-        "add_canister" | "update_config" => state.is_caller_governance_principal(),
+        "add_canister"
+        | "register_bot"
+        | "set_canister_class"
+        | "set_external_canister_group"
+        | "set_ops_group"
+        | "set_top_up_budget"
+        | "update_config" => state.is_caller_governance_principal(),
         _ => false,
     }
 }
@@ -12,11 +12,13 @@ use tracing::info;
 fn post_upgrade(args: Args) {
     let env = init_env();
 
-    let (data, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>) =
+    let (mut data, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>) =
         deserialize_from_stable_memory(UPGRADE_BUFFER_SIZE).unwrap();
 
     canister_logger::init_with_logs(data.test_mode, logs, traces);
 
+    data.migrate_sns_root_canister();
+
     init_state(env, data, args.wasm_version);
 
     info!(version = %args.wasm_version, "Post-upgrade complete");
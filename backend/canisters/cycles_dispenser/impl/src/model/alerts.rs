@@ -0,0 +1,80 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use types::{CanisterId, Cycles, TimestampMillis};
+use utils::time::DAY_IN_MS;
+
+const MAX_RECENT_ALERTS: usize = 100;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Alerts {
+    // Each alert is raised at most once per day per target (canister, class, etc) so that the ops
+    // group isn't flooded. Entries older than a day are pruned so this stays bounded.
+    last_raised: HashMap<String, TimestampMillis>,
+    recent: VecDeque<RaisedAlert>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RaisedAlert {
+    pub timestamp: TimestampMillis,
+    pub alert: Alert,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum Alert {
+    CanisterDailyCapReached(CanisterId, Cycles),
+    ClassDailyCapReached(String, Cycles),
+    AnomalousTopUpRate(CanisterId, Cycles, Cycles),
+    LowForecast(u64),
+}
+
+impl Alert {
+    fn key(&self) -> String {
+        match self {
+            Alert::CanisterDailyCapReached(canister_id, _) => format!("canister_cap_{canister_id}"),
+            Alert::ClassDailyCapReached(class, _) => format!("class_cap_{class}"),
+            Alert::AnomalousTopUpRate(canister_id, ..) => format!("anomaly_{canister_id}"),
+            Alert::LowForecast(_) => "low_forecast".to_string(),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        match self {
+            Alert::CanisterDailyCapReached(canister_id, cap) => {
+                format!("Canister {canister_id} has reached its daily top up cap of {cap} cycles")
+            }
+            Alert::ClassDailyCapReached(class, cap) => {
+                format!("Canisters in class '{class}' have reached their daily top up cap of {cap} cycles")
+            }
+            Alert::AnomalousTopUpRate(canister_id, last_day, usual) => format!(
+                "Canister {canister_id} has been sent {last_day} cycles in the last 24 hours compared to a usual rate of {usual} cycles per day"
+            ),
+            Alert::LowForecast(days) => {
+                format!("At the current top up rate the cycles dispenser will run out of cycles in {days} days")
+            }
+        }
+    }
+}
+
+impl Alerts {
+    // Returns true if the alert should be sent, or false if it has already been raised within the last day
+    pub fn raise(&mut self, alert: Alert, now: TimestampMillis) -> bool {
+        self.last_raised.retain(|_, t| now < *t + DAY_IN_MS);
+
+        let key = alert.key();
+        if self.last_raised.contains_key(&key) {
+            return false;
+        }
+        self.last_raised.insert(key, now);
+
+        if self.recent.len() >= MAX_RECENT_ALERTS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(RaisedAlert { timestamp: now, alert });
+        true
+    }
+
+    pub fn recent(&self) -> Vec<RaisedAlert> {
+        self.recent.iter().cloned().collect()
+    }
+}
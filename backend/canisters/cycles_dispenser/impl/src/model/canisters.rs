@@ -3,6 +3,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Entry::Vacant;
 use std::collections::{BinaryHeap, HashMap};
 use types::{CanisterId, Cycles, TimestampMillis};
+use utils::time::DAY_IN_MS;
+
+const USUAL_RATE_WINDOW_DAYS: u64 = 7;
 
 #[derive(Serialize, Deserialize)]
 pub struct Canisters {
@@ -21,6 +24,8 @@ impl Canisters {
                             added: now,
                             top_ups: Vec::new(),
                             top_up_in_progress: false,
+                            pending_top_up: 0,
+                            class: None,
                         },
                     )
                 })
@@ -34,6 +39,8 @@ impl Canisters {
                 added: now,
                 top_ups: Vec::new(),
                 top_up_in_progress: false,
+                pending_top_up: 0,
+                class: None,
             });
             true
         } else {
@@ -41,16 +48,35 @@ impl Canisters {
         }
     }
 
+    pub fn get(&self, canister_id: &CanisterId) -> Option<&Canister> {
+        self.canisters.get(canister_id)
+    }
+
     pub fn get_mut(&mut self, canister_id: &CanisterId) -> Option<&mut Canister> {
         self.canisters.get_mut(canister_id)
     }
 
+    // The total amount sent to canisters in the given class since `since`, including the amounts
+    // reserved by top ups which are still in progress
+    pub fn class_top_ups_since(&self, class: &str, since: TimestampMillis) -> Cycles {
+        self.canisters
+            .values()
+            .filter(|c| c.class.as_deref() == Some(class))
+            .map(|c| c.top_ups_since(since) + c.pending_top_up)
+            .sum()
+    }
+
+    pub fn top_ups_since(&self, since: TimestampMillis) -> Cycles {
+        self.canisters.values().map(|c| c.top_ups_since(since)).sum()
+    }
+
     pub fn metrics(&self) -> Vec<CanisterMetrics> {
         self.canisters
             .iter()
             .map(|(id, c)| CanisterMetrics {
                 canister_id: *id,
                 added: c.added,
+                class: c.class.clone(),
                 top_ups: c.top_ups.clone(),
             })
             .collect()
@@ -92,15 +118,38 @@ pub struct Canister {
     added: TimestampMillis,
     top_ups: Vec<CyclesTopUp>,
     top_up_in_progress: bool,
+    #[serde(default)]
+    pending_top_up: Cycles,
+    #[serde(default)]
+    class: Option<String>,
 }
 
 impl Canister {
+    pub fn class(&self) -> Option<&str> {
+        self.class.as_deref()
+    }
+
+    pub fn set_class(&mut self, class: Option<String>) {
+        self.class = class;
+    }
+
     pub fn top_up_in_progress(&self) -> bool {
         self.top_up_in_progress
     }
 
-    pub fn set_top_up_in_progress(&mut self, in_progress: bool) {
-        self.top_up_in_progress = in_progress;
+    // Reserves the amount so that it counts towards the daily caps while the transfer is in flight
+    pub fn start_top_up(&mut self, amount: Cycles) {
+        self.top_up_in_progress = true;
+        self.pending_top_up = amount;
+    }
+
+    // Releases the reservation, the amount is only recorded if the transfer succeeded
+    pub fn complete_top_up(&mut self, top_up_amount: Option<Cycles>, now: TimestampMillis) {
+        self.top_up_in_progress = false;
+        self.pending_top_up = 0;
+        if let Some(amount) = top_up_amount {
+            self.record_top_up(amount, now);
+        }
     }
 
     pub fn latest_top_up(&self) -> Option<TimestampMillis> {
@@ -110,6 +159,26 @@ impl Canister {
     pub fn record_top_up(&mut self, amount: Cycles, now: TimestampMillis) {
         self.top_ups.push(CyclesTopUp { date: now, amount });
     }
+
+    pub fn top_ups_since(&self, since: TimestampMillis) -> Cycles {
+        self.top_ups
+            .iter()
+            .rev()
+            .take_while(|t| t.date > since)
+            .map(|t| t.amount)
+            .sum()
+    }
+
+    // The average amount sent per day during the week prior to the last 24 hours, used as the
+    // baseline when checking whether the canister's current usage is anomalous
+    pub fn usual_daily_top_up_rate(&self, now: TimestampMillis) -> Cycles {
+        let window_end = now.saturating_sub(DAY_IN_MS);
+        let window_start = window_end.saturating_sub(USUAL_RATE_WINDOW_DAYS * DAY_IN_MS);
+
+        self.top_ups_since(window_start)
+            .saturating_sub(self.top_ups_since(window_end))
+            / USUAL_RATE_WINDOW_DAYS as Cycles
+    }
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -122,5 +191,28 @@ struct CyclesTopUp {
 pub struct CanisterMetrics {
     canister_id: CanisterId,
     added: TimestampMillis,
+    class: Option<String>,
     top_ups: Vec<CyclesTopUp>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usual_daily_top_up_rate_excludes_last_day() {
+        let canister_id = CanisterId::from_slice(&[1]);
+        let mut canisters = Canisters::new(vec![canister_id], 0);
+        let now = 10 * DAY_IN_MS;
+
+        let canister = canisters.get_mut(&canister_id).unwrap();
+        for day in 2..9 {
+            canister.record_top_up(100, day * DAY_IN_MS + 1);
+        }
+        canister.record_top_up(1000, now - 1);
+
+        assert_eq!(canister.top_ups_since(now - DAY_IN_MS), 1000);
+        assert_eq!(canister.usual_daily_top_up_rate(now), 100);
+        assert_eq!(canisters.top_ups_since(now - 7 * DAY_IN_MS), 1600);
+    }
+}
@@ -0,0 +1,203 @@
+use candid::CandidType;
+use cycles_dispenser_canister::{ExternalCanisterGroup, ExternalCanisters, TopUpDecision, TopUpReason};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use types::{CanisterId, Cycles, Milliseconds, TimestampMillis};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ExternalCanisterGroups {
+    groups: BTreeMap<String, ExternalGroup>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExternalGroup {
+    config: ExternalCanisterGroup,
+    canisters: HashMap<CanisterId, ExternalCanister>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ExternalCanister {
+    balance: Option<ObservedBalance>,
+    top_ups: Vec<(TimestampMillis, Cycles)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct ObservedBalance {
+    cycles: Cycles,
+    timestamp: TimestampMillis,
+}
+
+impl ExternalCanisterGroups {
+    pub fn set(&mut self, name: String, config: Option<ExternalCanisterGroup>) {
+        if let Some(config) = config {
+            let group = self.groups.entry(name).or_insert_with(|| ExternalGroup {
+                config: config.clone(),
+                canisters: HashMap::new(),
+            });
+            // Forget any canisters which are no longer part of the group
+            match (&group.config.canisters, &config.canisters) {
+                (_, ExternalCanisters::Canisters(canister_ids)) => {
+                    group.canisters.retain(|id, _| canister_ids.contains(id));
+                }
+                (ExternalCanisters::SnsRoot(previous), ExternalCanisters::SnsRoot(root)) if previous == root => {}
+                _ => group.canisters.clear(),
+            }
+            group.config = config;
+        } else {
+            self.groups.remove(&name);
+        }
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        self.groups.contains_key(name)
+    }
+
+    pub fn sources(&self) -> Vec<(String, ExternalCanisters)> {
+        self.groups
+            .iter()
+            .map(|(name, g)| (name.clone(), g.config.canisters.clone()))
+            .collect()
+    }
+
+    pub fn record_balances(&mut self, name: &str, balances: Vec<(CanisterId, Cycles)>, now: TimestampMillis) {
+        if let Some(group) = self.groups.get_mut(name) {
+            for (canister_id, cycles) in balances {
+                group.canisters.entry(canister_id).or_default().balance = Some(ObservedBalance { cycles, timestamp: now });
+            }
+        }
+    }
+
+    pub fn record_top_up(&mut self, name: &str, canister_id: CanisterId, amount: Cycles, now: TimestampMillis) {
+        if let Some(canister) = self.groups.get_mut(name).and_then(|g| g.canisters.get_mut(&canister_id)) {
+            canister.top_ups.push((now, amount));
+        }
+    }
+
+    pub fn top_ups_since(&self, since: TimestampMillis) -> Cycles {
+        self.groups
+            .values()
+            .flat_map(|g| g.canisters.values())
+            .flat_map(|c| c.top_ups.iter().rev().take_while(|(t, _)| *t > since))
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+
+    // Works out which canisters should be topped up based on their most recently observed
+    // balances. Each decision reduces `available_cycles` so that the dispenser never goes below
+    // its own minimum balance.
+    pub fn evaluate(
+        &self,
+        group_filter: Option<&str>,
+        min_interval: Milliseconds,
+        mut available_cycles: Cycles,
+        now: TimestampMillis,
+    ) -> Vec<TopUpDecision> {
+        let mut decisions = Vec::new();
+
+        for (name, group) in self
+            .groups
+            .iter()
+            .filter(|(n, _)| group_filter.map_or(true, |f| f == n.as_str()))
+        {
+            let mut canister_ids: Vec<_> = group.canisters.keys().copied().collect();
+            if let ExternalCanisters::Canisters(ids) = &group.config.canisters {
+                canister_ids.extend(ids.iter().filter(|id| !group.canisters.contains_key(id)));
+            }
+            canister_ids.sort();
+
+            for canister_id in canister_ids {
+                let canister = group.canisters.get(&canister_id);
+                let balance = canister.and_then(|c| c.balance);
+                let latest_top_up = canister.and_then(|c| c.top_ups.last()).map(|(t, _)| *t);
+                let amount = group.config.top_up_amount;
+
+                let reason = match balance {
+                    None => TopUpReason::BalanceUnknown,
+                    Some(b) if b.cycles >= group.config.min_cycles_balance => TopUpReason::AboveThreshold(b.cycles),
+                    Some(b) => {
+                        if let Some(wait) = latest_top_up.and_then(|t| (t + min_interval).checked_sub(now)) {
+                            TopUpReason::Throttled(wait)
+                        } else if available_cycles < amount {
+                            TopUpReason::DispenserBalanceTooLow
+                        } else {
+                            available_cycles -= amount;
+                            TopUpReason::BelowThreshold(b.cycles)
+                        }
+                    }
+                };
+
+                decisions.push(TopUpDecision {
+                    group: name.clone(),
+                    canister_id,
+                    cycles_balance: balance.map(|b| b.cycles),
+                    balance_observed_at: balance.map(|b| b.timestamp),
+                    top_up_amount: matches!(reason, TopUpReason::BelowThreshold(_)).then_some(amount),
+                    reason,
+                });
+            }
+        }
+
+        decisions
+    }
+
+    pub fn metrics(&self) -> Vec<ExternalCanisterGroupMetrics> {
+        self.groups
+            .iter()
+            .map(|(name, g)| ExternalCanisterGroupMetrics {
+                name: name.clone(),
+                config: g.config.clone(),
+                canisters: g.canisters.len() as u32,
+                top_ups: g.canisters.values().map(|c| c.top_ups.len() as u32).sum(),
+            })
+            .collect()
+    }
+}
+
+#[derive(CandidType, Serialize, Debug)]
+pub struct ExternalCanisterGroupMetrics {
+    name: String,
+    config: ExternalCanisterGroup,
+    canisters: u32,
+    top_ups: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canister(id: u8) -> CanisterId {
+        CanisterId::from_slice(&[id])
+    }
+
+    #[test]
+    fn decisions_respect_thresholds_and_available_cycles() {
+        let mut groups = ExternalCanisterGroups::default();
+        groups.set(
+            "partner".to_string(),
+            Some(ExternalCanisterGroup {
+                canisters: ExternalCanisters::Canisters(vec![canister(1), canister(2), canister(3), canister(4)]),
+                min_cycles_balance: 100,
+                top_up_amount: 50,
+            }),
+        );
+        groups.record_balances("partner", vec![(canister(1), 10), (canister(2), 20), (canister(3), 200)], 1);
+
+        let decisions = groups.evaluate(None, 1000, 60, 2);
+        let reasons: Vec<_> = decisions.iter().map(|d| d.reason.clone()).collect();
+
+        assert_eq!(
+            reasons,
+            vec![
+                TopUpReason::BelowThreshold(10),
+                TopUpReason::DispenserBalanceTooLow,
+                TopUpReason::AboveThreshold(200),
+                TopUpReason::BalanceUnknown,
+            ]
+        );
+
+        groups.record_top_up("partner", canister(1), 50, 2);
+        let decisions = groups.evaluate(Some("partner"), 1000, 1000, 10);
+        assert_eq!(decisions[0].reason, TopUpReason::Throttled(992));
+        assert_eq!(decisions[1].top_up_amount, Some(50));
+    }
+}
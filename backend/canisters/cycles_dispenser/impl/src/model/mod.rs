@@ -1 +1,4 @@
+pub mod alerts;
 pub mod canisters;
+pub mod external_canister_groups;
+pub mod policies;
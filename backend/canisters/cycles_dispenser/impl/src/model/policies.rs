@@ -0,0 +1,92 @@
+use cycles_dispenser_canister::{BudgetTarget, TopUpBudget};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::{CanisterId, Cycles};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct TopUpPolicies {
+    canister_budgets: HashMap<CanisterId, TopUpBudget>,
+    class_budgets: HashMap<String, TopUpBudget>,
+}
+
+impl TopUpPolicies {
+    pub fn set(&mut self, target: BudgetTarget, budget: Option<TopUpBudget>) {
+        match (target, budget) {
+            (BudgetTarget::Canister(canister_id), Some(budget)) => {
+                self.canister_budgets.insert(canister_id, budget);
+            }
+            (BudgetTarget::Canister(canister_id), None) => {
+                self.canister_budgets.remove(&canister_id);
+            }
+            (BudgetTarget::Class(class), Some(budget)) => {
+                self.class_budgets.insert(class, budget);
+            }
+            (BudgetTarget::Class(class), None) => {
+                self.class_budgets.remove(&class);
+            }
+        }
+    }
+
+    pub fn canister_budget(&self, canister_id: &CanisterId) -> Option<&TopUpBudget> {
+        self.canister_budgets.get(canister_id)
+    }
+
+    pub fn class_budget(&self, class: &str) -> Option<&TopUpBudget> {
+        self.class_budgets.get(class)
+    }
+
+    // The largest single top up allowed, taking the lowest of the global, canister and class limits
+    pub fn max_top_up_amount(&self, global_max: Cycles, canister_id: &CanisterId, class: Option<&str>) -> Cycles {
+        [self.canister_budget(canister_id), class.and_then(|c| self.class_budget(c))]
+            .into_iter()
+            .flatten()
+            .filter_map(|b| b.max_top_up_amount)
+            .fold(global_max, |max, amount| max.min(amount))
+    }
+
+    pub fn metrics(&self) -> Vec<(BudgetTarget, TopUpBudget)> {
+        self.canister_budgets
+            .iter()
+            .map(|(id, b)| (BudgetTarget::Canister(*id), b.clone()))
+            .chain(
+                self.class_budgets
+                    .iter()
+                    .map(|(c, b)| (BudgetTarget::Class(c.clone()), b.clone())),
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowest_max_top_up_amount_applies() {
+        let canister_id = CanisterId::from_slice(&[1]);
+        let mut policies = TopUpPolicies::default();
+
+        assert_eq!(policies.max_top_up_amount(100, &canister_id, Some("bucket")), 100);
+
+        policies.set(
+            BudgetTarget::Class("bucket".to_string()),
+            Some(TopUpBudget {
+                max_top_up_amount: Some(50),
+                daily_cap: None,
+            }),
+        );
+        policies.set(
+            BudgetTarget::Canister(canister_id),
+            Some(TopUpBudget {
+                max_top_up_amount: Some(80),
+                daily_cap: Some(200),
+            }),
+        );
+
+        assert_eq!(policies.max_top_up_amount(100, &canister_id, Some("bucket")), 50);
+        assert_eq!(policies.max_top_up_amount(100, &canister_id, None), 80);
+
+        policies.set(BudgetTarget::Canister(canister_id), None);
+        assert_eq!(policies.max_top_up_amount(100, &canister_id, None), 100);
+    }
+}
@@ -1 +1,2 @@
 mod http_request;
+mod top_up_dry_run;
@@ -0,0 +1,26 @@
+use crate::{read_state, State};
+use cycles_dispenser_canister::top_up_dry_run::{Response::*, *};
+use ic_cdk_macros::query;
+
+#[query]
+fn top_up_dry_run(args: Args) -> Response {
+    read_state(|state| top_up_dry_run_impl(args, state))
+}
+
+fn top_up_dry_run_impl(args: Args, state: &State) -> Response {
+    if let Some(group) = &args.group {
+        if !state.data.external_canister_groups.exists(group) {
+            return GroupNotFound;
+        }
+    }
+
+    let available = state.env.cycles_balance().saturating_sub(state.data.min_cycles_balance);
+    let decisions = state.data.external_canister_groups.evaluate(
+        args.group.as_deref(),
+        state.data.min_interval,
+        available,
+        state.env.now(),
+    );
+
+    Success(SuccessResult { decisions })
+}
@@ -1,3 +1,4 @@
+use crate::model::alerts::Alert;
 use crate::{mutate_state, State};
 use candid::{CandidType, Principal};
 use canister_tracing_macros::trace;
@@ -8,6 +9,7 @@ use std::cmp::min;
 use std::convert::TryInto;
 use tracing::{error, info};
 use types::{CanisterId, Cycles, Milliseconds, TimestampMillis};
+use utils::time::DAY_IN_MS;
 
 #[update]
 #[trace]
@@ -34,32 +36,77 @@ struct PrepareResult {
 
 fn prepare(args: Args, state: &mut State) -> Result<PrepareResult, Response> {
     let canister_id: CanisterId = state.env.caller();
-    let max_amount = state.data.max_top_up_amount;
-    let amount = args.amount.map_or(max_amount, |c| min(c, max_amount));
-
-    if let Some(canister) = state.data.canisters.get_mut(&canister_id) {
-        if canister.top_up_in_progress() {
-            Err(TopUpInProgress)
-        } else if let Some(interval) =
-            calc_required_wait_period(canister.latest_top_up(), state.data.min_interval, state.env.now())
-        {
-            Err(Throttled(interval))
-        } else if state.env.cycles_balance() < state.data.min_cycles_balance + amount {
-            Err(CyclesBalanceTooLow)
-        } else {
-            canister.set_top_up_in_progress(true);
-            Ok(PrepareResult { canister_id, amount })
+    let now = state.env.now();
+
+    let canister = match state.data.canisters.get(&canister_id) {
+        Some(c) => c,
+        None => return Err(NotAuthorized),
+    };
+
+    let class = canister.class().map(|c| c.to_string());
+    let max_amount = state
+        .data
+        .top_up_policies
+        .max_top_up_amount(state.data.max_top_up_amount, &canister_id, class.as_deref());
+    let mut amount = args.amount.map_or(max_amount, |c| min(c, max_amount));
+
+    if canister.top_up_in_progress() {
+        return Err(TopUpInProgress);
+    } else if let Some(interval) = calc_required_wait_period(canister.latest_top_up(), state.data.min_interval, now) {
+        return Err(Throttled(interval));
+    }
+
+    // The amount is clipped to whatever remains of the daily caps, the request is only rejected
+    // once a cap has been used up entirely. Alerts are deduplicated per target per day.
+    let one_day_ago = now.saturating_sub(DAY_IN_MS);
+    if let Some(cap) = state
+        .data
+        .top_up_policies
+        .canister_budget(&canister_id)
+        .and_then(|b| b.daily_cap)
+    {
+        let remaining = cap.saturating_sub(canister.top_ups_since(one_day_ago));
+        if remaining == 0 {
+            state.raise_alert(Alert::CanisterDailyCapReached(canister_id, cap));
+            return Err(DailyCapReached);
         }
-    } else {
-        Err(NotAuthorized)
+        amount = min(amount, remaining);
     }
+    if let Some(class) = class {
+        if let Some(cap) = state.data.top_up_policies.class_budget(&class).and_then(|b| b.daily_cap) {
+            let remaining = cap.saturating_sub(state.data.canisters.class_top_ups_since(&class, one_day_ago));
+            if remaining == 0 {
+                state.raise_alert(Alert::ClassDailyCapReached(class, cap));
+                return Err(DailyCapReached);
+            }
+            amount = min(amount, remaining);
+        }
+    }
+
+    if state.env.cycles_balance() < state.data.min_cycles_balance + amount {
+        return Err(CyclesBalanceTooLow);
+    }
+
+    state.data.canisters.get_mut(&canister_id).unwrap().start_top_up(amount);
+    Ok(PrepareResult { canister_id, amount })
 }
 
 fn commit(canister_id: &CanisterId, top_up_amount: Option<Cycles>, state: &mut State) {
+    let now = state.env.now();
     if let Some(canister) = state.data.canisters.get_mut(canister_id) {
-        canister.set_top_up_in_progress(false);
-        if let Some(amount) = top_up_amount {
-            canister.record_top_up(amount, state.env.now())
+        canister.complete_top_up(top_up_amount, now);
+        if top_up_amount.is_some() {
+            let last_day = canister.top_ups_since(now.saturating_sub(DAY_IN_MS));
+            let usual = canister.usual_daily_top_up_rate(now);
+            if usual > 0 && last_day >= usual * state.data.anomaly_multiplier as Cycles {
+                state.raise_alert(Alert::AnomalousTopUpRate(*canister_id, last_day, usual));
+            }
+
+            if let Some(days) = state.days_until_empty() {
+                if days < state.data.min_days_until_empty as u64 {
+                    state.raise_alert(Alert::LowForecast(days));
+                }
+            }
         }
     }
 }
@@ -104,3 +151,71 @@ fn calc_required_wait_period(
 ) -> Option<Milliseconds> {
     latest_top_up.and_then(|t| (t + min_interval).checked_sub(now))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Data;
+    use cycles_dispenser_canister::{BudgetTarget, TopUpBudget};
+    use ic_ledger_types::Tokens;
+    use utils::env::test::TestEnv;
+
+    #[test]
+    fn in_flight_top_ups_count_towards_class_cap() {
+        let canister1 = CanisterId::from_slice(&[1]);
+        let canister2 = CanisterId::from_slice(&[2]);
+        let mut state = setup_state(vec![canister1, canister2]);
+        for canister_id in [canister1, canister2] {
+            state
+                .data
+                .canisters
+                .get_mut(&canister_id)
+                .unwrap()
+                .set_class(Some("class".to_string()));
+        }
+        state.data.top_up_policies.set(
+            BudgetTarget::Class("class".to_string()),
+            Some(TopUpBudget {
+                max_top_up_amount: None,
+                daily_cap: Some(100),
+            }),
+        );
+
+        // Both requests are prepared before either transfer completes
+        let first = prepare_as(canister1, &mut state).unwrap();
+        assert_eq!(first.amount, 80);
+        let second = prepare_as(canister2, &mut state).unwrap();
+        assert_eq!(second.amount, 20);
+
+        // A failed transfer releases its reservation
+        commit(&canister1, None, &mut state);
+        commit(&canister2, Some(second.amount), &mut state);
+        assert_eq!(prepare_as(canister1, &mut state).unwrap().amount, 80);
+    }
+
+    fn prepare_as(canister_id: CanisterId, state: &mut State) -> Result<PrepareResult, Response> {
+        let env = TestEnv {
+            caller: canister_id,
+            ..TestEnv::default()
+        };
+        state.env = Box::new(env);
+        prepare(Args { amount: None }, state)
+    }
+
+    fn setup_state(canisters: Vec<CanisterId>) -> State {
+        let env = TestEnv::default();
+        let data = Data::new(
+            Vec::new(),
+            canisters,
+            80,
+            0,
+            0,
+            Tokens::from_e8s(0),
+            CanisterId::from_slice(&[10]),
+            CanisterId::from_slice(&[11]),
+            env.now,
+            true,
+        );
+        State::new(Box::new(env), data)
+    }
+}
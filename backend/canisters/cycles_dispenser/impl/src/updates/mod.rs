@@ -1,3 +1,8 @@
 mod add_canister;
 mod c2c_request_cycles;
+mod register_bot;
+mod set_canister_class;
+mod set_external_canister_group;
+mod set_ops_group;
+mod set_top_up_budget;
 mod update_config;
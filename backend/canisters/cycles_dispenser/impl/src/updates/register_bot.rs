@@ -0,0 +1,31 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, read_state};
+use canister_api_macros::proposal;
+use canister_tracing_macros::trace;
+use cycles_dispenser_canister::register_bot::{Response::*, *};
+use types::Cycles;
+
+const BOT_REGISTRATION_FEE: Cycles = 10_000_000_000_000; // 10T
+
+// Registers the dispenser as a bot user so that it can join the ops group and post alerts to it
+#[proposal(guard = "caller_is_governance_principal")]
+#[trace]
+async fn register_bot(args: Args) -> Response {
+    if read_state(|state| state.data.registered_as_bot) {
+        return AlreadyRegistered;
+    }
+
+    let c2c_args = user_index_canister::c2c_register_bot::Args {
+        username: args.username,
+        display_name: None,
+    };
+    match user_index_canister_c2c_client::c2c_register_bot(args.user_index_canister_id, &c2c_args, BOT_REGISTRATION_FEE).await {
+        Ok(user_index_canister::c2c_register_bot::Response::Success)
+        | Ok(user_index_canister::c2c_register_bot::Response::AlreadyRegistered) => {
+            mutate_state(|state| state.data.registered_as_bot = true);
+            Success
+        }
+        Ok(response) => RegistrationFailed(format!("{response:?}")),
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
@@ -0,0 +1,20 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, State};
+use canister_api_macros::proposal;
+use canister_tracing_macros::trace;
+use cycles_dispenser_canister::set_canister_class::{Response::*, *};
+
+#[proposal(guard = "caller_is_governance_principal")]
+#[trace]
+fn set_canister_class(args: Args) -> Response {
+    mutate_state(|state| set_canister_class_impl(args, state))
+}
+
+fn set_canister_class_impl(args: Args, state: &mut State) -> Response {
+    if let Some(canister) = state.data.canisters.get_mut(&args.canister_id) {
+        canister.set_class(args.class);
+        Success
+    } else {
+        CanisterNotFound
+    }
+}
@@ -0,0 +1,40 @@
+use crate::guards::caller_is_governance_principal;
+use crate::jobs::top_up_external_canisters::get_balances;
+use crate::mutate_state;
+use canister_api_macros::proposal;
+use canister_tracing_macros::trace;
+use cycles_dispenser_canister::set_external_canister_group::{Response::*, *};
+use cycles_dispenser_canister::ExternalCanisters;
+
+const MAX_NAME_LENGTH: usize = 50;
+
+#[proposal(guard = "caller_is_governance_principal")]
+#[trace]
+async fn set_external_canister_group(args: Args) -> Response {
+    if args.name.trim().is_empty() || args.name.len() > MAX_NAME_LENGTH {
+        return NameInvalid;
+    }
+
+    // Canisters whose balances can't be read would never be topped up, so they are rejected
+    let mut balances = Vec::new();
+    if let Some(ExternalCanisters::Canisters(canister_ids)) = args.group.as_ref().map(|g| &g.canisters) {
+        balances = get_balances(canister_ids.clone()).await;
+
+        let not_controlled: Vec<_> = canister_ids
+            .iter()
+            .filter(|id| !balances.iter().any(|(c, _)| c == *id))
+            .copied()
+            .collect();
+
+        if !not_controlled.is_empty() {
+            return NotController(not_controlled);
+        }
+    }
+
+    mutate_state(|state| {
+        let now = state.env.now();
+        state.data.external_canister_groups.set(args.name.clone(), args.group);
+        state.data.external_canister_groups.record_balances(&args.name, balances, now);
+    });
+    Success
+}
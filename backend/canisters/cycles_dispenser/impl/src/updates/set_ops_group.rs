@@ -0,0 +1,41 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, read_state};
+use canister_api_macros::proposal;
+use canister_tracing_macros::trace;
+use cycles_dispenser_canister::set_ops_group::{Response::*, *};
+use local_user_index_canister::join_group::Response as JoinGroupResponse;
+
+// Joins the group as the dispenser's bot user, since only members can post to a group, then sets
+// it as the group which alerts are posted to
+#[proposal(guard = "caller_is_governance_principal")]
+#[trace]
+async fn set_ops_group(args: Args) -> Response {
+    let Some(group_id) = args.group_id else {
+        mutate_state(|state| state.data.ops_group = None);
+        return Success;
+    };
+
+    if !read_state(|state| state.data.registered_as_bot) {
+        return BotNotRegistered;
+    }
+
+    let local_user_index_canister_id =
+        match group_canister_c2c_client::local_user_index(group_id.into(), &group_canister::local_user_index::Args {}).await {
+            Ok(group_canister::local_user_index::Response::Success(canister_id)) => canister_id,
+            Err(error) => return InternalError(format!("{error:?}")),
+        };
+
+    let c2c_args = local_user_index_canister::join_group::Args {
+        chat_id: group_id,
+        invite_code: args.invite_code,
+        correlation_id: 0,
+    };
+    match local_user_index_canister_c2c_client::join_group(local_user_index_canister_id, &c2c_args).await {
+        Ok(JoinGroupResponse::Success(_) | JoinGroupResponse::AlreadyInGroup | JoinGroupResponse::AlreadyInGroupV2(_)) => {
+            mutate_state(|state| state.data.ops_group = Some(group_id));
+            Success
+        }
+        Ok(response) => JoinGroupFailed(format!("{response:?}")),
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
@@ -0,0 +1,23 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, State};
+use canister_api_macros::proposal;
+use canister_tracing_macros::trace;
+use cycles_dispenser_canister::set_top_up_budget::{Response::*, *};
+use cycles_dispenser_canister::BudgetTarget;
+
+#[proposal(guard = "caller_is_governance_principal")]
+#[trace]
+fn set_top_up_budget(args: Args) -> Response {
+    mutate_state(|state| set_top_up_budget_impl(args, state))
+}
+
+fn set_top_up_budget_impl(args: Args, state: &mut State) -> Response {
+    if let BudgetTarget::Canister(canister_id) = &args.target {
+        if state.data.canisters.get(canister_id).is_none() {
+            return CanisterNotFound;
+        }
+    }
+
+    state.data.top_up_policies.set(args.target, args.budget);
+    Success
+}
@@ -23,5 +23,11 @@ fn update_config_impl(args: Args, state: &mut State) -> Response {
     if let Some(icp_burn_amount) = args.icp_burn_amount {
         state.data.icp_burn_amount = icp_burn_amount;
     }
+    if let Some(anomaly_multiplier) = args.anomaly_multiplier {
+        state.data.anomaly_multiplier = anomaly_multiplier;
+    }
+    if let Some(min_days_until_empty) = args.min_days_until_empty {
+        state.data.min_days_until_empty = min_days_until_empty;
+    }
     Success
 }
@@ -1,4 +1,5 @@
 use crate::lifecycle::{init_env, init_state};
+use crate::memory::{get_log_overflow_data_memory, get_log_overflow_index_memory};
 use crate::Data;
 use canister_tracing_macros::trace;
 use exchange_bot_canister::init::Args;
@@ -10,6 +11,7 @@ use utils::cycles::init_cycles_dispenser_client;
 #[trace]
 fn init(args: Args) {
     canister_logger::init(args.test_mode);
+    canister_logger::init_log_overflow(get_log_overflow_index_memory(), get_log_overflow_data_memory());
     init_cycles_dispenser_client(args.cycles_dispenser_canister_id);
 
     let env = init_env();
@@ -17,6 +17,7 @@ fn main() {
     generate_candid_method!(group, selected_updates_v2, query);
     generate_candid_method!(group, summary, query);
     generate_candid_method!(group, summary_updates, query);
+    generate_candid_method!(group, typing_indicators, query);
 
     generate_candid_method!(group, add_reaction, update);
     generate_candid_method!(group, block_user, update);
@@ -28,6 +29,7 @@ fn main() {
     generate_candid_method!(group, disable_invite_code, update);
     generate_candid_method!(group, edit_message_v2, update);
     generate_candid_method!(group, enable_invite_code, update);
+    generate_candid_method!(group, follow_proposal_votes, update);
     generate_candid_method!(group, follow_thread, update);
     generate_candid_method!(group, pin_message_v2, update);
     generate_candid_method!(group, register_poll_vote, update);
@@ -37,6 +39,7 @@ fn main() {
     generate_candid_method!(group, remove_reaction, update);
     generate_candid_method!(group, reset_invite_code, update);
     generate_candid_method!(group, send_message_v2, update);
+    generate_candid_method!(group, set_typing, update);
     generate_candid_method!(group, toggle_mute_notifications, update);
     generate_candid_method!(group, unblock_user, update);
     generate_candid_method!(group, undelete_messages, update);
@@ -0,0 +1,22 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, TypingIndicator};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Only indicators which have changed since this time are returned
+    pub updated_since: Option<TimestampMillis>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    SuccessNoUpdates(TimestampMillis),
+    CallerNotInGroup,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub timestamp: TimestampMillis,
+    pub indicators: Vec<TypingIndicator>,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use types::Empty;
+
+pub type Args = Empty;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NotStarted,
+    Incomplete(u64),
+    DeserializationFailed(String),
+}
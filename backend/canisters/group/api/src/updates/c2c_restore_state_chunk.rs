@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub total_bytes: u64,
+    pub from: u64,
+    pub bytes: ByteBuf,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    UnexpectedOffset(u64),
+    ExceedsTotalSize,
+}
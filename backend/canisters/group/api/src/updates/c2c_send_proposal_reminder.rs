@@ -0,0 +1,20 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{MessageId, MessageIndex};
+
+// Sent by the ProposalsBot into the thread of a proposal, notifying the thread's followers and the
+// members who usually vote but haven't yet voted on this proposal
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub thread_root_message_index: MessageIndex,
+    pub message_id: MessageId,
+    pub text: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    ProposalNotFound,
+    NotAuthorized,
+    ChatFrozen,
+}
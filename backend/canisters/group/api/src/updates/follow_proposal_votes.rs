@@ -0,0 +1,21 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::UserId;
+
+// Automatically votes on the group's proposals in the same way as `followee` does, passing in
+// `None` stops following
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub followee: Option<UserId>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    FolloweeNotInGroup,
+    CannotFollowSelf,
+    CircularFollow,
+    UserNotInGroup,
+    UserSuspended,
+    GroupFrozen,
+}
@@ -2,12 +2,16 @@ pub mod add_reaction;
 pub mod block_user;
 pub mod c2c_delete_group;
 pub mod c2c_export_group;
+pub mod c2c_export_state;
+pub mod c2c_finish_state_restore;
 pub mod c2c_freeze_group;
 pub mod c2c_invite_users;
 pub mod c2c_join_group;
 pub mod c2c_leave_group;
 pub mod c2c_report_message;
 pub mod c2c_report_message_v2;
+pub mod c2c_restore_state_chunk;
+pub mod c2c_send_proposal_reminder;
 pub mod c2c_set_user_suspended;
 pub mod c2c_start_import_into_community;
 pub mod c2c_tip_message;
@@ -23,6 +27,7 @@ pub mod delete_messages;
 pub mod disable_invite_code;
 pub mod edit_message_v2;
 pub mod enable_invite_code;
+pub mod follow_proposal_votes;
 pub mod follow_thread;
 pub mod pin_message_v2;
 pub mod register_poll_vote;
@@ -32,6 +37,7 @@ pub mod remove_participant;
 pub mod remove_reaction;
 pub mod reset_invite_code;
 pub mod send_message_v2;
+pub mod set_typing;
 pub mod toggle_mute_notifications;
 pub mod unblock_user;
 pub mod undelete_messages;
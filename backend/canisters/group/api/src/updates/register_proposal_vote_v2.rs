@@ -13,6 +13,7 @@ pub enum Response {
     Success,
     CallerNotInGroup,
     ProposalMessageNotFound,
+    ProposalNotSupported,
     UserSuspended,
     ChatFrozen,
 }
@@ -0,0 +1,18 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{MessageIndex, TypingActivity};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub thread_root_message_index: Option<MessageIndex>,
+    // Set to None once the user stops typing or recording
+    pub activity: Option<TypingActivity>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CallerNotInGroup,
+    UserSuspended,
+    ChatFrozen,
+}
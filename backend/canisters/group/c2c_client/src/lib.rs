@@ -16,12 +16,16 @@ generate_candid_c2c_call!(selected_initial);
 // Updates
 generate_c2c_call!(c2c_delete_group);
 generate_c2c_call!(c2c_export_group);
+generate_c2c_call!(c2c_export_state);
+generate_c2c_call!(c2c_finish_state_restore);
 generate_c2c_call!(c2c_freeze_group);
 generate_c2c_call!(c2c_invite_users);
 generate_c2c_call!(c2c_join_group);
 generate_c2c_call!(c2c_leave_group);
 generate_c2c_call!(c2c_report_message_v2);
 generate_c2c_call!(c2c_report_message);
+generate_c2c_call!(c2c_restore_state_chunk);
+generate_c2c_call!(c2c_send_proposal_reminder);
 generate_c2c_call!(c2c_set_user_suspended);
 generate_c2c_call!(c2c_start_import_into_community);
 generate_c2c_call!(c2c_tip_message);
@@ -9,7 +9,9 @@ use canister_state_macros::canister_state;
 use canister_timer_jobs::TimerJobs;
 use chat_events::{ChatEventInternal, Reader};
 use fire_and_forget_handler::FireAndForgetHandler;
-use group_chat_core::{AddResult as AddMemberResult, GroupChatCore, GroupMemberInternal, InvitedUsersResult, UserInvitation};
+use group_chat_core::{
+    AddResult as AddMemberResult, GroupChatCore, GroupMemberInternal, InvitedUsersResult, ProposalVoteFollows, UserInvitation,
+};
 use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use msgpack::serialize_then_unwrap;
 use notifications_canister::c2c_push_notification;
@@ -23,6 +25,7 @@ use types::{
     FrozenGroupInfo, GroupCanisterGroupChatSummary, GroupPermissions, GroupSubtype, MessageIndex, Milliseconds, Notification,
     Rules, TimestampMillis, Timestamped, UserId, MAX_THREADS_IN_SUMMARY,
 };
+use utils::canister::StateSnapshots;
 use utils::consts::OPENCHAT_BOT_USER_ID;
 use utils::env::Environment;
 use utils::regular_jobs::RegularJobs;
@@ -203,6 +206,21 @@ impl RuntimeState {
         }
     }
 
+    // Replaces the state with one restored from a snapshot. The ids of the canisters this group
+    // interacts with are kept, since the snapshot may have been taken from a group on another subnet.
+    pub fn restore_data(&mut self, mut data: Data) {
+        data.group_index_canister_id = self.data.group_index_canister_id;
+        data.local_group_index_canister_id = self.data.local_group_index_canister_id;
+        data.user_index_canister_id = self.data.user_index_canister_id;
+        data.local_user_index_canister_id = self.data.local_user_index_canister_id;
+        data.notifications_canister_id = self.data.notifications_canister_id;
+        data.proposals_bot_user_id = self.data.proposals_bot_user_id;
+        data.test_mode = self.data.test_mode;
+
+        self.data.timer_jobs.cancel_jobs(|_| true);
+        self.data = data;
+    }
+
     pub fn run_event_expiry_job(&mut self) {
         let now = self.env.now();
         self.data.chat.remove_expired_events(now);
@@ -255,6 +273,7 @@ impl RuntimeState {
             new_joiner_rewards: self.data.new_joiner_rewards.as_ref().map(|r| r.metrics()),
             frozen: self.data.is_frozen(),
             instruction_counts: self.data.instruction_counts_log.iter().collect(),
+            timer_jobs: self.data.timer_jobs.len() as u32,
             community_being_imported_into: self
                 .data
                 .community_being_imported_into
@@ -303,6 +322,10 @@ struct Data {
     pub serialized_chat_state: Option<ByteBuf>,
     #[serde(default)]
     pub next_event_expiry: Option<TimestampMillis>,
+    #[serde(default)]
+    pub proposal_vote_follows: ProposalVoteFollows,
+    #[serde(skip)]
+    pub state_snapshots: StateSnapshots,
 }
 
 fn init_instruction_counts_log() -> InstructionCountsLog {
@@ -371,6 +394,8 @@ impl Data {
             community_being_imported_into: None,
             serialized_chat_state: None,
             next_event_expiry: None,
+            proposal_vote_follows: ProposalVoteFollows::default(),
+            state_snapshots: StateSnapshots::default(),
         }
     }
 
@@ -460,6 +485,14 @@ impl Data {
             .record(function_id, instructions_count, wasm_version, now);
     }
 
+    // Per endpoint counts are only recorded in test mode (they are used by the load tests) so that
+    // the log doesn't grow with every message in production
+    pub fn record_endpoint_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        if self.test_mode {
+            self.record_instructions_count(function_id, now);
+        }
+    }
+
     pub fn mark_group_updated_in_user_canister(&self, user_id: UserId) {
         self.fire_and_forget_handler.send(
             user_id.into(),
@@ -504,6 +537,7 @@ pub struct Metrics {
     pub new_joiner_rewards: Option<NewJoinerRewardMetrics>,
     pub frozen: bool,
     pub instruction_counts: Vec<InstructionCountEntry>,
+    pub timer_jobs: u32,
     pub community_being_imported_into: Option<CommunityId>,
     pub serialized_chat_state_bytes: u64,
     pub canister_ids: CanisterIds,
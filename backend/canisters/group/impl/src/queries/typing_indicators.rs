@@ -0,0 +1,28 @@
+use crate::{read_state, RuntimeState};
+use group_canister::typing_indicators::{Response::*, *};
+use group_chat_core::TypingIndicatorsResult;
+use ic_cdk_macros::query;
+
+#[query]
+fn typing_indicators(args: Args) -> Response {
+    read_state(|state| typing_indicators_impl(args, state))
+}
+
+fn typing_indicators_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+
+    if let Some(user_id) = state.data.lookup_user_id(caller) {
+        let now = state.env.now();
+
+        match state.data.chat.typing_indicators(user_id, args.updated_since, now) {
+            TypingIndicatorsResult::Success(indicators) => Success(SuccessResult {
+                timestamp: now,
+                indicators,
+            }),
+            TypingIndicatorsResult::SuccessNoUpdates => SuccessNoUpdates(now),
+            TypingIndicatorsResult::UserNotInGroup => CallerNotInGroup,
+        }
+    } else {
+        CallerNotInGroup
+    }
+}
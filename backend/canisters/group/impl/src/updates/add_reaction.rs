@@ -5,6 +5,7 @@ use chat_events::Reader;
 use group_canister::add_reaction::{Response::*, *};
 use group_chat_core::AddRemoveReactionResult;
 use ic_cdk_macros::update;
+use instruction_counts_log::InstructionCountFunctionId;
 use types::{EventIndex, GroupReactionAddedNotification, Notification, UserId};
 
 #[update]
@@ -12,7 +13,13 @@ use types::{EventIndex, GroupReactionAddedNotification, Notification, UserId};
 fn add_reaction(args: Args) -> Response {
     run_regular_jobs();
 
-    mutate_state(|state| add_reaction_impl(args, state))
+    mutate_state(|state| {
+        let response = add_reaction_impl(args, state);
+        state
+            .data
+            .record_endpoint_instructions_count(InstructionCountFunctionId::AddReaction, state.env.now());
+        response
+    })
 }
 
 fn add_reaction_impl(args: Args, state: &mut RuntimeState) -> Response {
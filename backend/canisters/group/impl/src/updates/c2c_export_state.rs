@@ -0,0 +1,37 @@
+use crate::guards::caller_is_group_index_or_local_group_index;
+use crate::lifecycle::UPGRADE_BUFFER_SIZE;
+use crate::memory::get_upgrades_memory;
+use crate::{mutate_state, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use group_canister::c2c_export_state::{Response::*, *};
+use ic_stable_structures::reader::{BufferedReader, Reader};
+use ic_stable_structures::Memory;
+
+#[update_msgpack(guard = "caller_is_group_index_or_local_group_index")]
+#[trace]
+fn c2c_export_state(args: Args) -> Response {
+    mutate_state(|state| c2c_export_state_impl(args, state))
+}
+
+fn c2c_export_state_impl(args: Args, state: &mut RuntimeState) -> Response {
+    // The snapshot is the state written to the upgrades memory by the most recent `pre_upgrade`
+    let page = state
+        .data
+        .state_snapshots
+        .export_page(args.from, args.page_size, snapshot_length, |offset, bytes| {
+            get_upgrades_memory().read(offset, bytes)
+        });
+
+    match page {
+        Some((bytes, total_bytes)) => Success(SuccessResult { bytes, total_bytes }),
+        None => NotStarted,
+    }
+}
+
+fn snapshot_length() -> Option<u64> {
+    let memory = get_upgrades_memory();
+    let reader = BufferedReader::new(UPGRADE_BUFFER_SIZE, Reader::new(&memory, 0));
+
+    serializer::serialized_length(reader).ok()
+}
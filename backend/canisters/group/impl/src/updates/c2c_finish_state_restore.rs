@@ -0,0 +1,33 @@
+use crate::guards::caller_is_group_index_or_local_group_index;
+use crate::{mutate_state, Data, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_logger::LogEntry;
+use canister_tracing_macros::trace;
+use group_canister::c2c_finish_state_restore::{Response::*, *};
+use tracing::info;
+use utils::canister::TakeRestoreResult;
+
+#[update_msgpack(guard = "caller_is_group_index_or_local_group_index")]
+#[trace]
+fn c2c_finish_state_restore(_args: Args) -> Response {
+    mutate_state(c2c_finish_state_restore_impl)
+}
+
+fn c2c_finish_state_restore_impl(state: &mut RuntimeState) -> Response {
+    let bytes = match state.data.state_snapshots.take_restore() {
+        TakeRestoreResult::Success(bytes) => bytes,
+        TakeRestoreResult::NotStarted => return NotStarted,
+        TakeRestoreResult::Incomplete(received) => return Incomplete(received),
+    };
+
+    // The logs and traces belong to the canister the snapshot was taken from so are discarded
+    let result: Result<(Data, Vec<LogEntry>, Vec<LogEntry>), _> = serializer::deserialize(bytes.as_slice());
+    match result {
+        Ok((data, _, _)) => {
+            state.restore_data(data);
+            info!("Group state restored from snapshot");
+            Success
+        }
+        Err(error) => DeserializationFailed(error.to_string()),
+    }
+}
@@ -8,6 +8,7 @@ use chat_events::ChatEventInternal;
 use gated_groups::{check_if_passes_gate, CheckIfPassesGateResult};
 use group_canister::c2c_join_group::{Response::*, *};
 use group_chat_core::AddResult;
+use instruction_counts_log::InstructionCountFunctionId;
 use types::{AccessGate, CanisterId, MemberJoined, UserId, UsersUnblocked};
 
 #[update_msgpack(guard = "caller_is_user_index_or_local_user_index")]
@@ -27,7 +28,13 @@ async fn c2c_join_group(args: Args) -> Response {
         Err(response) => return response,
     };
 
-    mutate_state(|state| c2c_join_group_impl(args, state))
+    mutate_state(|state| {
+        let response = c2c_join_group_impl(args, state);
+        state
+            .data
+            .record_endpoint_instructions_count(InstructionCountFunctionId::JoinGroup, state.env.now());
+        response
+    })
 }
 
 fn is_permitted_to_join(
@@ -0,0 +1,24 @@
+use crate::guards::caller_is_group_index_or_local_group_index;
+use crate::{mutate_state, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use group_canister::c2c_restore_state_chunk::{Response::*, *};
+use utils::canister::PushRestoreChunkResult;
+
+#[update_msgpack(guard = "caller_is_group_index_or_local_group_index")]
+#[trace]
+fn c2c_restore_state_chunk(args: Args) -> Response {
+    mutate_state(|state| c2c_restore_state_chunk_impl(args, state))
+}
+
+fn c2c_restore_state_chunk_impl(args: Args, state: &mut RuntimeState) -> Response {
+    match state
+        .data
+        .state_snapshots
+        .push_restore_chunk(args.total_bytes, args.from, args.bytes.into_vec())
+    {
+        PushRestoreChunkResult::Success => Success,
+        PushRestoreChunkResult::UnexpectedOffset(expected) => UnexpectedOffset(expected),
+        PushRestoreChunkResult::ExceedsTotalSize => ExceedsTotalSize,
+    }
+}
@@ -0,0 +1,75 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use group_canister::c2c_send_proposal_reminder::{Response::*, *};
+use group_chat_core::SendMessageResult;
+use itertools::Itertools;
+use types::{GroupMessageNotification, MessageContentInitial, Notification, TextContent};
+
+#[update_msgpack]
+#[trace]
+fn c2c_send_proposal_reminder(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| c2c_send_proposal_reminder_impl(args, state))
+}
+
+fn c2c_send_proposal_reminder_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return ChatFrozen;
+    }
+
+    let proposals_bot_user_id = state.data.proposals_bot_user_id;
+    if state.env.caller() != proposals_bot_user_id.into() {
+        return NotAuthorized;
+    }
+
+    let recipients = match state.data.chat.proposal_reminder_recipients(args.thread_root_message_index) {
+        Some(users) => users,
+        None => return ProposalNotFound,
+    };
+
+    let now = state.env.now();
+    match state.data.chat.send_message(
+        proposals_bot_user_id,
+        Some(args.thread_root_message_index),
+        args.message_id,
+        MessageContentInitial::Text(TextContent { text: args.text }),
+        None,
+        Vec::new(),
+        false,
+        None,
+        proposals_bot_user_id,
+        now,
+    ) {
+        SendMessageResult::Success(result) => {
+            let content = &result.message_event.event.content;
+            let notification = Notification::GroupMessage(GroupMessageNotification {
+                chat_id: state.env.canister_id().into(),
+                thread_root_message_index: Some(args.thread_root_message_index),
+                message_index: result.message_event.event.message_index,
+                event_index: result.message_event.index,
+                group_name: state.data.chat.name.clone(),
+                sender: proposals_bot_user_id,
+                sender_name: "ProposalsBot".to_string(),
+                sender_display_name: None,
+                message_type: content.message_type(),
+                message_text: content.notification_text(&[], &[]),
+                image_url: None,
+                group_avatar_id: state.data.chat.avatar.as_ref().map(|d| d.id),
+                crypto_transfer: None,
+            });
+
+            // As well as the thread's followers, reminders are pushed to members with a history of voting
+            // who are yet to vote on this proposal
+            let users_to_notify = result.users_to_notify.into_iter().chain(recipients).unique().collect();
+
+            state.push_notification(users_to_notify, notification);
+            handle_activity_notification(state);
+            Success
+        }
+        SendMessageResult::ThreadMessageNotFound => ProposalNotFound,
+        _ => NotAuthorized,
+    }
+}
@@ -0,0 +1,42 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use group_canister::follow_proposal_votes::{Response::*, *};
+use group_chat_core::SetFolloweeResult;
+use ic_cdk_macros::update;
+
+#[update]
+#[trace]
+fn follow_proposal_votes(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| follow_proposal_votes_impl(args, state))
+}
+
+fn follow_proposal_votes_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return GroupFrozen;
+    }
+
+    let caller = state.env.caller();
+    let user_id = match state.data.get_member(caller) {
+        Some(m) if m.suspended.value => return UserSuspended,
+        Some(m) => m.user_id,
+        None => return UserNotInGroup,
+    };
+
+    if let Some(followee) = args.followee {
+        if state.data.chat.members.get(&followee).is_none() {
+            return FolloweeNotInGroup;
+        }
+    }
+
+    match state.data.proposal_vote_follows.set_followee(user_id, args.followee) {
+        SetFolloweeResult::Success => {
+            handle_activity_notification(state);
+            Success
+        }
+        SetFolloweeResult::CannotFollowSelf => CannotFollowSelf,
+        SetFolloweeResult::CircularFollow => CircularFollow,
+    }
+}
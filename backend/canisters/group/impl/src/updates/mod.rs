@@ -1,11 +1,15 @@
 pub mod add_reaction;
 pub mod c2c_delete_group;
 pub mod c2c_export_group;
+pub mod c2c_export_state;
+pub mod c2c_finish_state_restore;
 pub mod c2c_freeze_group;
 pub mod c2c_invite_users;
 pub mod c2c_join_group;
 pub mod c2c_leave_group;
 pub mod c2c_report_message;
+pub mod c2c_restore_state_chunk;
+pub mod c2c_send_proposal_reminder;
 pub mod c2c_set_user_suspended;
 pub mod c2c_start_import_into_community;
 pub mod c2c_tip_message;
@@ -21,6 +25,7 @@ pub mod delete_messages;
 pub mod disable_invite_code;
 pub mod edit_message;
 pub mod enable_invite_code;
+pub mod follow_proposal_votes;
 pub mod follow_thread;
 pub mod pin_message;
 pub mod register_poll_vote;
@@ -29,6 +34,7 @@ pub mod register_proposal_vote_v2;
 pub mod remove_participant;
 pub mod remove_reaction;
 pub mod send_message;
+pub mod set_typing;
 pub mod toggle_mute_notifications;
 pub mod unblock_user;
 pub mod undelete_messages;
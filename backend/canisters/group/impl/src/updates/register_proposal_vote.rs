@@ -4,7 +4,10 @@ use canister_tracing_macros::trace;
 use chat_events::{MessageContentInternal, Reader, RecordProposalVoteResult};
 use group_canister::register_proposal_vote::{Response::*, *};
 use ic_cdk_macros::update;
-use types::{CanisterId, ProposalId, UserId};
+use types::{CanisterId, EventIndex, MessageIndex, Proposal, ProposalId, UserId};
+
+// The number of followers' votes which are cast concurrently
+const FOLLOWER_VOTES_BATCH_SIZE: usize = 10;
 
 #[update]
 #[trace]
@@ -77,6 +80,9 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response>
     {
         if let Some(vote) = proposal.votes.get(&member.user_id) {
             Err(AlreadyVoted(*vote))
+        } else if matches!(proposal.proposal, Proposal::Generic(_)) {
+            // Votes on generic proposals must be cast directly with the governance canister
+            Err(NoEligibleNeurons)
         } else {
             Ok(PrepareResult {
                 user_id: member.user_id,
@@ -110,6 +116,7 @@ fn commit(user_id: UserId, args: Args, state: &mut RuntimeState) -> Response {
             if !votes.contains(&args.message_index) {
                 votes.push(args.message_index);
             }
+            vote_on_behalf_of_followers(user_id, args.message_index, args.adopt, state);
             handle_activity_notification(state);
             Success
         }
@@ -117,3 +124,87 @@ fn commit(user_id: UserId, args: Args, state: &mut RuntimeState) -> Response {
         RecordProposalVoteResult::ProposalNotFound => ProposalNotFound,
     }
 }
+
+// Votes on behalf of the members who follow the user's proposal votes in this group
+fn vote_on_behalf_of_followers(user_id: UserId, message_index: MessageIndex, adopt: bool, state: &RuntimeState) {
+    let followers = state.data.proposal_vote_follows.followers_of(user_id);
+    if followers.is_empty() {
+        return;
+    }
+
+    if let Some(proposal) = state
+        .data
+        .chat
+        .events
+        .visible_main_events_reader(EventIndex::default())
+        .message_internal(message_index.into())
+        .and_then(|m| if let MessageContentInternal::GovernanceProposal(p) = &m.content { Some(p) } else { None })
+        // Votes on generic proposals can't be cast via the user canisters
+        .filter(|p| !matches!(p.proposal, Proposal::Generic(_)))
+    {
+        let followers: Vec<_> = followers
+            .into_iter()
+            .filter(|u| !proposal.votes.contains_key(u))
+            .filter(|u| state.data.chat.members.get(u).map_or(false, |m| !m.suspended.value))
+            .collect();
+
+        if !followers.is_empty() {
+            let c2c_args = user_canister::c2c_vote_on_proposal::Args {
+                is_nns: proposal.proposal.is_nns(),
+                governance_canister_id: proposal.governance_canister_id,
+                proposal_id: proposal.proposal.id(),
+                adopt,
+            };
+            ic_cdk::spawn(vote_as_followers(message_index, followers, c2c_args));
+        }
+    }
+}
+
+async fn vote_as_followers(
+    message_index: MessageIndex,
+    followers: Vec<UserId>,
+    args: user_canister::c2c_vote_on_proposal::Args,
+) {
+    for batch in followers.chunks(FOLLOWER_VOTES_BATCH_SIZE) {
+        let futures: Vec<_> = batch
+            .iter()
+            .map(|u| user_canister_c2c_client::c2c_vote_on_proposal((*u).into(), &args))
+            .collect();
+
+        let results = futures::future::join_all(futures).await;
+
+        let voted: Vec<_> = batch
+            .iter()
+            .zip(results)
+            .filter(|(_, r)| matches!(r, Ok(user_canister::c2c_vote_on_proposal::Response::Success)))
+            .map(|(u, _)| *u)
+            .collect();
+
+        if !voted.is_empty() {
+            mutate_state(|state| record_follower_votes(message_index, args.adopt, voted, state));
+        }
+    }
+}
+
+fn record_follower_votes(message_index: MessageIndex, adopt: bool, user_ids: Vec<UserId>, state: &mut RuntimeState) {
+    let now = state.env.now();
+
+    for user_id in user_ids {
+        if let Some(member) = state.data.chat.members.get_mut(&user_id) {
+            let min_visible_event_index = member.min_visible_event_index();
+
+            if matches!(
+                state
+                    .data
+                    .chat
+                    .events
+                    .record_proposal_vote(user_id, min_visible_event_index, message_index, adopt),
+                RecordProposalVoteResult::Success
+            ) {
+                member.proposal_votes.entry(now).or_default().push(message_index);
+            }
+        }
+    }
+
+    handle_activity_notification(state);
+}
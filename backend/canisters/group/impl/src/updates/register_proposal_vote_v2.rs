@@ -1,9 +1,10 @@
 use crate::activity_notifications::handle_activity_notification;
 use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
-use chat_events::RecordProposalVoteResult;
+use chat_events::{MessageContentInternal, Reader, RecordProposalVoteResult};
 use group_canister::register_proposal_vote_v2::{Response::*, *};
 use ic_cdk_macros::update;
+use types::Proposal;
 
 #[update]
 #[trace]
@@ -32,6 +33,21 @@ fn register_proposal_vote_impl(args: Args, state: &mut RuntimeState) -> Response
     let min_visible_event_index = member.min_visible_event_index();
     let user_id = member.user_id;
 
+    let is_generic = state
+        .data
+        .chat
+        .events
+        .visible_main_events_reader(min_visible_event_index)
+        .message_internal(args.message_index.into())
+        .map_or(false, |m| {
+            matches!(&m.content, MessageContentInternal::GovernanceProposal(p) if matches!(p.proposal, Proposal::Generic(_)))
+        });
+
+    if is_generic {
+        // Votes on generic proposals are cast with the governance canister and can't be verified
+        return ProposalNotSupported;
+    }
+
     match state
         .data
         .chat
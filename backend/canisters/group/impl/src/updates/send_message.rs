@@ -6,6 +6,7 @@ use canister_timer_jobs::TimerJobs;
 use canister_tracing_macros::trace;
 use group_canister::send_message_v2::{Response::*, *};
 use group_chat_core::SendMessageResult;
+use instruction_counts_log::InstructionCountFunctionId;
 use types::{EventWrapper, GroupMessageNotification, Message, MessageContent, MessageIndex, Notification, TimestampMillis};
 
 #[update_candid_and_msgpack]
@@ -13,7 +14,13 @@ use types::{EventWrapper, GroupMessageNotification, Message, MessageContent, Mes
 fn send_message_v2(args: Args) -> Response {
     run_regular_jobs();
 
-    mutate_state(|state| send_message_impl(args, state))
+    mutate_state(|state| {
+        let response = send_message_impl(args, state);
+        state
+            .data
+            .record_endpoint_instructions_count(InstructionCountFunctionId::SendMessage, state.env.now());
+        response
+    })
 }
 
 fn send_message_impl(args: Args, state: &mut RuntimeState) -> Response {
@@ -0,0 +1,37 @@
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use group_canister::set_typing::{Response::*, *};
+use group_chat_core::SetTypingResult;
+use ic_cdk_macros::update;
+
+// Called every few seconds while a user is typing, so this skips `run_regular_jobs`
+#[update]
+#[trace]
+fn set_typing(args: Args) -> Response {
+    mutate_state(|state| set_typing_impl(args, state))
+}
+
+fn set_typing_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.is_frozen() {
+        return ChatFrozen;
+    }
+
+    let caller = state.env.caller();
+
+    let user_id = match state.data.lookup_user_id(caller) {
+        Some(uid) => uid,
+        None => return CallerNotInGroup,
+    };
+
+    let now = state.env.now();
+
+    match state
+        .data
+        .chat
+        .set_typing(user_id, args.thread_root_message_index, args.activity, now)
+    {
+        SetTypingResult::Success => Success,
+        SetTypingResult::UserNotInGroup => CallerNotInGroup,
+        SetTypingResult::UserSuspended => UserSuspended,
+    }
+}
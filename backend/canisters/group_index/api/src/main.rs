@@ -8,10 +8,18 @@ fn main() {
     generate_candid_method!(group_index, lookup_channel_by_group_id, query);
     generate_candid_method!(group_index, recommended_groups, query);
     generate_candid_method!(group_index, search, query);
+    generate_candid_method!(group_index, upgrade_rollout_status, query);
 
+    generate_candid_method!(group_index, commit_wasm_upload, update);
     generate_candid_method!(group_index, delete_frozen_group, update);
+    generate_candid_method!(group_index, export_state_snapshot, update);
+    generate_candid_method!(group_index, finish_state_restore, update);
     generate_candid_method!(group_index, freeze_group, update);
     generate_candid_method!(group_index, freeze_community, update);
+    generate_candid_method!(group_index, push_state_restore_chunk, update);
+    generate_candid_method!(group_index, push_wasm_chunk, update);
+    generate_candid_method!(group_index, start_state_restore, update);
+    generate_candid_method!(group_index, start_wasm_upload, update);
     generate_candid_method!(group_index, unfreeze_group, update);
     generate_candid_method!(group_index, unfreeze_community, update);
     generate_candid_method!(group_index, add_hot_group_exclusion, update);
@@ -0,0 +1,14 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::CommunityId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub community_id: CommunityId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Yes,
+    No,
+}
@@ -1,5 +1,6 @@
 pub mod active_groups;
 pub mod c2c_active_groups;
+pub mod c2c_community_exists;
 pub mod c2c_filter_groups;
 pub mod explore_communities;
 pub mod explore_groups;
@@ -7,3 +8,4 @@ pub mod filter_groups;
 pub mod lookup_channel_by_group_id;
 pub mod recommended_groups;
 pub mod search;
+pub mod upgrade_rollout_status;
@@ -0,0 +1,13 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{BuildVersion, CommitWasmUploadArgs, Hash};
+
+pub type Args = CommitWasmUploadArgs;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(BuildVersion),
+    UploadNotFound,
+    IncompleteUpload(u64),
+    HashMismatch(Hash),
+}
@@ -0,0 +1,26 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use types::CanisterId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub canister_id: CanisterId,
+    pub from: u64,
+    pub page_size: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    NotStarted,
+    CanisterNotFound,
+    UpgradeInProgress,
+    InternalError(String),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub bytes: ByteBuf,
+    pub total_bytes: u64,
+}
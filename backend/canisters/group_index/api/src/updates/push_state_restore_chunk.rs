@@ -0,0 +1,21 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use types::CanisterId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub canister_id: CanisterId,
+    pub total_bytes: u64,
+    pub from: u64,
+    pub bytes: ByteBuf,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    RestoreNotFound,
+    UnexpectedOffset(u64),
+    ExceedsTotalSize,
+    InternalError(String),
+}
@@ -0,0 +1,12 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{BuildVersion, RollbackCanisterWasmArgs};
+
+pub type Args = RollbackCanisterWasmArgs;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(BuildVersion),
+    NoPreviousWasm,
+    InternalError(String),
+}
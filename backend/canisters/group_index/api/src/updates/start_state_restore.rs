@@ -0,0 +1,16 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::CanisterId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub canister_id: CanisterId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CanisterNotFound,
+    ExportRequired,
+    InternalError(String),
+}
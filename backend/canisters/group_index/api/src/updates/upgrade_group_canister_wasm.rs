@@ -8,4 +8,5 @@ pub type Args = UpgradeCanisterWasmArgs;
 pub enum Response {
     Success,
     InternalError(String),
+    UploadedWasmNotFound,
 }
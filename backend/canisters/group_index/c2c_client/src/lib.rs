@@ -1,8 +1,10 @@
 use canister_client::generate_c2c_call;
 use group_index_canister::*;
+use types::{CanisterId, CommunityId};
 
 // Queries
 generate_c2c_call!(c2c_active_groups);
+generate_c2c_call!(c2c_community_exists);
 
 // Updates
 generate_c2c_call!(c2c_convert_group_into_community);
@@ -17,3 +19,14 @@ generate_c2c_call!(c2c_mark_community_active);
 generate_c2c_call!(c2c_start_importing_group_into_community);
 generate_c2c_call!(c2c_update_community);
 generate_c2c_call!(c2c_update_group);
+
+// Used to check that a caller claiming to be a community canister really is one
+pub async fn is_community(group_index_canister_id: CanisterId, community_id: CommunityId) -> Result<bool, String> {
+    let args = c2c_community_exists::Args { community_id };
+
+    match c2c_community_exists(group_index_canister_id, &args).await {
+        Ok(c2c_community_exists::Response::Yes) => Ok(true),
+        Ok(c2c_community_exists::Response::No) => Ok(false),
+        Err(error) => Err(format!("{error:?}")),
+    }
+}
@@ -1,5 +1,7 @@
 use canister_client::{generate_query_call, generate_update_call};
 use group_index_canister::*;
+use ic_agent::Agent;
+use types::CanisterId;
 
 // Queries
 generate_query_call!(explore_groups);
@@ -8,6 +10,44 @@ generate_query_call!(search);
 
 // Updates
 generate_update_call!(add_local_group_index_canister);
+generate_update_call!(commit_wasm_upload);
+generate_update_call!(export_state_snapshot);
+generate_update_call!(finish_state_restore);
+generate_update_call!(push_state_restore_chunk);
+generate_update_call!(push_wasm_chunk);
+generate_update_call!(rollback_community_canister_wasm);
+generate_update_call!(rollback_group_canister_wasm);
+generate_update_call!(rollback_local_group_index_canister_wasm);
+generate_update_call!(start_state_restore);
+generate_update_call!(start_wasm_upload);
 generate_update_call!(upgrade_community_canister_wasm);
 generate_update_call!(upgrade_group_canister_wasm);
 generate_update_call!(upgrade_local_group_index_canister_wasm);
+
+// Downloads the state of a group or community canister, in the same format that is written to
+// stable memory during upgrades, by paging through `export_state_snapshot`
+pub async fn download_state_snapshot(
+    agent: &Agent,
+    group_index_canister_id: &CanisterId,
+    canister_id: CanisterId,
+    page_size: u32,
+) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    loop {
+        let args = export_state_snapshot::Args {
+            canister_id,
+            from: bytes.len() as u64,
+            page_size,
+        };
+        match export_state_snapshot(agent, group_index_canister_id, &args).await {
+            Ok(export_state_snapshot::Response::Success(result)) => {
+                bytes.extend(result.bytes.into_vec());
+                if bytes.len() as u64 >= result.total_bytes {
+                    return Ok(bytes);
+                }
+            }
+            Ok(response) => return Err(format!("{response:?}")),
+            Err(error) => return Err(error.to_string()),
+        }
+    }
+}
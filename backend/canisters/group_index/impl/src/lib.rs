@@ -7,6 +7,7 @@ use crate::model::private_groups::PrivateGroups;
 use crate::model::public_communities::PublicCommunities;
 use crate::model::public_group_and_community_names::PublicGroupAndCommunityNames;
 use crate::model::public_groups::PublicGroups;
+use crate::model::state_restores::StateRestores;
 use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
 use fire_and_forget_handler::FireAndForgetHandler;
@@ -18,7 +19,7 @@ use types::{
     BuildVersion, CanisterId, CanisterWasm, ChatId, CommunityId, Cycles, FrozenGroupInfo, Milliseconds, TimestampMillis,
     Timestamped, UserId,
 };
-use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount};
+use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount, PreviousWasms, WasmUploads};
 use utils::env::Environment;
 use utils::time::MINUTE_IN_MS;
 
@@ -122,6 +123,16 @@ struct Data {
     pub community_canister_wasm: CanisterWasm,
     pub local_group_index_canister_wasm_for_new_canisters: CanisterWasm,
     pub local_group_index_canister_wasm_for_upgrades: CanisterWasm,
+    #[serde(default)]
+    pub previous_group_canister_wasms: PreviousWasms,
+    #[serde(default)]
+    pub previous_community_canister_wasms: PreviousWasms,
+    #[serde(default)]
+    pub previous_local_group_index_canister_wasms: PreviousWasms,
+    #[serde(default)]
+    pub wasm_uploads: WasmUploads,
+    #[serde(default)]
+    pub state_restores: StateRestores,
     pub user_index_canister_id: CanisterId,
     pub cycles_dispenser_canister_id: CanisterId,
     pub proposals_bot_user_id: UserId,
@@ -159,6 +170,11 @@ impl Data {
             community_canister_wasm,
             local_group_index_canister_wasm_for_new_canisters: local_group_index_canister_wasm.clone(),
             local_group_index_canister_wasm_for_upgrades: local_group_index_canister_wasm,
+            previous_group_canister_wasms: PreviousWasms::default(),
+            previous_community_canister_wasms: PreviousWasms::default(),
+            previous_local_group_index_canister_wasms: PreviousWasms::default(),
+            wasm_uploads: WasmUploads::default(),
+            state_restores: StateRestores::default(),
             user_index_canister_id,
             cycles_dispenser_canister_id,
             proposals_bot_user_id,
@@ -239,6 +255,11 @@ impl Default for Data {
             community_canister_wasm: CanisterWasm::default(),
             local_group_index_canister_wasm_for_new_canisters: CanisterWasm::default(),
             local_group_index_canister_wasm_for_upgrades: CanisterWasm::default(),
+            previous_group_canister_wasms: PreviousWasms::default(),
+            previous_community_canister_wasms: PreviousWasms::default(),
+            previous_local_group_index_canister_wasms: PreviousWasms::default(),
+            wasm_uploads: WasmUploads::default(),
+            state_restores: StateRestores::default(),
             user_index_canister_id: Principal::anonymous(),
             cycles_dispenser_canister_id: Principal::anonymous(),
             proposals_bot_user_id: Principal::anonymous().into(),
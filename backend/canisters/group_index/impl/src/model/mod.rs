@@ -8,3 +8,4 @@ pub mod private_groups;
 pub mod public_communities;
 pub mod public_group_and_community_names;
 pub mod public_groups;
+pub mod state_restores;
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::{CanisterId, TimestampMillis};
+use utils::time::HOUR_IN_MS;
+
+// Restoring into the original canister wipes its state, so is only allowed within this long of an
+// export of that canister having completed
+const MAX_EXPORT_AGE_FOR_IN_PLACE_RESTORE: u64 = HOUR_IN_MS;
+
+// Restores which have been started, keyed by the canister whose state is being restored
+#[derive(Serialize, Deserialize, Default)]
+pub struct StateRestores {
+    restores: HashMap<CanisterId, StateRestore>,
+    #[serde(default)]
+    completed_exports: HashMap<CanisterId, TimestampMillis>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct StateRestore {
+    pub is_community: bool,
+}
+
+impl StateRestores {
+    pub fn add(&mut self, canister_id: CanisterId, restore: StateRestore) {
+        self.restores.insert(canister_id, restore);
+    }
+
+    pub fn get(&self, canister_id: &CanisterId) -> Option<StateRestore> {
+        self.restores.get(canister_id).copied()
+    }
+
+    pub fn remove(&mut self, canister_id: &CanisterId) -> Option<StateRestore> {
+        self.restores.remove(canister_id)
+    }
+
+    pub fn record_completed_export(&mut self, canister_id: CanisterId, now: TimestampMillis) {
+        self.completed_exports
+            .retain(|_, completed| now.saturating_sub(*completed) < MAX_EXPORT_AGE_FOR_IN_PLACE_RESTORE);
+        self.completed_exports.insert(canister_id, now);
+    }
+
+    // Each export can only be used to authorize a single in place restore
+    pub fn take_recent_export(&mut self, canister_id: &CanisterId, now: TimestampMillis) -> bool {
+        self.completed_exports.remove(canister_id).map_or(false, |completed| {
+            now.saturating_sub(completed) < MAX_EXPORT_AGE_FOR_IN_PLACE_RESTORE
+        })
+    }
+}
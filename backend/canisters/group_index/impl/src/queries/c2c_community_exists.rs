@@ -0,0 +1,18 @@
+use crate::{read_state, RuntimeState};
+use canister_api_macros::query_msgpack;
+use group_index_canister::c2c_community_exists::{Response::*, *};
+
+#[query_msgpack]
+fn c2c_community_exists(args: Args) -> Response {
+    read_state(|state| c2c_community_exists_impl(args, state))
+}
+
+fn c2c_community_exists_impl(args: Args, state: &RuntimeState) -> Response {
+    if state.data.public_communities.get(&args.community_id).is_some()
+        || state.data.private_communities.get(&args.community_id).is_some()
+    {
+        Yes
+    } else {
+        No
+    }
+}
@@ -1,4 +1,5 @@
 pub mod active_groups;
+pub mod c2c_community_exists;
 pub mod explore_communities;
 pub mod explore_groups;
 pub mod filter_groups;
@@ -6,3 +7,4 @@ pub mod http_request;
 pub mod lookup_channel_by_group_id;
 pub mod recommended_groups;
 pub mod search;
+pub mod upgrade_rollout_status;
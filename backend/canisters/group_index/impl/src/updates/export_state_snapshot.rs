@@ -0,0 +1,96 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, read_state, RuntimeState};
+use canister_tracing_macros::trace;
+use group_index_canister::export_state_snapshot::{Response::*, *};
+use ic_cdk_macros::update;
+use local_group_index_canister::c2c_take_state_snapshot::Response as C2cResponse;
+use std::cmp::min;
+use types::CanisterId;
+
+const MAX_PAGE_SIZE: u32 = 19 * 102 * 1024; // Roughly 1.9MB (1.9 * 1024 * 1024)
+
+#[update(guard = "caller_is_governance_principal")]
+#[trace]
+async fn export_state_snapshot(args: Args) -> Response {
+    let PrepareResult {
+        is_community,
+        local_group_index_canister_id,
+    } = match read_state(|state| prepare(&args, state)) {
+        Some(ok) => ok,
+        None => return CanisterNotFound,
+    };
+
+    // Each export starts by upgrading the canister in place so that its current state is written
+    // to stable memory, from where it is then read in pages
+    if args.from == 0 {
+        let c2c_args = local_group_index_canister::c2c_take_state_snapshot::Args {
+            canister_id: args.canister_id,
+        };
+        match local_group_index_canister_c2c_client::c2c_take_state_snapshot(local_group_index_canister_id, &c2c_args).await {
+            Ok(C2cResponse::Success) => {}
+            Ok(C2cResponse::CanisterNotFound) => return CanisterNotFound,
+            Ok(C2cResponse::UpgradeInProgress) => return UpgradeInProgress,
+            Ok(C2cResponse::InternalError(error)) => return InternalError(error),
+            Err(error) => return InternalError(format!("{error:?}")),
+        }
+    }
+
+    let response = export_page(args.canister_id, is_community, args.from, min(args.page_size, MAX_PAGE_SIZE)).await;
+
+    if let Success(result) = &response {
+        if args.from + result.bytes.len() as u64 >= result.total_bytes {
+            mutate_state(|state| {
+                let now = state.env.now();
+                state.data.state_restores.record_completed_export(args.canister_id, now);
+            });
+        }
+    }
+    response
+}
+
+struct PrepareResult {
+    is_community: bool,
+    local_group_index_canister_id: CanisterId,
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Option<PrepareResult> {
+    let local_index_map = &state.data.local_index_map;
+
+    if let Some(local_group_index_canister_id) = local_index_map.get_index_canister_for_group(&args.canister_id.into()) {
+        Some(PrepareResult {
+            is_community: false,
+            local_group_index_canister_id,
+        })
+    } else {
+        local_index_map
+            .get_index_canister_for_community(&args.canister_id.into())
+            .map(|local_group_index_canister_id| PrepareResult {
+                is_community: true,
+                local_group_index_canister_id,
+            })
+    }
+}
+
+async fn export_page(canister_id: CanisterId, is_community: bool, from: u64, page_size: u32) -> Response {
+    if is_community {
+        let c2c_args = community_canister::c2c_export_state::Args { from, page_size };
+        match community_canister_c2c_client::c2c_export_state(canister_id, &c2c_args).await {
+            Ok(community_canister::c2c_export_state::Response::Success(result)) => Success(SuccessResult {
+                bytes: result.bytes,
+                total_bytes: result.total_bytes,
+            }),
+            Ok(community_canister::c2c_export_state::Response::NotStarted) => NotStarted,
+            Err(error) => InternalError(format!("{error:?}")),
+        }
+    } else {
+        let c2c_args = group_canister::c2c_export_state::Args { from, page_size };
+        match group_canister_c2c_client::c2c_export_state(canister_id, &c2c_args).await {
+            Ok(group_canister::c2c_export_state::Response::Success(result)) => Success(SuccessResult {
+                bytes: result.bytes,
+                total_bytes: result.total_bytes,
+            }),
+            Ok(group_canister::c2c_export_state::Response::NotStarted) => NotStarted,
+            Err(error) => InternalError(format!("{error:?}")),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, read_state};
+use canister_tracing_macros::trace;
+use group_index_canister::finish_state_restore::{Response::*, *};
+use ic_cdk_macros::update;
+use tracing::info;
+use types::{CanisterId, Empty};
+
+#[update(guard = "caller_is_governance_principal")]
+#[trace]
+async fn finish_state_restore(args: Args) -> Response {
+    let canister_id = args.canister_id;
+    let restore = match read_state(|state| state.data.state_restores.get(&canister_id)) {
+        Some(restore) => restore,
+        None => return RestoreNotFound,
+    };
+
+    if let Err(response) = finish_restore_in_canister(canister_id, restore.is_community).await {
+        return response;
+    }
+
+    mutate_state(|state| state.data.state_restores.remove(&canister_id));
+
+    info!(%canister_id, "State restore finished");
+    Success
+}
+
+async fn finish_restore_in_canister(canister_id: CanisterId, is_community: bool) -> Result<(), Response> {
+    if is_community {
+        use community_canister::c2c_finish_state_restore::Response as C2cResponse;
+
+        match community_canister_c2c_client::c2c_finish_state_restore(canister_id, &Empty {}).await {
+            Ok(C2cResponse::Success) => Ok(()),
+            Ok(C2cResponse::NotStarted) => Err(Incomplete(0)),
+            Ok(C2cResponse::Incomplete(received)) => Err(Incomplete(received)),
+            Ok(C2cResponse::DeserializationFailed(error)) => Err(DeserializationFailed(error)),
+            Err(error) => Err(InternalError(format!("{error:?}"))),
+        }
+    } else {
+        use group_canister::c2c_finish_state_restore::Response as C2cResponse;
+
+        match group_canister_c2c_client::c2c_finish_state_restore(canister_id, &Empty {}).await {
+            Ok(C2cResponse::Success) => Ok(()),
+            Ok(C2cResponse::NotStarted) => Err(Incomplete(0)),
+            Ok(C2cResponse::Incomplete(received)) => Err(Incomplete(received)),
+            Ok(C2cResponse::DeserializationFailed(error)) => Err(DeserializationFailed(error)),
+            Err(error) => Err(InternalError(format!("{error:?}"))),
+        }
+    }
+}
@@ -13,15 +13,25 @@ pub mod c2c_mark_group_import_complete;
 pub mod c2c_start_importing_group_into_community;
 pub mod c2c_update_community;
 pub mod c2c_update_group;
+pub mod commit_wasm_upload;
 pub mod delete_frozen_group;
+pub mod export_state_snapshot;
+pub mod finish_state_restore;
 pub mod freeze_community;
 pub mod freeze_group;
 pub mod mark_local_group_index_full;
+pub mod push_state_restore_chunk;
+pub mod push_wasm_chunk;
+pub mod rollback_community_canister_wasm;
+pub mod rollback_group_canister_wasm;
+pub mod rollback_local_group_index_canister_wasm;
 pub mod set_community_moderation_flags;
 pub mod set_community_upgrade_concurrency;
 pub mod set_group_upgrade_concurrency;
 pub mod set_max_concurrent_community_canister_upgrades;
 pub mod set_max_concurrent_group_canister_upgrades;
+pub mod start_state_restore;
+pub mod start_wasm_upload;
 pub mod upgrade_community_canister_wasm;
 pub mod upgrade_group_canister_wasm;
 pub mod upgrade_local_group_index_canister_wasm;
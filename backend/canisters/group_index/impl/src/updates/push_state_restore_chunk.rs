@@ -0,0 +1,44 @@
+use crate::guards::caller_is_governance_principal;
+use crate::read_state;
+use canister_tracing_macros::trace;
+use group_index_canister::push_state_restore_chunk::{Response::*, *};
+use ic_cdk_macros::update;
+
+#[update(guard = "caller_is_governance_principal")]
+#[trace]
+async fn push_state_restore_chunk(args: Args) -> Response {
+    let restore = match read_state(|state| state.data.state_restores.get(&args.canister_id)) {
+        Some(restore) => restore,
+        None => return RestoreNotFound,
+    };
+
+    if restore.is_community {
+        use community_canister::c2c_restore_state_chunk::{Args as C2cArgs, Response as C2cResponse};
+
+        let c2c_args = C2cArgs {
+            total_bytes: args.total_bytes,
+            from: args.from,
+            bytes: args.bytes,
+        };
+        match community_canister_c2c_client::c2c_restore_state_chunk(args.canister_id, &c2c_args).await {
+            Ok(C2cResponse::Success) => Success,
+            Ok(C2cResponse::UnexpectedOffset(expected)) => UnexpectedOffset(expected),
+            Ok(C2cResponse::ExceedsTotalSize) => ExceedsTotalSize,
+            Err(error) => InternalError(format!("{error:?}")),
+        }
+    } else {
+        use group_canister::c2c_restore_state_chunk::{Args as C2cArgs, Response as C2cResponse};
+
+        let c2c_args = C2cArgs {
+            total_bytes: args.total_bytes,
+            from: args.from,
+            bytes: args.bytes,
+        };
+        match group_canister_c2c_client::c2c_restore_state_chunk(args.canister_id, &c2c_args).await {
+            Ok(C2cResponse::Success) => Success,
+            Ok(C2cResponse::UnexpectedOffset(expected)) => UnexpectedOffset(expected),
+            Ok(C2cResponse::ExceedsTotalSize) => ExceedsTotalSize,
+            Err(error) => InternalError(format!("{error:?}")),
+        }
+    }
+}
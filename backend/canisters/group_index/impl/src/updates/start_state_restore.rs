@@ -0,0 +1,57 @@
+use crate::guards::caller_is_governance_principal;
+use crate::model::state_restores::StateRestore;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use group_index_canister::start_state_restore::{Response::*, *};
+use ic_cdk_macros::update;
+use local_group_index_canister::c2c_prepare_state_restore::Response as C2cResponse;
+use types::CanisterId;
+
+// Reinstalls the original canister as a placeholder into which the state can then be restored. This
+// wipes the canister's state, so is only allowed shortly after an export of the canister has
+// completed. Restoring into a new canister isn't supported since every reference to the canister
+// held by users, communities and other canisters would then need updating.
+#[update(guard = "caller_is_governance_principal")]
+#[trace]
+async fn start_state_restore(args: Args) -> Response {
+    let local_group_index_canister_id = match mutate_state(|state| prepare(&args, state)) {
+        Ok(canister_id) => canister_id,
+        Err(response) => return response,
+    };
+
+    let c2c_args = local_group_index_canister::c2c_prepare_state_restore::Args {
+        canister_id: args.canister_id,
+    };
+    match local_group_index_canister_c2c_client::c2c_prepare_state_restore(local_group_index_canister_id, &c2c_args).await {
+        Ok(C2cResponse::Success(result)) => {
+            mutate_state(|state| {
+                state.data.state_restores.add(
+                    args.canister_id,
+                    StateRestore {
+                        is_community: result.is_community,
+                    },
+                )
+            });
+            Success
+        }
+        Ok(C2cResponse::CanisterNotFound) => CanisterNotFound,
+        Ok(C2cResponse::InternalError(error)) => InternalError(error),
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
+
+fn prepare(args: &Args, state: &mut RuntimeState) -> Result<CanisterId, Response> {
+    let local_index_map = &state.data.local_index_map;
+
+    let local_group_index_canister_id = local_index_map
+        .get_index_canister_for_group(&args.canister_id.into())
+        .or_else(|| local_index_map.get_index_canister_for_community(&args.canister_id.into()))
+        .ok_or(CanisterNotFound)?;
+
+    let now = state.env.now();
+    if !state.data.state_restores.take_recent_export(&args.canister_id, now) {
+        return Err(ExportRequired);
+    }
+
+    Ok(local_group_index_canister_id)
+}
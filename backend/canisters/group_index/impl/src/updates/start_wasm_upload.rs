@@ -0,0 +1,20 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use group_index_canister::start_wasm_upload::{Response::*, *};
+use ic_cdk_macros::update;
+use utils::canister::StartUploadResult;
+
+#[update(guard = "caller_is_governance_principal")]
+#[trace]
+fn start_wasm_upload(args: Args) -> Response {
+    mutate_state(|state| start_wasm_upload_impl(args, state))
+}
+
+fn start_wasm_upload_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    match state.data.wasm_uploads.start(args.version, args.total_size, args.sha256, now) {
+        StartUploadResult::Success => Success,
+        StartUploadResult::TooLarge(max) => WasmTooLarge(max),
+    }
+}
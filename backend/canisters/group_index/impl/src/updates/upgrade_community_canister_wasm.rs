@@ -3,16 +3,17 @@ use crate::{mutate_state, read_state, RuntimeState};
 use canister_api_macros::proposal;
 use canister_tracing_macros::trace;
 use group_index_canister::upgrade_community_canister_wasm::{Response::*, *};
-use ic_cdk::api::call::CallResult;
 use tracing::info;
 use types::{CanisterId, CanisterWasm, UpgradeCanisterWasmArgs, UpgradesFilter};
-use utils::canister::build_filter_map;
+use utils::canister::{args_for_uploaded_wasm, build_filter_map, requires_chunked_upload, upload_wasm_in_chunks};
 
 #[proposal(guard = "caller_is_governance_principal")]
 #[trace]
 async fn upgrade_community_canister_wasm(args: Args) -> Response {
     let version = args.wasm.version;
     let use_for_new_canisters = args.use_for_new_canisters.unwrap_or(true);
+    let rollout = args.rollout.clone();
+    let uploaded_wasm_hash = args.uploaded_wasm_hash;
 
     let PrepareResult {
         wasm,
@@ -31,6 +32,8 @@ async fn upgrade_community_canister_wasm(args: Args) -> Response {
                     wasm: wasm.clone(),
                     filter: Some(filter),
                     use_for_new_canisters: Some(use_for_new_canisters),
+                    rollout: rollout.clone(),
+                    uploaded_wasm_hash: None,
                 },
             )
         })
@@ -39,13 +42,19 @@ async fn upgrade_community_canister_wasm(args: Args) -> Response {
     let result = futures::future::join_all(futures).await;
 
     if let Some(first_error) = result.into_iter().filter_map(|res| res.err()).next() {
-        InternalError(format!("{first_error:?}"))
+        InternalError(first_error)
     } else {
-        if use_for_new_canisters {
-            mutate_state(|state| {
-                state.data.community_canister_wasm = wasm;
-            });
-        }
+        mutate_state(|state| {
+            if use_for_new_canisters {
+                let previous_wasm = std::mem::replace(&mut state.data.community_canister_wasm, wasm);
+                if previous_wasm.version != version {
+                    state.data.previous_community_canister_wasms.push(previous_wasm);
+                }
+            }
+            if let Some(hash) = uploaded_wasm_hash {
+                state.data.wasm_uploads.remove(&hash);
+            }
+        });
 
         info!(%version, "Community canister wasm upgraded");
         Success
@@ -57,7 +66,11 @@ struct PrepareResult {
     local_group_index_canisters: Vec<(CanisterId, UpgradesFilter)>,
 }
 
-fn prepare(args: Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+fn prepare(mut args: Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    if !state.data.wasm_uploads.resolve(&mut args) {
+        return Err(UploadedWasmNotFound);
+    }
+
     let local_group_index_canister_ids: Vec<_> = state.data.local_index_map.canisters().copied().collect();
 
     let local_group_index_canisters = build_filter_map(local_group_index_canister_ids, args.filter.unwrap_or_default(), |c| {
@@ -70,9 +83,27 @@ fn prepare(args: Args, state: &RuntimeState) -> Result<PrepareResult, Response>
     })
 }
 
+// The wasm is pushed to the local index in chunks if it is too large to fit in a single cross-subnet
+// message, and the subsequent call then references the uploaded wasm by its hash
 async fn c2c_upgrade_community_canister_wasm(
     canister_id: CanisterId,
     args: local_group_index_canister::c2c_upgrade_community_canister_wasm::Args,
-) -> CallResult<local_group_index_canister::c2c_upgrade_community_canister_wasm::Response> {
-    local_group_index_canister_c2c_client::c2c_upgrade_community_canister_wasm(canister_id, &args).await
+) -> Result<(), String> {
+    let args = if requires_chunked_upload(&args.wasm) {
+        let sha256 = upload_wasm_in_chunks(&args.wasm, |chunk| {
+            local_group_index_canister_c2c_client::push_wasm_chunk(canister_id, chunk)
+        })
+        .await?;
+        args_for_uploaded_wasm(args, sha256)
+    } else {
+        args
+    };
+
+    match local_group_index_canister_c2c_client::c2c_upgrade_community_canister_wasm(canister_id, &args).await {
+        Ok(local_group_index_canister::c2c_upgrade_community_canister_wasm::Response::UploadedWasmNotFound) => {
+            Err(format!("Uploaded wasm not found. Canister: {canister_id}"))
+        }
+        Ok(_) => Ok(()),
+        Err(error) => Err(format!("{error:?}")),
+    }
 }
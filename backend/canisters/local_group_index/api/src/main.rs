@@ -1,5 +1,9 @@
+use candid_gen::generate_candid_method;
+
 #[allow(deprecated)]
 fn main() {
+    generate_candid_method!(local_group_index, upgrade_rollout_status, query);
+
     candid::export_service!();
     std::print!("{}", __export_service());
 }
@@ -1 +1,2 @@
 pub mod c2c_can_push_notifications;
+pub mod upgrade_rollout_status;
@@ -0,0 +1,16 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{Empty, UpgradeRolloutStatus};
+
+pub type Args = Empty;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub groups: Option<UpgradeRolloutStatus>,
+    pub communities: Option<UpgradeRolloutStatus>,
+}
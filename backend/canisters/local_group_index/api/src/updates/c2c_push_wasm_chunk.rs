@@ -0,0 +1,16 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{Hash, WasmChunk};
+
+pub type Args = WasmChunk;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    UploadCommitted,
+    WasmTooLarge(u64),
+    UploadNotFound,
+    UnexpectedChunkIndex(u32),
+    ExceedsTotalSize,
+    HashMismatch(Hash),
+}
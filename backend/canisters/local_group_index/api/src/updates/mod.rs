@@ -3,10 +3,15 @@ pub mod c2c_create_group;
 pub mod c2c_delete_community;
 pub mod c2c_delete_group;
 pub mod c2c_notify_low_balance;
+pub mod c2c_prepare_state_restore;
+pub mod c2c_push_wasm_chunk;
+pub mod c2c_rollback_community_canister_wasm;
+pub mod c2c_rollback_group_canister_wasm;
 pub mod c2c_set_community_upgrade_concurrency;
 pub mod c2c_set_group_upgrade_concurrency;
 pub mod c2c_set_max_concurrent_community_upgrades;
 pub mod c2c_set_max_concurrent_group_upgrades;
+pub mod c2c_take_state_snapshot;
 pub mod c2c_trigger_upgrade;
 pub mod c2c_upgrade_community_canister_wasm;
 pub mod c2c_upgrade_group_canister_wasm;
@@ -1,5 +1,6 @@
 use canister_client::generate_c2c_call;
 use local_group_index_canister::*;
+use types::{CanisterId, WasmChunk};
 
 // Queries
 generate_c2c_call!(c2c_can_push_notifications);
@@ -10,10 +11,25 @@ generate_c2c_call!(c2c_create_group);
 generate_c2c_call!(c2c_delete_community);
 generate_c2c_call!(c2c_delete_group);
 generate_c2c_call!(c2c_notify_low_balance);
+generate_c2c_call!(c2c_prepare_state_restore);
+generate_c2c_call!(c2c_push_wasm_chunk);
+generate_c2c_call!(c2c_rollback_community_canister_wasm);
+generate_c2c_call!(c2c_rollback_group_canister_wasm);
 generate_c2c_call!(c2c_set_community_upgrade_concurrency);
 generate_c2c_call!(c2c_set_group_upgrade_concurrency);
 generate_c2c_call!(c2c_set_max_concurrent_community_upgrades);
 generate_c2c_call!(c2c_set_max_concurrent_group_upgrades);
+generate_c2c_call!(c2c_take_state_snapshot);
 generate_c2c_call!(c2c_trigger_upgrade);
 generate_c2c_call!(c2c_upgrade_community_canister_wasm);
 generate_c2c_call!(c2c_upgrade_group_canister_wasm);
+
+// Returns true once the final chunk has been received and the upload committed
+pub async fn push_wasm_chunk(canister_id: CanisterId, chunk: WasmChunk) -> Result<bool, String> {
+    match crate::c2c_push_wasm_chunk(canister_id, &chunk).await {
+        Ok(c2c_push_wasm_chunk::Response::Success) => Ok(false),
+        Ok(c2c_push_wasm_chunk::Response::UploadCommitted) => Ok(true),
+        Ok(response) => Err(format!("{response:?}")),
+        Err(error) => Err(format!("{error:?}")),
+    }
+}
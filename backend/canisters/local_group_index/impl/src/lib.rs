@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use types::{BuildVersion, CanisterId, CanisterWasm, Cycles, Milliseconds, TimestampMillis, Timestamped, UserId};
 use utils::canister;
-use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount};
+use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount, WasmUploads};
 use utils::consts::CYCLES_REQUIRED_FOR_UPGRADE;
 use utils::env::Environment;
 
@@ -106,6 +106,8 @@ struct Data {
     pub group_canister_wasm_for_upgrades: CanisterWasm,
     pub community_canister_wasm_for_new_canisters: CanisterWasm,
     pub community_canister_wasm_for_upgrades: CanisterWasm,
+    #[serde(default)]
+    pub wasm_uploads: WasmUploads,
     pub user_index_canister_id: CanisterId,
     pub local_user_index_canister_id: CanisterId,
     pub group_index_canister_id: CanisterId,
@@ -144,6 +146,7 @@ impl Data {
             group_canister_wasm_for_upgrades: group_canister_wasm,
             community_canister_wasm_for_new_canisters: community_canister_wasm.clone(),
             community_canister_wasm_for_upgrades: community_canister_wasm,
+            wasm_uploads: WasmUploads::default(),
             user_index_canister_id,
             local_user_index_canister_id,
             group_index_canister_id,
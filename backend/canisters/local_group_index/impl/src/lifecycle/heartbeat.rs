@@ -2,7 +2,7 @@ use crate::{mutate_state, read_state, RuntimeState, GROUP_CANISTER_INITIAL_CYCLE
 use ic_cdk::api::management_canister::main::CanisterInstallMode;
 use ic_cdk_macros::heartbeat;
 use types::{BuildVersion, CanisterId, ChatId, Cycles, CyclesTopUp};
-use utils::canister::{self, FailedUpgrade};
+use utils::canister::{self, install_trapped, FailedUpgrade, RolloutProgress};
 use utils::consts::{CREATE_CANISTER_CYCLES_FEE, MIN_CYCLES_BALANCE};
 
 #[heartbeat]
@@ -25,6 +25,13 @@ mod upgrade_groups {
     }
 
     fn next_batch(state: &mut RuntimeState) -> Vec<CanisterToUpgrade> {
+        if !matches!(
+            state.data.groups_requiring_upgrade.update_rollout(state.env.now()),
+            RolloutProgress::Proceed
+        ) {
+            return Vec::new();
+        }
+
         let count_in_progress = state.data.groups_requiring_upgrade.count_in_progress();
         let group_upgrade_concurrency = state.data.group_upgrade_concurrency as usize;
 
@@ -83,8 +90,9 @@ mod upgrade_groups {
             Ok(_) => {
                 mutate_state(|state| on_success(canister_id, to_version, None, state));
             }
-            Err(_) => {
-                mutate_state(|state| on_failure(canister_id, from_version, to_version, state));
+            Err(error) => {
+                let trapped = install_trapped(&error);
+                mutate_state(|state| on_failure(canister_id, from_version, to_version, trapped, state));
             }
         }
     }
@@ -106,14 +114,25 @@ mod upgrade_groups {
         state.data.groups_requiring_upgrade.mark_success(&canister_id);
     }
 
-    fn on_failure(canister_id: CanisterId, from_version: BuildVersion, to_version: BuildVersion, state: &mut RuntimeState) {
+    fn on_failure(
+        canister_id: CanisterId,
+        from_version: BuildVersion,
+        to_version: BuildVersion,
+        trapped: bool,
+        state: &mut RuntimeState,
+    ) {
         mark_upgrade_complete(canister_id.into(), None, state);
 
-        state.data.groups_requiring_upgrade.mark_failure(FailedUpgrade {
-            canister_id,
-            from_version,
-            to_version,
-        });
+        let now = state.env.now();
+        state.data.groups_requiring_upgrade.mark_failure(
+            FailedUpgrade {
+                canister_id,
+                from_version,
+                to_version,
+                trapped,
+            },
+            now,
+        );
     }
 
     fn mark_upgrade_complete(chat_id: ChatId, new_wasm_version: Option<BuildVersion>, state: &mut RuntimeState) {
@@ -137,6 +156,13 @@ mod upgrade_communities {
     }
 
     fn next_batch(state: &mut RuntimeState) -> Vec<CanisterToUpgrade> {
+        if !matches!(
+            state.data.communities_requiring_upgrade.update_rollout(state.env.now()),
+            RolloutProgress::Proceed
+        ) {
+            return Vec::new();
+        }
+
         let count_in_progress = state.data.communities_requiring_upgrade.count_in_progress();
         let community_upgrade_concurrency = state.data.community_upgrade_concurrency as usize;
 
@@ -195,8 +221,9 @@ mod upgrade_communities {
             Ok(_) => {
                 mutate_state(|state| on_success(canister_id, to_version, None, state));
             }
-            Err(_) => {
-                mutate_state(|state| on_failure(canister_id, from_version, to_version, state));
+            Err(error) => {
+                let trapped = install_trapped(&error);
+                mutate_state(|state| on_failure(canister_id, from_version, to_version, trapped, state));
             }
         }
     }
@@ -218,14 +245,25 @@ mod upgrade_communities {
         state.data.communities_requiring_upgrade.mark_success(&canister_id);
     }
 
-    fn on_failure(canister_id: CanisterId, from_version: BuildVersion, to_version: BuildVersion, state: &mut RuntimeState) {
+    fn on_failure(
+        canister_id: CanisterId,
+        from_version: BuildVersion,
+        to_version: BuildVersion,
+        trapped: bool,
+        state: &mut RuntimeState,
+    ) {
         mark_upgrade_complete(canister_id.into(), None, state);
 
-        state.data.communities_requiring_upgrade.mark_failure(FailedUpgrade {
-            canister_id,
-            from_version,
-            to_version,
-        });
+        let now = state.env.now();
+        state.data.communities_requiring_upgrade.mark_failure(
+            FailedUpgrade {
+                canister_id,
+                from_version,
+                to_version,
+                trapped,
+            },
+            now,
+        );
     }
 
     fn mark_upgrade_complete(community_id: CommunityId, new_wasm_version: Option<BuildVersion>, state: &mut RuntimeState) {
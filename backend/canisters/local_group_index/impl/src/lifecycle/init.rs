@@ -1,4 +1,5 @@
 use crate::lifecycle::{init_env, init_state};
+use crate::memory::{get_log_overflow_data_memory, get_log_overflow_index_memory};
 use crate::Data;
 use canister_tracing_macros::trace;
 use ic_cdk_macros::init;
@@ -12,6 +13,7 @@ const CANISTER_POOL_TARGET_SIZE: u16 = 20;
 #[trace]
 fn init(args: Args) {
     canister_logger::init(args.test_mode);
+    canister_logger::init_log_overflow(get_log_overflow_index_memory(), get_log_overflow_data_memory());
     init_cycles_dispenser_client(args.cycles_dispenser_canister_id);
 
     let env = init_env();
@@ -1,2 +1,3 @@
 pub mod c2c_can_push_notifications;
 pub mod http_request;
+pub mod upgrade_rollout_status;
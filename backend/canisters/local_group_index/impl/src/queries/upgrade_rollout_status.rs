@@ -0,0 +1,15 @@
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use local_group_index_canister::upgrade_rollout_status::{Response::*, *};
+
+#[query]
+fn upgrade_rollout_status(_args: Args) -> Response {
+    read_state(upgrade_rollout_status_impl)
+}
+
+fn upgrade_rollout_status_impl(state: &RuntimeState) -> Response {
+    Success(SuccessResult {
+        groups: state.data.groups_requiring_upgrade.rollout_status(),
+        communities: state.data.communities_requiring_upgrade.rollout_status(),
+    })
+}
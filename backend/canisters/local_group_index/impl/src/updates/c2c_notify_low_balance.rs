@@ -55,10 +55,20 @@ fn commit(canister_id: CanisterId, top_up: CyclesTopUp, is_group: bool, state: &
     state.data.total_cycles_spent_on_canisters += top_up.amount;
 
     if is_group {
+        state
+            .data
+            .groups_requiring_upgrade
+            .mark_low_cycles_notification(&canister_id, top_up.date);
         if !state.data.local_groups.mark_cycles_top_up(&canister_id.into(), top_up) {
             panic!("Group not found. {canister_id}");
         }
-    } else if !state.data.local_communities.mark_cycles_top_up(&canister_id.into(), top_up) {
-        panic!("Community not found. {canister_id}");
+    } else {
+        state
+            .data
+            .communities_requiring_upgrade
+            .mark_low_cycles_notification(&canister_id, top_up.date);
+        if !state.data.local_communities.mark_cycles_top_up(&canister_id.into(), top_up) {
+            panic!("Community not found. {canister_id}");
+        }
     }
 }
@@ -0,0 +1,167 @@
+use crate::guards::caller_is_group_index_canister;
+use crate::{mutate_state, read_state, RuntimeState, MARK_ACTIVE_DURATION};
+use candid::CandidType;
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use ic_cdk::api::call::CallResult;
+use ic_cdk::api::management_canister::main::CanisterInstallMode;
+use local_group_index_canister::c2c_prepare_state_restore::{Response::*, *};
+use types::{BuildVersion, CanisterId, CanisterWasm, Cycles, Rules};
+use utils::canister::{self, CanisterToInstall};
+use utils::consts::OPENCHAT_BOT_USER_ID;
+
+// Reinstalls the group or community as a placeholder into which a snapshot of its state can then be
+// restored
+#[update_msgpack(guard = "caller_is_group_index_canister")]
+#[trace]
+async fn c2c_prepare_state_restore(args: Args) -> Response {
+    let prepare_ok = match read_state(|state| prepare(&args, state)) {
+        Err(response) => return response,
+        Ok(ok) => ok,
+    };
+
+    let is_community = prepare_ok.is_community;
+    let wasm_version = prepare_ok.canister_wasm.version;
+    let result = match prepare_ok.init_canister_args {
+        InitCanisterArgs::Group(init_args) => {
+            install_placeholder(
+                args.canister_id,
+                prepare_ok.current_wasm_version,
+                prepare_ok.canister_wasm,
+                init_args,
+            )
+            .await
+        }
+        InitCanisterArgs::Community(init_args) => {
+            install_placeholder(
+                args.canister_id,
+                prepare_ok.current_wasm_version,
+                prepare_ok.canister_wasm,
+                init_args,
+            )
+            .await
+        }
+    };
+
+    match result {
+        Ok(_) => {
+            mutate_state(|state| commit(args.canister_id, is_community, wasm_version, state));
+            Success(SuccessResult { is_community })
+        }
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
+
+struct PrepareOk {
+    current_wasm_version: BuildVersion,
+    is_community: bool,
+    canister_wasm: CanisterWasm,
+    init_canister_args: InitCanisterArgs,
+}
+
+enum InitCanisterArgs {
+    Group(group_canister::init::Args),
+    Community(community_canister::init::Args),
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareOk, Response> {
+    let (current_wasm_version, is_community) = if let Some(group) = state.data.local_groups.get(&args.canister_id.into()) {
+        (group.wasm_version, false)
+    } else if let Some(community) = state.data.local_communities.get(&args.canister_id.into()) {
+        (community.wasm_version, true)
+    } else {
+        return Err(CanisterNotFound);
+    };
+
+    // The placeholder's state is replaced once the snapshot has been restored, other than the ids
+    // of the canisters it interacts with, which are taken from here
+    let (canister_wasm, init_canister_args) = if is_community {
+        let canister_wasm = state.data.community_canister_wasm_for_new_canisters.clone();
+        let init_args = community_canister::init::Args {
+            is_public: false,
+            name: "Restoring".to_string(),
+            description: String::new(),
+            rules: Rules::default(),
+            avatar: None,
+            banner: None,
+            permissions: Default::default(),
+            primary_language: "en".to_string(),
+            created_by_principal: OPENCHAT_BOT_USER_ID.into(),
+            created_by_user_id: OPENCHAT_BOT_USER_ID,
+            mark_active_duration: MARK_ACTIVE_DURATION,
+            user_index_canister_id: state.data.user_index_canister_id,
+            local_user_index_canister_id: state.data.local_user_index_canister_id,
+            group_index_canister_id: state.data.group_index_canister_id,
+            local_group_index_canister_id: state.env.canister_id(),
+            notifications_canister_id: state.data.notifications_canister_id,
+            proposals_bot_user_id: state.data.proposals_bot_user_id,
+            gate: None,
+            default_channels: Vec::new(),
+            default_channel_rules: None,
+            source_group: None,
+            wasm_version: canister_wasm.version,
+            test_mode: state.data.test_mode,
+        };
+        (canister_wasm, InitCanisterArgs::Community(init_args))
+    } else {
+        let canister_wasm = state.data.group_canister_wasm_for_new_canisters.clone();
+        let init_args = group_canister::init::Args {
+            is_public: false,
+            name: "Restoring".to_string(),
+            description: String::new(),
+            rules: Rules::default(),
+            subtype: None,
+            avatar: None,
+            history_visible_to_new_joiners: false,
+            permissions: None,
+            created_by_principal: OPENCHAT_BOT_USER_ID.into(),
+            created_by_user_id: OPENCHAT_BOT_USER_ID,
+            events_ttl: None,
+            mark_active_duration: MARK_ACTIVE_DURATION,
+            group_index_canister_id: state.data.group_index_canister_id,
+            local_group_index_canister_id: state.env.canister_id(),
+            user_index_canister_id: state.data.user_index_canister_id,
+            local_user_index_canister_id: state.data.local_user_index_canister_id,
+            notifications_canister_id: state.data.notifications_canister_id,
+            proposals_bot_user_id: state.data.proposals_bot_user_id,
+            gate: None,
+            wasm_version: canister_wasm.version,
+            test_mode: state.data.test_mode,
+        };
+        (canister_wasm, InitCanisterArgs::Group(init_args))
+    };
+
+    Ok(PrepareOk {
+        current_wasm_version,
+        is_community,
+        canister_wasm,
+        init_canister_args,
+    })
+}
+
+async fn install_placeholder<A: CandidType>(
+    canister_id: CanisterId,
+    current_wasm_version: BuildVersion,
+    canister_wasm: CanisterWasm,
+    init_args: A,
+) -> CallResult<Option<Cycles>> {
+    canister::install(CanisterToInstall {
+        canister_id,
+        current_wasm_version,
+        new_wasm: canister_wasm,
+        deposit_cycles_if_needed: true,
+        args: init_args,
+        mode: CanisterInstallMode::Reinstall,
+        stop_start_canister: true,
+    })
+    .await
+}
+
+fn commit(canister_id: CanisterId, is_community: bool, wasm_version: BuildVersion, state: &mut RuntimeState) {
+    // Reinstalling the canister resets its wasm version
+    if is_community {
+        state.data.local_communities.add(canister_id.into(), wasm_version);
+    } else {
+        state.data.local_groups.add(canister_id.into(), wasm_version);
+    }
+}
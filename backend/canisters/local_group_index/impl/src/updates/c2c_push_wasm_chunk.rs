@@ -0,0 +1,26 @@
+use crate::guards::caller_is_group_index_canister;
+use crate::{mutate_state, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use local_group_index_canister::c2c_push_wasm_chunk::{Response::*, *};
+use utils::canister::PushWasmChunkResult;
+
+#[update_msgpack(guard = "caller_is_group_index_canister")]
+#[trace]
+fn c2c_push_wasm_chunk(args: Args) -> Response {
+    mutate_state(|state| c2c_push_wasm_chunk_impl(args, state))
+}
+
+fn c2c_push_wasm_chunk_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+
+    match state.data.wasm_uploads.push_wasm_chunk(args, now) {
+        PushWasmChunkResult::Success => Success,
+        PushWasmChunkResult::Committed => UploadCommitted,
+        PushWasmChunkResult::TooLarge(max) => WasmTooLarge(max),
+        PushWasmChunkResult::NotFound => UploadNotFound,
+        PushWasmChunkResult::UnexpectedChunkIndex(expected) => UnexpectedChunkIndex(expected),
+        PushWasmChunkResult::ExceedsTotalSize => ExceedsTotalSize,
+        PushWasmChunkResult::HashMismatch(actual) => HashMismatch(actual),
+    }
+}
@@ -0,0 +1,134 @@
+use crate::guards::caller_is_group_index_canister;
+use crate::{mutate_state, RuntimeState};
+use candid::CandidType;
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use ic_cdk::api::management_canister::main::CanisterInstallMode;
+use local_group_index_canister::c2c_take_state_snapshot::{Response::*, *};
+use types::{BuildVersion, CanisterId, CanisterWasm};
+use utils::canister::{self, CanisterToInstall};
+use utils::consts::MIN_CYCLES_BALANCE;
+
+// Upgrades the group or community in place so that its current state is written to stable memory
+// by `pre_upgrade`, from where it can then be exported in pages
+#[update_msgpack(guard = "caller_is_group_index_canister")]
+#[trace]
+async fn c2c_take_state_snapshot(args: Args) -> Response {
+    let prepare_ok = match mutate_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    let canister_id = args.canister_id;
+    let is_community = prepare_ok.is_community;
+    let current_wasm_version = prepare_ok.current_wasm_version;
+    let new_wasm_version = prepare_ok.canister_wasm.version;
+
+    let result = if is_community {
+        upgrade(
+            canister_id,
+            current_wasm_version,
+            prepare_ok.canister_wasm,
+            community_canister::post_upgrade::Args {
+                wasm_version: new_wasm_version,
+            },
+        )
+        .await
+    } else {
+        upgrade(
+            canister_id,
+            current_wasm_version,
+            prepare_ok.canister_wasm,
+            group_canister::post_upgrade::Args {
+                wasm_version: new_wasm_version,
+            },
+        )
+        .await
+    };
+
+    let success = result.is_ok();
+    mutate_state(|state| set_upgrade_status(canister_id, is_community, false, success.then_some(new_wasm_version), state));
+
+    match result {
+        Ok(_) => Success,
+        Err(error) => InternalError(error),
+    }
+}
+
+struct PrepareOk {
+    is_community: bool,
+    current_wasm_version: BuildVersion,
+    canister_wasm: CanisterWasm,
+}
+
+fn prepare(args: &Args, state: &mut RuntimeState) -> Result<PrepareOk, Response> {
+    let (current_wasm_version, upgrade_in_progress, is_community) =
+        if let Some(group) = state.data.local_groups.get(&args.canister_id.into()) {
+            (group.wasm_version, group.upgrade_in_progress, false)
+        } else if let Some(community) = state.data.local_communities.get(&args.canister_id.into()) {
+            (community.wasm_version, community.upgrade_in_progress, true)
+        } else {
+            return Err(CanisterNotFound);
+        };
+
+    if upgrade_in_progress {
+        return Err(UpgradeInProgress);
+    }
+
+    // The canister is upgraded to the wasm it would next be upgraded to anyway
+    let canister_wasm = if is_community {
+        state.data.community_canister_wasm_for_upgrades.clone()
+    } else {
+        state.data.group_canister_wasm_for_upgrades.clone()
+    };
+    if canister_wasm.version < current_wasm_version {
+        return Err(InternalError(format!(
+            "Canister is running a newer wasm ({current_wasm_version}) than is available ({})",
+            canister_wasm.version
+        )));
+    }
+
+    set_upgrade_status(args.canister_id, is_community, true, None, state);
+
+    Ok(PrepareOk {
+        is_community,
+        current_wasm_version,
+        canister_wasm,
+    })
+}
+
+async fn upgrade<A: CandidType>(
+    canister_id: CanisterId,
+    current_wasm_version: BuildVersion,
+    new_wasm: CanisterWasm,
+    args: A,
+) -> Result<(), String> {
+    canister::install(CanisterToInstall {
+        canister_id,
+        current_wasm_version,
+        new_wasm,
+        deposit_cycles_if_needed: ic_cdk::api::canister_balance128() > MIN_CYCLES_BALANCE,
+        args,
+        mode: CanisterInstallMode::Upgrade,
+        stop_start_canister: true,
+    })
+    .await
+    .map(|_| ())
+    .map_err(|error| format!("{error:?}"))
+}
+
+fn set_upgrade_status(
+    canister_id: CanisterId,
+    is_community: bool,
+    upgrade_in_progress: bool,
+    new_version: Option<BuildVersion>,
+    state: &mut RuntimeState,
+) {
+    if is_community {
+        if let Some(community) = state.data.local_communities.get_mut(&canister_id.into()) {
+            community.set_canister_upgrade_status(upgrade_in_progress, new_version);
+        }
+    } else if let Some(group) = state.data.local_groups.get_mut(&canister_id.into()) {
+        group.set_canister_upgrade_status(upgrade_in_progress, new_version);
+    }
+}
@@ -14,12 +14,17 @@ fn c2c_upgrade_community_canister_wasm(args: Args) -> Response {
     mutate_state(|state| c2c_upgrade_community_canister_wasm_impl(args, state))
 }
 
-fn c2c_upgrade_community_canister_wasm_impl(args: Args, state: &mut RuntimeState) -> Response {
+fn c2c_upgrade_community_canister_wasm_impl(mut args: Args, state: &mut RuntimeState) -> Response {
     let version = args.wasm.version;
 
-    if !state.data.test_mode && Some(version) <= min_canister_version(&state.data) {
+    if !state.data.wasm_uploads.resolve(&mut args) {
+        UploadedWasmNotFound
+    } else if !state.data.test_mode && Some(version) <= min_canister_version(&state.data) {
         VersionNotHigher
     } else {
+        if let Some(hash) = args.uploaded_wasm_hash {
+            state.data.wasm_uploads.remove(&hash);
+        }
         state.data.communities_requiring_upgrade.clear();
         if args.use_for_new_canisters.unwrap_or(true) {
             state.data.community_canister_wasm_for_new_canisters = args.wasm.clone();
@@ -42,6 +47,10 @@ fn c2c_upgrade_community_canister_wasm_impl(args: Args, state: &mut RuntimeState
         {
             state.data.communities_requiring_upgrade.enqueue(canister_id, false);
         }
+        if let Some(rollout) = args.rollout {
+            let now = state.env.now();
+            state.data.communities_requiring_upgrade.start_rollout(rollout, version, now);
+        }
 
         let canisters_queued_for_upgrade = state.data.communities_requiring_upgrade.count_pending();
         info!(%version, canisters_queued_for_upgrade, "Community canister wasm upgraded");
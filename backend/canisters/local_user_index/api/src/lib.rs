@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use types::nns::CryptoAmount;
 use types::{
     ChannelLatestMessageIndex, ChatId, CommunityId, Cryptocurrency, DiamondMembershipPlanDuration, MessageContent,
-    MessageIndex, PhoneNumber, ReferralType, SuspensionDuration, TimestampMillis, UserId,
+    MessageIndex, PhoneNumber, ReferralCampaignCode, ReferralType, SuspensionDuration, TimestampMillis, UserId,
 };
 
 mod lifecycle;
@@ -30,6 +30,7 @@ pub enum Event {
     DiamondMembershipPaymentReceived(DiamondMembershipPaymentReceived),
     OpenChatBotMessage(Box<OpenChatBotMessage>),
     ReferralCodeAdded(ReferralCodeAdded),
+    ReferralCampaignCodesAdded(ReferralCampaignCodesAdded),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -134,6 +135,14 @@ pub struct ReferralCodeAdded {
     pub expiry: Option<TimestampMillis>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReferralCampaignCodesAdded {
+    pub campaign: String,
+    pub codes: Vec<ReferralCampaignCode>,
+    pub start: TimestampMillis,
+    pub end: TimestampMillis,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct GlobalUser {
     pub user_id: UserId,
@@ -14,7 +14,12 @@ pub struct Args {
     pub group_index_canister_id: CanisterId,
     pub notifications_canister_id: CanisterId,
     pub proposals_bot_canister_id: CanisterId,
+    pub online_users_canister_id: CanisterId,
     pub cycles_dispenser_canister_id: CanisterId,
     pub internet_identity_canister_id: CanisterId,
     pub test_mode: bool,
+    // Only ever set on local devnets. Allows users to register with keys not derived from Internet
+    // Identity so that tooling (eg. the devnet seeder) can create users. Requires `test_mode`.
+    #[serde(default)]
+    pub skip_internet_identity_check: bool,
 }
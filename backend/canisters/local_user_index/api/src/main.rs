@@ -2,6 +2,8 @@ use candid_gen::generate_candid_method;
 
 #[allow(deprecated)]
 fn main() {
+    generate_candid_method!(local_user_index, upgrade_rollout_status, query);
+
     generate_candid_method!(local_user_index, invite_users_to_channel, update);
     generate_candid_method!(local_user_index, invite_users_to_community, update);
     generate_candid_method!(local_user_index, invite_users_to_group, update);
@@ -1,7 +1,7 @@
 use candid::Principal;
 use canister_client::{generate_c2c_call, generate_candid_c2c_call};
 use local_user_index_canister::*;
-use types::CanisterId;
+use types::{CanisterId, WasmChunk};
 
 // Queries
 generate_c2c_call!(c2c_can_push_notifications);
@@ -11,6 +11,8 @@ generate_c2c_call!(c2c_user_principals);
 // Updates
 generate_c2c_call!(c2c_notify_low_balance);
 generate_c2c_call!(c2c_notify_user_index_events);
+generate_c2c_call!(c2c_push_wasm_chunk);
+generate_c2c_call!(c2c_rollback_user_canister_wasm);
 generate_c2c_call!(c2c_upgrade_user_canister_wasm);
 
 generate_candid_c2c_call!(join_group);
@@ -33,3 +35,13 @@ pub async fn lookup_user(
         Err(error) => Err(LookupUserError::InternalError(format!("{error:?}"))),
     }
 }
+
+// Returns true once the final chunk has been received and the upload committed
+pub async fn push_wasm_chunk(canister_id: CanisterId, chunk: WasmChunk) -> Result<bool, String> {
+    match crate::c2c_push_wasm_chunk(canister_id, &chunk).await {
+        Ok(c2c_push_wasm_chunk::Response::Success) => Ok(false),
+        Ok(c2c_push_wasm_chunk::Response::UploadCommitted) => Ok(true),
+        Ok(response) => Err(format!("{response:?}")),
+        Err(error) => Err(format!("{error:?}")),
+    }
+}
@@ -0,0 +1,10 @@
+use canister_client::generate_update_call;
+use local_user_index_canister::*;
+
+// Queries
+
+// Updates
+generate_update_call!(join_channel);
+generate_update_call!(join_community);
+generate_update_call!(join_group);
+generate_update_call!(register_user);
@@ -4,8 +4,8 @@ use ic_cdk_timers::TimerId;
 use std::cell::Cell;
 use std::time::Duration;
 use tracing::trace;
-use types::{BuildVersion, CanisterId, Cycles, CyclesTopUp, UserId};
-use utils::canister::{install, FailedUpgrade};
+use types::{BuildVersion, CanisterId, Cycles, CyclesTopUp, Milliseconds, UserId};
+use utils::canister::{install, install_trapped, FailedUpgrade, RolloutProgress};
 use utils::consts::MIN_CYCLES_BALANCE;
 
 type CanisterToUpgrade = utils::canister::CanisterToInstall<user_canister::post_upgrade::Args>;
@@ -29,26 +29,59 @@ pub(crate) fn start_job_if_required(state: &RuntimeState) -> bool {
 }
 
 fn run() {
-    if let Some(batch) = mutate_state(next_batch) {
-        if !batch.is_empty() {
-            ic_cdk::spawn(perform_upgrades(batch));
+    match mutate_state(next_batch) {
+        NextBatchResult::Success(batch) => {
+            if !batch.is_empty() {
+                ic_cdk::spawn(perform_upgrades(batch));
+            }
         }
-    } else if let Some(timer_id) = TIMER_ID.with(|t| t.take()) {
+        NextBatchResult::QueueEmpty => stop_job(),
+        NextBatchResult::RolloutPaused(resume_in) => {
+            stop_job();
+            if let Some(resume_in) = resume_in {
+                ic_cdk_timers::set_timer(Duration::from_millis(resume_in), resume_rollout);
+            }
+        }
+    }
+}
+
+fn stop_job() {
+    if let Some(timer_id) = TIMER_ID.with(|t| t.take()) {
         ic_cdk_timers::clear_timer(timer_id);
         trace!("'upgrade_canisters' job stopped");
     }
 }
 
-fn next_batch(state: &mut RuntimeState) -> Option<Vec<CanisterToUpgrade>> {
+fn resume_rollout() {
+    mutate_state(|state| {
+        state.data.canisters_requiring_upgrade.update_rollout(state.env.now());
+        start_job_if_required(state);
+    });
+}
+
+enum NextBatchResult {
+    Success(Vec<CanisterToUpgrade>),
+    QueueEmpty,
+    RolloutPaused(Option<Milliseconds>),
+}
+
+fn next_batch(state: &mut RuntimeState) -> NextBatchResult {
+    let now = state.env.now();
+    match state.data.canisters_requiring_upgrade.update_rollout(now) {
+        RolloutProgress::Proceed => {}
+        RolloutProgress::Soaking(until) => return NextBatchResult::RolloutPaused(Some(until.saturating_sub(now))),
+        RolloutProgress::Halted => return NextBatchResult::RolloutPaused(None),
+    }
+
     let count_in_progress = state.data.canisters_requiring_upgrade.count_in_progress();
     let count_pending = state.data.canisters_requiring_upgrade.count_pending();
 
     if count_in_progress == 0 && count_pending == 0 {
-        None
+        NextBatchResult::QueueEmpty
     } else {
         let user_upgrade_concurrency = state.data.user_upgrade_concurrency as usize;
 
-        Some(
+        NextBatchResult::Success(
             (0..(user_upgrade_concurrency.saturating_sub(count_in_progress)))
                 .map_while(|_| try_get_next(state))
                 .collect(),
@@ -106,8 +139,9 @@ async fn perform_upgrade(canister_to_upgrade: CanisterToUpgrade) {
         Ok(cycles_top_up) => {
             mutate_state(|state| on_success(canister_id, to_version, cycles_top_up, state));
         }
-        Err(_) => {
-            mutate_state(|state| on_failure(canister_id, from_version, to_version, state));
+        Err(error) => {
+            let trapped = install_trapped(&error);
+            mutate_state(|state| on_failure(canister_id, from_version, to_version, trapped, state));
         }
     }
 }
@@ -129,14 +163,25 @@ fn on_success(canister_id: CanisterId, to_version: BuildVersion, top_up: Option<
     state.data.canisters_requiring_upgrade.mark_success(&canister_id);
 }
 
-fn on_failure(canister_id: CanisterId, from_version: BuildVersion, to_version: BuildVersion, state: &mut RuntimeState) {
+fn on_failure(
+    canister_id: CanisterId,
+    from_version: BuildVersion,
+    to_version: BuildVersion,
+    trapped: bool,
+    state: &mut RuntimeState,
+) {
     mark_upgrade_complete(canister_id.into(), None, state);
 
-    state.data.canisters_requiring_upgrade.mark_failure(FailedUpgrade {
-        canister_id,
-        from_version,
-        to_version,
-    });
+    let now = state.env.now();
+    state.data.canisters_requiring_upgrade.mark_failure(
+        FailedUpgrade {
+            canister_id,
+            from_version,
+            to_version,
+            trapped,
+        },
+        now,
+    );
 }
 
 fn mark_upgrade_complete(canister_id: UserId, new_wasm_version: Option<BuildVersion>, state: &mut RuntimeState) {
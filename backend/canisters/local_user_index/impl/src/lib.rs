@@ -16,7 +16,7 @@ use types::{
 use user_canister::Event as UserEvent;
 use user_index_canister::Event as UserIndexEvent;
 use utils::canister;
-use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount};
+use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount, WasmUploads};
 use utils::canister_event_sync_queue::CanisterEventSyncQueue;
 use utils::consts::CYCLES_REQUIRED_FOR_UPGRADE;
 use utils::env::Environment;
@@ -170,6 +170,7 @@ impl RuntimeState {
             max_concurrent_canister_upgrades: self.data.max_concurrent_canister_upgrades,
             user_upgrade_concurrency: self.data.user_upgrade_concurrency,
             user_events_queue_length: self.data.user_event_sync_queue.len(),
+            timer_jobs: self.data.timer_jobs.len() as u32,
             referral_codes: self.data.referral_codes.metrics(),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
@@ -188,11 +189,15 @@ struct Data {
     pub global_users: GlobalUserMap,
     pub user_canister_wasm_for_new_canisters: CanisterWasm,
     pub user_canister_wasm_for_upgrades: CanisterWasm,
+    #[serde(default)]
+    pub wasm_uploads: WasmUploads,
     pub user_index_canister_id: CanisterId,
     pub group_index_canister_id: CanisterId,
     pub notifications_canister_id: CanisterId,
     #[serde(default = "proposals_bot_canister_id")]
     pub proposals_bot_canister_id: CanisterId,
+    #[serde(default = "online_users_canister_id")]
+    pub online_users_canister_id: CanisterId,
     pub cycles_dispenser_canister_id: CanisterId,
     pub internet_identity_canister_id: CanisterId,
     pub canisters_requiring_upgrade: CanistersRequiringUpgrade,
@@ -207,12 +212,18 @@ struct Data {
     pub referral_codes: ReferralCodes,
     pub timer_jobs: TimerJobs<TimerJob>,
     pub btc_miami_payments_queue: BtcMiamiPaymentsQueue,
+    #[serde(default)]
+    pub skip_internet_identity_check: bool,
 }
 
 fn proposals_bot_canister_id() -> CanisterId {
     CanisterId::from_text("iywa7-ayaaa-aaaaf-aemga-cai").unwrap()
 }
 
+fn online_users_canister_id() -> CanisterId {
+    CanisterId::from_text("3vlw6-fiaaa-aaaaf-aaa3a-cai").unwrap()
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct FailedMessageUsers {
     pub sender: UserId,
@@ -227,20 +238,24 @@ impl Data {
         group_index_canister_id: CanisterId,
         notifications_canister_id: CanisterId,
         proposals_bot_canister_id: CanisterId,
+        online_users_canister_id: CanisterId,
         cycles_dispenser_canister_id: CanisterId,
         internet_identity_canister_id: CanisterId,
         canister_pool_target_size: u16,
         test_mode: bool,
+        skip_internet_identity_check: bool,
     ) -> Self {
         Data {
             local_users: LocalUserMap::default(),
             global_users: GlobalUserMap::default(),
             user_canister_wasm_for_new_canisters: user_canister_wasm.clone(),
             user_canister_wasm_for_upgrades: user_canister_wasm,
+            wasm_uploads: WasmUploads::default(),
             user_index_canister_id,
             group_index_canister_id,
             notifications_canister_id,
             proposals_bot_canister_id,
+            online_users_canister_id,
             cycles_dispenser_canister_id,
             internet_identity_canister_id,
             canisters_requiring_upgrade: CanistersRequiringUpgrade::default(),
@@ -255,6 +270,7 @@ impl Data {
             referral_codes: ReferralCodes::default(),
             timer_jobs: TimerJobs::default(),
             btc_miami_payments_queue: BtcMiamiPaymentsQueue::default(),
+            skip_internet_identity_check,
         }
     }
 }
@@ -278,6 +294,7 @@ pub struct Metrics {
     pub max_concurrent_canister_upgrades: u32,
     pub user_upgrade_concurrency: u32,
     pub user_events_queue_length: usize,
+    pub timer_jobs: u32,
     pub referral_codes: HashMap<ReferralType, ReferralTypeMetrics>,
     pub canister_ids: CanisterIds,
 }
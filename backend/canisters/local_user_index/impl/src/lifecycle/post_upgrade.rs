@@ -1,5 +1,5 @@
 use crate::lifecycle::{init_env, init_state, UPGRADE_BUFFER_SIZE};
-use crate::memory::get_upgrades_memory;
+use crate::memory::{get_log_overflow_data_memory, get_log_overflow_index_memory, get_upgrades_memory};
 use crate::{mutate_state, Data};
 use canister_logger::LogEntry;
 use canister_tracing_macros::trace;
@@ -19,6 +19,7 @@ fn post_upgrade(args: Args) {
 
     let (data, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>) = serializer::deserialize(reader).unwrap();
 
+    canister_logger::init_log_overflow(get_log_overflow_index_memory(), get_log_overflow_data_memory());
     canister_logger::init_with_logs(data.test_mode, logs, traces);
 
     init_cycles_dispenser_client(data.cycles_dispenser_canister_id);
@@ -1,12 +1,13 @@
 use candid::{Deserialize, Principal};
 use serde::Serialize;
 use std::collections::{hash_map::Entry, HashMap};
-use types::{ReferralType, TimestampMillis, UserId};
+use types::{ReferralCampaignCode, ReferralType, TimestampMillis, UserId};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub enum ReferralCode {
     BtcMiami(String),
     User(UserId),
+    Campaign(String, UserId),
 }
 
 impl ReferralCode {
@@ -14,6 +15,14 @@ impl ReferralCode {
         match self {
             ReferralCode::BtcMiami(_) => None,
             ReferralCode::User(user_id) => Some(*user_id),
+            ReferralCode::Campaign(_, referrer) => Some(*referrer),
+        }
+    }
+
+    pub fn campaign(&self) -> Option<String> {
+        match self {
+            ReferralCode::Campaign(campaign, _) => Some(campaign.clone()),
+            _ => None,
         }
     }
 }
@@ -21,6 +30,8 @@ impl ReferralCode {
 #[derive(Serialize, Deserialize, Default)]
 pub struct ReferralCodes {
     codes: HashMap<String, ReferralCodeDetails>,
+    #[serde(default)]
+    campaign_codes: HashMap<String, CampaignCodeDetails>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -31,6 +42,15 @@ pub struct ReferralCodeDetails {
     expiry: Option<TimestampMillis>,
 }
 
+// Campaign codes can be used any number of times, but only within the campaign's window
+#[derive(Serialize, Deserialize)]
+pub struct CampaignCodeDetails {
+    campaign: String,
+    referrer: UserId,
+    start: TimestampMillis,
+    end: TimestampMillis,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ReferralCodeClaim {
     when: TimestampMillis,
@@ -57,6 +77,10 @@ impl ReferralCodes {
         expiry: Option<TimestampMillis>,
         now: TimestampMillis,
     ) -> bool {
+        if self.campaign_codes.contains_key(&code) {
+            return false;
+        }
+
         match self.codes.entry(code) {
             Entry::Occupied(_) => false,
             Entry::Vacant(e) => {
@@ -71,6 +95,33 @@ impl ReferralCodes {
         }
     }
 
+    // Returns the codes which were rejected because they clash with existing one-off codes
+    pub fn add_campaign_codes(
+        &mut self,
+        campaign: String,
+        codes: Vec<ReferralCampaignCode>,
+        start: TimestampMillis,
+        end: TimestampMillis,
+    ) -> Vec<String> {
+        let mut rejected = Vec::new();
+        for code in codes {
+            if self.codes.contains_key(&code.code) {
+                rejected.push(code.code);
+            } else {
+                self.campaign_codes.insert(
+                    code.code,
+                    CampaignCodeDetails {
+                        campaign: campaign.clone(),
+                        referrer: code.referrer,
+                        start,
+                        end,
+                    },
+                );
+            }
+        }
+        rejected
+    }
+
     pub fn claim(&mut self, code: String, user_id: UserId, now: TimestampMillis) -> bool {
         match self.codes.entry(code) {
             Entry::Occupied(mut e) => {
@@ -100,6 +151,14 @@ impl ReferralCodes {
             } else {
                 Ok(ReferralCode::BtcMiami(code.clone()))
             }
+        } else if let Some(details) = self.campaign_codes.get(code) {
+            if now < details.start {
+                Err(ReferralCodeError::NotFound)
+            } else if now > details.end {
+                Err(ReferralCodeError::Expired)
+            } else {
+                Ok(ReferralCode::Campaign(details.campaign.clone(), details.referrer))
+            }
         } else if let Ok(user_id) = Principal::from_text(code).map(|p| p.into()) {
             Ok(ReferralCode::User(user_id))
         } else {
@@ -0,0 +1,15 @@
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use local_user_index_canister::upgrade_rollout_status::{Response::*, *};
+
+#[query]
+fn upgrade_rollout_status(_args: Args) -> Response {
+    read_state(upgrade_rollout_status_impl)
+}
+
+fn upgrade_rollout_status_impl(state: &RuntimeState) -> Response {
+    match state.data.canisters_requiring_upgrade.rollout_status() {
+        Some(status) => Success(status),
+        None => NoRollout,
+    }
+}
@@ -6,11 +6,12 @@ use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
 use local_user_index_canister::c2c_notify_events::{Response::*, *};
 use local_user_index_canister::Event;
-use tracing::info;
+use tracing::{error, info};
 use user_canister::{
     DiamondMembershipPaymentReceived, DisplayNameChanged, Event as UserEvent, PhoneNumberConfirmed, ReferredUserRegistered,
     StorageUpgraded, UserJoinedCommunityOrChannel, UserJoinedGroup, UserSuspended, UsernameChanged,
 };
+use user_index_canister::{Event as UserIndexEvent, ReferralCampaignCodesRejected};
 
 #[update_msgpack(guard = "caller_is_user_index_canister")]
 #[trace]
@@ -146,5 +147,25 @@ fn handle_event(event: Event, state: &mut RuntimeState) {
                 .referral_codes
                 .add(ev.referral_type, ev.code, ev.expiry, state.env.now());
         }
+        Event::ReferralCampaignCodesAdded(ev) => {
+            let rejected = state
+                .data
+                .referral_codes
+                .add_campaign_codes(ev.campaign.clone(), ev.codes, ev.start, ev.end);
+
+            if !rejected.is_empty() {
+                error!(
+                    campaign = ev.campaign,
+                    ?rejected,
+                    "Referral campaign codes clash with one-off codes"
+                );
+                state.push_event_to_user_index(UserIndexEvent::ReferralCampaignCodesRejected(Box::new(
+                    ReferralCampaignCodesRejected {
+                        campaign: ev.campaign,
+                        codes: rejected,
+                    },
+                )));
+            }
+        }
     }
 }
@@ -47,6 +47,10 @@ fn prepare(state: &RuntimeState) -> Result<PrepareResult, NotifyLowBalanceRespon
 
 fn commit(user_id: UserId, top_up: CyclesTopUp, state: &mut RuntimeState) {
     state.data.total_cycles_spent_on_canisters += top_up.amount;
+    state
+        .data
+        .canisters_requiring_upgrade
+        .mark_low_cycles_notification(&user_id.into(), top_up.date);
     if !state.data.local_users.mark_cycles_top_up(&user_id, top_up) {
         panic!("User not found. {user_id:?}");
     }
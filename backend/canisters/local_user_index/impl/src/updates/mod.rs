@@ -1,5 +1,7 @@
 pub mod c2c_notify_events;
 pub mod c2c_notify_low_balance;
+pub mod c2c_push_wasm_chunk;
+pub mod c2c_rollback_user_canister_wasm;
 pub mod c2c_upgrade_user_canister_wasm;
 pub mod invite_users_to_channel;
 pub mod invite_users_to_community;
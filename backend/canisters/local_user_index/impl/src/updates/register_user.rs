@@ -91,7 +91,12 @@ fn prepare(args: &Args, state: &mut RuntimeState) -> Result<PrepareOk, Response>
         return Err(AlreadyRegistered);
     }
 
-    if let Err(error) = validate_public_key(caller, &args.public_key, state.data.internet_identity_canister_id) {
+    if let Err(error) = validate_public_key(
+        caller,
+        &args.public_key,
+        state.data.internet_identity_canister_id,
+        state.data.skip_internet_identity_check,
+    ) {
         return Err(PublicKeyInvalid(error));
     }
 
@@ -169,6 +174,7 @@ fn prepare(args: &Args, state: &mut RuntimeState) -> Result<PrepareOk, Response>
         local_user_index_canister_id: state.env.canister_id(),
         notifications_canister_id: state.data.notifications_canister_id,
         proposals_bot_canister_id: state.data.proposals_bot_canister_id,
+        online_users_canister_id: state.data.online_users_canister_id,
         wasm_version: canister_wasm.version,
         username: args.username.clone(),
         display_name: args.display_name.clone(),
@@ -207,10 +213,11 @@ fn commit(
         username: username.clone(),
         display_name: display_name.clone(),
         referred_by: referral_code.as_ref().and_then(|r| r.user()),
+        referral_campaign: referral_code.as_ref().and_then(|r| r.campaign()),
     })));
 
     match referral_code {
-        Some(ReferralCode::User(referred_by)) => {
+        Some(ReferralCode::User(referred_by)) | Some(ReferralCode::Campaign(_, referred_by)) => {
             if state.data.local_users.get(&referred_by).is_some() {
                 state.push_event_to_user(
                     referred_by,
@@ -264,13 +271,22 @@ fn welcome_messages() -> Vec<String> {
     WELCOME_MESSAGES.iter().map(|t| t.to_string()).collect()
 }
 
-fn validate_public_key(caller: Principal, public_key: &[u8], internet_identity_canister_id: CanisterId) -> Result<(), String> {
-    let key_info = SubjectPublicKeyInfo::from_der(public_key).map_err(|e| format!("{e:?}"))?.1;
-    let canister_id_length = key_info.subject_public_key.data[0];
+// On local devnets the Internet Identity check can be skipped, allowing tooling (eg. the devnet
+// seeder) to register users with any self-authenticating key
+fn validate_public_key(
+    caller: Principal,
+    public_key: &[u8],
+    internet_identity_canister_id: CanisterId,
+    skip_internet_identity_check: bool,
+) -> Result<(), String> {
+    if !skip_internet_identity_check {
+        let key_info = SubjectPublicKeyInfo::from_der(public_key).map_err(|e| format!("{e:?}"))?.1;
+        let canister_id_length = key_info.subject_public_key.data[0];
 
-    let canister_id = CanisterId::from_slice(&key_info.subject_public_key.data[1..=(canister_id_length as usize)]);
-    if canister_id != internet_identity_canister_id {
-        return Err("PublicKey is not derived from the InternetIdentity canister".to_string());
+        let canister_id = CanisterId::from_slice(&key_info.subject_public_key.data[1..=(canister_id_length as usize)]);
+        if canister_id != internet_identity_canister_id {
+            return Err("PublicKey is not derived from the InternetIdentity canister".to_string());
+        }
     }
 
     let expected_caller = Principal::self_authenticating(public_key);
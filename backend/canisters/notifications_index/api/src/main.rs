@@ -3,10 +3,14 @@ use candid_gen::generate_candid_method;
 #[allow(deprecated)]
 fn main() {
     generate_candid_method!(notifications_index, subscription_exists, query);
+    generate_candid_method!(notifications_index, upgrade_rollout_status, query);
 
+    generate_candid_method!(notifications_index, commit_wasm_upload, update);
     generate_candid_method!(notifications_index, push_subscription, update);
+    generate_candid_method!(notifications_index, push_wasm_chunk, update);
     generate_candid_method!(notifications_index, remove_subscription, update);
     generate_candid_method!(notifications_index, remove_subscriptions_for_user, update);
+    generate_candid_method!(notifications_index, start_wasm_upload, update);
 
     candid::export_service!();
     std::print!("{}", __export_service());
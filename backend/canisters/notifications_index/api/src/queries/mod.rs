@@ -1 +1,2 @@
 pub mod subscription_exists;
+pub mod upgrade_rollout_status;
@@ -0,0 +1,11 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{Empty, UpgradeRolloutStatus};
+
+pub type Args = Empty;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(UpgradeRolloutStatus),
+    NoRollout,
+}
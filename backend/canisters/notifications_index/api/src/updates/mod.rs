@@ -1,7 +1,11 @@
 pub mod add_notifications_canister;
 pub mod c2c_update_user_principal;
+pub mod commit_wasm_upload;
 pub mod push_subscription;
+pub mod push_wasm_chunk;
 pub mod remove_subscription;
 pub mod remove_subscriptions;
 pub mod remove_subscriptions_for_user;
+pub mod rollback_notifications_canister_wasm;
+pub mod start_wasm_upload;
 pub mod upgrade_notifications_canister_wasm;
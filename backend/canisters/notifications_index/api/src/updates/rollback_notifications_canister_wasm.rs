@@ -0,0 +1,11 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{BuildVersion, RollbackCanisterWasmArgs};
+
+pub type Args = RollbackCanisterWasmArgs;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(BuildVersion),
+    NoPreviousWasm,
+}
@@ -0,0 +1,11 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::StartWasmUploadArgs;
+
+pub type Args = StartWasmUploadArgs;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    WasmTooLarge(u64),
+}
@@ -5,6 +5,10 @@ use notifications_index_canister::*;
 
 // Updates
 generate_update_call!(add_notifications_canister);
+generate_update_call!(commit_wasm_upload);
 generate_update_call!(push_subscription);
+generate_update_call!(push_wasm_chunk);
 generate_update_call!(remove_subscriptions);
+generate_update_call!(rollback_notifications_canister_wasm);
+generate_update_call!(start_wasm_upload);
 generate_update_call!(upgrade_notifications_canister_wasm);
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use types::{BuildVersion, CanisterId, CanisterWasm, Cycles, SubscriptionInfo, TimestampMillis, Timestamped, UserId};
-use utils::canister::CanistersRequiringUpgrade;
+use utils::canister::{CanistersRequiringUpgrade, PreviousWasms, WasmUploads};
 use utils::canister_event_sync_queue::CanisterEventSyncQueue;
 use utils::env::Environment;
 
@@ -118,6 +118,10 @@ struct Data {
     pub subscriptions: Subscriptions,
     pub notifications_canister_wasm_for_new_canisters: CanisterWasm,
     pub notifications_canister_wasm_for_upgrades: CanisterWasm,
+    #[serde(default)]
+    pub previous_notifications_canister_wasms: PreviousWasms,
+    #[serde(default)]
+    pub wasm_uploads: WasmUploads,
     pub canisters_requiring_upgrade: CanistersRequiringUpgrade,
     pub notifications_index_event_sync_queue: CanisterEventSyncQueue<NotificationsIndexEvent>,
     pub test_mode: bool,
@@ -142,6 +146,8 @@ impl Data {
             subscriptions: Subscriptions::default(),
             notifications_canister_wasm_for_new_canisters: notifications_canister_wasm.clone(),
             notifications_canister_wasm_for_upgrades: notifications_canister_wasm,
+            previous_notifications_canister_wasms: PreviousWasms::default(),
+            wasm_uploads: WasmUploads::default(),
             canisters_requiring_upgrade: CanistersRequiringUpgrade::default(),
             notifications_index_event_sync_queue: CanisterEventSyncQueue::default(),
             test_mode,
@@ -1,2 +1,3 @@
 mod http_request;
 mod subscription_exists;
+pub mod upgrade_rollout_status;
@@ -0,0 +1,25 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use notifications_index_canister::commit_wasm_upload::{Response::*, *};
+use tracing::info;
+use utils::canister::CommitUploadResult;
+
+#[update(guard = "caller_is_governance_principal")]
+#[trace]
+fn commit_wasm_upload(args: Args) -> Response {
+    mutate_state(|state| commit_wasm_upload_impl(args, state))
+}
+
+fn commit_wasm_upload_impl(args: Args, state: &mut RuntimeState) -> Response {
+    match state.data.wasm_uploads.commit(&args.sha256) {
+        CommitUploadResult::Success(version) => {
+            info!(%version, "Wasm upload committed");
+            Success(version)
+        }
+        CommitUploadResult::NotFound => UploadNotFound,
+        CommitUploadResult::Incomplete(received) => IncompleteUpload(received),
+        CommitUploadResult::HashMismatch(actual) => HashMismatch(actual),
+    }
+}
@@ -1,8 +1,12 @@
 mod add_notifications_canister;
 mod c2c_update_user_principal;
+mod commit_wasm_upload;
 mod push_subscription;
+mod push_wasm_chunk;
 mod remove_subscription;
 mod remove_subscriptions;
 mod remove_subscriptions_for_user;
+pub mod rollback_notifications_canister_wasm;
+mod start_wasm_upload;
 mod upgrade_notifications_canister_wasm;
 mod wallet_receive;
@@ -12,17 +12,25 @@ fn upgrade_notifications_canister_wasm(args: Args) -> Response {
     mutate_state(|state| upgrade_notifications_canister_wasm_impl(args, state))
 }
 
-fn upgrade_notifications_canister_wasm_impl(args: Args, state: &mut RuntimeState) -> Response {
+fn upgrade_notifications_canister_wasm_impl(mut args: Args, state: &mut RuntimeState) -> Response {
     let version = args.wasm.version;
 
-    if !state.data.test_mode && version < state.data.notifications_canister_wasm_for_new_canisters.version {
+    if !state.data.wasm_uploads.resolve(&mut args) {
+        UploadedWasmNotFound
+    } else if !state.data.test_mode && version < state.data.notifications_canister_wasm_for_new_canisters.version {
         VersionNotHigher
     } else {
+        if let Some(hash) = args.uploaded_wasm_hash {
+            state.data.wasm_uploads.remove(&hash);
+        }
         state.data.canisters_requiring_upgrade.clear();
         if args.use_for_new_canisters.unwrap_or(true) {
             state.data.notifications_canister_wasm_for_new_canisters = args.wasm.clone();
         }
-        state.data.notifications_canister_wasm_for_upgrades = args.wasm;
+        let previous_wasm = std::mem::replace(&mut state.data.notifications_canister_wasm_for_upgrades, args.wasm);
+        if previous_wasm.version != version {
+            state.data.previous_notifications_canister_wasms.push(previous_wasm);
+        }
 
         let filter = args.filter.unwrap_or_default();
         let include: HashSet<_> = filter.include.into_iter().collect();
@@ -40,6 +48,10 @@ fn upgrade_notifications_canister_wasm_impl(args: Args, state: &mut RuntimeState
         {
             state.data.canisters_requiring_upgrade.enqueue(canister_id, false);
         }
+        if let Some(rollout) = args.rollout {
+            let now = state.env.now();
+            state.data.canisters_requiring_upgrade.start_rollout(rollout, version, now);
+        }
 
         crate::jobs::upgrade_canisters::start_job_if_required(state);
 
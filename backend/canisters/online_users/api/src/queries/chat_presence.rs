@@ -0,0 +1,24 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{Chat, Milliseconds, TypingActivity, UserId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub chat: Chat,
+    pub user_ids: Vec<UserId>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(Vec<UserPresence>),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct UserPresence {
+    pub user_id: UserId,
+    // Both of these are None if the user has hidden their last seen status from the caller
+    pub online: Option<bool>,
+    pub duration_since_last_online: Option<Milliseconds>,
+    // Only populated for direct chats, activity in group chats and channels comes from their canisters
+    pub activity: Option<TypingActivity>,
+}
@@ -1 +1,3 @@
+pub mod chat_presence;
 pub mod last_online;
+pub mod typing_indicators;
@@ -0,0 +1,23 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, TypingIndicator};
+
+// Returns the indicators of the users who are typing in a direct chat with the caller
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Only indicators which have changed since this time are returned
+    pub updated_since: Option<TimestampMillis>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    SuccessNoUpdates(TimestampMillis),
+    UserNotFound,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub timestamp: TimestampMillis,
+    pub indicators: Vec<TypingIndicator>,
+}
@@ -1 +1,4 @@
+pub mod c2c_set_blocked_users;
+pub mod c2c_set_last_seen_visibility;
 pub mod mark_as_online;
+pub mod set_typing;
@@ -0,0 +1,20 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{MessageIndex, TypingActivity, UserId};
+
+// Only for direct chats, typing indicators in group chats and channels are set via the group or
+// community canister so that membership can be checked
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub recipient: UserId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    // Set to None once the user stops typing or recording
+    pub activity: Option<TypingActivity>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    UserNotFound,
+    Blocked,
+}
@@ -1,9 +1,13 @@
+use crate::model::blocked_users::BlockedUsers;
 use crate::model::last_online_dates::LastOnlineDates;
+use crate::model::last_seen_settings::LastSeenSettings;
 use crate::model::principal_to_user_id_map::PrincipalToUserIdMap;
+use crate::model::typing_indicators::TypingIndicators;
 use canister_state_macros::canister_state;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
-use types::{BuildVersion, CanisterId, Cycles, TimestampMillis, Timestamped};
+use types::{BuildVersion, CanisterId, Cycles, TimestampMillis, Timestamped, UserId};
+use user_index_canister::c2c_lookup_user;
 use utils::env::Environment;
 
 mod jobs;
@@ -19,6 +23,9 @@ thread_local! {
 
 canister_state!(RuntimeState);
 
+// The most entries accepted from a user canister for each list (eg. contacts or blocked users)
+const MAX_USERS_PER_LIST: usize = 1000;
+
 struct RuntimeState {
     pub env: Box<dyn Environment>,
     pub data: Data,
@@ -29,6 +36,11 @@ impl RuntimeState {
         RuntimeState { env, data }
     }
 
+    // Returns the user id of the caller if they have previously called `mark_as_online`
+    pub fn caller_user_id(&self) -> Option<UserId> {
+        self.data.principal_to_user_id_map.get(&self.env.caller())
+    }
+
     pub fn metrics(&self) -> Metrics {
         Metrics {
             memory_used: utils::memory::used(),
@@ -40,6 +52,7 @@ impl RuntimeState {
             batches_sent_to_user_index: self.data.batches_sent_to_user_index,
             failed_batches: self.data.failed_batches,
             active_users: self.data.cached_active_users.clone(),
+            users_with_restricted_last_seen: self.data.last_seen_settings.restricted_count() as u32,
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 cycles_dispenser: self.data.cycles_dispenser_canister_id,
@@ -48,10 +61,30 @@ impl RuntimeState {
     }
 }
 
+// Checks with the UserIndex that the caller is a user canister, returning its user id if so
+async fn caller_user_canister_id() -> Result<Option<UserId>, String> {
+    let (caller, user_index_canister_id) = read_state(|state| (state.env.caller(), state.data.user_index_canister_id));
+
+    let args = c2c_lookup_user::Args {
+        user_id_or_principal: caller,
+    };
+    match user_index_canister_c2c_client::c2c_lookup_user(user_index_canister_id, &args).await {
+        Ok(c2c_lookup_user::Response::Success(user)) if CanisterId::from(user.user_id) == caller => Ok(Some(user.user_id)),
+        Ok(_) => Ok(None),
+        Err(error) => Err(format!("{error:?}")),
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 struct Data {
     pub last_online_dates: LastOnlineDates,
     pub principal_to_user_id_map: PrincipalToUserIdMap,
+    #[serde(default)]
+    pub last_seen_settings: LastSeenSettings,
+    #[serde(default)]
+    pub blocked_users: BlockedUsers,
+    #[serde(skip)]
+    pub typing_indicators: TypingIndicators,
     pub user_index_canister_id: CanisterId,
     pub cycles_dispenser_canister_id: CanisterId,
     pub mark_as_online_count: u64,
@@ -66,6 +99,9 @@ impl Data {
         Data {
             last_online_dates: LastOnlineDates::default(),
             principal_to_user_id_map: PrincipalToUserIdMap::default(),
+            last_seen_settings: LastSeenSettings::default(),
+            blocked_users: BlockedUsers::default(),
+            typing_indicators: TypingIndicators::default(),
             user_index_canister_id,
             cycles_dispenser_canister_id,
             mark_as_online_count: 0,
@@ -88,6 +124,7 @@ pub struct Metrics {
     pub batches_sent_to_user_index: u64,
     pub failed_batches: u64,
     pub active_users: ActiveUsers,
+    pub users_with_restricted_last_seen: u32,
     pub canister_ids: CanisterIds,
 }
 
@@ -6,6 +6,8 @@ use ic_stable_structures::{
 const UPGRADES: MemoryId = MemoryId::new(0);
 const PRINCIPAL_TO_USER_ID_MAP: MemoryId = MemoryId::new(1);
 const LAST_ONLINE_DATES: MemoryId = MemoryId::new(2);
+const LOG_OVERFLOW_INDEX: MemoryId = MemoryId::new(3);
+const LOG_OVERFLOW_DATA: MemoryId = MemoryId::new(4);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -26,6 +28,14 @@ pub fn get_last_online_dates_memory() -> Memory {
     get_memory(LAST_ONLINE_DATES)
 }
 
+pub fn get_log_overflow_index_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_INDEX)
+}
+
+pub fn get_log_overflow_data_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_DATA)
+}
+
 fn get_memory(id: MemoryId) -> Memory {
     MEMORY_MANAGER.with(|m| m.get(id))
 }
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use types::UserId;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct BlockedUsers {
+    // Users who haven't blocked anyone have no entry
+    users: HashMap<UserId, HashSet<UserId>>,
+}
+
+impl BlockedUsers {
+    pub fn set(&mut self, user_id: UserId, blocked_users: HashSet<UserId>) {
+        if blocked_users.is_empty() {
+            self.users.remove(&user_id);
+        } else {
+            self.users.insert(user_id, blocked_users);
+        }
+    }
+
+    pub fn is_blocked(&self, user_id: UserId, by: UserId) -> bool {
+        self.users.get(&by).map_or(false, |b| b.contains(&user_id))
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use types::{LastSeenVisibility, UserId};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct LastSeenSettings {
+    // Users who haven't restricted their last seen visibility have no entry
+    users: HashMap<UserId, UserLastSeenSettings>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct UserLastSeenSettings {
+    visibility: LastSeenVisibility,
+    contacts: HashSet<UserId>,
+}
+
+impl LastSeenSettings {
+    pub fn set(&mut self, user_id: UserId, visibility: LastSeenVisibility, contacts: Vec<UserId>) {
+        if visibility == LastSeenVisibility::Everyone {
+            self.users.remove(&user_id);
+        } else {
+            let contacts = if visibility == LastSeenVisibility::Contacts {
+                contacts.into_iter().collect()
+            } else {
+                HashSet::new()
+            };
+            self.users.insert(user_id, UserLastSeenSettings { visibility, contacts });
+        }
+    }
+
+    pub fn is_visible_to(&self, user_id: UserId, viewer: Option<UserId>) -> bool {
+        if viewer == Some(user_id) {
+            return true;
+        }
+
+        match self.users.get(&user_id) {
+            None => true,
+            Some(settings) => match settings.visibility {
+                LastSeenVisibility::Everyone => true,
+                LastSeenVisibility::Contacts => viewer.map_or(false, |v| settings.contacts.contains(&v)),
+                LastSeenVisibility::Nobody => false,
+            },
+        }
+    }
+
+    pub fn restricted_count(&self) -> usize {
+        self.users.len()
+    }
+}
@@ -1,2 +1,5 @@
+pub mod blocked_users;
 pub mod last_online_dates;
+pub mod last_seen_settings;
 pub mod principal_to_user_id_map;
+pub mod typing_indicators;
@@ -0,0 +1,44 @@
+use std::collections::{HashMap, HashSet};
+use types::{Milliseconds, TimestampMillis, UserId};
+use utils::time::MINUTE_IN_MS;
+
+const PRUNE_INTERVAL: Milliseconds = MINUTE_IN_MS;
+
+// The indicators of users typing in direct chats, keyed by the recipient so that only they can see
+// them. Held in heap memory only, see `typing_indicators::TypingIndicators`.
+#[derive(Default)]
+pub struct TypingIndicators {
+    recipients: HashMap<UserId, typing_indicators::TypingIndicators>,
+    last_pruned: TimestampMillis,
+}
+
+impl TypingIndicators {
+    pub fn get(&self, recipient: &UserId) -> Option<&typing_indicators::TypingIndicators> {
+        self.recipients.get(recipient)
+    }
+
+    pub fn get_mut(&mut self, recipient: UserId, now: TimestampMillis) -> &mut typing_indicators::TypingIndicators {
+        if now.saturating_sub(self.last_pruned) > PRUNE_INTERVAL {
+            self.prune(now);
+        }
+        self.recipients.entry(recipient).or_default()
+    }
+
+    pub fn remove_users(&mut self, recipient: &UserId, user_ids: &HashSet<UserId>, now: TimestampMillis) {
+        if let Some(indicators) = self.recipients.get_mut(recipient) {
+            for user_id in user_ids {
+                indicators.remove_user(*user_id, now);
+            }
+        }
+    }
+
+    fn prune(&mut self, now: TimestampMillis) {
+        for indicators in self.recipients.values_mut() {
+            indicators.prune(now);
+        }
+        // Keep recently updated chats so that pollers are told the indicators have been removed
+        self.recipients
+            .retain(|_, i| !i.is_empty() || now.saturating_sub(i.last_updated()) < PRUNE_INTERVAL);
+        self.last_pruned = now;
+    }
+}
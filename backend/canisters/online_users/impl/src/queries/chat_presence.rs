@@ -0,0 +1,45 @@
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use online_users_canister::chat_presence::{Response::*, *};
+use types::{Chat, Milliseconds};
+use utils::time::MINUTE_IN_MS;
+
+const ONLINE_THRESHOLD: Milliseconds = 5 * MINUTE_IN_MS;
+
+#[query]
+fn chat_presence(args: Args) -> Response {
+    read_state(|state| chat_presence_impl(args, state))
+}
+
+fn chat_presence_impl(args: Args, state: &RuntimeState) -> Response {
+    let now = state.env.now();
+    let caller = state.caller_user_id();
+
+    // Indicators in direct chats are keyed by the recipient. Those in group chats and channels are
+    // held by the group and community canisters.
+    let indicators = match args.chat {
+        Chat::Direct(_) => caller.and_then(|c| state.data.typing_indicators.get(&c)),
+        _ => None,
+    };
+
+    let result = args
+        .user_ids
+        .into_iter()
+        .map(|user_id| {
+            let duration_since_last_online = if state.data.last_seen_settings.is_visible_to(user_id, caller) {
+                state.data.last_online_dates.get(user_id).map(|ts| now.saturating_sub(ts))
+            } else {
+                None
+            };
+
+            UserPresence {
+                user_id,
+                online: duration_since_last_online.map(|d| d < ONLINE_THRESHOLD),
+                duration_since_last_online,
+                activity: indicators.and_then(|i| i.activity(user_id, now)),
+            }
+        })
+        .collect();
+
+    Success(result)
+}
@@ -11,10 +11,12 @@ fn last_online(args: Args) -> Response {
 
 fn last_online_impl(args: Args, state: &RuntimeState) -> Response {
     let now = state.env.now();
+    let caller = state.caller_user_id();
 
     let result = args
         .user_ids
         .into_iter()
+        .filter(|u| state.data.last_seen_settings.is_visible_to(*u, caller))
         .filter_map(|u| {
             state.data.last_online_dates.get(u).map(|ts| UserLastOnline {
                 user_id: u,
@@ -1,2 +1,4 @@
+mod chat_presence;
 mod http_request;
 mod last_online;
+mod typing_indicators;
@@ -0,0 +1,29 @@
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use online_users_canister::typing_indicators::{Response::*, *};
+
+#[query]
+fn typing_indicators(args: Args) -> Response {
+    read_state(|state| typing_indicators_impl(args, state))
+}
+
+fn typing_indicators_impl(args: Args, state: &RuntimeState) -> Response {
+    let now = state.env.now();
+    let Some(user_id) = state.caller_user_id() else {
+        return UserNotFound;
+    };
+
+    let indicators = match state.data.typing_indicators.get(&user_id) {
+        Some(i) => i.get(args.updated_since, now),
+        None if args.updated_since.is_some() => None,
+        None => Some(Vec::new()),
+    };
+
+    match indicators {
+        Some(indicators) => Success(SuccessResult {
+            timestamp: now,
+            indicators,
+        }),
+        None => SuccessNoUpdates(now),
+    }
+}
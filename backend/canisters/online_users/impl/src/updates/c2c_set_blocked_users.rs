@@ -0,0 +1,26 @@
+use crate::{caller_user_canister_id, mutate_state, MAX_USERS_PER_LIST};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use online_users_canister::c2c_set_blocked_users::{Response::*, *};
+use std::collections::HashSet;
+
+// Called by user canisters so that users can't show typing indicators to users who have blocked them
+#[update_msgpack]
+#[trace]
+async fn c2c_set_blocked_users(args: Args) -> Response {
+    let user_id = match caller_user_canister_id().await {
+        Ok(Some(u)) => u,
+        Ok(None) => return NotAuthorized,
+        Err(error) => return InternalError(error),
+    };
+
+    let blocked_users: HashSet<_> = args.blocked_users.into_iter().take(MAX_USERS_PER_LIST).collect();
+
+    mutate_state(|state| {
+        // Hide any indicators which newly blocked users are currently showing to the user
+        let now = state.env.now();
+        state.data.typing_indicators.remove_users(&user_id, &blocked_users, now);
+        state.data.blocked_users.set(user_id, blocked_users);
+    });
+    Success
+}
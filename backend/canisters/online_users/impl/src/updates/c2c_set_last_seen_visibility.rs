@@ -0,0 +1,21 @@
+use crate::{caller_user_canister_id, mutate_state, MAX_USERS_PER_LIST};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use online_users_canister::c2c_set_last_seen_visibility::{Response::*, *};
+
+// Called by user canisters, the caller is checked with the UserIndex since only user canisters may
+// set the visibility for their user
+#[update_msgpack]
+#[trace]
+async fn c2c_set_last_seen_visibility(args: Args) -> Response {
+    let user_id = match caller_user_canister_id().await {
+        Ok(Some(u)) => u,
+        Ok(None) => return NotAuthorized,
+        Err(error) => return InternalError(error),
+    };
+
+    let contacts = args.contacts.into_iter().take(MAX_USERS_PER_LIST).collect();
+
+    mutate_state(|state| state.data.last_seen_settings.set(user_id, args.visibility, contacts));
+    Success
+}
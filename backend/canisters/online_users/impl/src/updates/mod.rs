@@ -1,2 +1,5 @@
+mod c2c_set_blocked_users;
+mod c2c_set_last_seen_visibility;
 mod mark_as_online;
+mod set_typing;
 mod wallet_receive;
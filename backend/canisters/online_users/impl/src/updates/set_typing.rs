@@ -0,0 +1,30 @@
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use online_users_canister::set_typing::{Response::*, *};
+
+#[update]
+#[trace]
+fn set_typing(args: Args) -> Response {
+    mutate_state(|state| set_typing_impl(args, state))
+}
+
+fn set_typing_impl(args: Args, state: &mut RuntimeState) -> Response {
+    // The caller is resolved locally since clients always call `mark_as_online` before they can be typing
+    if let Some(user_id) = state.caller_user_id() {
+        if state.data.blocked_users.is_blocked(user_id, args.recipient) {
+            return Blocked;
+        }
+
+        let now = state.env.now();
+        state.data.typing_indicators.get_mut(args.recipient, now).set(
+            user_id,
+            args.thread_root_message_index,
+            args.activity,
+            now,
+        );
+        Success
+    } else {
+        UserNotFound
+    }
+}
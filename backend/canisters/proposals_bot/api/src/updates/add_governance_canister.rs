@@ -10,6 +10,11 @@ pub struct Args {
     pub name: String,
     pub description: Option<String>,
     pub avatar: Option<Document>,
+    // Set for governance canisters which are neither the NNS nor an SNS but which implement the
+    // generic governance interface. Only set this once the group and community canisters have been
+    // upgraded to a version which understands generic proposals, otherwise pushing them will fail.
+    #[serde(default)]
+    pub is_generic: Option<bool>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -26,6 +31,7 @@ pub struct HumanReadableArgs {
     name: String,
     description: Option<String>,
     avatar: Option<String>,
+    is_generic: Option<bool>,
 }
 
 impl ToHumanReadable for Args {
@@ -38,6 +44,7 @@ impl ToHumanReadable for Args {
             name: self.name.clone(),
             description: self.description.clone(),
             avatar: self.avatar.as_ref().map(|a| format!("{a:?}")),
+            is_generic: self.is_generic,
         }
     }
 }
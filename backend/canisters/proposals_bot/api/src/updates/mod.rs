@@ -3,4 +3,5 @@ pub mod appoint_admins;
 pub mod c2c_submit_proposal;
 pub mod import_proposals_group_into_community;
 pub mod remove_governance_canister;
+pub mod set_proposal_reminders;
 pub mod stake_neuron_for_submitting_proposals;
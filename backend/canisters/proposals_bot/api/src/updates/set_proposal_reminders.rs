@@ -0,0 +1,35 @@
+use candid::CandidType;
+use human_readable::{HumanReadablePrincipal, ToHumanReadable};
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, Milliseconds};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub governance_canister_id: CanisterId,
+    // How long before each proposal's deadline to post a reminder into its thread
+    pub reminders: Vec<Milliseconds>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NotFound,
+    TooManyReminders(u32),
+}
+
+#[derive(Serialize)]
+pub struct HumanReadableArgs {
+    governance_canister_id: HumanReadablePrincipal,
+    reminders: Vec<Milliseconds>,
+}
+
+impl ToHumanReadable for Args {
+    type Target = HumanReadableArgs;
+
+    fn to_human_readable(&self) -> Self::Target {
+        HumanReadableArgs {
+            governance_canister_id: self.governance_canister_id.into(),
+            reminders: self.reminders.clone(),
+        }
+    }
+}
@@ -5,11 +5,16 @@ use types::{Proposal, ProposalId, Tally};
 
 pub const REWARD_STATUS_ACCEPT_VOTES: i32 = 1;
 pub const REWARD_STATUS_READY_TO_SETTLE: i32 = 2;
+pub const BATCH_SIZE_LIMIT: u32 = 50;
 
 pub trait RawProposal: TryInto<Proposal, Error = &'static str> {
     fn id(&self) -> ProposalId;
 }
 
+pub fn into_proposals<R: RawProposal>(raw_proposals: Vec<R>) -> Vec<Proposal> {
+    raw_proposals.into_iter().filter_map(|p| p.try_into().ok()).collect()
+}
+
 impl RawProposal for ProposalData {
     fn id(&self) -> ProposalId {
         self.id.as_ref().map_or(ProposalId::default(), |p| p.id)
@@ -0,0 +1,94 @@
+use super::common::BATCH_SIZE_LIMIT;
+use super::GovernanceSource;
+use async_trait::async_trait;
+use generic_governance_canister::types::GovernanceProposal;
+use ic_cdk::api::call::CallResult;
+use types::{
+    CanisterId, GenericProposal, Proposal, ProposalDecisionStatus, ProposalId, ProposalRewardStatus, ProposalStatusUpdate,
+    TimestampMillis,
+};
+
+// A governance canister which implements the generic governance interface defined in
+// `external_canisters/generic_governance`, allowing DAO frameworks other than the NNS and SNS to
+// have their proposals pushed into chats
+pub struct GenericGovernance(pub CanisterId);
+
+#[async_trait(?Send)]
+impl GovernanceSource for GenericGovernance {
+    async fn list_active_proposals(&self) -> CallResult<Vec<Proposal>> {
+        let now = utils::time::now_millis();
+        let mut proposals: Vec<GovernanceProposal> = Vec::new();
+
+        loop {
+            let list_proposals_args = generic_governance_canister::list_proposals::Args {
+                limit: BATCH_SIZE_LIMIT,
+                before_proposal: proposals.iter().next_back().map(|p| p.id),
+                active_only: true,
+            };
+
+            let response = generic_governance_canister_c2c_client::list_proposals(self.0, &list_proposals_args)
+                .await?
+                .proposals;
+
+            let finished = response.len() < BATCH_SIZE_LIMIT as usize;
+            proposals.extend(response);
+
+            if finished {
+                break;
+            }
+        }
+
+        Ok(proposals.into_iter().map(|p| Proposal::Generic(convert(p, now))).collect())
+    }
+
+    async fn get_proposal_status(
+        &self,
+        proposal_id: ProposalId,
+        now: TimestampMillis,
+    ) -> CallResult<Option<ProposalStatusUpdate>> {
+        let response = generic_governance_canister_c2c_client::get_proposal(
+            self.0,
+            &generic_governance_canister::get_proposal::Args { proposal_id },
+        )
+        .await?;
+
+        if let generic_governance_canister::get_proposal::Response::Success(p) = response {
+            let proposal = convert(p, now);
+            Ok(Some(ProposalStatusUpdate {
+                status: Some(proposal.status),
+                reward_status: Some(proposal.reward_status),
+                latest_tally: Some(proposal.tally),
+                deadline: None,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn convert(proposal: GovernanceProposal, now: TimestampMillis) -> GenericProposal {
+    let status: ProposalDecisionStatus = proposal.status.into();
+
+    // Generic governance canisters have no concept of voting rewards, so proposals are considered
+    // settled as soon as they stop accepting votes
+    let reward_status = if status == ProposalDecisionStatus::Open && now < proposal.deadline {
+        ProposalRewardStatus::AcceptVotes
+    } else {
+        ProposalRewardStatus::Settled
+    };
+
+    GenericProposal {
+        id: proposal.id,
+        proposer: proposal.proposer,
+        created: proposal.created,
+        title: proposal.title,
+        summary: proposal.summary,
+        url: proposal.url,
+        status,
+        reward_status,
+        tally: proposal.tally.into(),
+        deadline: proposal.deadline,
+        payload_text_rendering: proposal.payload_text_rendering,
+        last_updated: now,
+    }
+}
@@ -1,2 +1,37 @@
+use async_trait::async_trait;
+use ic_cdk::api::call::CallResult;
+use types::{CanisterId, Proposal, ProposalId, ProposalStatusUpdate, TimestampMillis};
+
 pub mod common;
+pub mod generic;
 pub mod nns;
+pub mod sns;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GovernanceType {
+    Nns,
+    Sns,
+    Generic,
+}
+
+// Implemented for each type of governance canister whose proposals can be pushed into chats
+#[async_trait(?Send)]
+pub trait GovernanceSource {
+    // Returns all proposals which are either accepting votes or are waiting for rewards to be settled
+    async fn list_active_proposals(&self) -> CallResult<Vec<Proposal>>;
+
+    // Returns the latest status and tally of a proposal which is no longer active
+    async fn get_proposal_status(
+        &self,
+        proposal_id: ProposalId,
+        now: TimestampMillis,
+    ) -> CallResult<Option<ProposalStatusUpdate>>;
+}
+
+pub fn governance_source(governance_type: GovernanceType, governance_canister_id: CanisterId) -> Box<dyn GovernanceSource> {
+    match governance_type {
+        GovernanceType::Nns => Box::new(nns::NnsGovernance(governance_canister_id)),
+        GovernanceType::Sns => Box::new(sns::SnsGovernance(governance_canister_id)),
+        GovernanceType::Generic => Box::new(generic::GenericGovernance(governance_canister_id)),
+    }
+}
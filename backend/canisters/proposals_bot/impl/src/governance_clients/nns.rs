@@ -1,15 +1,75 @@
 use self::governance_response_types::{ListProposalInfoResponse, ProposalInfo};
-use super::common::{RawProposal, RawTally, WrappedProposalId};
+use super::common::{
+    into_proposals, RawProposal, RawTally, WrappedProposalId, BATCH_SIZE_LIMIT, REWARD_STATUS_ACCEPT_VOTES,
+    REWARD_STATUS_READY_TO_SETTLE,
+};
+use super::GovernanceSource;
+use async_trait::async_trait;
 use candid::CandidType;
 use ic_cdk::api::call::CallResult;
 use serde::Deserialize;
 use tracing::error;
-use types::{CanisterId, NnsNeuronId, ProposalId};
+use types::{
+    CanisterId, NnsNeuronId, Proposal, ProposalDecisionStatus, ProposalId, ProposalRewardStatus, ProposalStatusUpdate,
+    TimestampMillis,
+};
 
 pub const TOPIC_NEURON_MANAGEMENT: i32 = 1;
 pub const TOPIC_EXCHANGE_RATE: i32 = 2;
 
-pub async fn list_proposals(governance_canister_id: CanisterId, args: &ListProposalInfo) -> CallResult<Vec<ProposalInfo>> {
+pub struct NnsGovernance(pub CanisterId);
+
+#[async_trait(?Send)]
+impl GovernanceSource for NnsGovernance {
+    async fn list_active_proposals(&self) -> CallResult<Vec<Proposal>> {
+        let mut proposals: Vec<ProposalInfo> = Vec::new();
+
+        loop {
+            let list_proposals_args = ListProposalInfo {
+                limit: BATCH_SIZE_LIMIT,
+                before_proposal: proposals.iter().next_back().and_then(|p| p.id.clone()),
+                exclude_topic: vec![TOPIC_NEURON_MANAGEMENT, TOPIC_EXCHANGE_RATE],
+                include_reward_status: vec![REWARD_STATUS_ACCEPT_VOTES, REWARD_STATUS_READY_TO_SETTLE],
+                ..Default::default()
+            };
+
+            let response = list_proposals(self.0, &list_proposals_args).await?;
+            let finished = response.len() < BATCH_SIZE_LIMIT as usize;
+            proposals.extend(response);
+
+            if finished {
+                break;
+            }
+        }
+
+        Ok(into_proposals(proposals))
+    }
+
+    async fn get_proposal_status(
+        &self,
+        proposal_id: ProposalId,
+        _now: TimestampMillis,
+    ) -> CallResult<Option<ProposalStatusUpdate>> {
+        let response = list_proposals(
+            self.0,
+            &ListProposalInfo {
+                limit: 1,
+                before_proposal: Some(WrappedProposalId { id: proposal_id + 1 }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(response.into_iter().next().map(|p| ProposalStatusUpdate {
+            status: ProposalDecisionStatus::try_from(p.status).ok(),
+            reward_status: ProposalRewardStatus::try_from(p.reward_status).ok(),
+            latest_tally: p.latest_tally.map(|t| t.into()),
+            deadline: None,
+        }))
+    }
+}
+
+async fn list_proposals(governance_canister_id: CanisterId, args: &ListProposalInfo) -> CallResult<Vec<ProposalInfo>> {
     let method_name = "list_proposals";
     let response: CallResult<(ListProposalInfoResponse,)> =
         ic_cdk::api::call::call(governance_canister_id, method_name, (args,)).await;
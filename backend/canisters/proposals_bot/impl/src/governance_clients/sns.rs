@@ -0,0 +1,61 @@
+use super::common::{into_proposals, BATCH_SIZE_LIMIT, REWARD_STATUS_ACCEPT_VOTES, REWARD_STATUS_READY_TO_SETTLE};
+use super::GovernanceSource;
+use async_trait::async_trait;
+use ic_cdk::api::call::CallResult;
+use sns_governance_canister::types::{ListProposals, ProposalData};
+use types::{CanisterId, Proposal, ProposalId, ProposalStatusUpdate, TimestampMillis};
+
+pub struct SnsGovernance(pub CanisterId);
+
+#[async_trait(?Send)]
+impl GovernanceSource for SnsGovernance {
+    async fn list_active_proposals(&self) -> CallResult<Vec<Proposal>> {
+        let mut proposals: Vec<ProposalData> = Vec::new();
+
+        loop {
+            let list_proposals_args = ListProposals {
+                limit: BATCH_SIZE_LIMIT,
+                before_proposal: proposals.iter().next_back().and_then(|p| p.id),
+                include_reward_status: vec![REWARD_STATUS_ACCEPT_VOTES, REWARD_STATUS_READY_TO_SETTLE],
+                ..Default::default()
+            };
+
+            let response = sns_governance_canister_c2c_client::list_proposals(self.0, &list_proposals_args)
+                .await?
+                .proposals;
+
+            let finished = response.len() < BATCH_SIZE_LIMIT as usize;
+            proposals.extend(response);
+
+            if finished {
+                break;
+            }
+        }
+
+        Ok(into_proposals(proposals))
+    }
+
+    async fn get_proposal_status(
+        &self,
+        proposal_id: ProposalId,
+        now: TimestampMillis,
+    ) -> CallResult<Option<ProposalStatusUpdate>> {
+        let response = sns_governance_canister_c2c_client::list_proposals(
+            self.0,
+            &ListProposals {
+                limit: 1,
+                before_proposal: Some(sns_governance_canister::types::ProposalId { id: proposal_id + 1 }),
+                ..Default::default()
+            },
+        )
+        .await?
+        .proposals;
+
+        Ok(response.into_iter().next().map(|p| ProposalStatusUpdate {
+            status: Some(p.status()),
+            reward_status: Some(p.reward_status(now)),
+            latest_tally: p.latest_tally.map(|t| t.into()),
+            deadline: None,
+        }))
+    }
+}
@@ -1,13 +1,10 @@
-use crate::governance_clients::common::WrappedProposalId;
-use crate::governance_clients::nns::ListProposalInfo;
-use crate::{generate_message_id, governance_clients, mutate_state, RuntimeState};
-use ic_cdk::api::call::CallResult;
+use crate::governance_clients::{governance_source, GovernanceType};
+use crate::{generate_message_id, mutate_state, RuntimeState};
 use ic_cdk_timers::TimerId;
-use sns_governance_canister::types::ListProposals;
 use std::cell::Cell;
 use std::time::Duration;
 use tracing::trace;
-use types::{CanisterId, ProposalDecisionStatus, ProposalId, ProposalRewardStatus, ProposalUpdate, TimestampMillis};
+use types::{CanisterId, ProposalId, ProposalUpdate, TimestampMillis};
 
 thread_local! {
     static TIMER_ID: Cell<Option<TimerId>> = Cell::default();
@@ -31,10 +28,10 @@ fn run() {
 fn run_impl(state: &mut RuntimeState) {
     if let Some((governance_canister_id, proposal_id)) = state.data.finished_proposals_to_process.pop_front() {
         if state.data.nervous_systems.exists(&governance_canister_id) {
-            let is_nns = governance_canister_id == state.data.nns_governance_canister_id;
+            let governance_type = state.governance_type(&governance_canister_id);
             let now = state.env.now();
 
-            ic_cdk::spawn(process_proposal(governance_canister_id, proposal_id, is_nns, now));
+            ic_cdk::spawn(process_proposal(governance_canister_id, proposal_id, governance_type, now));
         }
     } else if let Some(timer_id) = TIMER_ID.with(|t| t.take()) {
         ic_cdk_timers::clear_timer(timer_id);
@@ -42,19 +39,28 @@ fn run_impl(state: &mut RuntimeState) {
     }
 }
 
-async fn process_proposal(governance_canister_id: CanisterId, proposal_id: ProposalId, is_nns: bool, now: TimestampMillis) {
-    let response = if is_nns {
-        get_nns_proposal(governance_canister_id, proposal_id).await
-    } else {
-        get_sns_proposal(governance_canister_id, proposal_id, now).await
-    };
+async fn process_proposal(
+    governance_canister_id: CanisterId,
+    proposal_id: ProposalId,
+    governance_type: GovernanceType,
+    now: TimestampMillis,
+) {
+    let response = governance_source(governance_type, governance_canister_id)
+        .get_proposal_status(proposal_id, now)
+        .await;
 
     match response {
-        Ok(Some(proposal)) => mutate_state(|state| {
-            state
-                .data
-                .nervous_systems
-                .queue_proposal_to_update(governance_canister_id, proposal)
+        Ok(Some(update)) => mutate_state(|state| {
+            state.data.nervous_systems.queue_proposal_to_update(
+                governance_canister_id,
+                ProposalUpdate {
+                    message_id: generate_message_id(governance_canister_id, proposal_id),
+                    status: update.status,
+                    reward_status: update.reward_status,
+                    latest_tally: update.latest_tally,
+                    deadline: update.deadline,
+                },
+            )
         }),
         Ok(None) => {}
         Err(_) => {
@@ -67,48 +73,3 @@ async fn process_proposal(governance_canister_id: CanisterId, proposal_id: Propo
         }
     }
 }
-
-async fn get_nns_proposal(governance_canister_id: CanisterId, proposal_id: ProposalId) -> CallResult<Option<ProposalUpdate>> {
-    let response = governance_clients::nns::list_proposals(
-        governance_canister_id,
-        &ListProposalInfo {
-            limit: 1,
-            before_proposal: Some(WrappedProposalId { id: proposal_id + 1 }),
-            ..Default::default()
-        },
-    )
-    .await?;
-
-    Ok(response.into_iter().next().map(|p| ProposalUpdate {
-        message_id: generate_message_id(governance_canister_id, proposal_id),
-        status: ProposalDecisionStatus::try_from(p.status).ok(),
-        reward_status: ProposalRewardStatus::try_from(p.reward_status).ok(),
-        latest_tally: p.latest_tally.map(|t| t.into()),
-        deadline: None,
-    }))
-}
-
-async fn get_sns_proposal(
-    governance_canister_id: CanisterId,
-    proposal_id: ProposalId,
-    now: TimestampMillis,
-) -> CallResult<Option<ProposalUpdate>> {
-    let response = sns_governance_canister_c2c_client::list_proposals(
-        governance_canister_id,
-        &ListProposals {
-            limit: 1,
-            before_proposal: Some(sns_governance_canister::types::ProposalId { id: proposal_id + 1 }),
-            ..Default::default()
-        },
-    )
-    .await?
-    .proposals;
-
-    Ok(response.into_iter().next().map(|p| ProposalUpdate {
-        message_id: generate_message_id(governance_canister_id, proposal_id),
-        status: Some(p.status()),
-        reward_status: Some(p.reward_status(now)),
-        latest_tally: p.latest_tally.map(|t| t.into()),
-        deadline: None,
-    }))
-}
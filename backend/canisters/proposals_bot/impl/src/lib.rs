@@ -1,3 +1,4 @@
+use crate::governance_clients::GovernanceType;
 use crate::model::nervous_systems::NervousSystems;
 use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
@@ -15,6 +16,7 @@ mod jobs;
 mod lifecycle;
 mod memory;
 mod model;
+mod proposal_threads;
 mod queries;
 mod updates;
 
@@ -39,6 +41,16 @@ impl RuntimeState {
         self.data.governance_principals.contains(&caller)
     }
 
+    pub fn governance_type(&self, governance_canister_id: &CanisterId) -> GovernanceType {
+        if *governance_canister_id == self.data.nns_governance_canister_id {
+            GovernanceType::Nns
+        } else if self.data.nervous_systems.is_generic(governance_canister_id) {
+            GovernanceType::Generic
+        } else {
+            GovernanceType::Sns
+        }
+    }
+
     pub fn metrics(&self) -> Metrics {
         Metrics {
             memory_used: utils::memory::used(),
@@ -129,6 +141,7 @@ pub struct NervousSystemMetrics {
     pub queued_proposals: Vec<ProposalId>,
     pub active_proposals: Vec<ProposalId>,
     pub neuron_for_submitting_proposals: Option<String>,
+    pub is_generic: bool,
 }
 
 #[derive(Serialize, Debug)]
@@ -151,3 +164,16 @@ fn generate_message_id(governance_canister_id: CanisterId, proposal_id: Proposal
     let array16: [u8; 16] = array32[..16].try_into().unwrap();
     u128::from_ne_bytes(array16).into()
 }
+
+// Each message posted into a proposal's thread is derived from the proposal plus a discriminator
+// so that retries after failed calls never result in duplicate messages
+fn generate_thread_message_id(governance_canister_id: CanisterId, proposal_id: ProposalId, discriminator: u64) -> MessageId {
+    let mut hash = Sha256::new();
+    hash.update(b"proposals_bot_thread");
+    hash.update(governance_canister_id.as_slice());
+    hash.update(proposal_id.to_ne_bytes());
+    hash.update(discriminator.to_ne_bytes());
+    let array32: [u8; 32] = hash.finalize().try_into().unwrap();
+    let array16: [u8; 16] = array32[..16].try_into().unwrap();
+    u128::from_ne_bytes(array16).into()
+}
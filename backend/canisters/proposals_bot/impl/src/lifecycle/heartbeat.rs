@@ -1,115 +1,48 @@
-use crate::generate_message_id;
-use crate::governance_clients;
-use crate::governance_clients::common::{RawProposal, REWARD_STATUS_ACCEPT_VOTES, REWARD_STATUS_READY_TO_SETTLE};
-use crate::governance_clients::nns::governance_response_types::ProposalInfo;
-use crate::governance_clients::nns::{ListProposalInfo, TOPIC_EXCHANGE_RATE, TOPIC_NEURON_MANAGEMENT};
-use crate::model::nervous_systems::{ProposalToPush, ProposalsToUpdate};
-use crate::{mutate_state, RuntimeState};
+use crate::governance_clients::{governance_source, GovernanceType};
+use crate::model::nervous_systems::{ProposalToPush, ProposalsToUpdate, ThreadMessage, ThreadMessageKind, ThreadMessageToSend};
+use crate::{generate_message_id, generate_thread_message_id, mutate_state, proposal_threads, read_state, RuntimeState};
 use ic_cdk::api::call::{CallResult, RejectionCode};
 use ic_cdk_macros::heartbeat;
 use std::collections::HashSet;
 use types::{
-    CanisterId, ChannelId, ChatId, CommunityId, MessageContentInitial, MessageId, MultiUserChat, Proposal, ProposalContent,
-    ProposalUpdate,
+    CanisterId, ChannelId, ChatId, CommunityId, MessageContentInitial, MessageId, MessageIndex, MultiUserChat, Proposal,
+    ProposalContent, ProposalUpdate, TextContent,
 };
 
 #[heartbeat]
 fn heartbeat() {
     retrieve_proposals::run();
     push_proposals::run();
+    push_thread_messages::run();
     update_proposals::run();
 }
 
 mod retrieve_proposals {
     use super::*;
-    use sns_governance_canister::types::ProposalData;
-
-    const BATCH_SIZE_LIMIT: u32 = 50;
 
     pub fn run() {
-        if let Some((governance_canister_id, is_nns)) = mutate_state(get_next) {
-            if is_nns {
-                ic_cdk::spawn(get_and_process_nns_proposals(governance_canister_id));
-            } else {
-                ic_cdk::spawn(get_and_process_sns_proposals(governance_canister_id));
-            }
+        if let Some((governance_canister_id, governance_type)) = mutate_state(get_next) {
+            ic_cdk::spawn(get_and_process_proposals(governance_canister_id, governance_type));
         }
     }
 
-    fn get_next(state: &mut RuntimeState) -> Option<(CanisterId, bool)> {
+    fn get_next(state: &mut RuntimeState) -> Option<(CanisterId, GovernanceType)> {
         let now = state.env.now();
         let governance_canister_id = state.data.nervous_systems.start_next_sync(now)?;
-        let is_nns = governance_canister_id == state.data.nns_governance_canister_id;
-        Some((governance_canister_id, is_nns))
-    }
-
-    async fn get_and_process_nns_proposals(governance_canister_id: CanisterId) {
-        let response = get_nns_proposals(governance_canister_id).await;
-
-        handle_proposals_response(&governance_canister_id, response);
-    }
-
-    async fn get_nns_proposals(governance_canister_id: CanisterId) -> CallResult<Vec<ProposalInfo>> {
-        let mut proposals: Vec<ProposalInfo> = Vec::new();
-
-        loop {
-            let list_proposals_args = ListProposalInfo {
-                limit: BATCH_SIZE_LIMIT,
-                before_proposal: proposals.iter().next_back().and_then(|p| p.id.clone()),
-                exclude_topic: vec![TOPIC_NEURON_MANAGEMENT, TOPIC_EXCHANGE_RATE],
-                include_reward_status: vec![REWARD_STATUS_ACCEPT_VOTES, REWARD_STATUS_READY_TO_SETTLE],
-                ..Default::default()
-            };
-
-            let response = governance_clients::nns::list_proposals(governance_canister_id, &list_proposals_args).await?;
-            let finished = response.len() < BATCH_SIZE_LIMIT as usize;
-            proposals.extend(response);
-
-            if finished {
-                break;
-            }
-        }
-
-        Ok(proposals)
+        Some((governance_canister_id, state.governance_type(&governance_canister_id)))
     }
 
-    async fn get_and_process_sns_proposals(governance_canister_id: CanisterId) {
-        let response = get_sns_proposals(governance_canister_id).await;
+    async fn get_and_process_proposals(governance_canister_id: CanisterId, governance_type: GovernanceType) {
+        let response = governance_source(governance_type, governance_canister_id)
+            .list_active_proposals()
+            .await;
 
         handle_proposals_response(&governance_canister_id, response);
     }
 
-    async fn get_sns_proposals(governance_canister_id: CanisterId) -> CallResult<Vec<ProposalData>> {
-        let mut proposals: Vec<ProposalData> = Vec::new();
-
-        loop {
-            let list_proposals_args = sns_governance_canister::list_proposals::Args {
-                limit: BATCH_SIZE_LIMIT,
-                before_proposal: proposals.iter().next_back().and_then(|p| p.id),
-                include_reward_status: vec![REWARD_STATUS_ACCEPT_VOTES, REWARD_STATUS_READY_TO_SETTLE],
-                ..Default::default()
-            };
-
-            let response = sns_governance_canister_c2c_client::list_proposals(governance_canister_id, &list_proposals_args)
-                .await?
-                .proposals;
-
-            let finished = response.len() < BATCH_SIZE_LIMIT as usize;
-            proposals.extend(response);
-
-            if finished {
-                break;
-            }
-        }
-
-        Ok(proposals)
-    }
-
-    fn handle_proposals_response<R: RawProposal>(governance_canister_id: &CanisterId, response: CallResult<Vec<R>>) {
+    fn handle_proposals_response(governance_canister_id: &CanisterId, response: CallResult<Vec<Proposal>>) {
         match response {
-            Ok(raw_proposals) => {
-                let mut proposals: Vec<Proposal> = raw_proposals.into_iter().filter_map(|p| p.try_into().ok()).collect();
-
+            Ok(mut proposals) => {
                 // TODO Remove this!
                 // Temp hack for Dragginz
                 // Dfinity are fixing a bug in their governance canister which is causing it to
@@ -206,8 +139,18 @@ mod push_proposals {
         };
 
         let response = group_canister_c2c_client::send_message_v2(group_id.into(), &send_message_args).await;
+        let message_index = match &response {
+            Ok(group_canister::send_message_v2::Response::Success(result)) => Some(result.message_index),
+            _ => None,
+        };
 
-        mark_proposal_pushed(governance_canister_id, proposal, message_id, is_failure(response));
+        mark_proposal_pushed(
+            governance_canister_id,
+            proposal,
+            message_id,
+            message_index,
+            is_failure(response),
+        );
     }
 
     async fn push_channel_proposal(
@@ -236,27 +179,47 @@ mod push_proposals {
         };
 
         let response = community_canister_c2c_client::send_message(community_id.into(), &send_message_args).await;
+        let message_index = match &response {
+            Ok(community_canister::send_message::Response::Success(result)) => Some(result.message_index),
+            _ => None,
+        };
 
-        mark_proposal_pushed(governance_canister_id, proposal, message_id, is_failure(response));
+        mark_proposal_pushed(
+            governance_canister_id,
+            proposal,
+            message_id,
+            message_index,
+            is_failure(response),
+        );
     }
 
-    fn mark_proposal_pushed(governance_canister_id: CanisterId, proposal: Proposal, message_id: MessageId, failed: bool) {
+    fn mark_proposal_pushed(
+        governance_canister_id: CanisterId,
+        proposal: Proposal,
+        message_id: MessageId,
+        message_index: Option<MessageIndex>,
+        failed: bool,
+    ) {
         mutate_state(|state| {
+            let now = state.env.now();
             if failed {
                 state
                     .data
                     .nervous_systems
                     .mark_proposal_push_failed(&governance_canister_id, proposal);
             } else {
-                state
-                    .data
-                    .nervous_systems
-                    .mark_proposal_pushed(&governance_canister_id, proposal, message_id);
+                state.data.nervous_systems.mark_proposal_pushed(
+                    &governance_canister_id,
+                    proposal,
+                    message_id,
+                    message_index,
+                    now,
+                );
             }
         });
     }
 
-    fn is_failure<T>(response: CallResult<T>) -> bool {
+    pub(super) fn is_failure<T>(response: CallResult<T>) -> bool {
         match response {
             // If the messageId has already been used, treat that as success
             Err((code, error)) if code == RejectionCode::CanisterError && error.contains("MessageId") => false,
@@ -266,6 +229,127 @@ mod push_proposals {
     }
 }
 
+mod push_thread_messages {
+    use super::*;
+
+    pub fn run() {
+        if let Some(ThreadMessageToSend {
+            governance_canister_id,
+            chat_id,
+            message,
+        }) = mutate_state(next)
+        {
+            ic_cdk::spawn(push_thread_message(governance_canister_id, chat_id, message));
+        }
+    }
+
+    fn next(state: &mut RuntimeState) -> Option<ThreadMessageToSend> {
+        let now = state.env.now();
+        state.data.nervous_systems.queue_due_reminders(now);
+        state.data.nervous_systems.dequeue_next_thread_message()
+    }
+
+    async fn push_thread_message(governance_canister_id: CanisterId, chat_id: MultiUserChat, message: ThreadMessage) {
+        let now = read_state(|state| state.env.now());
+        let (text, discriminator) = match &message.kind {
+            ThreadMessageKind::Summary(proposal) => (proposal_threads::summary_text(proposal, now), 0),
+            ThreadMessageKind::Reminder(remaining, tally) => (proposal_threads::reminder_text(*remaining, tally), *remaining),
+        };
+        let message_id = generate_thread_message_id(governance_canister_id, message.proposal_id, discriminator);
+
+        let failed = if matches!(message.kind, ThreadMessageKind::Reminder(..)) {
+            send_reminder(chat_id, message.root_message_index, message_id, text).await
+        } else {
+            send_message(chat_id, message.root_message_index, message_id, text).await
+        };
+
+        mutate_state(|state| {
+            state
+                .data
+                .nervous_systems
+                .mark_thread_message_sent(&governance_canister_id, message, failed)
+        });
+    }
+
+    async fn send_message(
+        chat_id: MultiUserChat,
+        root_message_index: MessageIndex,
+        message_id: MessageId,
+        text: String,
+    ) -> bool {
+        let content = MessageContentInitial::Text(TextContent { text });
+
+        match chat_id {
+            MultiUserChat::Group(group_id) => {
+                let send_message_args = group_canister::send_message_v2::Args {
+                    message_id,
+                    thread_root_message_index: Some(root_message_index),
+                    content,
+                    sender_name: "ProposalsBot".to_string(),
+                    sender_display_name: None,
+                    replies_to: None,
+                    mentioned: Vec::new(),
+                    forwarding: false,
+                    rules_accepted: None,
+                    correlation_id: 0,
+                };
+                push_proposals::is_failure(
+                    group_canister_c2c_client::send_message_v2(group_id.into(), &send_message_args).await,
+                )
+            }
+            MultiUserChat::Channel(community_id, channel_id) => {
+                let send_message_args = community_canister::send_message::Args {
+                    message_id,
+                    thread_root_message_index: Some(root_message_index),
+                    content,
+                    sender_name: "ProposalsBot".to_string(),
+                    sender_display_name: None,
+                    replies_to: None,
+                    mentioned: Vec::new(),
+                    forwarding: false,
+                    channel_id,
+                    community_rules_accepted: None,
+                    channel_rules_accepted: None,
+                };
+                push_proposals::is_failure(
+                    community_canister_c2c_client::send_message(community_id.into(), &send_message_args).await,
+                )
+            }
+        }
+    }
+
+    // Messages sent by the ProposalsBot can't mention anyone, so reminders go via a dedicated
+    // endpoint which notifies each member who has not yet voted on the proposal
+    async fn send_reminder(
+        chat_id: MultiUserChat,
+        root_message_index: MessageIndex,
+        message_id: MessageId,
+        text: String,
+    ) -> bool {
+        match chat_id {
+            MultiUserChat::Group(group_id) => {
+                let args = group_canister::c2c_send_proposal_reminder::Args {
+                    thread_root_message_index: root_message_index,
+                    message_id,
+                    text,
+                };
+                push_proposals::is_failure(group_canister_c2c_client::c2c_send_proposal_reminder(group_id.into(), &args).await)
+            }
+            MultiUserChat::Channel(community_id, channel_id) => {
+                let args = community_canister::c2c_send_proposal_reminder::Args {
+                    channel_id,
+                    thread_root_message_index: root_message_index,
+                    message_id,
+                    text,
+                };
+                push_proposals::is_failure(
+                    community_canister_c2c_client::c2c_send_proposal_reminder(community_id.into(), &args).await,
+                )
+            }
+        }
+    }
+}
+
 mod update_proposals {
     use super::*;
 
@@ -3,14 +3,18 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::cmp::max;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use tracing::error;
 use types::{
-    CanisterId, MessageId, Milliseconds, MultiUserChat, Proposal, ProposalId, ProposalRewardStatus, ProposalUpdate,
-    SnsNeuronId, TimestampMillis,
+    CanisterId, MessageId, MessageIndex, Milliseconds, MultiUserChat, Proposal, ProposalDecisionStatus, ProposalId,
+    ProposalRewardStatus, ProposalUpdate, SnsNeuronId, Tally, TimestampMillis,
 };
-use utils::time::MINUTE_IN_MS;
+use utils::time::{DAY_IN_MS, HOUR_IN_MS, MINUTE_IN_MS};
 
 const MIN_INTERVAL_BETWEEN_SYNCS: Milliseconds = MINUTE_IN_MS; // 1 minute
+const DEFAULT_REMINDERS: [Milliseconds; 2] = [DAY_IN_MS, HOUR_IN_MS];
+const MAX_THREAD_MESSAGE_ATTEMPTS: u32 = 5;
+pub const MAX_REMINDERS_PER_PROPOSAL: usize = 3;
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct NervousSystems {
@@ -19,9 +23,17 @@ pub struct NervousSystems {
 }
 
 impl NervousSystems {
-    pub fn add(&mut self, governance_canister_id: CanisterId, chat_id: MultiUserChat) {
+    pub fn add(&mut self, governance_canister_id: CanisterId, chat_id: MultiUserChat, is_generic: bool) {
+        self.nervous_systems.insert(
+            governance_canister_id,
+            NervousSystem::new(governance_canister_id, chat_id, is_generic),
+        );
+    }
+
+    pub fn is_generic(&self, governance_canister_id: &CanisterId) -> bool {
         self.nervous_systems
-            .insert(governance_canister_id, NervousSystem::new(governance_canister_id, chat_id));
+            .get(governance_canister_id)
+            .map_or(false, |ns| ns.is_generic)
     }
 
     pub fn get_chat_id(&self, governance_canister_id: &CanisterId) -> Option<MultiUserChat> {
@@ -47,6 +59,16 @@ impl NervousSystems {
         }
     }
 
+    pub fn set_reminders(&mut self, governance_canister_id: &CanisterId, reminders: Vec<Milliseconds>) -> bool {
+        if let Some(ns) = self.nervous_systems.get_mut(governance_canister_id) {
+            ns.proposal_threads.reschedule_all(&reminders);
+            ns.reminders = reminders;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn remove(&mut self, governance_canister_id: &CanisterId) -> bool {
         self.nervous_systems.remove(governance_canister_id).is_some()
     }
@@ -94,6 +116,52 @@ impl NervousSystems {
         None
     }
 
+    // Queues a reminder in each proposal thread whose deadline has come within one of the
+    // configured reminder periods
+    pub fn queue_due_reminders(&mut self, now: TimestampMillis) {
+        for ns in self.nervous_systems.values_mut() {
+            for message in ns.proposal_threads.take_due_reminders(&ns.reminders, now) {
+                ns.thread_messages_to_be_sent.queue.push_back(message);
+            }
+        }
+    }
+
+    pub fn dequeue_next_thread_message(&mut self) -> Option<ThreadMessageToSend> {
+        self.nervous_systems
+            .values_mut()
+            .find(|ns| !ns.thread_messages_to_be_sent.queue.is_empty() && !ns.thread_messages_to_be_sent.in_progress)
+            .and_then(|ns| {
+                let message = ns.thread_messages_to_be_sent.queue.pop_front()?;
+                ns.thread_messages_to_be_sent.in_progress = true;
+
+                Some(ThreadMessageToSend {
+                    governance_canister_id: ns.governance_canister_id,
+                    chat_id: ns.chat_id,
+                    message,
+                })
+            })
+    }
+
+    pub fn mark_thread_message_sent(&mut self, governance_canister_id: &CanisterId, message: ThreadMessage, failed: bool) {
+        if let Some(ns) = self.nervous_systems.get_mut(governance_canister_id) {
+            if failed {
+                let mut message = message;
+                message.attempts += 1;
+                if message.attempts < MAX_THREAD_MESSAGE_ATTEMPTS {
+                    // Retried after the rest of the queue so that one bad thread can't block the others
+                    ns.thread_messages_to_be_sent.queue.push_back(message);
+                } else {
+                    error!(
+                        governance_canister_id = %governance_canister_id,
+                        proposal_id = message.proposal_id,
+                        "Failed to send thread message, giving up"
+                    );
+                }
+            }
+            ns.thread_messages_to_be_sent.in_progress = false;
+        }
+    }
+
     pub fn dequeue_next_proposals_to_update(&mut self) -> Option<ProposalsToUpdate> {
         self.nervous_systems
             .values_mut()
@@ -158,8 +226,18 @@ impl NervousSystems {
         }
     }
 
-    pub fn mark_proposal_pushed(&mut self, governance_canister_id: &CanisterId, proposal: Proposal, message_id: MessageId) {
+    pub fn mark_proposal_pushed(
+        &mut self,
+        governance_canister_id: &CanisterId,
+        proposal: Proposal,
+        message_id: MessageId,
+        message_index: Option<MessageIndex>,
+        now: TimestampMillis,
+    ) {
         if let Some(ns) = self.nervous_systems.get_mut(governance_canister_id) {
+            if let Some(root_message_index) = message_index {
+                ns.open_thread(&proposal, root_message_index, now);
+            }
             ns.active_proposals.insert(proposal.id(), (proposal, message_id));
             ns.proposals_to_be_pushed.in_progress = false;
         }
@@ -222,6 +300,18 @@ pub struct NervousSystem {
     active_proposals: BTreeMap<ProposalId, (Proposal, MessageId)>,
     #[serde(default)]
     neuron_id_for_submitting_proposals: Option<SnsNeuronId>,
+    #[serde(default)]
+    proposal_threads: ProposalThreads,
+    #[serde(default)]
+    thread_messages_to_be_sent: ThreadMessagesToBeSent,
+    #[serde(default = "default_reminders")]
+    reminders: Vec<Milliseconds>,
+    #[serde(default)]
+    is_generic: bool,
+}
+
+fn default_reminders() -> Vec<Milliseconds> {
+    DEFAULT_REMINDERS.to_vec()
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -236,8 +326,190 @@ struct ProposalsToBeUpdated {
     pub in_progress: bool,
 }
 
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct ThreadMessagesToBeSent {
+    pub queue: VecDeque<ThreadMessage>,
+    pub in_progress: bool,
+}
+
+// The open proposal threads, indexed by when their next reminder is due so that each heartbeat
+// only looks at the threads which have a reminder to send
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(
+    from = "BTreeMap<ProposalId, ProposalThread>",
+    into = "BTreeMap<ProposalId, ProposalThread>"
+)]
+struct ProposalThreads {
+    threads: BTreeMap<ProposalId, ProposalThread>,
+    reminders_due: BTreeSet<(TimestampMillis, ProposalId)>,
+}
+
+impl ProposalThreads {
+    fn insert(&mut self, proposal_id: ProposalId, thread: ProposalThread, reminders: &[Milliseconds]) {
+        self.remove(&proposal_id);
+        self.threads.insert(proposal_id, thread);
+        self.schedule(proposal_id, reminders);
+    }
+
+    fn remove(&mut self, proposal_id: &ProposalId) {
+        if let Some(due) = self.threads.remove(proposal_id).and_then(|t| t.next_reminder_due) {
+            self.reminders_due.remove(&(due, *proposal_id));
+        }
+    }
+
+    fn update(
+        &mut self,
+        proposal_id: ProposalId,
+        tally: Option<Tally>,
+        deadline: Option<TimestampMillis>,
+        reminders: &[Milliseconds],
+    ) {
+        if let Some(thread) = self.threads.get_mut(&proposal_id) {
+            if let Some(t) = tally {
+                thread.tally = t;
+            }
+            if let Some(d) = deadline {
+                thread.deadline = d;
+                self.schedule(proposal_id, reminders);
+            }
+        }
+    }
+
+    fn reschedule_all(&mut self, reminders: &[Milliseconds]) {
+        let proposal_ids: Vec<_> = self.threads.keys().copied().collect();
+        for proposal_id in proposal_ids {
+            self.schedule(proposal_id, reminders);
+        }
+    }
+
+    fn take_due_reminders(&mut self, reminders: &[Milliseconds], now: TimestampMillis) -> Vec<ThreadMessage> {
+        let mut messages = Vec::new();
+        while let Some((due, proposal_id)) = self.reminders_due.first().copied() {
+            if due > now {
+                break;
+            }
+            self.reminders_due.pop_first();
+
+            let Some(thread) = self.threads.get_mut(&proposal_id) else {
+                continue;
+            };
+            thread.next_reminder_due = None;
+
+            if let Some(remaining) = thread.take_due_reminder(reminders, now) {
+                messages.push(ThreadMessage {
+                    proposal_id,
+                    root_message_index: thread.root_message_index,
+                    kind: ThreadMessageKind::Reminder(remaining, thread.tally.clone()),
+                    attempts: 0,
+                });
+            }
+            // Once the deadline has passed the thread is only rescheduled if the deadline is extended
+            if now < thread.deadline {
+                self.schedule(proposal_id, reminders);
+            }
+        }
+        messages
+    }
+
+    fn schedule(&mut self, proposal_id: ProposalId, reminders: &[Milliseconds]) {
+        if let Some(thread) = self.threads.get_mut(&proposal_id) {
+            if let Some(previous) = thread.next_reminder_due.take() {
+                self.reminders_due.remove(&(previous, proposal_id));
+            }
+            if let Some(due) = thread.next_reminder_due(reminders) {
+                thread.next_reminder_due = Some(due);
+                self.reminders_due.insert((due, proposal_id));
+            }
+        }
+    }
+}
+
+impl From<BTreeMap<ProposalId, ProposalThread>> for ProposalThreads {
+    fn from(mut threads: BTreeMap<ProposalId, ProposalThread>) -> Self {
+        // The reminder config isn't available here, so each thread is checked on the next
+        // heartbeat, which then schedules its next reminder
+        let mut reminders_due = BTreeSet::new();
+        for (proposal_id, thread) in threads.iter_mut() {
+            thread.next_reminder_due = Some(0);
+            reminders_due.insert((0, *proposal_id));
+        }
+        ProposalThreads { threads, reminders_due }
+    }
+}
+
+impl From<ProposalThreads> for BTreeMap<ProposalId, ProposalThread> {
+    fn from(value: ProposalThreads) -> Self {
+        value.threads
+    }
+}
+
+// The discussion thread opened on a proposal message, kept until the proposal stops accepting votes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ProposalThread {
+    root_message_index: MessageIndex,
+    deadline: TimestampMillis,
+    tally: Tally,
+    // Includes the reminders which were skipped because their time had passed before the thread
+    // was opened
+    reminders_sent: Vec<Milliseconds>,
+    #[serde(default)]
+    reminders_posted: usize,
+    #[serde(skip)]
+    next_reminder_due: Option<TimestampMillis>,
+}
+
+impl ProposalThread {
+    // The earliest time at which one of the reminders yet to be sent becomes due
+    fn next_reminder_due(&self, reminders: &[Milliseconds]) -> Option<TimestampMillis> {
+        if self.reminders_posted >= MAX_REMINDERS_PER_PROPOSAL {
+            return None;
+        }
+        reminders
+            .iter()
+            .filter(|r| !self.reminders_sent.contains(r))
+            .map(|r| self.deadline.saturating_sub(*r))
+            .min()
+    }
+
+    // Returns the time remaining until the deadline if a reminder is due. If several reminders
+    // are due at once (eg. because the proposal was only just pushed) only one is sent.
+    fn take_due_reminder(&mut self, reminders: &[Milliseconds], now: TimestampMillis) -> Option<Milliseconds> {
+        if now >= self.deadline || self.reminders_posted >= MAX_REMINDERS_PER_PROPOSAL {
+            return None;
+        }
+
+        let remaining = self.deadline - now;
+        let mut due = false;
+        for reminder in reminders.iter().filter(|r| **r >= remaining) {
+            if !self.reminders_sent.contains(reminder) {
+                self.reminders_sent.push(*reminder);
+                due = true;
+            }
+        }
+        if due {
+            self.reminders_posted += 1;
+        }
+        due.then_some(remaining)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ThreadMessage {
+    pub proposal_id: ProposalId,
+    pub root_message_index: MessageIndex,
+    pub kind: ThreadMessageKind,
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ThreadMessageKind {
+    Summary(Box<Proposal>),
+    Reminder(Milliseconds, Tally),
+}
+
 impl NervousSystem {
-    pub fn new(governance_canister_id: CanisterId, chat_id: MultiUserChat) -> NervousSystem {
+    pub fn new(governance_canister_id: CanisterId, chat_id: MultiUserChat, is_generic: bool) -> NervousSystem {
         NervousSystem {
             governance_canister_id,
             chat_id,
@@ -249,6 +521,10 @@ impl NervousSystem {
             proposals_to_be_updated: ProposalsToBeUpdated::default(),
             active_proposals: BTreeMap::default(),
             neuron_id_for_submitting_proposals: None,
+            proposal_threads: ProposalThreads::default(),
+            thread_messages_to_be_sent: ThreadMessagesToBeSent::default(),
+            reminders: default_reminders(),
+            is_generic,
         }
     }
 
@@ -269,6 +545,13 @@ impl NervousSystem {
                 deadline: (deadline != previous.deadline()).then_some(deadline),
             };
 
+            if status != ProposalDecisionStatus::Open {
+                self.proposal_threads.remove(&proposal_id);
+            } else {
+                self.proposal_threads
+                    .update(proposal_id, update.latest_tally.clone(), update.deadline, &self.reminders);
+            }
+
             self.upsert_proposal_update(update);
         } else {
             self.proposals_to_be_pushed.queue.insert(proposal_id, proposal);
@@ -276,6 +559,7 @@ impl NervousSystem {
     }
 
     pub fn mark_proposal_inactive(&mut self, proposal_id: ProposalId) {
+        self.proposal_threads.remove(&proposal_id);
         if let Some((_, message_id)) = self.active_proposals.remove(&proposal_id) {
             self.upsert_proposal_update(ProposalUpdate {
                 message_id,
@@ -287,6 +571,36 @@ impl NervousSystem {
         }
     }
 
+    fn open_thread(&mut self, proposal: &Proposal, root_message_index: MessageIndex, now: TimestampMillis) {
+        let proposal_id = proposal.id();
+
+        self.thread_messages_to_be_sent.queue.push_back(ThreadMessage {
+            proposal_id,
+            root_message_index,
+            kind: ThreadMessageKind::Summary(Box::new(proposal.clone())),
+            attempts: 0,
+        });
+
+        if proposal.status() == ProposalDecisionStatus::Open {
+            // Reminders whose time has already passed are skipped, the summary covers them
+            let remaining = proposal.deadline().saturating_sub(now);
+            let reminders_sent = self.reminders.iter().filter(|r| **r >= remaining).copied().collect();
+
+            self.proposal_threads.insert(
+                proposal_id,
+                ProposalThread {
+                    root_message_index,
+                    deadline: proposal.deadline(),
+                    tally: proposal.tally(),
+                    reminders_sent,
+                    reminders_posted: 0,
+                    next_reminder_due: None,
+                },
+                &self.reminders,
+            );
+        }
+    }
+
     pub fn latest_sync(&self) -> Option<TimestampMillis> {
         max(self.latest_successful_sync, self.latest_failed_sync)
     }
@@ -324,6 +638,7 @@ impl From<&NervousSystem> for NervousSystemMetrics {
             queued_proposals: ns.proposals_to_be_pushed.queue.keys().copied().collect(),
             active_proposals: ns.active_proposals.keys().copied().collect(),
             neuron_for_submitting_proposals: ns.neuron_id_for_submitting_proposals.map(hex::encode),
+            is_generic: ns.is_generic,
         }
     }
 }
@@ -334,8 +649,123 @@ pub struct ProposalToPush {
     pub proposal: Proposal,
 }
 
+pub struct ThreadMessageToSend {
+    pub governance_canister_id: CanisterId,
+    pub chat_id: MultiUserChat,
+    pub message: ThreadMessage,
+}
+
 pub struct ProposalsToUpdate {
     pub governance_canister_id: CanisterId,
     pub chat_id: MultiUserChat,
     pub proposals: Vec<ProposalUpdate>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn due_reminders_are_taken_in_order_of_due_time() {
+        let mut threads = ProposalThreads::default();
+        let reminders = [DAY_IN_MS];
+
+        threads.insert(1, thread(3 * DAY_IN_MS), &reminders);
+        threads.insert(2, thread(2 * DAY_IN_MS + HOUR_IN_MS), &reminders);
+
+        assert!(threads.take_due_reminders(&reminders, DAY_IN_MS).is_empty());
+
+        let messages = threads.take_due_reminders(&reminders, 2 * DAY_IN_MS);
+        assert_eq!(messages.iter().map(|m| m.proposal_id).collect::<Vec<_>>(), vec![2, 1]);
+        assert!(matches!(messages[0].kind, ThreadMessageKind::Reminder(remaining, _) if remaining == HOUR_IN_MS));
+        assert!(threads.reminders_due.is_empty());
+    }
+
+    #[test]
+    fn reminders_are_rescheduled_when_deadline_changes() {
+        let mut threads = ProposalThreads::default();
+        let reminders = [HOUR_IN_MS, DAY_IN_MS];
+
+        threads.insert(1, thread(3 * DAY_IN_MS), &reminders);
+        assert!(threads.take_due_reminders(&reminders, DAY_IN_MS).is_empty());
+
+        threads.update(1, None, Some(2 * DAY_IN_MS), &reminders);
+
+        let messages = threads.take_due_reminders(&reminders, DAY_IN_MS);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            threads.reminders_due.iter().copied().collect::<Vec<_>>(),
+            vec![(2 * DAY_IN_MS - HOUR_IN_MS, 1)]
+        );
+
+        threads.remove(&1);
+        assert!(threads.reminders_due.is_empty());
+    }
+
+    #[test]
+    fn reminders_are_capped_per_proposal() {
+        let mut threads = ProposalThreads::default();
+        let reminders = [HOUR_IN_MS, 2 * HOUR_IN_MS, 3 * HOUR_IN_MS, 4 * HOUR_IN_MS];
+
+        threads.insert(1, thread(10 * HOUR_IN_MS), &reminders);
+
+        let sent: usize = (6..10)
+            .map(|hour| threads.take_due_reminders(&reminders, hour * HOUR_IN_MS).len())
+            .sum();
+
+        assert_eq!(sent, MAX_REMINDERS_PER_PROPOSAL);
+        assert!(threads.reminders_due.is_empty());
+    }
+
+    #[test]
+    fn failed_thread_messages_are_dropped_after_max_attempts() {
+        let governance_canister_id = Principal::from_slice(&[1]);
+        let mut nervous_systems = NervousSystems::default();
+        nervous_systems.add(
+            governance_canister_id,
+            MultiUserChat::Group(Principal::from_slice(&[2]).into()),
+            false,
+        );
+        nervous_systems
+            .nervous_systems
+            .get_mut(&governance_canister_id)
+            .unwrap()
+            .thread_messages_to_be_sent
+            .queue
+            .push_back(ThreadMessage {
+                proposal_id: 1,
+                root_message_index: 0.into(),
+                kind: ThreadMessageKind::Reminder(HOUR_IN_MS, tally()),
+                attempts: 0,
+            });
+
+        let mut attempts = 0;
+        while let Some(next) = nervous_systems.dequeue_next_thread_message() {
+            attempts += 1;
+            nervous_systems.mark_thread_message_sent(&governance_canister_id, next.message, true);
+        }
+
+        assert_eq!(attempts, MAX_THREAD_MESSAGE_ATTEMPTS);
+    }
+
+    fn thread(deadline: TimestampMillis) -> ProposalThread {
+        ProposalThread {
+            root_message_index: 0.into(),
+            deadline,
+            tally: tally(),
+            reminders_sent: Vec::new(),
+            reminders_posted: 0,
+            next_reminder_due: None,
+        }
+    }
+
+    fn tally() -> Tally {
+        Tally {
+            yes: 0,
+            no: 0,
+            total: 0,
+            timestamp: 0,
+        }
+    }
+}
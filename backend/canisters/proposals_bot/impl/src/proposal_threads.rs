@@ -0,0 +1,200 @@
+use std::fmt::Write;
+use types::{Milliseconds, Proposal, SnsProposal, Tally, TimestampMillis};
+use utils::time::{DAY_IN_MS, HOUR_IN_MS, MINUTE_IN_MS};
+
+const MAX_PAYLOAD_FIELDS: usize = 10;
+const MAX_FIELD_VALUE_LENGTH: usize = 200;
+const MAX_MOTION_TEXT_LENGTH: usize = 1500;
+
+// Builds the message which opens the discussion thread on each newly pushed proposal
+pub fn summary_text(proposal: &Proposal, now: TimestampMillis) -> String {
+    let mut text = format!("**Proposal {}: {}**\n", proposal.id(), proposal.title());
+
+    match proposal {
+        Proposal::NNS(p) => {
+            writeln!(&mut text, "Proposer: neuron {}", p.proposer).unwrap();
+        }
+        Proposal::SNS(p) => {
+            writeln!(&mut text, "Type: {}", sns_action_name(p.action)).unwrap();
+            writeln!(&mut text, "Proposer: neuron {}", hex::encode(p.proposer)).unwrap();
+        }
+        Proposal::Generic(p) => {
+            writeln!(&mut text, "Proposer: {}", p.proposer).unwrap();
+        }
+    }
+
+    writeln!(
+        &mut text,
+        "Voting closes in: {}",
+        format_duration(proposal.deadline().saturating_sub(now))
+    )
+    .unwrap();
+
+    if let Proposal::SNS(p) = proposal {
+        write_sns_payload_summary(&mut text, p);
+    }
+
+    text.push_str("\nUse this thread to discuss the proposal.");
+    text
+}
+
+pub fn reminder_text(remaining: Milliseconds, tally: &Tally) -> String {
+    let mut text = format!(
+        "⏰ Voting on this proposal closes in {}. If you haven't voted yet, now's the time!",
+        format_duration(remaining)
+    );
+
+    if tally.total > 0 {
+        let percentage = |votes: u64| (votes as f64 * 100.0) / tally.total as f64;
+        write!(
+            &mut text,
+            "\nCurrent tally: {:.2}% yes, {:.2}% no",
+            percentage(tally.yes),
+            percentage(tally.no)
+        )
+        .unwrap();
+    }
+
+    text
+}
+
+fn write_sns_payload_summary(text: &mut String, proposal: &SnsProposal) {
+    let payload = match proposal.payload_text_rendering.as_deref() {
+        Some(p) if !p.is_empty() => p,
+        _ => return,
+    };
+
+    match proposal.action {
+        // Motion
+        1 => {
+            if let Some(motion_text) = motion_text(payload) {
+                writeln!(text, "\nMotion text:\n{}", truncate(motion_text, MAX_MOTION_TEXT_LENGTH)).unwrap();
+            }
+        }
+        2 | 3 | 8..=14 => {
+            let fields = payload_fields(payload);
+            if !fields.is_empty() {
+                text.push_str("\nDetails:\n");
+                for (key, value) in fields {
+                    writeln!(text, "- {key}: {}", truncate(value, MAX_FIELD_VALUE_LENGTH)).unwrap();
+                }
+            }
+        }
+        // Custom functions have payloads rendered by the SNS's own validators, so there is no
+        // common structure to parse
+        _ => {}
+    }
+}
+
+fn sns_action_name(action: u64) -> String {
+    let name = match action {
+        1 => "Motion",
+        2 => "Manage nervous system parameters",
+        3 => "Upgrade SNS controlled canister",
+        4 => "Add generic nervous system function",
+        5 => "Remove generic nervous system function",
+        6 => "Execute generic nervous system function",
+        7 => "Upgrade SNS to next version",
+        8 => "Manage SNS metadata",
+        9 => "Transfer SNS treasury funds",
+        10 => "Register dapp canisters",
+        11 => "Deregister dapp canisters",
+        12 => "Mint SNS tokens",
+        13 => "Manage ledger parameters",
+        14 => "Manage dapp canister settings",
+        _ => return format!("Custom function ({action})"),
+    };
+    name.to_string()
+}
+
+// The SNS renders payloads as markdown where each field is on its own line in the form
+// "## Key: Value", preceded by a heading describing the proposal type
+fn payload_fields(payload: &str) -> Vec<(&str, &str)> {
+    payload
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim_start_matches(['#', '-', '*', ' ']).split_once(':')?;
+            let key = key.trim();
+            let value = value.trim();
+            // Skip headings (which have no value) and lines of prose which happen to contain a colon
+            (!key.is_empty() && !value.is_empty() && key.split_whitespace().count() <= 4).then_some((key, value))
+        })
+        .take(MAX_PAYLOAD_FIELDS)
+        .collect()
+}
+
+fn motion_text(payload: &str) -> Option<&str> {
+    let (_, motion_text) = payload.split_once("Motion Text:")?;
+    let motion_text = motion_text.trim();
+    (!motion_text.is_empty()).then_some(motion_text)
+}
+
+fn truncate(value: &str, max_length: usize) -> String {
+    if value.chars().count() > max_length {
+        let mut truncated: String = value.chars().take(max_length).collect();
+        truncated.push('…');
+        truncated
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_duration(duration: Milliseconds) -> String {
+    let days = duration / DAY_IN_MS;
+    let hours = (duration % DAY_IN_MS) / HOUR_IN_MS;
+    let minutes = (duration % HOUR_IN_MS) / MINUTE_IN_MS;
+
+    let plural = |count: u64, unit: &str| format!("{count} {unit}{}", if count == 1 { "" } else { "s" });
+
+    if days > 0 {
+        if hours > 0 {
+            format!("{} {}", plural(days, "day"), plural(hours, "hour"))
+        } else {
+            plural(days, "day")
+        }
+    } else if hours > 0 {
+        plural(hours, "hour")
+    } else {
+        plural(minutes.max(1), "minute")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn treasury_transfer_payload_is_parsed() {
+        let payload = "# Proposal to transfer SNS Treasury funds:
+## Source treasury: SNS Token Treasury
+## Amount (e8s): 100000000
+## Target principal: 2vxsx-fae
+## Memo: 0";
+
+        assert_eq!(
+            payload_fields(payload),
+            vec![
+                ("Source treasury", "SNS Token Treasury"),
+                ("Amount (e8s)", "100000000"),
+                ("Target principal", "2vxsx-fae"),
+                ("Memo", "0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn motion_text_is_extracted() {
+        let payload = "# Motion Proposal:\n## Motion Text:\n\nLet's do it";
+
+        assert_eq!(motion_text(payload), Some("Let's do it"));
+    }
+
+    #[test]
+    fn durations_are_formatted() {
+        assert_eq!(format_duration(DAY_IN_MS), "1 day");
+        assert_eq!(format_duration(DAY_IN_MS + 2 * HOUR_IN_MS), "1 day 2 hours");
+        assert_eq!(format_duration(HOUR_IN_MS), "1 hour");
+        assert_eq!(format_duration(10 * MINUTE_IN_MS), "10 minutes");
+        assert_eq!(format_duration(0), "1 minute");
+    }
+}
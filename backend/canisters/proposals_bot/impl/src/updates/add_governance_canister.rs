@@ -21,7 +21,10 @@ async fn add_governance_canister(args: Args) -> Response {
     };
 
     mutate_state(|state| {
-        state.data.nervous_systems.add(args.governance_canister_id, chat_id);
+        state
+            .data
+            .nervous_systems
+            .add(args.governance_canister_id, chat_id, args.is_generic.unwrap_or_default());
     });
 
     Success
@@ -58,7 +61,7 @@ async fn create_group(args: &Args, is_nns: bool) -> Result<MultiUserChat, Respon
         description: args
             .description
             .clone()
-            .unwrap_or_else(|| default_description(&args.name, false)),
+            .unwrap_or_else(|| default_description(&args.name, false, args.is_generic.unwrap_or_default())),
         rules: Rules::default(),
         subtype: Some(GroupSubtype::GovernanceProposals(GovernanceProposalsSubtype {
             governance_canister_id: args.governance_canister_id,
@@ -91,7 +94,7 @@ async fn create_channel(args: &Args, is_nns: bool) -> Result<MultiUserChat, Resp
         description: args
             .description
             .clone()
-            .unwrap_or_else(|| default_description(&args.name, true)),
+            .unwrap_or_else(|| default_description(&args.name, true, args.is_generic.unwrap_or_default())),
         rules: Rules::default(),
         subtype: Some(GroupSubtype::GovernanceProposals(GovernanceProposalsSubtype {
             governance_canister_id: args.governance_canister_id,
@@ -106,6 +109,8 @@ async fn create_channel(args: &Args, is_nns: bool) -> Result<MultiUserChat, Resp
         }),
         events_ttl: None,
         gate: None,
+        category_id: None,
+        is_announcement: None,
     };
 
     let community_id = args.community_id.unwrap();
@@ -121,8 +126,13 @@ async fn create_channel(args: &Args, is_nns: bool) -> Result<MultiUserChat, Resp
     }
 }
 
-fn default_description(name: &str, is_channel: bool) -> String {
+fn default_description(name: &str, is_channel: bool, is_generic: bool) -> String {
     let chat_type = if is_channel { "channel" } else { "group" };
+    if is_generic {
+        // Votes on generic proposals can't be cast from within OpenChat
+        return format!("Join this {chat_type} to view and discuss {name} proposals.");
+    }
+
     let mut description = String::new();
     writeln!(
         &mut description,
@@ -3,5 +3,6 @@ mod appoint_admins;
 mod c2c_submit_proposal;
 mod import_proposals_group_into_community;
 mod remove_governance_canister;
+mod set_proposal_reminders;
 mod stake_neuron_for_submitting_proposals;
 mod wallet_receive;
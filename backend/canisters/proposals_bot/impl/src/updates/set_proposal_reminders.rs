@@ -0,0 +1,33 @@
+use crate::guards::caller_is_governance_principal;
+use crate::model::nervous_systems::MAX_REMINDERS_PER_PROPOSAL;
+use crate::{mutate_state, RuntimeState};
+use canister_api_macros::proposal;
+use canister_tracing_macros::trace;
+use proposals_bot_canister::set_proposal_reminders::{Response::*, *};
+
+// dfx --identity openchat canister --network ic call proposals_bot set_proposal_reminders '(record { governance_canister_id=principal "rrkah-fqaaa-aaaaa-aaaaq-cai"; reminders=vec { 86400000; 3600000 } })'
+#[proposal(guard = "caller_is_governance_principal")]
+#[trace]
+fn set_proposal_reminders(args: Args) -> Response {
+    mutate_state(|state| set_proposal_reminders_impl(args, state))
+}
+
+fn set_proposal_reminders_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let mut reminders = args.reminders;
+    reminders.sort_unstable();
+    reminders.dedup();
+
+    if reminders.len() > MAX_REMINDERS_PER_PROPOSAL {
+        return TooManyReminders(MAX_REMINDERS_PER_PROPOSAL as u32);
+    }
+
+    if state
+        .data
+        .nervous_systems
+        .set_reminders(&args.governance_canister_id, reminders)
+    {
+        Success
+    } else {
+        NotFound
+    }
+}
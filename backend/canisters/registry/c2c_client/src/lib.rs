@@ -0,0 +1,5 @@
+use canister_client::generate_candid_c2c_call;
+use registry_canister::*;
+
+// Queries
+generate_candid_c2c_call!(updates);
@@ -4,6 +4,8 @@ use ic_stable_structures::{
 };
 
 const UPGRADES: MemoryId = MemoryId::new(0);
+const LOG_OVERFLOW_INDEX: MemoryId = MemoryId::new(1);
+const LOG_OVERFLOW_DATA: MemoryId = MemoryId::new(2);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -16,6 +18,14 @@ pub fn get_upgrades_memory() -> Memory {
     get_memory(UPGRADES)
 }
 
+pub fn get_log_overflow_index_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_INDEX)
+}
+
+pub fn get_log_overflow_data_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_DATA)
+}
+
 fn get_memory(id: MemoryId) -> Memory {
     MEMORY_MANAGER.with(|m| m.get(id))
 }
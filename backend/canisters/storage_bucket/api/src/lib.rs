@@ -1,3 +1,7 @@
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+use types::{AccessorId, FileId, TimestampMillis};
+
 mod lifecycle;
 mod queries;
 mod updates;
@@ -5,3 +9,13 @@ mod updates;
 pub use lifecycle::*;
 pub use queries::*;
 pub use updates::*;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MigratedFile {
+    pub file_id: FileId,
+    pub owner: Principal,
+    pub created: TimestampMillis,
+    pub accessors: Vec<AccessorId>,
+    pub mime_type: String,
+    pub expiry: Option<TimestampMillis>,
+}
@@ -1,2 +1,3 @@
 pub mod file_info;
 pub mod file_status;
+pub mod upload_progress;
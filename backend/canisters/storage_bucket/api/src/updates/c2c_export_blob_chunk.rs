@@ -0,0 +1,18 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use types::Hash;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub hash: Hash,
+    pub offset: u64,
+    pub length: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(ByteBuf),
+    NotAuthorized,
+    NotFound,
+}
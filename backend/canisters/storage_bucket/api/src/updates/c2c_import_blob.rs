@@ -0,0 +1,26 @@
+use crate::MigratedFile;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, Hash};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub source_bucket: CanisterId,
+    pub hash: Hash,
+    pub size: u64,
+    pub files: Vec<MigratedFile>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    InsufficientSpace,
+    HashMismatch,
+    InternalError(String),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub bytes_used: u64,
+    pub bytes_remaining: i64,
+}
@@ -0,0 +1,22 @@
+use crate::MigratedFile;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, Hash};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub target_bucket: CanisterId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    NothingToMigrate,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub hash: Hash,
+    pub size: u64,
+    pub files: Vec<MigratedFile>,
+}
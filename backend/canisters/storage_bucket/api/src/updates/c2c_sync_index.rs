@@ -1,6 +1,6 @@
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
-use types::{AccessorId, FileId, FileRemoved};
+use types::{AccessorId, CanisterId, FileId, FileRemoved};
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
@@ -9,6 +9,7 @@ pub struct Args {
     pub accessors_removed: Vec<AccessorId>,
     pub user_ids_updated: Vec<(Principal, Principal)>,
     pub files_to_remove: Vec<FileId>,
+    pub files_migrated: Vec<(FileId, CanisterId)>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
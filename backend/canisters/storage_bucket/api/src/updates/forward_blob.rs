@@ -0,0 +1,25 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{AccessorId, FileId, Hash, TimestampMillis};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub file_id: FileId,
+    pub hash: Hash,
+    pub possession_proof: Hash,
+    pub mime_type: String,
+    pub accessors: Vec<AccessorId>,
+    pub expiry: Option<TimestampMillis>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    FileAlreadyExists,
+    InvalidFileId,
+    InvalidPossessionProof,
+    NotFound,
+    AllowanceExceeded,
+    UserNotFound,
+    InternalError(String),
+}
@@ -1,5 +1,9 @@
+pub mod c2c_export_blob_chunk;
+pub mod c2c_import_blob;
+pub mod c2c_prepare_blob_migration;
 pub mod c2c_sync_index;
 pub mod delete_file;
 pub mod delete_files;
+pub mod forward_blob;
 pub mod forward_file;
 pub mod upload_chunk_v2;
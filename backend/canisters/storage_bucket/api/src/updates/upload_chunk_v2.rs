@@ -15,6 +15,8 @@ pub struct Args {
     pub total_size: u64,
     pub bytes: ByteBuf,
     pub expiry: Option<TimestampMillis>,
+    #[serde(default)]
+    pub chunk_hash: Option<Hash>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -27,6 +29,7 @@ pub enum Response {
     ChunkAlreadyExists,
     ChunkIndexTooHigh,
     ChunkSizeMismatch,
+    ChunkHashMismatch,
     Full,
     HashMismatch,
     InvalidFileId,
@@ -45,6 +48,7 @@ impl Debug for Args {
             .field("total_size", &self.total_size)
             .field("byte_length", &self.bytes.len())
             .field("expiry", &self.expiry)
+            .field("chunk_hash", &self.chunk_hash)
             .finish()
     }
 }
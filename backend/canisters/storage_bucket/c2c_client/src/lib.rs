@@ -5,6 +5,9 @@ use storage_bucket_canister::*;
 generate_candid_c2c_call!(file_status);
 
 // Updates
+generate_candid_c2c_call!(c2c_export_blob_chunk);
+generate_candid_c2c_call!(c2c_import_blob);
+generate_candid_c2c_call!(c2c_prepare_blob_migration);
 generate_candid_c2c_call!(c2c_sync_index);
 generate_candid_c2c_call!(delete_file);
 generate_candid_c2c_call!(delete_files);
@@ -5,8 +5,9 @@ use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
-use types::{BuildVersion, CanisterId, Cycles, FileId, TimestampMillis, Timestamped};
+use types::{BuildVersion, CanisterId, Cycles, FileId, Milliseconds, TimestampMillis, Timestamped};
 use utils::env::Environment;
+use utils::time::{DAY_IN_MS, HOUR_IN_MS};
 
 mod guards;
 mod jobs;
@@ -19,6 +20,10 @@ mod updates;
 const DATA_LIMIT_BYTES: u64 = 1 << 34; // 16GB
 const MAX_BLOB_SIZE_BYTES: u64 = 100 * (1 << 20); // 100MB
 const MAX_EVENTS_TO_SYNC_PER_BATCH: usize = 1000;
+const MAX_BLOB_MIGRATION_CHUNK_SIZE_BYTES: u32 = 1 << 20; // 1MB
+const BLOB_MIGRATION_GRANT_DURATION: Milliseconds = HOUR_IN_MS;
+const PENDING_FILE_TTL: Milliseconds = DAY_IN_MS;
+const MIGRATED_FILE_REDIRECT_DURATION: Milliseconds = 30 * DAY_IN_MS;
 
 #[derive(CandidType, Serialize, Deserialize)]
 enum StateVersion {
@@ -8,6 +8,7 @@ use types::CanisterId;
 fn heartbeat() {
     sync_index::run();
     remove_expired_files::run();
+    remove_inactive_pending_files::run();
 }
 
 mod sync_index {
@@ -82,3 +83,19 @@ mod remove_expired_files {
         });
     }
 }
+
+mod remove_inactive_pending_files {
+    use crate::{mutate_state, EventToSync};
+
+    pub fn run() {
+        mutate_state(|state| {
+            let now = state.env.now();
+            for file in state.data.files.remove_inactive_pending_files(now, 10) {
+                if let Some(user) = state.data.users.get_mut(&file.meta_data.owner) {
+                    user.remove_file(&file.file_id);
+                }
+                state.data.index_sync_state.enqueue(EventToSync::FileRemoved(file));
+            }
+        });
+    }
+}
@@ -5,6 +5,8 @@ use ic_stable_structures::{
 
 const UPGRADES: MemoryId = MemoryId::new(0);
 const BLOBS: MemoryId = MemoryId::new(1);
+const LOG_OVERFLOW_INDEX: MemoryId = MemoryId::new(2);
+const LOG_OVERFLOW_DATA: MemoryId = MemoryId::new(3);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -21,6 +23,14 @@ pub fn get_blobs_memory() -> Memory {
     get_memory(BLOBS)
 }
 
+pub fn get_log_overflow_index_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_INDEX)
+}
+
+pub fn get_log_overflow_data_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_DATA)
+}
+
 fn get_memory(id: MemoryId) -> Memory {
     MEMORY_MANAGER.with(|m| m.get(id))
 }
@@ -1,15 +1,17 @@
 use crate::model::stable_blob_storage::StableBlobStorage;
-use crate::{calc_chunk_count, MAX_BLOB_SIZE_BYTES};
+use crate::{calc_chunk_count, MAX_BLOB_SIZE_BYTES, MIGRATED_FILE_REDIRECT_DURATION, PENDING_FILE_TTL};
 use candid::Principal;
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
-use std::cmp::Ordering;
+use std::cmp::{max, Ordering};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use storage_bucket_canister::c2c_prepare_blob_migration::SuccessResult as PrepareBlobMigrationResult;
 use storage_bucket_canister::upload_chunk_v2::Args as UploadChunkArgs;
+use storage_bucket_canister::MigratedFile;
 use types::{AccessorId, CanisterId, FileAdded, FileId, FileMetaData, FileRemoved, Hash, TimestampMillis};
 use utils::file_id::generate_file_id;
-use utils::hasher::hash_bytes;
+use utils::hasher::{hash_bytes, possession_proof_from_chunks};
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct Files {
@@ -20,6 +22,22 @@ pub struct Files {
     blobs: StableBlobStorage,
     expiration_queue: BTreeMap<TimestampMillis, VecDeque<FileId>>,
     bytes_used: u64,
+    #[serde(default)]
+    blob_migrations: HashMap<Hash, BlobMigration>,
+    #[serde(default)]
+    migrated_files: HashMap<FileId, CanisterId>,
+    #[serde(default)]
+    migrated_files_queue: VecDeque<(TimestampMillis, FileId)>,
+    #[serde(default)]
+    files_by_hash: HashMap<Hash, HashSet<FileId>>,
+    #[serde(default)]
+    file_expiries: HashMap<FileId, TimestampMillis>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BlobMigration {
+    target_bucket: CanisterId,
+    expires: TimestampMillis,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -58,6 +76,14 @@ impl Files {
         self.blobs.get(hash)
     }
 
+    pub fn blob_exists(&self, hash: &Hash) -> bool {
+        self.blobs.exists(hash)
+    }
+
+    pub fn migrated_to(&self, file_id: &FileId) -> Option<CanisterId> {
+        self.migrated_files.get(file_id).copied()
+    }
+
     pub fn owner(&self, file_id: &FileId) -> Option<Principal> {
         self.files
             .get(file_id)
@@ -92,6 +118,8 @@ impl Files {
                         owner: args.owner,
                         created: args.now,
                     },
+                    mime_type: Some(args.mime_type.clone()),
+                    accessors: Some(args.accessors.clone()),
                 });
                 let pending_file: PendingFile = args.into();
                 if pending_file.is_completed() {
@@ -104,7 +132,7 @@ impl Files {
             Occupied(mut e) => {
                 let pending_file = e.get_mut();
                 match pending_file.add_chunk(args.chunk_index, args.bytes) {
-                    AddChunkResult::Success => {}
+                    AddChunkResult::Success => pending_file.last_active = args.now,
                     AddChunkResult::ChunkIndexTooHigh => return PutChunkResult::ChunkIndexTooHigh,
                     AddChunkResult::ChunkAlreadyExists => return PutChunkResult::ChunkAlreadyExists,
                     AddChunkResult::ChunkSizeMismatch(m) => return PutChunkResult::ChunkSizeMismatch(m),
@@ -188,20 +216,23 @@ impl Files {
         self.reference_counts.incr(hash);
 
         let meta_data = file.meta_data();
+        let file_added_accessors = accessors.iter().copied().collect();
         let new_file = File {
             owner: caller,
             created: now,
             accessors,
             hash,
-            mime_type: file.mime_type,
+            mime_type: file.mime_type.clone(),
         };
 
-        if self.files.insert(new_file_id, new_file).is_none() {
+        if self.insert_file(new_file_id, new_file, None) {
             ForwardFileResult::Success(FileAdded {
                 file_id: new_file_id,
                 hash,
                 size,
                 meta_data,
+                mime_type: Some(file.mime_type),
+                accessors: Some(file_added_accessors),
             })
         } else {
             // There should never be a file_id clash
@@ -209,10 +240,238 @@ impl Files {
         }
     }
 
+    // Picks a blob which isn't already being migrated and grants the target bucket permission to
+    // copy it. The grant expires so that a failed migration doesn't lock the blob in place forever.
+    pub fn prepare_blob_migration(
+        &mut self,
+        target_bucket: CanisterId,
+        expires: TimestampMillis,
+        now: TimestampMillis,
+    ) -> Option<PrepareBlobMigrationResult> {
+        self.blob_migrations.retain(|_, m| m.expires > now);
+
+        let hash = self
+            .reference_counts
+            .counts
+            .keys()
+            .find(|h| !self.blob_migrations.contains_key(*h))
+            .copied()?;
+
+        let size = self.blobs.data_size(&hash)?;
+
+        let files = self
+            .files_by_hash
+            .get(&hash)
+            .into_iter()
+            .flatten()
+            .filter_map(|file_id| self.files.get(file_id).map(|f| (*file_id, f)))
+            .map(|(file_id, f)| MigratedFile {
+                file_id,
+                owner: f.owner,
+                created: f.created,
+                accessors: f.accessors.iter().copied().collect(),
+                mime_type: f.mime_type.clone(),
+                expiry: self.file_expiries.get(&file_id).copied(),
+            })
+            .collect();
+
+        self.blob_migrations.insert(hash, BlobMigration { target_bucket, expires });
+
+        Some(PrepareBlobMigrationResult { hash, size, files })
+    }
+
+    pub fn export_blob_chunk(
+        &self,
+        caller: CanisterId,
+        hash: &Hash,
+        offset: u64,
+        length: u32,
+        now: TimestampMillis,
+    ) -> ExportBlobChunkResult {
+        if !self
+            .blob_migrations
+            .get(hash)
+            .map_or(false, |m| m.target_bucket == caller && m.expires > now)
+        {
+            ExportBlobChunkResult::NotAuthorized
+        } else if let Some(bytes) = self.blobs.get_range(hash, offset, length) {
+            ExportBlobChunkResult::Success(bytes)
+        } else {
+            ExportBlobChunkResult::NotFound
+        }
+    }
+
+    // Adds the files which have been copied from another bucket, returning those which were added.
+    // If `bytes` is None then the blob must already exist in this bucket.
+    pub fn import_blob(&mut self, hash: Hash, bytes: Option<Vec<u8>>, files: Vec<MigratedFile>) -> Vec<MigratedFile> {
+        let mut imported = Vec::new();
+
+        for file in files {
+            if self.files.contains_key(&file.file_id) {
+                continue;
+            }
+
+            let accessors: HashSet<_> = file.accessors.iter().copied().collect();
+            self.accessors_map
+                .link_many(file.owner, accessors.iter().copied(), file.file_id);
+            self.reference_counts.incr(hash);
+
+            self.insert_file(
+                file.file_id,
+                File {
+                    owner: file.owner,
+                    created: file.created,
+                    accessors,
+                    hash,
+                    mime_type: file.mime_type.clone(),
+                },
+                file.expiry,
+            );
+            imported.push(file);
+        }
+
+        if !imported.is_empty() {
+            if let Some(bytes) = bytes {
+                self.add_blob_if_not_exists(hash, bytes);
+            }
+        }
+
+        imported
+    }
+
+    // Removes a file which has been copied to another bucket, without the removal being synced to
+    // the index, since the index has already moved the file reference over to the new bucket.
+    pub fn migrate_out(&mut self, file_id: FileId, new_bucket: CanisterId, now: TimestampMillis) -> Option<Principal> {
+        let file = self.files.remove(&file_id)?;
+        let owner = file.owner;
+        let hash = file.hash;
+
+        self.process_removed_file(file_id, file);
+        if !self.blobs.exists(&hash) {
+            self.blob_migrations.remove(&hash);
+        }
+
+        // Requests for migrated files are redirected to their new bucket for a limited time, by
+        // which point the index will have given clients the file's new location
+        while let Some((_, expired)) = self
+            .migrated_files_queue
+            .front()
+            .filter(|(t, _)| now.saturating_sub(*t) > MIGRATED_FILE_REDIRECT_DURATION)
+            .copied()
+        {
+            self.migrated_files_queue.pop_front();
+            self.migrated_files.remove(&expired);
+        }
+        self.migrated_files.insert(file_id, new_bucket);
+        self.migrated_files_queue.push_back((now, file_id));
+
+        Some(owner)
+    }
+
+    // Populates the indexes which were added after files had already been stored. This only does
+    // any work on the first upgrade after they were added.
+    pub fn populate_indexes_if_required(&mut self) {
+        if !self.files_by_hash.is_empty() || self.files.is_empty() {
+            return;
+        }
+        for (file_id, file) in self.files.iter() {
+            self.files_by_hash.entry(file.hash).or_default().insert(*file_id);
+        }
+        for (expiry, file_ids) in self.expiration_queue.iter() {
+            for file_id in file_ids.iter().filter(|id| self.files.contains_key(id)) {
+                self.file_expiries.insert(*file_id, *expiry);
+            }
+        }
+    }
+
+    // Checks that the owner holds the blob's bytes, since knowing its hash alone isn't enough to be
+    // allowed to reference it. The blob is hashed one chunk at a time so that large blobs are never
+    // loaded into the heap in full. Returns the size of the blob if the proof is valid.
+    pub fn check_possession_proof(&self, owner: Principal, hash: &Hash, proof: Hash) -> PossessionProofResult {
+        let Some(chunks) = self.blobs.chunks(hash) else {
+            return PossessionProofResult::NotFound;
+        };
+
+        let mut size = 0;
+        let expected = possession_proof_from_chunks(owner, chunks.inspect(|c| size += c.len() as u64));
+
+        if expected == proof {
+            PossessionProofResult::Valid(size)
+        } else {
+            PossessionProofResult::Invalid
+        }
+    }
+
+    // Adds a new file referencing an existing blob, so that a blob which already exists doesn't need to
+    // be uploaded again. The caller must already have checked the owner's possession proof.
+    pub fn forward_blob(&mut self, args: ForwardBlobArgs) -> ForwardBlobResult {
+        if self.files.contains_key(&args.file_id) || self.pending_files.contains_key(&args.file_id) {
+            return ForwardBlobResult::FileAlreadyExists;
+        }
+
+        let Some(size) = self.blobs.data_size(&args.hash) else {
+            return ForwardBlobResult::NotFound;
+        };
+
+        let file_id = args.file_id;
+        let accessors: HashSet<_> = args.accessors.into_iter().collect();
+        let file_added_accessors = accessors.iter().copied().collect();
+
+        self.accessors_map.link_many(args.owner, accessors.iter().copied(), file_id);
+        self.reference_counts.incr(args.hash);
+
+        self.insert_file(
+            file_id,
+            File {
+                owner: args.owner,
+                created: args.now,
+                accessors,
+                hash: args.hash,
+                mime_type: args.mime_type.clone(),
+            },
+            args.expiry,
+        );
+
+        ForwardBlobResult::Success(FileAdded {
+            file_id,
+            hash: args.hash,
+            size,
+            meta_data: FileMetaData {
+                owner: args.owner,
+                created: args.now,
+            },
+            mime_type: Some(args.mime_type),
+            accessors: Some(file_added_accessors),
+        })
+    }
+
     pub fn remove_pending_file(&mut self, file_id: &FileId) -> bool {
         self.pending_files.remove(file_id).is_some()
     }
 
+    pub fn remove_inactive_pending_files(&mut self, now: TimestampMillis, max_count: usize) -> Vec<FileRemoved> {
+        let file_ids: Vec<_> = self
+            .pending_files
+            .iter()
+            .filter(|(_, f)| f.upload_expiry() < now)
+            .map(|(file_id, _)| *file_id)
+            .take(max_count)
+            .collect();
+
+        file_ids
+            .into_iter()
+            .filter_map(|file_id| {
+                self.pending_files.remove(&file_id).map(|f| FileRemoved {
+                    file_id,
+                    meta_data: FileMetaData {
+                        owner: f.owner,
+                        created: f.created,
+                    },
+                })
+            })
+            .collect()
+    }
+
     pub fn remove_accessor(&mut self, accessor_id: &AccessorId) -> Vec<FileRemoved> {
         let mut files_removed = Vec::new();
 
@@ -317,14 +576,7 @@ impl Files {
         self.reference_counts.incr(completed_file.hash);
         self.add_blob_if_not_exists(completed_file.hash, completed_file.bytes.into_vec());
 
-        if let Some(expiry) = completed_file.expiry {
-            self.expiration_queue
-                .entry(expiry)
-                .or_insert_with(VecDeque::new)
-                .push_back(file_id);
-        }
-
-        self.files.insert(
+        self.insert_file(
             file_id,
             File {
                 owner: completed_file.owner,
@@ -333,10 +585,44 @@ impl Files {
                 hash: completed_file.hash,
                 mime_type: completed_file.mime_type,
             },
+            completed_file.expiry,
         );
     }
 
+    // Returns false if a file with the same id already exists, in which case nothing is changed
+    fn insert_file(&mut self, file_id: FileId, file: File, expiry: Option<TimestampMillis>) -> bool {
+        let Vacant(e) = self.files.entry(file_id) else {
+            return false;
+        };
+
+        self.files_by_hash.entry(file.hash).or_default().insert(file_id);
+        if let Some(expiry) = expiry {
+            self.expiration_queue
+                .entry(expiry)
+                .or_insert_with(VecDeque::new)
+                .push_back(file_id);
+            self.file_expiries.insert(file_id, expiry);
+        }
+        e.insert(file);
+        true
+    }
+
     fn process_removed_file(&mut self, file_id: FileId, file: File) -> FileRemoved {
+        if let Occupied(mut e) = self.files_by_hash.entry(file.hash) {
+            e.get_mut().remove(&file_id);
+            if e.get().is_empty() {
+                e.remove();
+            }
+        }
+        if let Some(expiry) = self.file_expiries.remove(&file_id) {
+            if let Occupied(mut e) = self.expiration_queue.entry(expiry) {
+                e.get_mut().retain(|id| *id != file_id);
+                if e.get().is_empty() {
+                    e.remove();
+                }
+            }
+        }
+
         if self.reference_counts.decr(file.hash) == 0 {
             self.remove_blob(&file.hash);
         }
@@ -452,31 +738,48 @@ pub struct PendingFile {
     pub remaining_chunks: HashSet<u32>,
     pub bytes: ByteBuf,
     pub expiry: Option<TimestampMillis>,
+    #[serde(default)]
+    pub last_active: TimestampMillis,
 }
 
 impl PendingFile {
     pub fn add_chunk(&mut self, chunk_index: u32, bytes: ByteBuf) -> AddChunkResult {
-        if self.remaining_chunks.remove(&chunk_index) {
-            if let Some(expected_chunk_size) = self.expected_chunk_size(chunk_index) {
-                let actual_chunk_size = bytes.len() as u32;
-                if expected_chunk_size != actual_chunk_size {
-                    return AddChunkResult::ChunkSizeMismatch(ChunkSizeMismatch {
-                        expected_size: expected_chunk_size,
-                        actual_size: actual_chunk_size,
-                    });
-                }
-            } else {
-                return AddChunkResult::ChunkIndexTooHigh;
-            }
-
-            let start_index = self.chunk_size as usize * chunk_index as usize;
-            let end_index = start_index + bytes.len();
-            self.bytes[start_index..end_index].copy_from_slice(&bytes);
+        if !self.remaining_chunks.contains(&chunk_index) {
+            return AddChunkResult::ChunkAlreadyExists;
+        }
 
-            AddChunkResult::Success
+        // Only mark the chunk as received once it has been validated, so that an invalid chunk can
+        // be retried as part of resuming the upload
+        if let Some(expected_chunk_size) = self.expected_chunk_size(chunk_index) {
+            let actual_chunk_size = bytes.len() as u32;
+            if expected_chunk_size != actual_chunk_size {
+                return AddChunkResult::ChunkSizeMismatch(ChunkSizeMismatch {
+                    expected_size: expected_chunk_size,
+                    actual_size: actual_chunk_size,
+                });
+            }
         } else {
-            AddChunkResult::ChunkAlreadyExists
+            return AddChunkResult::ChunkIndexTooHigh;
         }
+
+        let start_index = self.chunk_size as usize * chunk_index as usize;
+        let end_index = start_index + bytes.len();
+        self.bytes[start_index..end_index].copy_from_slice(&bytes);
+        self.remaining_chunks.remove(&chunk_index);
+
+        AddChunkResult::Success
+    }
+
+    pub fn chunks_received(&self) -> Vec<u32> {
+        (0..self.chunk_count())
+            .filter(|i| !self.remaining_chunks.contains(i))
+            .collect()
+    }
+
+    // Pending files which receive no chunks within the TTL are abandoned. Files uploaded before
+    // `last_active` was introduced fall back to their created date.
+    pub fn upload_expiry(&self) -> TimestampMillis {
+        max(self.created, self.last_active).saturating_add(PENDING_FILE_TTL)
     }
 
     pub fn chunk_count(&self) -> u32 {
@@ -551,6 +854,7 @@ impl From<PutChunkArgs> for PendingFile {
             remaining_chunks: (0..chunk_count).collect(),
             bytes: ByteBuf::from(vec![0; args.total_size as usize]),
             expiry: args.expiry,
+            last_active: args.now,
         };
         pending_file.add_chunk(args.chunk_index, args.bytes);
         pending_file
@@ -579,6 +883,34 @@ pub enum RemoveFileResult {
     NotFound,
 }
 
+pub enum ExportBlobChunkResult {
+    Success(Vec<u8>),
+    NotAuthorized,
+    NotFound,
+}
+
+pub struct ForwardBlobArgs {
+    pub owner: Principal,
+    pub file_id: FileId,
+    pub hash: Hash,
+    pub mime_type: String,
+    pub accessors: Vec<AccessorId>,
+    pub expiry: Option<TimestampMillis>,
+    pub now: TimestampMillis,
+}
+
+pub enum ForwardBlobResult {
+    Success(FileAdded),
+    FileAlreadyExists,
+    NotFound,
+}
+
+pub enum PossessionProofResult {
+    Valid(u64),
+    Invalid,
+    NotFound,
+}
+
 pub enum ForwardFileResult {
     Success(FileAdded),
     NotFound,
@@ -600,3 +932,42 @@ pub struct Metrics {
     pub file_count: u64,
     pub blob_count: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrating_out_removes_file_from_indexes() {
+        let mut files = Files::default();
+        let owner = Principal::from_slice(&[1]);
+        let bucket = CanisterId::from_slice(&[2]);
+        let bytes = vec![1, 2, 3];
+        let hash = hash_bytes(&bytes);
+
+        let migrated_file = |file_id, expiry| MigratedFile {
+            file_id,
+            owner,
+            created: 0,
+            accessors: Vec::new(),
+            mime_type: "image/png".to_string(),
+            expiry,
+        };
+        files.import_blob(hash, Some(bytes), vec![migrated_file(1, Some(100)), migrated_file(2, None)]);
+
+        let prepared = files.prepare_blob_migration(bucket, 50, 0).unwrap();
+        let mut expiries: Vec<_> = prepared.files.iter().map(|f| (f.file_id, f.expiry)).collect();
+        expiries.sort();
+        assert_eq!(expiries, vec![(1, Some(100)), (2, None)]);
+
+        files.migrate_out(1, bucket, 10);
+        assert_eq!(files.migrated_to(&1), Some(bucket));
+        assert!(files.expiration_queue.is_empty());
+        assert_eq!(files.files_by_hash.get(&hash).map(|f| f.len()), Some(1));
+
+        // The redirect is dropped once it has expired
+        files.migrate_out(2, bucket, 10 + MIGRATED_FILE_REDIRECT_DURATION + 1);
+        assert!(files.migrated_to(&1).is_none());
+        assert!(files.files_by_hash.is_empty());
+    }
+}
@@ -27,6 +27,38 @@ impl StableBlobStorage {
         Some(iter.map(|(_, c)| c.bytes.len() as u64).sum())
     }
 
+    pub fn get_range(&self, hash: &Hash, offset: u64, length: u32) -> Option<Vec<u8>> {
+        if !self.exists(hash) {
+            return None;
+        }
+
+        let hash = *hash;
+        let first_chunk_index = (offset / MAX_CHUNK_SIZE as u64) as u32;
+        let mut skip = (offset % MAX_CHUNK_SIZE as u64) as usize;
+        let mut bytes = Vec::with_capacity(length as usize);
+
+        for (_, chunk) in self
+            .blobs
+            .range(Key::new(hash, first_chunk_index)..)
+            .take_while(|(k, _)| k.prefix == hash)
+        {
+            let chunk_bytes = &chunk.bytes[skip.min(chunk.bytes.len())..];
+            let remaining = length as usize - bytes.len();
+            bytes.extend_from_slice(&chunk_bytes[..remaining.min(chunk_bytes.len())]);
+            skip = 0;
+
+            if bytes.len() >= length as usize {
+                break;
+            }
+        }
+
+        Some(bytes)
+    }
+
+    pub fn chunks(&self, hash: &Hash) -> Option<impl Iterator<Item = Vec<u8>> + '_> {
+        self.value_chunks_iterator(*hash).map(|i| i.map(|(_, c)| c.bytes))
+    }
+
     pub fn exists(&self, hash: &Hash) -> bool {
         self.value_chunks_iterator(*hash).is_some()
     }
@@ -179,6 +211,21 @@ mod tests {
         assert_eq!(value_in, value_out)
     }
 
+    #[test]
+    fn get_range_spans_chunks() {
+        let mut stable_storage = StableBlobStorage::default();
+
+        let hash = default_hash();
+        let value: Vec<_> = (0..10000).map(|i| (i % 101) as u8).collect();
+
+        stable_storage.insert(hash, value.clone());
+
+        assert_eq!(stable_storage.get_range(&hash, 0, 100).unwrap(), value[..100]);
+        assert_eq!(stable_storage.get_range(&hash, 4000, 5000).unwrap(), value[4000..9000]);
+        assert_eq!(stable_storage.get_range(&hash, 9000, 5000).unwrap(), value[9000..]);
+        assert!(stable_storage.get_range(&hash, 10000, 100).unwrap().is_empty());
+    }
+
     // Checks that for keys with matching prefixes, KeyA > KeyB <=> chunk_index A > chunk_index B
     #[test]
     fn key_ordering() {
@@ -60,6 +60,10 @@ impl UserRecord {
     pub fn set_file_status(&mut self, file_id: FileId, status: FileStatusInternal) -> Option<FileStatusInternal> {
         self.files_owned.insert(file_id, status)
     }
+
+    pub fn remove_file(&mut self, file_id: &FileId) -> Option<FileStatusInternal> {
+        self.files_owned.remove(file_id)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
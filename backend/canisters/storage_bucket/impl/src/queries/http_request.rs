@@ -1,12 +1,13 @@
 use crate::{calc_chunk_count, read_state, RuntimeState};
+use canister_logger::LogQuery;
 use http_request::{build_json_response, encode_logs, extract_route, Route};
 use ic_cdk_macros::query;
 use num_traits::cast::ToPrimitive;
 use serde_bytes::ByteBuf;
 use std::cmp::min;
 use types::{
-    CallbackFunc, FileId, HeaderField, HttpRequest, HttpResponse, StreamingCallbackHttpResponse, StreamingStrategy,
-    TimestampMillis, Token,
+    CallbackFunc, CanisterId, FileId, HeaderField, HttpRequest, HttpResponse, StreamingCallbackHttpResponse, StreamingStrategy,
+    Token,
 };
 
 const BLOB_RESPONSE_CHUNK_SIZE_BYTES: u32 = 1 << 19; // 1/2 MB
@@ -14,12 +15,12 @@ const CACHE_HEADER_VALUE: &str = "public, max-age=100000000, immutable";
 
 #[query]
 fn http_request(request: HttpRequest) -> HttpResponse {
-    fn get_logs_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_logs(), since.unwrap_or(0))
+    fn get_logs_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::query_logs(&query))
     }
 
-    fn get_traces_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_traces(), since.unwrap_or(0))
+    fn get_traces_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::query_traces(&query))
     }
 
     fn get_metrics_impl(state: &RuntimeState) -> HttpResponse {
@@ -27,9 +28,9 @@ fn http_request(request: HttpRequest) -> HttpResponse {
     }
 
     match extract_route(&request.url) {
-        Route::File(file_id) => read_state(|state| start_streaming_file(file_id, state)),
-        Route::Logs(since) => get_logs_impl(since),
-        Route::Traces(since) => get_traces_impl(since),
+        Route::File(file_id) => read_state(|state| start_streaming_file(file_id, &request, state)),
+        Route::Logs(query) => get_logs_impl(query),
+        Route::Traces(query) => get_traces_impl(query),
         Route::Metrics => read_state(get_metrics_impl),
         _ => HttpResponse::not_found(),
     }
@@ -40,7 +41,7 @@ fn http_request_streaming_callback(token: Token) -> StreamingCallbackHttpRespons
     read_state(|state| continue_streaming_file(token, state))
 }
 
-fn start_streaming_file(file_id: FileId, state: &RuntimeState) -> HttpResponse {
+fn start_streaming_file(file_id: FileId, request: &HttpRequest, state: &RuntimeState) -> HttpResponse {
     if let Some(file) = state.data.files.get(&file_id) {
         if let Some(bytes) = state.data.files.blob_bytes(&file.hash) {
             let canister_id = state.env.canister_id();
@@ -73,9 +74,34 @@ fn start_streaming_file(file_id: FileId, state: &RuntimeState) -> HttpResponse {
         }
     }
 
+    if let Some(bucket) = state.data.files.migrated_to(&file_id) {
+        return redirect_to_bucket(bucket, request, state);
+    }
+
     HttpResponse::not_found()
 }
 
+// Files which have been migrated to another bucket are redirected so that existing links keep working
+fn redirect_to_bucket(bucket: CanisterId, request: &HttpRequest, state: &RuntimeState) -> HttpResponse {
+    let this_canister_id = state.env.canister_id().to_string();
+
+    request
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("host"))
+        .map(|(_, host)| host)
+        .filter(|host| host.contains(&this_canister_id))
+        .map(|host| {
+            let location = format!(
+                "https://{}{}",
+                host.replace(&this_canister_id, &bucket.to_string()),
+                request.url
+            );
+            HttpResponse::moved_permanently(&location)
+        })
+        .unwrap_or_else(HttpResponse::not_found)
+}
+
 fn continue_streaming_file(token: Token, state: &RuntimeState) -> StreamingCallbackHttpResponse {
     if let Route::File(file_id) = extract_route(&token.key) {
         let chunk_index = token.index.0.to_u32().unwrap();
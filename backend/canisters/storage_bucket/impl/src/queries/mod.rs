@@ -1,3 +1,4 @@
 mod file_info;
 mod file_status;
 mod http_request;
+mod upload_progress;
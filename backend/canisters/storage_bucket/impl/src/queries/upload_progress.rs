@@ -0,0 +1,26 @@
+use crate::guards::caller_is_known_user;
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use storage_bucket_canister::upload_progress::{Response::*, *};
+
+#[query(guard = "caller_is_known_user")]
+fn upload_progress(args: Args) -> Response {
+    read_state(|state| upload_progress_impl(args, state))
+}
+
+fn upload_progress_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+
+    if let Some(pending_file) = state.data.files.pending_file(&args.file_id).filter(|f| f.owner == caller) {
+        Success(SuccessResult {
+            chunk_size: pending_file.chunk_size,
+            total_size: pending_file.total_size,
+            chunks_received: pending_file.chunks_received(),
+            expires: pending_file.upload_expiry(),
+        })
+    } else if state.data.files.get(&args.file_id).map_or(false, |f| f.owner == caller) {
+        Completed
+    } else {
+        NotFound
+    }
+}
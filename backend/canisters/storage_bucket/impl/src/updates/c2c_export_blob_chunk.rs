@@ -0,0 +1,27 @@
+use crate::model::files::ExportBlobChunkResult;
+use crate::{read_state, RuntimeState, MAX_BLOB_MIGRATION_CHUNK_SIZE_BYTES};
+use ic_cdk_macros::query;
+use serde_bytes::ByteBuf;
+use storage_bucket_canister::c2c_export_blob_chunk::{Response::*, *};
+
+// Called by other buckets to copy a blob which the index has asked this bucket to migrate to them
+#[query]
+fn c2c_export_blob_chunk(args: Args) -> Response {
+    read_state(|state| c2c_export_blob_chunk_impl(args, state))
+}
+
+fn c2c_export_blob_chunk_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let now = state.env.now();
+    let length = args.length.min(MAX_BLOB_MIGRATION_CHUNK_SIZE_BYTES);
+
+    match state
+        .data
+        .files
+        .export_blob_chunk(caller, &args.hash, args.offset, length, now)
+    {
+        ExportBlobChunkResult::Success(bytes) => Success(ByteBuf::from(bytes)),
+        ExportBlobChunkResult::NotAuthorized => NotAuthorized,
+        ExportBlobChunkResult::NotFound => NotFound,
+    }
+}
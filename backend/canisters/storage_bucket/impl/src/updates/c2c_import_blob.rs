@@ -0,0 +1,77 @@
+use crate::guards::caller_is_storage_index_canister;
+use crate::model::users::{FileStatusInternal, IndexSyncComplete};
+use crate::{mutate_state, read_state, RuntimeState, DATA_LIMIT_BYTES, MAX_BLOB_MIGRATION_CHUNK_SIZE_BYTES};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use storage_bucket_canister::c2c_export_blob_chunk;
+use storage_bucket_canister::c2c_import_blob::{Response::*, *};
+use types::{CanisterId, Hash};
+use utils::hasher::hash_bytes;
+
+// Copies a blob and its files from another bucket. The index only moves its file references over
+// to this bucket once this call succeeds, after which it tells the source bucket to delete its copy.
+#[update(guard = "caller_is_storage_index_canister")]
+#[trace]
+async fn c2c_import_blob(args: Args) -> Response {
+    let blob_exists = match read_state(|state| prepare(&args, state)) {
+        Ok(exists) => exists,
+        Err(response) => return response,
+    };
+
+    let bytes = if blob_exists {
+        None
+    } else {
+        match download_blob(args.source_bucket, args.hash, args.size).await {
+            Ok(bytes) if hash_bytes(&bytes) == args.hash => Some(bytes),
+            Ok(_) => return HashMismatch,
+            Err(error) => return InternalError(error),
+        }
+    };
+
+    mutate_state(|state| commit(args, bytes, state))
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<bool, Response> {
+    if state.data.files.blob_exists(&args.hash) {
+        Ok(true)
+    } else if state.data.files.bytes_used().saturating_add(args.size) > DATA_LIMIT_BYTES {
+        Err(InsufficientSpace)
+    } else {
+        Ok(false)
+    }
+}
+
+async fn download_blob(source_bucket: CanisterId, hash: Hash, size: u64) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(size as usize);
+
+    while (bytes.len() as u64) < size {
+        let args = c2c_export_blob_chunk::Args {
+            hash,
+            offset: bytes.len() as u64,
+            length: MAX_BLOB_MIGRATION_CHUNK_SIZE_BYTES,
+        };
+
+        match storage_bucket_canister_c2c_client::c2c_export_blob_chunk(source_bucket, &args).await {
+            Ok(c2c_export_blob_chunk::Response::Success(chunk)) if !chunk.is_empty() => bytes.extend(chunk.into_vec()),
+            Ok(response) => return Err(format!("Failed to export blob chunk: {response:?}")),
+            Err(error) => return Err(format!("{error:?}")),
+        }
+    }
+
+    Ok(bytes)
+}
+
+fn commit(args: Args, bytes: Option<Vec<u8>>, state: &mut RuntimeState) -> Response {
+    for file in state.data.files.import_blob(args.hash, bytes, args.files) {
+        if let Some(user) = state.data.users.get_mut(&file.owner) {
+            user.set_file_status(file.file_id, FileStatusInternal::Complete(IndexSyncComplete::Yes));
+        }
+    }
+
+    let bytes_used = state.data.files.bytes_used();
+
+    Success(SuccessResult {
+        bytes_used,
+        bytes_remaining: (DATA_LIMIT_BYTES as i64) - (bytes_used as i64),
+    })
+}
@@ -0,0 +1,21 @@
+use crate::guards::caller_is_storage_index_canister;
+use crate::{mutate_state, RuntimeState, BLOB_MIGRATION_GRANT_DURATION};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use storage_bucket_canister::c2c_prepare_blob_migration::{Response::*, *};
+
+#[update(guard = "caller_is_storage_index_canister")]
+#[trace]
+fn c2c_prepare_blob_migration(args: Args) -> Response {
+    mutate_state(|state| c2c_prepare_blob_migration_impl(args, state))
+}
+
+fn c2c_prepare_blob_migration_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    let expires = now + BLOB_MIGRATION_GRANT_DURATION;
+
+    match state.data.files.prepare_blob_migration(args.target_bucket, expires, now) {
+        Some(result) => Success(result),
+        None => NothingToMigrate,
+    }
+}
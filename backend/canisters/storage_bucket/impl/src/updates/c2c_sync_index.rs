@@ -50,6 +50,15 @@ fn c2c_sync_index_impl(args: Args, state: &mut RuntimeState) -> Response {
         }
     }
 
+    let now = state.env.now();
+    for (file_id, new_bucket) in args.files_migrated {
+        if let Some(owner) = state.data.files.migrate_out(file_id, new_bucket, now) {
+            if let Some(user) = state.data.users.get_mut(&owner) {
+                user.remove_file(&file_id);
+            }
+        }
+    }
+
     for (old_user_id, new_user_id) in args.user_ids_updated {
         if state.data.users.update_user_id(old_user_id, new_user_id) {
             let user = state.data.users.get(&new_user_id).unwrap();
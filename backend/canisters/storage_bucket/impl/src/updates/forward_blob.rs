@@ -0,0 +1,109 @@
+use crate::guards::caller_is_known_user;
+use crate::model::files::{ForwardBlobArgs, ForwardBlobResult, PossessionProofResult};
+use crate::model::index_sync_state::EventToSync;
+use crate::model::users::{FileStatusInternal, IndexSyncComplete};
+use crate::{mutate_state, read_state, RuntimeState};
+use candid::Principal;
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use storage_bucket_canister::forward_blob::{Response::*, *};
+use storage_index_canister::c2c_check_allowance;
+use types::CanisterId;
+use utils::file_id::validate_file_id;
+
+#[update(guard = "caller_is_known_user")]
+#[trace]
+async fn forward_blob(args: Args) -> Response {
+    let PrepareResult {
+        caller,
+        storage_index_canister_id,
+        size,
+    } = match read_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    // Forwarded blobs are charged to the user's allowance in the same way as uploads, once the file
+    // is synced to the index. Checking the allowance up front means files which would only be
+    // rejected by the index are never added in the first place.
+    let check_allowance_args = c2c_check_allowance::Args {
+        user_id: caller,
+        file_hash: args.hash,
+        file_size: size,
+    };
+    match storage_index_canister_c2c_client::c2c_check_allowance(storage_index_canister_id, &check_allowance_args).await {
+        Ok(c2c_check_allowance::Response::Success(_)) => {}
+        Ok(c2c_check_allowance::Response::AllowanceExceeded(_)) => return AllowanceExceeded,
+        Ok(c2c_check_allowance::Response::UserNotFound) => return UserNotFound,
+        Err(error) => return InternalError(format!("{error:?}")),
+    }
+
+    mutate_state(|state| forward_blob_impl(caller, args, state))
+}
+
+struct PrepareResult {
+    caller: Principal,
+    storage_index_canister_id: CanisterId,
+    size: u64,
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    let caller = state.env.caller();
+
+    if !validate_file_id(args.file_id, state.env.canister_id()) {
+        return Err(InvalidFileId);
+    }
+
+    if state
+        .data
+        .users
+        .get(&caller)
+        .map_or(false, |u| u.file_status(&args.file_id).is_some())
+    {
+        return Err(FileAlreadyExists);
+    }
+
+    match state
+        .data
+        .files
+        .check_possession_proof(caller, &args.hash, args.possession_proof)
+    {
+        PossessionProofResult::Valid(size) => Ok(PrepareResult {
+            caller,
+            storage_index_canister_id: state.data.storage_index_canister_id,
+            size,
+        }),
+        PossessionProofResult::Invalid => Err(InvalidPossessionProof),
+        PossessionProofResult::NotFound => Err(NotFound),
+    }
+}
+
+fn forward_blob_impl(caller: Principal, args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+    let file_id = args.file_id;
+
+    let Some(user) = state.data.users.get_mut(&caller) else {
+        return UserNotFound;
+    };
+    if user.file_status(&file_id).is_some() {
+        return FileAlreadyExists;
+    }
+
+    match state.data.files.forward_blob(ForwardBlobArgs {
+        owner: caller,
+        file_id,
+        hash: args.hash,
+        mime_type: args.mime_type,
+        accessors: args.accessors,
+        expiry: args.expiry,
+        now,
+    }) {
+        ForwardBlobResult::Success(file_added) => {
+            user.set_file_status(file_id, FileStatusInternal::Complete(IndexSyncComplete::No));
+            state.data.index_sync_state.enqueue(EventToSync::FileAdded(file_added));
+            Success
+        }
+        ForwardBlobResult::FileAlreadyExists => FileAlreadyExists,
+        ForwardBlobResult::NotFound => NotFound,
+    }
+}
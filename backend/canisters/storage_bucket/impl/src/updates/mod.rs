@@ -1,6 +1,10 @@
+mod c2c_export_blob_chunk;
+mod c2c_import_blob;
+mod c2c_prepare_blob_migration;
 mod c2c_sync_index;
 mod delete_file;
 mod delete_files;
+mod forward_blob;
 mod forward_file;
 mod upload_chunk;
 mod wallet_receive;
@@ -8,6 +8,7 @@ use ic_cdk_macros::update;
 use storage_bucket_canister::upload_chunk_v2::{Response::*, *};
 use types::{FileRemoved, RejectedReason};
 use utils::file_id::validate_file_id;
+use utils::hasher::hash_bytes;
 
 #[update(guard = "caller_is_known_user")]
 #[trace]
@@ -25,6 +26,10 @@ fn upload_chunk_impl(args: Args, state: &mut RuntimeState) -> Response {
         return InvalidFileId;
     }
 
+    if args.chunk_hash.map_or(false, |h| h != hash_bytes(&args.bytes)) {
+        return ChunkHashMismatch;
+    }
+
     let mut index_sync_complete = IndexSyncComplete::No;
     if let Some(status) = user.file_status(&file_id) {
         match status {
@@ -5,9 +5,11 @@ fn main() {
     generate_candid_method!(storage_index, allocated_bucket_v2, query);
     generate_candid_method!(storage_index, can_forward, query);
     generate_candid_method!(storage_index, user, query);
+    generate_candid_method!(storage_index, user_files, query);
 
     generate_candid_method!(storage_index, add_bucket_canister, update);
     generate_candid_method!(storage_index, add_or_update_users, update);
+    generate_candid_method!(storage_index, delete_user_files, update);
     generate_candid_method!(storage_index, remove_accessor, update);
     generate_candid_method!(storage_index, remove_user, update);
     generate_candid_method!(storage_index, set_bucket_full, update);
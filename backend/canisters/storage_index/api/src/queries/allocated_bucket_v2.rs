@@ -27,4 +27,5 @@ pub struct SuccessResult {
     pub bytes_used: u64,
     pub bytes_used_after_upload: u64,
     pub projected_allowance: ProjectedAllowance,
+    pub blob_exists: bool,
 }
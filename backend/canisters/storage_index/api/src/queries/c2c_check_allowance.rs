@@ -0,0 +1,12 @@
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+use types::Hash;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub user_id: Principal,
+    pub file_hash: Hash,
+    pub file_size: u64,
+}
+
+pub type Response = crate::can_forward::Response;
@@ -1,3 +1,5 @@
 pub mod allocated_bucket_v2;
+pub mod c2c_check_allowance;
 pub mod can_forward;
 pub mod user;
+pub mod user_files;
@@ -0,0 +1,46 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{AccessorId, CanisterId, FileId, Hash, TimestampMillis};
+
+#[derive(CandidType, Serialize, Deserialize, Debug, Default)]
+pub struct Args {
+    // Pass in the `next` value from the previous page to continue from where it left off
+    #[serde(default)]
+    pub start_after: Option<UserFilesCursor>,
+    #[serde(default)]
+    pub max_results: Option<u32>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    UserNotFound,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub byte_limit: u64,
+    pub bytes_used: u64,
+    pub files: Vec<UserFile>,
+    // Set if there are more files to fetch
+    pub next: Option<UserFilesCursor>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct UserFilesCursor {
+    pub created: TimestampMillis,
+    pub file_id: FileId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct UserFile {
+    pub file_id: FileId,
+    pub bucket: CanisterId,
+    pub hash: Hash,
+    pub size: u64,
+    pub created: TimestampMillis,
+    // Will be `None` for files uploaded before mime types were synced to the index
+    pub mime_type: Option<String>,
+    // The chats (and the owner) which have access to the file
+    pub accessors: Vec<AccessorId>,
+}
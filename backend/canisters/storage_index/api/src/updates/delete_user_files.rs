@@ -0,0 +1,22 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::FileId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub file_ids: Vec<FileId>,
+    // If set, the user's oldest files will also be deleted until at least this many bytes are freed
+    pub free_up_bytes: Option<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    UserNotFound,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub files_deleted: Vec<FileId>,
+    pub bytes_freed: u64,
+}
@@ -3,6 +3,7 @@ use storage_index_canister::*;
 
 // Queries
 generate_candid_c2c_call!(allocated_bucket_v2);
+generate_candid_c2c_call!(c2c_check_allowance);
 generate_candid_c2c_call!(user);
 
 // Updates
@@ -1,15 +1,17 @@
 use crate::model::bucket_sync_state::EventToSync;
 use crate::model::buckets::{BucketRecord, Buckets};
-use crate::model::files::Files;
+use crate::model::files::{Files, RemoveFileError, UserFile};
 use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use storage_bucket_canister::MigratedFile;
 use storage_index_canister::init::CyclesDispenserConfig;
+use storage_index_canister::{can_forward, ProjectedAllowance};
 use types::{
     BuildVersion, CanisterId, CanisterWasm, Cycles, CyclesTopUp, FileAdded, FileRejected, FileRejectedReason, FileRemoved,
-    TimestampMillis, Timestamped,
+    Hash, TimestampMillis, Timestamped,
 };
 use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount};
 use utils::env::Environment;
@@ -121,6 +123,32 @@ impl Data {
         }
     }
 
+    // Checks whether the user has enough allowance remaining to add a file, without charging for it.
+    // Files are only charged for once their buckets sync them to the index.
+    pub fn check_allowance(&self, user_id: Principal, file_hash: Hash, file_size: u64) -> can_forward::Response {
+        if let Some(user) = self.users.get(&user_id) {
+            let user_owns_blob = self.files.user_owns_blob(user_id, file_hash);
+            let file_size = self.files.blob_size(&file_hash).unwrap_or(file_size);
+
+            let bytes_used_after_operation = if user_owns_blob { user.bytes_used } else { user.bytes_used + file_size };
+
+            let projected_allowance = ProjectedAllowance {
+                bytes_used: user.bytes_used,
+                byte_limit: user.byte_limit,
+                bytes_used_after_upload: bytes_used_after_operation,
+                bytes_used_after_operation,
+            };
+
+            if user.byte_limit >= bytes_used_after_operation || user.delete_oldest_if_limit_exceeded {
+                can_forward::Response::Success(projected_allowance)
+            } else {
+                can_forward::Response::AllowanceExceeded(projected_allowance)
+            }
+        } else {
+            can_forward::Response::UserNotFound
+        }
+    }
+
     pub fn add_file_reference(&mut self, bucket: CanisterId, file: FileAdded) -> Result<(), FileRejected> {
         let user_id = file.meta_data.owner;
         if let Some(user) = self.users.get_mut(&user_id) {
@@ -176,12 +204,60 @@ impl Data {
 
     pub fn remove_file_reference(&mut self, bucket: CanisterId, file: FileRemoved) {
         let user_id = file.meta_data.owner;
-        if let Ok(result) = self.files.remove(file, bucket) {
-            if !self.files.user_owns_blob(user_id, result.hash) {
-                if let Some(user) = self.users.get_mut(&user_id) {
-                    user.bytes_used = user.bytes_used.saturating_sub(result.size);
+        let file_id = file.file_id;
+        match self.files.remove(file, bucket) {
+            Ok(result) => {
+                if !self.files.user_owns_blob(user_id, result.hash) {
+                    if let Some(user) = self.users.get_mut(&user_id) {
+                        user.bytes_used = user.bytes_used.saturating_sub(result.size);
+                    }
                 }
             }
+            Err(RemoveFileError::InOtherBucket(other_bucket)) => {
+                // The file was removed from its old bucket while being migrated, so remove the new copy too
+                if let Some(bucket) = self.buckets.get_mut(&other_bucket) {
+                    bucket.sync_state.enqueue(EventToSync::FileToRemove(file_id));
+                }
+            }
+            Err(RemoveFileError::NotFound) => {}
+        }
+    }
+
+    // Called once a blob has been copied to the target bucket. The file references are moved over in
+    // a single step, then the source bucket is told to delete its copies of the files which moved.
+    // Any files which were deleted from the source bucket in the meantime are deleted from the target.
+    pub fn complete_blob_migration(
+        &mut self,
+        source: CanisterId,
+        target: CanisterId,
+        hash: Hash,
+        files: Vec<MigratedFile>,
+    ) -> BlobMigrationSummary {
+        let mut summary = BlobMigrationSummary::default();
+
+        for file in files {
+            let (bucket, event) = if self.files.move_file(file.file_id, file.owner, file.created, source, target) {
+                summary.files_moved += 1;
+                (source, EventToSync::FileMigrated(file.file_id, target))
+            } else {
+                summary.files_skipped += 1;
+                (target, EventToSync::FileToRemove(file.file_id))
+            };
+
+            if let Some(bucket) = self.buckets.get_mut(&bucket) {
+                bucket.sync_state.enqueue(event);
+            }
+        }
+
+        summary.references_remaining_in_source = self.files.blob_reference_count(hash, source);
+        summary
+    }
+
+    pub fn queue_files_for_deletion(&mut self, files: Vec<UserFile>) {
+        for file in files {
+            if let Some(bucket) = self.buckets.get_mut(&file.bucket) {
+                bucket.sync_state.enqueue(EventToSync::FileToRemove(file.file_id));
+            }
         }
     }
 
@@ -193,6 +269,13 @@ impl Data {
     }
 }
 
+#[derive(Default, Debug)]
+struct BlobMigrationSummary {
+    files_moved: u32,
+    files_skipped: u32,
+    references_remaining_in_source: u32,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct UserRecordInternal {
     pub byte_limit: u64,
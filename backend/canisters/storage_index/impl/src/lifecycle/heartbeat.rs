@@ -1,7 +1,7 @@
 use crate::{mutate_state, RuntimeState};
 use ic_cdk_macros::heartbeat;
 use storage_bucket_canister::c2c_sync_index::{Args, Response, SuccessResult};
-use tracing::error;
+use tracing::{error, info};
 use types::{BuildVersion, CanisterId, CanisterWasm, Cycles};
 
 const MAX_CONCURRENT_CANISTER_UPGRADES: usize = 1;
@@ -13,6 +13,7 @@ fn heartbeat() {
     ensure_sufficient_active_buckets::run();
     sync_buckets::run();
     upgrade_canisters::run();
+    rebalance_buckets::run();
 }
 
 mod ensure_sufficient_active_buckets {
@@ -132,10 +133,74 @@ mod sync_buckets {
     }
 }
 
+mod rebalance_buckets {
+    use super::*;
+    use storage_bucket_canister::{c2c_import_blob, c2c_prepare_blob_migration};
+    use types::TimestampMillis;
+
+    pub fn run() {
+        if let Some((source, target, started)) = mutate_state(|state| {
+            let now = state.env.now();
+            state
+                .data
+                .buckets
+                .try_to_acquire_migration_lock(now)
+                .map(|(source, target)| (source, target, now))
+        }) {
+            ic_cdk::spawn(migrate_blob(source, target, started));
+        }
+    }
+
+    async fn migrate_blob(source: CanisterId, target: CanisterId, started: TimestampMillis) {
+        if let Err(error) = migrate_blob_inner(source, target).await {
+            error!(%source, %target, %error, "Failed to migrate blob");
+        }
+        mutate_state(|state| state.data.buckets.release_migration_lock(started));
+    }
+
+    async fn migrate_blob_inner(source: CanisterId, target: CanisterId) -> Result<(), String> {
+        let prepare_args = c2c_prepare_blob_migration::Args { target_bucket: target };
+        let prepared = match storage_bucket_canister_c2c_client::c2c_prepare_blob_migration(source, &prepare_args).await {
+            Ok(c2c_prepare_blob_migration::Response::Success(result)) => result,
+            Ok(c2c_prepare_blob_migration::Response::NothingToMigrate) => return Ok(()),
+            Err(error) => return Err(format!("{error:?}")),
+        };
+
+        let import_args = c2c_import_blob::Args {
+            source_bucket: source,
+            hash: prepared.hash,
+            size: prepared.size,
+            files: prepared.files,
+        };
+        match storage_bucket_canister_c2c_client::c2c_import_blob(target, &import_args).await {
+            Ok(c2c_import_blob::Response::Success(result)) => {
+                mutate_state(|state| {
+                    let summary = state
+                        .data
+                        .complete_blob_migration(source, target, import_args.hash, import_args.files);
+
+                    state.data.buckets.record_blob_migrated(
+                        source,
+                        target,
+                        import_args.size,
+                        result.bytes_used,
+                        result.bytes_remaining,
+                    );
+
+                    info!(%source, %target, ?summary, "Blob migrated");
+                });
+                Ok(())
+            }
+            Ok(response) => Err(format!("{response:?}")),
+            Err(error) => Err(format!("{error:?}")),
+        }
+    }
+}
+
 mod upgrade_canisters {
     use super::*;
     use ic_cdk::api::management_canister::main::CanisterInstallMode;
-    use utils::canister::FailedUpgrade;
+    use utils::canister::{install_trapped, FailedUpgrade};
 
     type CanisterToUpgrade = utils::canister::CanisterToInstall<storage_bucket_canister::post_upgrade::Args>;
 
@@ -185,8 +250,9 @@ mod upgrade_canisters {
             Ok(_) => {
                 mutate_state(|state| on_success(canister_id, to_version, state));
             }
-            Err(_) => {
-                mutate_state(|state| on_failure(canister_id, from_version, to_version, state));
+            Err(error) => {
+                let trapped = install_trapped(&error);
+                mutate_state(|state| on_failure(canister_id, from_version, to_version, trapped, state));
             }
         }
     }
@@ -198,11 +264,22 @@ mod upgrade_canisters {
         state.data.canisters_requiring_upgrade.mark_success(&canister_id);
     }
 
-    fn on_failure(canister_id: CanisterId, from_version: BuildVersion, to_version: BuildVersion, state: &mut RuntimeState) {
-        state.data.canisters_requiring_upgrade.mark_failure(FailedUpgrade {
-            canister_id,
-            from_version,
-            to_version,
-        });
+    fn on_failure(
+        canister_id: CanisterId,
+        from_version: BuildVersion,
+        to_version: BuildVersion,
+        trapped: bool,
+        state: &mut RuntimeState,
+    ) {
+        let now = state.env.now();
+        state.data.canisters_requiring_upgrade.mark_failure(
+            FailedUpgrade {
+                canister_id,
+                from_version,
+                to_version,
+                trapped,
+            },
+            now,
+        );
     }
 }
@@ -9,6 +9,9 @@ const BLOB_REFERENCE_COUNTS: MemoryId = MemoryId::new(2);
 const BLOB_SIZES: MemoryId = MemoryId::new(3);
 const TOTAL_FILE_BYTES: MemoryId = MemoryId::new(4);
 const TOTAL_BLOB_BYTES: MemoryId = MemoryId::new(5);
+const FILE_DETAILS: MemoryId = MemoryId::new(6);
+const LOG_OVERFLOW_INDEX: MemoryId = MemoryId::new(7);
+const LOG_OVERFLOW_DATA: MemoryId = MemoryId::new(8);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -41,6 +44,18 @@ pub fn get_total_blob_bytes_memory() -> Memory {
     get_memory(TOTAL_BLOB_BYTES)
 }
 
+pub fn get_file_details_memory() -> Memory {
+    get_memory(FILE_DETAILS)
+}
+
+pub fn get_log_overflow_index_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_INDEX)
+}
+
+pub fn get_log_overflow_data_memory() -> Memory {
+    get_memory(LOG_OVERFLOW_DATA)
+}
+
 fn get_memory(id: MemoryId) -> Memory {
     MEMORY_MANAGER.with(|m| m.get(id))
 }
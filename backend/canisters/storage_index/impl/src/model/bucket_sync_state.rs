@@ -3,7 +3,7 @@ use candid::Principal;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use storage_bucket_canister::c2c_sync_index::Args;
-use types::{AccessorId, FileId};
+use types::{AccessorId, CanisterId, FileId};
 
 // We want to send events to the each bucket in order, so while a sync is in progress we avoid sending
 // more events in case the first batch fails and the second succeeds. If a sync fails, the args that
@@ -35,6 +35,7 @@ impl BucketSyncState {
                 accessors_removed: Vec::new(),
                 user_ids_updated: Vec::new(),
                 files_to_remove: Vec::new(),
+                files_migrated: Vec::new(),
             };
 
             for _ in 0..MAX_EVENTS_TO_SYNC_PER_BATCH {
@@ -45,6 +46,7 @@ impl BucketSyncState {
                         EventToSync::AccessorRemoved(r) => args.accessors_removed.push(r),
                         EventToSync::UserIdUpdated(old, new) => args.user_ids_updated.push((old, new)),
                         EventToSync::FileToRemove(file_id) => args.files_to_remove.push(file_id),
+                        EventToSync::FileMigrated(file_id, bucket) => args.files_migrated.push((file_id, bucket)),
                     }
                 } else {
                     break;
@@ -72,4 +74,5 @@ pub enum EventToSync {
     AccessorRemoved(AccessorId),
     UserIdUpdated(Principal, Principal),
     FileToRemove(FileId),
+    FileMigrated(FileId, CanisterId),
 }
@@ -4,15 +4,22 @@ use crate::BucketMetrics;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use storage_bucket_canister::c2c_sync_index;
-use types::{BuildVersion, CanisterId, CyclesTopUp, Hash};
+use types::{BuildVersion, CanisterId, CyclesTopUp, Hash, Milliseconds, TimestampMillis};
+use utils::time::HOUR_IN_MS;
 
 const TARGET_ACTIVE_BUCKETS: usize = 4;
+const MIN_BYTES_REMAINING_BEFORE_REBALANCING: i64 = 1 << 30; // 1GB
+const MIN_BYTES_REMAINING_TO_RECEIVE_BLOBS: i64 = 1 << 33; // 8GB
+const MIGRATION_LOCK_TIMEOUT: Milliseconds = HOUR_IN_MS;
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct Buckets {
     active_buckets: Vec<BucketRecord>,
     full_buckets: HashMap<CanisterId, BucketRecord>,
     creation_in_progress: bool,
+    // Not persisted, so that a migration interrupted by an upgrade can't hold the lock forever
+    #[serde(skip)]
+    migration_started: Option<TimestampMillis>,
 }
 
 impl Buckets {
@@ -51,6 +58,69 @@ impl Buckets {
         }
     }
 
+    // Blobs are migrated one at a time from the bucket with the least space remaining to the active
+    // bucket with the most space remaining, until every bucket is back above the threshold
+    pub fn try_to_acquire_migration_lock(&mut self, now: TimestampMillis) -> Option<(CanisterId, CanisterId)> {
+        // Buckets only grant access to a blob being migrated for a limited time, so beyond that
+        // the migration can't complete and the lock is safe to take over
+        if self
+            .migration_started
+            .map_or(false, |started| now.saturating_sub(started) < MIGRATION_LOCK_TIMEOUT)
+        {
+            return None;
+        }
+
+        let source = self
+            .iter()
+            .filter(|b| b.has_reported_usage() && b.bytes_remaining < MIN_BYTES_REMAINING_BEFORE_REBALANCING)
+            .min_by_key(|b| b.bytes_remaining)?
+            .canister_id;
+
+        let target = self
+            .active_buckets
+            .iter()
+            .filter(|b| b.canister_id != source)
+            .map(|b| {
+                (
+                    b.canister_id,
+                    if b.has_reported_usage() { b.bytes_remaining } else { i64::MAX },
+                )
+            })
+            .filter(|(_, bytes_remaining)| *bytes_remaining >= MIN_BYTES_REMAINING_TO_RECEIVE_BLOBS)
+            .max_by_key(|(_, bytes_remaining)| *bytes_remaining)?
+            .0;
+
+        self.migration_started = Some(now);
+        Some((source, target))
+    }
+
+    pub fn release_migration_lock(&mut self, started: TimestampMillis) {
+        // The lock may have timed out and been taken by a newer migration
+        if self.migration_started == Some(started) {
+            self.migration_started = None;
+        }
+    }
+
+    // Migrations don't generate any events for the buckets to sync back to the index, so we update
+    // the source bucket's usage here and take the target bucket's usage from its response
+    pub fn record_blob_migrated(
+        &mut self,
+        source: CanisterId,
+        target: CanisterId,
+        size: u64,
+        target_bytes_used: u64,
+        target_bytes_remaining: i64,
+    ) {
+        if let Some(bucket) = self.get_mut(&source) {
+            bucket.bytes_used = bucket.bytes_used.saturating_sub(size);
+            bucket.bytes_remaining = bucket.bytes_remaining.saturating_add(size as i64);
+        }
+        if let Some(bucket) = self.get_mut(&target) {
+            bucket.bytes_used = target_bytes_used;
+            bucket.bytes_remaining = target_bytes_remaining;
+        }
+    }
+
     pub fn allocate(&self, blob_hash: Hash) -> Option<CanisterId> {
         let bucket_count = self.active_buckets.len();
         if bucket_count == 0 {
@@ -140,6 +210,11 @@ impl BucketRecord {
             cycle_top_ups: Vec::new(),
         }
     }
+
+    // Buckets only report their usage once they have synced some files to the index
+    fn has_reported_usage(&self) -> bool {
+        self.bytes_used > 0 || self.bytes_remaining != 0
+    }
 }
 
 impl From<&BucketRecord> for BucketMetrics {
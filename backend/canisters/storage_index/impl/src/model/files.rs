@@ -1,12 +1,13 @@
 use crate::memory::{
-    get_blob_reference_counts_memory, get_blob_sizes_memory, get_files_by_user_memory, get_total_blob_bytes_memory,
-    get_total_file_bytes_memory, Memory,
+    get_blob_reference_counts_memory, get_blob_sizes_memory, get_file_details_memory, get_files_by_user_memory,
+    get_total_blob_bytes_memory, get_total_file_bytes_memory, Memory,
 };
 use candid::Principal;
 use ic_stable_structures::{BoundedStorable, StableBTreeMap, StableCell, Storable};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use types::{CanisterId, FileAdded, FileId, FileRemoved, Hash, TimestampMillis};
+use std::ops::Bound;
+use types::{AccessorId, CanisterId, FileAdded, FileId, FileRemoved, Hash, TimestampMillis};
 
 #[derive(Serialize, Deserialize)]
 pub struct Files {
@@ -20,6 +21,8 @@ pub struct Files {
     total_file_bytes: StableCell<u64, Memory>,
     #[serde(skip, default = "init_total_blob_bytes")]
     total_blob_bytes: StableCell<u64, Memory>,
+    #[serde(skip, default = "init_file_details")]
+    file_details: StableBTreeMap<[u8; 16], FileDetails, Memory>,
 }
 
 impl Files {
@@ -35,6 +38,11 @@ impl Files {
             }
         }
 
+        if let Some(mime_type) = file.mime_type {
+            let details = FileDetails::new(mime_type, file.accessors.unwrap_or_default());
+            self.file_details.insert(file.file_id.to_be_bytes(), details);
+        }
+
         let blob_reference = BlobReference {
             hash: file.hash,
             user_id: file.meta_data.owner,
@@ -59,8 +67,17 @@ impl Files {
         }
     }
 
-    pub fn remove(&mut self, file: FileRemoved, bucket: CanisterId) -> Result<RemoveFileSuccess, ()> {
-        if let Some(HashAndBucket { hash, .. }) = self.files_by_user.remove(&(&file).into()) {
+    pub fn remove(&mut self, file: FileRemoved, bucket: CanisterId) -> Result<RemoveFileSuccess, RemoveFileError> {
+        let key: FileIdByUserThenCreated = (&file).into();
+        if let Some(existing) = self.files_by_user.get(&key) {
+            if existing.bucket != bucket {
+                return Err(RemoveFileError::InOtherBucket(existing.bucket));
+            }
+        }
+
+        if let Some(HashAndBucket { hash, .. }) = self.files_by_user.remove(&key) {
+            self.file_details.remove(&file.file_id.to_be_bytes());
+
             let blob_reference = BlobReference {
                 hash,
                 user_id: file.meta_data.owner,
@@ -93,14 +110,80 @@ impl Files {
 
             Ok(RemoveFileSuccess { hash, size })
         } else {
-            Err(())
+            Err(RemoveFileError::NotFound)
+        }
+    }
+
+    // Moves a file reference over to a new bucket once its blob has been copied there. Returns false
+    // if the file is no longer held in the `from` bucket, eg. if it was deleted during the migration.
+    pub fn move_file(
+        &mut self,
+        file_id: FileId,
+        owner: Principal,
+        created: TimestampMillis,
+        from: CanisterId,
+        to: CanisterId,
+    ) -> bool {
+        let key = FileIdByUserThenCreated {
+            user_id: owner,
+            created,
+            file_id,
+        };
+
+        let hash = match self.files_by_user.get(&key) {
+            Some(existing) if existing.bucket == from => existing.hash,
+            _ => return false,
+        };
+
+        self.files_by_user.insert(key, HashAndBucket { hash, bucket: to });
+
+        let from_reference = BlobReference {
+            hash,
+            user_id: owner,
+            canister_id: from,
+        };
+        let from_count = self
+            .blob_reference_counts
+            .get(&from_reference)
+            .unwrap_or_default()
+            .saturating_sub(1);
+
+        if from_count == 0 {
+            self.blob_reference_counts.remove(&from_reference);
+        } else {
+            self.blob_reference_counts.insert(from_reference, from_count);
         }
+
+        let to_reference = BlobReference {
+            hash,
+            user_id: owner,
+            canister_id: to,
+        };
+        let to_count = self
+            .blob_reference_counts
+            .get(&to_reference)
+            .unwrap_or_default()
+            .saturating_add(1);
+
+        self.blob_reference_counts.insert(to_reference, to_count);
+        true
+    }
+
+    pub fn blob_reference_count(&self, hash: Hash, bucket: CanisterId) -> u32 {
+        self.iter_blob_reference_counts(hash, None)
+            .filter(|(r, _)| r.canister_id == bucket)
+            .map(|(_, c)| c)
+            .sum()
     }
 
     pub fn blob_size(&self, hash: &Hash) -> Option<u64> {
         self.blob_sizes.get(hash)
     }
 
+    pub fn file_details(&self, file_id: &FileId) -> Option<FileDetails> {
+        self.file_details.get(&file_id.to_be_bytes())
+    }
+
     pub fn user_owns_blob(&self, user_id: Principal, hash: Hash) -> bool {
         self.iter_blob_reference_counts(hash, Some(user_id)).next().is_some()
     }
@@ -110,12 +193,22 @@ impl Files {
     }
 
     pub fn iter_user_files_from_oldest(&self, user_id: Principal) -> impl Iterator<Item = UserFile> + '_ {
-        self.iter_user_files_from_oldest_internal(user_id).map(|(k, v)| UserFile {
-            file_id: k.file_id,
-            created: k.created,
-            hash: v.hash,
-            bucket: v.bucket,
-        })
+        self.iter_user_files_from_oldest_after(user_id, None)
+    }
+
+    // Continues on from the file identified by `after`, given as its created date and id
+    pub fn iter_user_files_from_oldest_after(
+        &self,
+        user_id: Principal,
+        after: Option<(TimestampMillis, FileId)>,
+    ) -> impl Iterator<Item = UserFile> + '_ {
+        self.iter_user_files_from_oldest_internal(user_id, after)
+            .map(|(k, v)| UserFile {
+                file_id: k.file_id,
+                created: k.created,
+                hash: v.hash,
+                bucket: v.bucket,
+            })
     }
 
     pub fn metrics(&self) -> Metrics {
@@ -130,14 +223,22 @@ impl Files {
     fn iter_user_files_from_oldest_internal(
         &self,
         user_id: Principal,
+        after: Option<(TimestampMillis, FileId)>,
     ) -> impl Iterator<Item = (FileIdByUserThenCreated, HashAndBucket)> + '_ {
-        let range_start = FileIdByUserThenCreated {
-            user_id,
-            created: 0,
-            file_id: 0,
+        let range_start = match after {
+            Some((created, file_id)) => Bound::Excluded(FileIdByUserThenCreated {
+                user_id,
+                created,
+                file_id,
+            }),
+            None => Bound::Included(FileIdByUserThenCreated {
+                user_id,
+                created: 0,
+                file_id: 0,
+            }),
         };
         self.files_by_user
-            .range(range_start..)
+            .range((range_start, Bound::Unbounded))
             .take_while(move |(k, _)| k.user_id == user_id)
     }
 
@@ -248,6 +349,74 @@ impl Storable for HashAndBucket {
     }
 }
 
+pub struct FileDetails {
+    pub mime_type: String,
+    pub accessors: Vec<AccessorId>,
+}
+
+impl FileDetails {
+    const MAX_MIME_TYPE_LEN: usize = 100;
+    const MAX_ACCESSORS: usize = 10;
+    const MAX_SIZE: usize =
+        1 /* mime_type_len */ + Self::MAX_MIME_TYPE_LEN + Self::MAX_ACCESSORS * (1 /* accessor_len */ + 29 /* accessor */);
+
+    // The details are only used for reporting, so we truncate them to keep the size of each entry bounded
+    fn new(mut mime_type: String, mut accessors: Vec<AccessorId>) -> FileDetails {
+        if mime_type.len() > Self::MAX_MIME_TYPE_LEN {
+            let mut end = Self::MAX_MIME_TYPE_LEN;
+            while !mime_type.is_char_boundary(end) {
+                end -= 1;
+            }
+            mime_type.truncate(end);
+        }
+        accessors.truncate(Self::MAX_ACCESSORS);
+
+        FileDetails { mime_type, accessors }
+    }
+}
+
+impl Storable for FileDetails {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        let mut bytes = Vec::with_capacity(Self::MAX_SIZE);
+        bytes.push(self.mime_type.len() as u8);
+        bytes.extend_from_slice(self.mime_type.as_bytes());
+        for accessor in self.accessors.iter() {
+            let accessor_bytes = accessor.as_slice();
+            bytes.push(accessor_bytes.len() as u8);
+            bytes.extend_from_slice(accessor_bytes);
+        }
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let (mime_type_len, remaining) = bytes.split_at(1);
+        let (mime_type_bytes, mut remaining) = remaining.split_at(mime_type_len[0] as usize);
+
+        let mut accessors = Vec::new();
+        while let Some((accessor_len, rest)) = remaining.split_first() {
+            let (accessor_bytes, rest) = rest.split_at(*accessor_len as usize);
+            accessors.push(Principal::from_slice(accessor_bytes));
+            remaining = rest;
+        }
+
+        Self {
+            mime_type: String::from_utf8_lossy(mime_type_bytes).into_owned(),
+            accessors,
+        }
+    }
+}
+
+impl BoundedStorable for FileDetails {
+    const MAX_SIZE: u32 = Self::MAX_SIZE as u32;
+    const IS_FIXED_SIZE: bool = false;
+}
+
+#[derive(Debug)]
+pub enum RemoveFileError {
+    NotFound,
+    InOtherBucket(CanisterId),
+}
+
 pub struct RemoveFileSuccess {
     pub hash: Hash,
     pub size: u64,
@@ -306,6 +475,7 @@ impl Default for Files {
             blob_sizes: init_blob_sizes(),
             total_file_bytes: init_total_file_bytes(),
             total_blob_bytes: init_total_blob_bytes(),
+            file_details: init_file_details(),
         }
     }
 }
@@ -340,6 +510,12 @@ fn init_total_blob_bytes() -> StableCell<u64, Memory> {
     StableCell::init(memory, 0).unwrap()
 }
 
+fn init_file_details() -> StableBTreeMap<[u8; 16], FileDetails, Memory> {
+    let memory = get_file_details_memory();
+
+    StableBTreeMap::init(memory)
+}
+
 pub struct Metrics {
     pub file_count: u64,
     pub total_file_bytes: u64,
@@ -368,6 +544,8 @@ mod tests {
                         owner: user_id,
                         created: i.into(),
                     },
+                    mime_type: None,
+                    accessors: None,
                 },
                 bucket,
             );
@@ -397,6 +575,8 @@ mod tests {
                             owner: user_id,
                             created: i.into(),
                         },
+                        mime_type: None,
+                        accessors: None,
                     },
                     bucket,
                 );
@@ -411,6 +591,41 @@ mod tests {
         assert_eq!(created_dates, (30u64..40).collect::<Vec<_>>())
     }
 
+    #[test]
+    fn iter_user_files_from_oldest_after_continues_from_file() {
+        let mut files = Files::default();
+        let bucket = CanisterId::from_slice(&[2]);
+
+        for u in 0..2 {
+            let user_id = Principal::from_slice(&[u]);
+            let start = u * 10;
+
+            for i in start..(start + 10) {
+                files.add(
+                    FileAdded {
+                        file_id: i.into(),
+                        hash: [i; 32],
+                        size: i.into(),
+                        meta_data: FileMetaData {
+                            owner: user_id,
+                            created: i.into(),
+                        },
+                        mime_type: None,
+                        accessors: None,
+                    },
+                    bucket,
+                );
+            }
+        }
+
+        let created_dates: Vec<_> = files
+            .iter_user_files_from_oldest_after(Principal::from_slice(&[0]), Some((4, 4)))
+            .map(|f| f.created)
+            .collect();
+
+        assert_eq!(created_dates, (5u64..10).collect::<Vec<_>>())
+    }
+
     #[test]
     fn add_then_remove_leaves_empty() {
         let mut files = Files::default();
@@ -427,6 +642,8 @@ mod tests {
                         owner: user_id,
                         created: i.into(),
                     },
+                    mime_type: None,
+                    accessors: None,
                 },
                 bucket,
             );
@@ -450,7 +667,75 @@ mod tests {
         assert!(files.files_by_user.is_empty());
         assert!(files.blob_reference_counts.is_empty());
         assert!(files.blob_sizes.is_empty());
+        assert!(files.file_details.is_empty());
         assert_eq!(*files.total_file_bytes.get(), 0);
         assert_eq!(*files.total_blob_bytes.get(), 0);
     }
+
+    #[test]
+    fn move_file_updates_bucket_and_reference_counts() {
+        let mut files = Files::default();
+        let user_id = Principal::from_slice(&[1]);
+        let old_bucket = CanisterId::from_slice(&[2]);
+        let new_bucket = CanisterId::from_slice(&[3]);
+        let hash = [1; 32];
+
+        for i in 0u8..3 {
+            files.add(
+                FileAdded {
+                    file_id: i.into(),
+                    hash,
+                    size: 100,
+                    meta_data: FileMetaData {
+                        owner: user_id,
+                        created: i.into(),
+                    },
+                    mime_type: None,
+                    accessors: None,
+                },
+                old_bucket,
+            );
+        }
+
+        for i in 0u8..2 {
+            assert!(files.move_file(i.into(), user_id, i.into(), old_bucket, new_bucket));
+        }
+        assert!(!files.move_file(0, user_id, 0, old_bucket, new_bucket));
+
+        assert_eq!(files.blob_reference_count(hash, old_bucket), 1);
+        assert_eq!(files.blob_reference_count(hash, new_bucket), 2);
+        assert_eq!(files.metrics().total_blob_bytes, 100);
+
+        let removed_from_old_bucket = files.remove(
+            FileRemoved {
+                file_id: 0,
+                meta_data: FileMetaData {
+                    owner: user_id,
+                    created: 0,
+                },
+            },
+            old_bucket,
+        );
+        assert!(matches!(removed_from_old_bucket, Err(RemoveFileError::InOtherBucket(b)) if b == new_bucket));
+    }
+
+    #[test]
+    fn file_details_round_trip() {
+        let details = FileDetails::new(
+            "image/png".to_string(),
+            vec![Principal::from_slice(&[1, 2, 3]), Principal::from_slice(&[4; 29])],
+        );
+
+        let from_bytes = FileDetails::from_bytes(details.to_bytes());
+
+        assert_eq!(from_bytes.mime_type, details.mime_type);
+        assert_eq!(from_bytes.accessors, details.accessors);
+    }
+
+    #[test]
+    fn file_details_truncated_to_max_size() {
+        let details = FileDetails::new("a".repeat(200), (0..20u8).map(|i| Principal::from_slice(&[i; 29])).collect());
+
+        assert!(details.to_bytes().len() <= FileDetails::MAX_SIZE);
+    }
 }
@@ -33,11 +33,9 @@ fn allocated_bucket_impl(args: Args, state: &RuntimeState) -> Response {
             });
         }
 
-        let bucket = state
-            .data
-            .files
-            .bucket_for_blob(args.file_hash)
-            .or_else(|| state.data.buckets.allocate(args.file_hash));
+        let existing_bucket = state.data.files.bucket_for_blob(args.file_hash);
+        let blob_exists = existing_bucket.is_some();
+        let bucket = existing_bucket.or_else(|| state.data.buckets.allocate(args.file_hash));
 
         if let Some(canister_id) = bucket {
             let now = state.env.now();
@@ -61,6 +59,7 @@ fn allocated_bucket_impl(args: Args, state: &RuntimeState) -> Response {
                     bytes_used_after_upload,
                     bytes_used_after_operation: bytes_used_after_upload,
                 },
+                blob_exists,
             })
         } else {
             BucketUnavailable
@@ -0,0 +1,15 @@
+use crate::guards::caller_is_bucket;
+use crate::{read_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::query;
+use storage_index_canister::c2c_check_allowance::*;
+
+#[query(guard = "caller_is_bucket")]
+#[trace]
+fn c2c_check_allowance(args: Args) -> Response {
+    read_state(|state| c2c_check_allowance_impl(args, state))
+}
+
+fn c2c_check_allowance_impl(args: Args, state: &RuntimeState) -> Response {
+    state.data.check_allowance(args.user_id, args.file_hash, args.file_size)
+}
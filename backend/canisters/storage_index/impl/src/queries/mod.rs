@@ -1,4 +1,6 @@
 pub mod allocated_bucket;
+pub mod c2c_check_allowance;
 pub mod can_forward;
 pub mod http_request;
 pub mod user;
+pub mod user_files;
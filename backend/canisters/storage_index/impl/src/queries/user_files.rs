@@ -0,0 +1,60 @@
+use crate::{read_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::query;
+use storage_index_canister::user_files::{Response::*, *};
+
+const DEFAULT_MAX_RESULTS: u32 = 100;
+const MAX_RESULTS_LIMIT: u32 = 1000;
+
+#[query]
+#[trace]
+fn user_files(args: Args) -> Response {
+    read_state(|state| user_files_impl(args, state))
+}
+
+fn user_files_impl(args: Args, state: &RuntimeState) -> Response {
+    let user_id = state.env.caller();
+    if let Some(user) = state.data.users.get(&user_id) {
+        let max_results = args.max_results.unwrap_or(DEFAULT_MAX_RESULTS).clamp(1, MAX_RESULTS_LIMIT) as usize;
+
+        // Take one extra file to determine whether there are more to fetch
+        let mut files: Vec<_> = state
+            .data
+            .files
+            .iter_user_files_from_oldest_after(user_id, args.start_after.map(|c| (c.created, c.file_id)))
+            .take(max_results + 1)
+            .map(|f| {
+                let details = state.data.files.file_details(&f.file_id);
+
+                UserFile {
+                    file_id: f.file_id,
+                    bucket: f.bucket,
+                    hash: f.hash,
+                    size: state.data.files.blob_size(&f.hash).unwrap_or_default(),
+                    created: f.created,
+                    mime_type: details.as_ref().map(|d| d.mime_type.clone()),
+                    accessors: details.map(|d| d.accessors).unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let next = if files.len() > max_results {
+            files.truncate(max_results);
+            files.last().map(|f| UserFilesCursor {
+                created: f.created,
+                file_id: f.file_id,
+            })
+        } else {
+            None
+        };
+
+        Success(SuccessResult {
+            byte_limit: user.byte_limit,
+            bytes_used: user.bytes_used,
+            files,
+            next,
+        })
+    } else {
+        UserNotFound
+    }
+}
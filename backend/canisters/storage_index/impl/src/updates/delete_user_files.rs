@@ -0,0 +1,69 @@
+use crate::model::files::Files;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use std::collections::{HashMap, HashSet};
+use storage_index_canister::delete_user_files::{Response::*, *};
+use types::Hash;
+
+#[update]
+#[trace]
+fn delete_user_files(args: Args) -> Response {
+    mutate_state(|state| delete_user_files_impl(args, state))
+}
+
+fn delete_user_files_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let user_id = state.env.caller();
+    if !state.data.users.contains_key(&user_id) {
+        return UserNotFound;
+    }
+
+    let file_ids: HashSet<_> = args.file_ids.into_iter().collect();
+    let free_up_bytes = args.free_up_bytes.unwrap_or_default();
+
+    let user_files: Vec<_> = state.data.files.iter_user_files_from_oldest(user_id).collect();
+
+    // Blobs are only counted once per user, so deleting a file only frees up space once all of the user's files
+    // referencing the same blob have been deleted
+    let mut remaining_per_blob: HashMap<_, usize> = HashMap::new();
+    for file in user_files.iter() {
+        *remaining_per_blob.entry(file.hash).or_default() += 1;
+    }
+
+    let mut bytes_freed = 0;
+
+    // The files selected by the user are deleted first, then the oldest remaining files until enough space is freed
+    let (mut files_to_delete, oldest_files): (Vec<_>, Vec<_>) =
+        user_files.into_iter().partition(|f| file_ids.contains(&f.file_id));
+
+    for file in files_to_delete.iter() {
+        bytes_freed += release_blob(file.hash, &mut remaining_per_blob, &state.data.files);
+    }
+    for file in oldest_files {
+        if bytes_freed >= free_up_bytes {
+            break;
+        }
+        bytes_freed += release_blob(file.hash, &mut remaining_per_blob, &state.data.files);
+        files_to_delete.push(file);
+    }
+
+    let files_deleted = files_to_delete.iter().map(|f| f.file_id).collect();
+
+    state.data.queue_files_for_deletion(files_to_delete);
+
+    Success(SuccessResult {
+        files_deleted,
+        bytes_freed,
+    })
+}
+
+fn release_blob(hash: Hash, remaining_per_blob: &mut HashMap<Hash, usize>, files: &Files) -> u64 {
+    let remaining = remaining_per_blob.entry(hash).or_default();
+    *remaining = remaining.saturating_sub(1);
+
+    if *remaining == 0 {
+        files.blob_size(&hash).unwrap_or_default()
+    } else {
+        0
+    }
+}
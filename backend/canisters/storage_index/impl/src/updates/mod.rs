@@ -2,6 +2,7 @@ pub mod add_bucket_canister;
 pub mod add_or_update_users;
 pub mod c2c_notify_low_balance;
 pub mod c2c_sync_bucket;
+pub mod delete_user_files;
 pub mod remove_accessor;
 pub mod remove_user;
 pub mod set_bucket_full;
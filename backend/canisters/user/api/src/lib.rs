@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use types::{
     ChannelId, ChannelLatestMessageIndex, Chat, ChatId, CommunityId, Cryptocurrency, DiamondMembershipPlanDuration, EventIndex,
-    MessageContent, MessageIndex, PhoneNumber, SuspensionDuration, TimestampMillis, UserId,
+    MessageContent, MessageIndex, NnsNeuronId, PhoneNumber, SnsNeuronId, SuspensionDuration, TimestampMillis, UserId,
 };
 
 mod lifecycle;
@@ -178,3 +178,10 @@ pub struct NamedAccount {
     pub name: String,
     pub account: String,
 }
+
+// The subset of a user's neurons to vote with for a given governance canister
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum ProposalVotingNeurons {
+    Nns(Vec<NnsNeuronId>),
+    Sns(Vec<SnsNeuronId>),
+}
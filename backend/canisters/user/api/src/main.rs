@@ -11,6 +11,7 @@ fn main() {
     generate_candid_method!(user, hot_group_exclusions, query);
     generate_candid_method!(user, initial_state, query);
     generate_candid_method!(user, messages_by_message_index, query);
+    generate_candid_method!(user, proposal_voting_neurons, query);
     generate_candid_method!(user, public_profile, query);
     generate_candid_method!(user, search_messages, query);
     generate_candid_method!(user, saved_crypto_accounts, query);
@@ -44,7 +45,9 @@ fn main() {
     generate_candid_method!(user, set_bio, update);
     generate_candid_method!(user, set_community_indexes, update);
     generate_candid_method!(user, set_contact, update);
+    generate_candid_method!(user, set_last_seen_visibility, update);
     generate_candid_method!(user, set_message_reminder_v2, update);
+    generate_candid_method!(user, set_proposal_voting_neurons, update);
     generate_candid_method!(user, submit_proposal, update);
     generate_candid_method!(user, tip_message, update);
     generate_candid_method!(user, unblock_user, update);
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use types::CanisterId;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub ledger_canister_id: CanisterId,
+    pub amount: u128,
+    pub fee: u128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(u64),
+    InsufficientFunds(u128),
+    TransferFailed(String),
+    InternalError(String),
+}
@@ -3,6 +3,7 @@ pub mod add_reaction;
 pub mod archive_unarchive_chats;
 pub mod block_user;
 pub mod c2c_charge_user_account;
+pub mod c2c_charge_user_account_v2;
 pub mod c2c_delete_messages;
 pub mod c2c_edit_message;
 pub mod c2c_grant_super_admin;
@@ -47,7 +48,9 @@ pub mod set_avatar;
 pub mod set_bio;
 pub mod set_community_indexes;
 pub mod set_contact;
+pub mod set_last_seen_visibility;
 pub mod set_message_reminder_v2;
+pub mod set_proposal_voting_neurons;
 pub mod submit_proposal;
 pub mod tip_message;
 pub mod unblock_user;
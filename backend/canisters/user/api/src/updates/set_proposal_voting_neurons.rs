@@ -0,0 +1,18 @@
+use crate::ProposalVotingNeurons;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::CanisterId;
+
+// Passing in `None` reverts to voting with all eligible neurons
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub governance_canister_id: CanisterId,
+    pub neurons: Option<ProposalVotingNeurons>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NoNeuronsSelected,
+    UserSuspended,
+}
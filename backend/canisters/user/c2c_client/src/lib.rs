@@ -3,6 +3,7 @@ use user_canister::*;
 
 // Updates
 generate_c2c_call!(c2c_charge_user_account);
+generate_c2c_call!(c2c_charge_user_account_v2);
 generate_c2c_call!(c2c_delete_messages);
 generate_c2c_call!(c2c_edit_message);
 generate_c2c_call!(c2c_grant_super_admin);
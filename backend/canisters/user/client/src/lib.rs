@@ -9,6 +9,7 @@ generate_update_call!(add_reaction);
 generate_update_call!(block_user);
 generate_update_call!(delete_messages);
 generate_update_call!(edit_message_v2);
+generate_update_call!(create_community);
 generate_update_call!(create_group);
 generate_update_call!(leave_group);
 generate_update_call!(mark_read);
@@ -18,13 +18,13 @@ use notifications_canister::c2c_push_notification;
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use types::{
-    BuildVersion, CanisterId, Chat, ChatId, ChatMetrics, CommunityId, Cryptocurrency, Cycles, Document, Notification,
-    TimestampMillis, Timestamped, UserId,
+    BuildVersion, CanisterId, Chat, ChatId, ChatMetrics, CommunityId, Cryptocurrency, Cycles, Document, LastSeenVisibility,
+    Notification, TimestampMillis, Timestamped, UserId,
 };
-use user_canister::NamedAccount;
+use user_canister::{NamedAccount, ProposalVotingNeurons};
 use utils::env::Environment;
 use utils::regular_jobs::RegularJobs;
 
@@ -100,6 +100,33 @@ impl RuntimeState {
         }
     }
 
+    // Sends the user's last seen visibility to the OnlineUsers canister, along with their contacts
+    // if only contacts are allowed to see when the user was last online
+    pub fn push_last_seen_visibility(&self) {
+        let visibility = self.data.last_seen_visibility;
+        let contacts = if visibility == LastSeenVisibility::Contacts {
+            self.data.contacts.iter().map(|(user_id, _)| *user_id).collect()
+        } else {
+            Vec::new()
+        };
+        self.data.fire_and_forget_handler.send(
+            self.data.online_users_canister_id,
+            "c2c_set_last_seen_visibility_msgpack".to_string(),
+            msgpack::serialize_then_unwrap(online_users_canister::c2c_set_last_seen_visibility::Args { visibility, contacts }),
+        );
+    }
+
+    // Sends the user's blocked users to the OnlineUsers canister so that blocked users can't show
+    // typing indicators in their direct chat with the user
+    pub fn push_blocked_users(&self) {
+        let blocked_users = self.data.blocked_users.value.iter().copied().collect();
+        self.data.fire_and_forget_handler.send(
+            self.data.online_users_canister_id,
+            "c2c_set_blocked_users_msgpack".to_string(),
+            msgpack::serialize_then_unwrap(online_users_canister::c2c_set_blocked_users::Args { blocked_users }),
+        );
+    }
+
     pub fn run_event_expiry_job(&mut self) {
         let now = self.env.now();
         let mut next_event_expiry = None;
@@ -159,6 +186,8 @@ struct Data {
     pub notifications_canister_id: CanisterId,
     #[serde(default = "proposals_bot_canister_id")]
     pub proposals_bot_canister_id: CanisterId,
+    #[serde(default = "online_users_canister_id")]
+    pub online_users_canister_id: CanisterId,
     pub avatar: Timestamped<Option<Document>>,
     pub test_mode: bool,
     pub is_platform_moderator: bool,
@@ -180,12 +209,20 @@ struct Data {
     pub saved_crypto_accounts: Vec<NamedAccount>,
     #[serde(default)]
     pub next_event_expiry: Option<TimestampMillis>,
+    #[serde(default)]
+    pub proposal_voting_neurons: HashMap<CanisterId, ProposalVotingNeurons>,
+    #[serde(default)]
+    pub last_seen_visibility: LastSeenVisibility,
 }
 
 fn proposals_bot_canister_id() -> CanisterId {
     CanisterId::from_text("iywa7-ayaaa-aaaaf-aemga-cai").unwrap()
 }
 
+fn online_users_canister_id() -> CanisterId {
+    CanisterId::from_text("3vlw6-fiaaa-aaaaf-aaa3a-cai").unwrap()
+}
+
 impl Data {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -195,6 +232,7 @@ impl Data {
         group_index_canister_id: CanisterId,
         notifications_canister_id: CanisterId,
         proposals_bot_canister_id: CanisterId,
+        online_users_canister_id: CanisterId,
         username: String,
         display_name: Option<String>,
         test_mode: bool,
@@ -212,6 +250,7 @@ impl Data {
             group_index_canister_id,
             notifications_canister_id,
             proposals_bot_canister_id,
+            online_users_canister_id,
             avatar: Timestamped::default(),
             test_mode,
             is_platform_moderator: false,
@@ -231,6 +270,8 @@ impl Data {
             fire_and_forget_handler: FireAndForgetHandler::default(),
             saved_crypto_accounts: Vec::new(),
             next_event_expiry: None,
+            proposal_voting_neurons: HashMap::new(),
+            last_seen_visibility: LastSeenVisibility::default(),
         }
     }
 
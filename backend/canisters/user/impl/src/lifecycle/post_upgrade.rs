@@ -1,5 +1,5 @@
 use crate::lifecycle::{init_env, init_state, UPGRADE_BUFFER_SIZE};
-use crate::Data;
+use crate::{read_state, Data};
 use canister_logger::LogEntry;
 use canister_tracing_macros::trace;
 use ic_cdk_macros::post_upgrade;
@@ -19,5 +19,12 @@ fn post_upgrade(args: Args) {
 
     init_state(env, data, args.wasm_version);
 
+    // Sync users blocked before the OnlineUsers canister knew about them
+    read_state(|state| {
+        if !state.data.blocked_users.is_empty() {
+            state.push_blocked_users();
+        }
+    });
+
     info!(version = %args.wasm_version, "Post-upgrade complete");
 }
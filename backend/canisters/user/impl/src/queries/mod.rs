@@ -8,6 +8,7 @@ pub mod hot_group_exclusions;
 pub mod http_request;
 pub mod initial_state;
 pub mod messages_by_message_index;
+pub mod proposal_voting_neurons;
 pub mod public_profile;
 pub mod saved_crypto_accounts;
 pub mod search_messages;
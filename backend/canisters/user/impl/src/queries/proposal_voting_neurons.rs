@@ -0,0 +1,20 @@
+use crate::guards::caller_is_owner;
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use user_canister::proposal_voting_neurons::{Response::*, *};
+
+#[query(guard = "caller_is_owner")]
+fn proposal_voting_neurons(_args: Args) -> Response {
+    read_state(proposal_voting_neurons_impl)
+}
+
+fn proposal_voting_neurons_impl(state: &RuntimeState) -> Response {
+    Success(
+        state
+            .data
+            .proposal_voting_neurons
+            .iter()
+            .map(|(governance_canister_id, neurons)| (*governance_canister_id, neurons.clone()))
+            .collect(),
+    )
+}
@@ -19,5 +19,6 @@ fn block_user_impl(args: Args, state: &mut RuntimeState) -> Response {
 
     let now = state.env.now();
     state.data.block_user(args.user_id, now);
+    state.push_blocked_users();
     Success
 }
@@ -0,0 +1,37 @@
+use crate::guards::caller_is_user_index;
+use crate::{read_state, run_regular_jobs};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use tracing::error;
+use types::icrc1::{Account, TransferArg, TransferError};
+use user_canister::c2c_charge_user_account_v2::{Response::*, *};
+
+// Used to charge users in any ICRC1 token. ICP is still charged via `c2c_charge_user_account`
+#[update_msgpack(guard = "caller_is_user_index")]
+#[trace]
+async fn c2c_charge_user_account_v2(args: Args) -> Response {
+    run_regular_jobs();
+
+    let user_index_canister_id = read_state(|state| state.data.user_index_canister_id);
+
+    let transfer_args = TransferArg {
+        from_subaccount: None,
+        to: Account::from(user_index_canister_id),
+        fee: Some(args.fee.into()),
+        created_at_time: None,
+        memo: None,
+        amount: args.amount.saturating_sub(args.fee).into(),
+    };
+
+    match icrc1_ledger_canister_c2c_client::icrc1_transfer(args.ledger_canister_id, &transfer_args).await {
+        // The transfer has already happened so this must not trap, the block index is only recorded
+        // for reference
+        Ok(Ok(block_index)) => Success(block_index.0.try_into().unwrap_or_else(|_| {
+            error!(%block_index, "Block index too large to fit in a u64");
+            u64::MAX
+        })),
+        Ok(Err(TransferError::InsufficientFunds { balance })) => InsufficientFunds(balance.0.try_into().unwrap_or(u128::MAX)),
+        Ok(Err(error)) => TransferFailed(format!("{error:?}")),
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
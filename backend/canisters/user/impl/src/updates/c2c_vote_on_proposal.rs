@@ -6,27 +6,53 @@ use canister_tracing_macros::trace;
 use ic_cdk::api::call::CallResult;
 use types::{CanisterId, NnsNeuronId, ProposalId, SnsNeuronId};
 use user_canister::c2c_vote_on_proposal::{Response::*, *};
+use user_canister::ProposalVotingNeurons;
 
 #[update_msgpack(guard = "caller_is_known_group_or_community_canister")]
 #[trace]
 async fn c2c_vote_on_proposal(args: Args) -> Response {
     run_regular_jobs();
 
+    // If the user has selected a subset of their neurons for this governance canister then only vote with those
+    let selected_neurons = read_state(|state| state.data.proposal_voting_neurons.get(&args.governance_canister_id).cloned());
+
     if args.is_nns {
-        nns::vote_on_proposal(args.governance_canister_id, args.proposal_id, args.adopt).await
+        let selected = match selected_neurons {
+            Some(ProposalVotingNeurons::Nns(ids)) => Some(ids),
+            _ => None,
+        };
+        nns::vote_on_proposal(args.governance_canister_id, args.proposal_id, args.adopt, selected).await
     } else {
-        sns::vote_on_proposal(args.governance_canister_id, args.proposal_id, args.adopt).await
+        let selected = match selected_neurons {
+            Some(ProposalVotingNeurons::Sns(ids)) => Some(ids),
+            _ => None,
+        };
+        sns::vote_on_proposal(args.governance_canister_id, args.proposal_id, args.adopt, selected).await
     }
 }
 
 mod nns {
     use super::*;
 
-    pub async fn vote_on_proposal(governance_canister_id: CanisterId, proposal_id: ProposalId, adopt: bool) -> Response {
+    pub async fn vote_on_proposal(
+        governance_canister_id: CanisterId,
+        proposal_id: ProposalId,
+        adopt: bool,
+        selected: Option<Vec<NnsNeuronId>>,
+    ) -> Response {
         let ballots = match crate::governance_clients::nns::get_ballots(governance_canister_id, proposal_id).await {
             Ok(r) => match r {
-                GetBallotsResult::Success(b) if !b.is_empty() => b,
-                GetBallotsResult::Success(_) => return NoEligibleNeurons,
+                GetBallotsResult::Success(b) => {
+                    let b: Vec<_> = b
+                        .into_iter()
+                        .filter(|(neuron_id, _)| selected.as_ref().map_or(true, |s| s.contains(neuron_id)))
+                        .collect();
+
+                    if b.is_empty() {
+                        return NoEligibleNeurons;
+                    }
+                    b
+                }
                 GetBallotsResult::ProposalNotFound => return ProposalNotFound,
                 GetBallotsResult::ProposalNotAcceptingVotes => return ProposalNotAcceptingVotes,
             },
@@ -60,15 +86,26 @@ mod sns {
     use super::*;
     use sns_governance_canister::types::GovernanceError;
 
-    pub async fn vote_on_proposal(governance_canister_id: CanisterId, proposal_id: ProposalId, adopt: bool) -> Response {
+    pub async fn vote_on_proposal(
+        governance_canister_id: CanisterId,
+        proposal_id: ProposalId,
+        adopt: bool,
+        selected: Option<Vec<SnsNeuronId>>,
+    ) -> Response {
         let (canister_id, now) = read_state(|state| (state.env.canister_id(), state.env.now()));
 
-        let neuron_ids = match crate::governance_clients::sns::list_neurons(governance_canister_id, 10, canister_id, now).await
-        {
-            Ok(n) if n.is_empty() => return NoEligibleNeurons,
-            Ok(n) => n,
-            Err(error) => return InternalError(format!("{error:?}")),
-        };
+        let neuron_ids: Vec<_> =
+            match crate::governance_clients::sns::list_neurons(governance_canister_id, 10, canister_id, now).await {
+                Ok(n) => n
+                    .into_iter()
+                    .filter(|neuron_id| selected.as_ref().map_or(true, |s| s.contains(neuron_id)))
+                    .collect(),
+                Err(error) => return InternalError(format!("{error:?}")),
+            };
+
+        if neuron_ids.is_empty() {
+            return NoEligibleNeurons;
+        }
 
         let vote_futures: Vec<_> = neuron_ids
             .into_iter()
@@ -3,6 +3,7 @@ use crate::model::contacts::SetContactResponse;
 use crate::{mutate_state, run_regular_jobs, RuntimeState};
 use canister_tracing_macros::trace;
 use ic_cdk_macros::update;
+use types::LastSeenVisibility;
 use user_canister::set_contact::{Response::*, *};
 
 #[update(guard = "caller_is_owner")]
@@ -19,7 +20,12 @@ fn set_contact_impl(args: Args, state: &mut RuntimeState) -> Response {
     }
 
     match state.data.contacts.set_contact(args.contact) {
-        SetContactResponse::Success => Success,
+        SetContactResponse::Success => {
+            if state.data.last_seen_visibility == LastSeenVisibility::Contacts {
+                state.push_last_seen_visibility();
+            }
+            Success
+        }
         SetContactResponse::NoChange => NoChange,
         SetContactResponse::NicknameTooLong(n) => NicknameTooLong(n),
         SetContactResponse::NicknameTooShort(n) => NicknameTooShort(n),
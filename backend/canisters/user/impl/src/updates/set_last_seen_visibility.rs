@@ -0,0 +1,25 @@
+use crate::guards::caller_is_owner;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use user_canister::set_last_seen_visibility::{Response::*, *};
+
+#[update(guard = "caller_is_owner")]
+#[trace]
+fn set_last_seen_visibility(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| set_last_seen_visibility_impl(args, state))
+}
+
+fn set_last_seen_visibility_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.suspended.value {
+        return UserSuspended;
+    }
+
+    if state.data.last_seen_visibility != args.visibility {
+        state.data.last_seen_visibility = args.visibility;
+        state.push_last_seen_visibility();
+    }
+    Success
+}
@@ -0,0 +1,36 @@
+use crate::guards::caller_is_owner;
+use crate::{mutate_state, run_regular_jobs, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use user_canister::set_proposal_voting_neurons::{Response::*, *};
+use user_canister::ProposalVotingNeurons;
+
+#[update(guard = "caller_is_owner")]
+#[trace]
+fn set_proposal_voting_neurons(args: Args) -> Response {
+    run_regular_jobs();
+
+    mutate_state(|state| set_proposal_voting_neurons_impl(args, state))
+}
+
+fn set_proposal_voting_neurons_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if state.data.suspended.value {
+        return UserSuspended;
+    }
+
+    match args.neurons {
+        Some(ProposalVotingNeurons::Nns(ids)) if ids.is_empty() => NoNeuronsSelected,
+        Some(ProposalVotingNeurons::Sns(ids)) if ids.is_empty() => NoNeuronsSelected,
+        Some(neurons) => {
+            state
+                .data
+                .proposal_voting_neurons
+                .insert(args.governance_canister_id, neurons);
+            Success
+        }
+        None => {
+            state.data.proposal_voting_neurons.remove(&args.governance_canister_id);
+            Success
+        }
+    }
+}
@@ -17,6 +17,7 @@ pub enum Event {
     UserJoinedCommunityOrChannel(Box<UserJoinedCommunityOrChannel>),
     JoinUserToGroup(Box<JoinUserToGroup>),
     OpenChatBotMessage(Box<OpenChatBotMessage>),
+    ReferralCampaignCodesRejected(Box<ReferralCampaignCodesRejected>),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -26,6 +27,8 @@ pub struct UserRegistered {
     pub username: String,
     pub display_name: Option<String>,
     pub referred_by: Option<UserId>,
+    #[serde(default)]
+    pub referral_campaign: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -53,3 +56,9 @@ pub struct OpenChatBotMessage {
     pub user_id: UserId,
     pub message: MessageContent,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReferralCampaignCodesRejected {
+    pub campaign: String,
+    pub codes: Vec<String>,
+}
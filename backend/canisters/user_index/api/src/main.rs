@@ -7,10 +7,12 @@ fn main() {
     generate_candid_method!(user_index, platform_moderators, query);
     generate_candid_method!(user_index, platform_moderators_group, query);
     generate_candid_method!(user_index, platform_operators, query);
+    generate_candid_method!(user_index, referral_campaign_performance, query);
     generate_candid_method!(user_index, referral_leaderboard, query);
     generate_candid_method!(user_index, referral_metrics, query);
     generate_candid_method!(user_index, search, query);
     generate_candid_method!(user_index, suspected_bots, query);
+    generate_candid_method!(user_index, upgrade_rollout_status, query);
     generate_candid_method!(user_index, user, query);
     generate_candid_method!(user_index, user_registration_canister, query);
     generate_candid_method!(user_index, users, query);
@@ -18,16 +20,25 @@ fn main() {
 
     generate_candid_method!(user_index, add_platform_moderator, update);
     generate_candid_method!(user_index, add_platform_operator, update);
+    generate_candid_method!(user_index, add_referral_campaign, update);
+    generate_candid_method!(user_index, add_referral_campaign_codes, update);
     generate_candid_method!(user_index, add_referral_codes, update);
     generate_candid_method!(user_index, assign_platform_moderators_group, update);
+    generate_candid_method!(user_index, commit_wasm_upload, update);
+    generate_candid_method!(user_index, diamond_membership_quote, update);
+    generate_candid_method!(user_index, gift_diamond_membership, update);
     generate_candid_method!(user_index, mark_suspected_bot, update);
     generate_candid_method!(user_index, pay_for_diamond_membership, update);
+    generate_candid_method!(user_index, push_wasm_chunk, update);
+    generate_candid_method!(user_index, redeem_diamond_gift_code, update);
     generate_candid_method!(user_index, remove_platform_moderator, update);
     generate_candid_method!(user_index, remove_platform_operator, update);
+    generate_candid_method!(user_index, set_diamond_membership_pricing, update);
     generate_candid_method!(user_index, set_display_name, update);
     generate_candid_method!(user_index, set_user_upgrade_concurrency, update);
     generate_candid_method!(user_index, set_moderation_flags, update);
     generate_candid_method!(user_index, set_username, update);
+    generate_candid_method!(user_index, start_wasm_upload, update);
     generate_candid_method!(user_index, suspend_user, update);
     generate_candid_method!(user_index, unsuspend_user, update);
 
@@ -0,0 +1,51 @@
+use crate::referral_leaderboard::ReferralStats;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CommunityId, Cryptocurrency, TimestampMillis};
+
+type Year = u32;
+type Month = u8;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub campaign: String,
+    // Set when querying a campaign run by a community
+    #[serde(default)]
+    pub community: Option<CommunityId>,
+    pub top_referrers: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    CampaignNotFound,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub name: String,
+    pub sponsor_community: Option<CommunityId>,
+    pub start: TimestampMillis,
+    pub end: TimestampMillis,
+    pub token: Cryptocurrency,
+    pub budget_e8s: u64,
+    pub spent_e8s: u64,
+    pub total: CampaignStats,
+    pub top_referrers: Vec<ReferralStats>,
+    pub months: Vec<MonthPerformance>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct MonthPerformance {
+    pub year: Year,
+    pub month: Month,
+    pub stats: CampaignStats,
+    pub top_referrers: Vec<ReferralStats>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct CampaignStats {
+    pub signups: u32,
+    pub diamond_conversions: u32,
+    pub rewards_e8s: u64,
+}
@@ -0,0 +1,26 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CommunityId, Cryptocurrency, ReferralCampaignCode, ReferralCampaignRewards, TimestampMillis};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub name: String,
+    pub sponsor_community: Option<CommunityId>,
+    pub start: TimestampMillis,
+    pub end: TimestampMillis,
+    pub token: Cryptocurrency,
+    pub rewards: ReferralCampaignRewards,
+    pub second_tier_rewards: Option<ReferralCampaignRewards>,
+    pub budget_e8s: u64,
+    pub codes: Vec<ReferralCampaignCode>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    AlreadyExists,
+    NameInvalid,
+    InvalidDates,
+    TokenNotSupported,
+    CodeAlreadyInUse(String),
+}
@@ -0,0 +1,17 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::ReferralCampaignCode;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub campaign: String,
+    pub codes: Vec<ReferralCampaignCode>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    CampaignNotFound,
+    CampaignEnded,
+    CodeAlreadyInUse(String),
+}
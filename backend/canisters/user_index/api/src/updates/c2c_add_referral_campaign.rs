@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use types::{Cryptocurrency, ReferralCampaignCode, ReferralCampaignRewards, TimestampMillis, UserId};
+
+// Called by a community canister on behalf of its owner, who funds the campaign's budget
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub owner: UserId,
+    pub name: String,
+    pub start: TimestampMillis,
+    pub end: TimestampMillis,
+    pub token: Cryptocurrency,
+    pub rewards: ReferralCampaignRewards,
+    pub second_tier_rewards: Option<ReferralCampaignRewards>,
+    pub budget_e8s: u64,
+    pub codes: Vec<ReferralCampaignCode>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    NotAuthorized,
+    AlreadyExists,
+    InvalidDates,
+    TokenNotSupported,
+    CodeAlreadyInUse(String),
+    InsufficientFunds(u128),
+    TransferFailed(String),
+    InternalError(String),
+}
@@ -0,0 +1,26 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, Cryptocurrency, DiamondMembershipPlanDuration, TimestampMillis};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub duration: DiamondMembershipPlanDuration,
+    pub token: Cryptocurrency,
+    // Required for tokens which don't have their own `Cryptocurrency` variant
+    #[serde(default)]
+    pub ledger: Option<CanisterId>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    CurrencyNotSupported,
+    UserNotFound,
+    InternalError(String),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub price_e8s: u64,
+    pub expires_at: TimestampMillis,
+}
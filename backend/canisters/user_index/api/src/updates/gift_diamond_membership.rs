@@ -0,0 +1,37 @@
+use crate::pay_for_diamond_membership::CannotExtendResult;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, Cryptocurrency, DiamondMembershipPlanDuration, TimestampMillis, UserId};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub recipient: GiftRecipient,
+    pub duration: DiamondMembershipPlanDuration,
+    pub token: Cryptocurrency,
+    // Required for tokens which don't have their own `Cryptocurrency` variant
+    #[serde(default)]
+    pub ledger: Option<CanisterId>,
+    pub expected_price_e8s: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum GiftRecipient {
+    User(UserId),
+    GiftCode,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(TimestampMillis), // The recipient's new Diamond membership expiry
+    GiftCode(String),
+    CannotExtend(CannotExtendResult),
+    CannotGiftToSelf,
+    RecipientNotFound,
+    CurrencyNotSupported,
+    PriceMismatch,
+    PaymentAlreadyInProgress,
+    UserNotFound,
+    InsufficientFunds(u64), // Returns the account balance in e8s
+    TransferFailed(String),
+    InternalError(String),
+}
@@ -1,11 +1,14 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
-use types::{Cryptocurrency, DiamondMembershipDetails, DiamondMembershipPlanDuration, TimestampMillis};
+use types::{CanisterId, Cryptocurrency, DiamondMembershipDetails, DiamondMembershipPlanDuration, TimestampMillis};
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
     pub duration: DiamondMembershipPlanDuration,
     pub token: Cryptocurrency,
+    // Required for tokens which don't have their own `Cryptocurrency` variant
+    #[serde(default)]
+    pub ledger: Option<CanisterId>,
     pub expected_price_e8s: u64,
     pub recurring: bool,
 }
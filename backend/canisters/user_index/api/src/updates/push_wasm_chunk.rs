@@ -0,0 +1,14 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::PushWasmChunkArgs;
+
+pub type Args = PushWasmChunkArgs;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    UploadNotFound,
+    UploadAlreadyCommitted,
+    UnexpectedChunkIndex(u32),
+    ExceedsTotalSize,
+}
@@ -0,0 +1,19 @@
+use crate::pay_for_diamond_membership::CannotExtendResult;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::DiamondMembershipDetails;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub code: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(DiamondMembershipDetails),
+    CodeNotFound,
+    CodeAlreadyRedeemed,
+    CannotExtend(CannotExtendResult),
+    PaymentAlreadyInProgress,
+    UserNotFound,
+}
@@ -0,0 +1,56 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, TokenInfo};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub usd_prices: Option<DiamondMembershipUsdPrices>,
+    pub price_feeds: Vec<DiamondMembershipPriceFeed>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct DiamondMembershipUsdPrices {
+    pub one_month_cents: u32,
+    pub three_months_cents: u32,
+    pub one_year_cents: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DiamondMembershipPriceFeed {
+    pub ledger_canister_id: CanisterId,
+    pub source: PriceSource,
+    pub bounds: PriceBounds,
+}
+
+// Quotes implying a price outside of these bounds are rejected, so that a manipulated or broken
+// price feed can't be used to buy Diamond membership cheaply. Bounds are in token units per USD.
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct PriceBounds {
+    pub min_units_per_usd: u128,
+    pub max_units_per_usd: u128,
+}
+
+// Each source quotes the token against a USD pegged token
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum PriceSource {
+    ICPSwap(ICPSwapPriceSource),
+    ICDex(ICDexPriceSource),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ICPSwapPriceSource {
+    pub swap_canister_id: CanisterId,
+    pub usd_token: TokenInfo,
+    pub usd_token_is_token0: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ICDexPriceSource {
+    pub dex_canister_id: CanisterId,
+    pub usd_token: TokenInfo,
+}
@@ -9,6 +9,7 @@ generate_candid_c2c_call!(platform_moderators_group);
 generate_c2c_call!(user);
 
 // Updates
+generate_c2c_call!(c2c_add_referral_campaign);
 generate_c2c_call!(c2c_migrate_user_principal);
 generate_c2c_call!(c2c_notify_events);
 generate_candid_c2c_call_with_payment!(c2c_register_bot);
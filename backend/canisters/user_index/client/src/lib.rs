@@ -14,9 +14,18 @@ generate_query_call!(users);
 generate_update_call!(add_local_user_index_canister);
 generate_update_call!(add_platform_moderator);
 generate_update_call!(add_platform_operator);
+generate_update_call!(commit_wasm_upload);
+generate_update_call!(gift_diamond_membership);
+generate_update_call!(pay_for_diamond_membership);
+generate_update_call!(push_wasm_chunk);
+generate_update_call!(redeem_diamond_gift_code);
 generate_update_call!(remove_sms_messages);
 generate_update_call!(remove_platform_moderator);
 generate_update_call!(remove_platform_operator);
+generate_update_call!(rollback_local_user_index_canister_wasm);
+generate_update_call!(rollback_user_canister_wasm);
+generate_update_call!(set_diamond_membership_pricing);
 generate_update_call!(set_username);
+generate_update_call!(start_wasm_upload);
 generate_update_call!(upgrade_local_user_index_canister_wasm);
 generate_update_call!(upgrade_user_canister_wasm);
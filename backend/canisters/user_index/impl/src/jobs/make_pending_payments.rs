@@ -1,8 +1,9 @@
 use crate::model::pending_payments_queue::{PendingPayment, PendingPaymentReason};
 use crate::LocalUserIndexEvent;
-use crate::{mutate_state, RuntimeState};
+use crate::{mutate_state, read_state, RuntimeState};
 use ic_cdk_timers::TimerId;
-use ic_ledger_types::{BlockIndex, Tokens};
+use ic_ledger_types::BlockIndex;
+use ledger_utils::format_crypto_amount_with_symbol;
 use local_user_index_canister::OpenChatBotMessage;
 use serde::Serialize;
 use std::cell::Cell;
@@ -55,6 +56,18 @@ async fn process_payment(pending_payment: PendingPayment) {
 
 // Error response contains a boolean stating if the transfer should be retried
 async fn make_payment(pending_payment: &PendingPayment) -> Result<BlockIndex, bool> {
+    let token_info = read_state(|state| state.data.token_info(&pending_payment.currency, pending_payment.ledger));
+    let ledger_canister_id = match token_info {
+        Some(token) => token.ledger,
+        None => {
+            error!(
+                token = pending_payment.currency.token_symbol(),
+                "Unable to find ledger for pending payment"
+            );
+            return Err(false);
+        }
+    };
+
     let to = Account::from(pending_payment.recipient);
 
     let args = TransferArg {
@@ -66,9 +79,13 @@ async fn make_payment(pending_payment: &PendingPayment) -> Result<BlockIndex, bo
         amount: pending_payment.amount.into(),
     };
 
-    match icrc1_ledger_canister_c2c_client::icrc1_transfer(pending_payment.currency.ledger_canister_id().unwrap(), &args).await
-    {
-        Ok(Ok(block_index)) => Ok(block_index.0.try_into().unwrap()),
+    match icrc1_ledger_canister_c2c_client::icrc1_transfer(ledger_canister_id, &args).await {
+        Ok(Ok(block_index)) => Ok(block_index.0.try_into().unwrap_or_else(|_| {
+            // The payment has been made so it must not be retried, the block index is only used for
+            // the link in the message sent to the recipient
+            error!(%block_index, "Block index too large to fit in a u64");
+            u64::MAX
+        })),
         Ok(Err(transfer_error)) => {
             error!(?transfer_error, ?args, "Transfer failed");
             Err(false)
@@ -86,14 +103,17 @@ fn inform_referrer(
     reason: PendingPaymentReason,
     state: &mut RuntimeState,
 ) {
-    if matches!(reason, PendingPaymentReason::Treasury) {
+    if matches!(reason, PendingPaymentReason::Treasury | PendingPaymentReason::Refund) {
         return;
     }
 
     let user_id = pending_payment.recipient.into();
-    let amount = Tokens::from_e8s(pending_payment.amount);
+    let decimals = match state.data.token_info(&pending_payment.currency, pending_payment.ledger) {
+        Some(token) => token.decimals,
+        None => return,
+    };
     let symbol = pending_payment.currency.token_symbol();
-    let mut amount_text = format!("{} {}", amount, symbol);
+    let mut amount_text = format_crypto_amount_with_symbol(pending_payment.amount as u128, decimals, symbol);
 
     if matches!(pending_payment.currency, Cryptocurrency::CHAT) {
         let link = format!(
@@ -105,7 +125,8 @@ fn inform_referrer(
 
     let messages = match reason {
         PendingPaymentReason::ReferralReward => vec![MessageContent::Text(TextContent { text: format!("You have received a referral reward of {}. This is because one of the users you referred has made a Diamond membership payment.", amount_text) })],
-        PendingPaymentReason::Treasury => vec![],
+        PendingPaymentReason::ReferralCampaignReward(campaign) => vec![MessageContent::Text(TextContent { text: format!("You have received a referral reward of {} as part of the \"{}\" referral campaign. Thank you for helping to grow OpenChat!", amount_text, campaign) })],
+        PendingPaymentReason::Treasury | PendingPaymentReason::Refund => vec![],
     };
 
     for message in messages {
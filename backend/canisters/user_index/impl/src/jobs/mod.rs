@@ -2,6 +2,7 @@ use crate::RuntimeState;
 pub mod make_pending_payments;
 pub mod notify_user_principal_migrations;
 pub mod sync_events_to_local_user_index_canisters;
+pub mod sync_registry_tokens;
 pub mod sync_users_to_storage_index;
 pub mod upgrade_canisters;
 
@@ -11,4 +12,5 @@ pub(crate) fn start(state: &RuntimeState) {
     sync_users_to_storage_index::start_job_if_required(state);
     upgrade_canisters::start_job_if_required(state);
     make_pending_payments::start_job_if_required(state);
+    sync_registry_tokens::start_job_if_required();
 }
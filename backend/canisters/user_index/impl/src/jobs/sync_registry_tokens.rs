@@ -0,0 +1,43 @@
+use crate::{mutate_state, read_state};
+use ic_cdk_timers::TimerId;
+use registry_canister::updates::{Response, SuccessResult};
+use std::cell::Cell;
+use std::time::Duration;
+use tracing::{error, trace};
+use utils::time::HOUR_IN_MS;
+
+thread_local! {
+    static TIMER_ID: Cell<Option<TimerId>> = Cell::default();
+}
+
+pub(crate) fn start_job_if_required() -> bool {
+    if TIMER_ID.with(|t| t.get().is_none()) {
+        let timer_id = ic_cdk_timers::set_timer_interval(Duration::from_millis(HOUR_IN_MS), run);
+        TIMER_ID.with(|t| t.set(Some(timer_id)));
+        ic_cdk_timers::set_timer(Duration::ZERO, run);
+        trace!("'sync_registry_tokens' job started");
+        true
+    } else {
+        false
+    }
+}
+
+fn run() {
+    ic_cdk::spawn(run_async());
+}
+
+async fn run_async() {
+    let (registry_canister_id, since) =
+        read_state(|state| (state.data.registry_canister_id, state.data.registry_tokens.last_updated()));
+
+    match registry_canister_c2c_client::updates(registry_canister_id, &registry_canister::updates::Args { since }).await {
+        Ok(Response::Success(SuccessResult {
+            last_updated,
+            token_details: Some(tokens),
+        })) => {
+            mutate_state(|state| state.data.registry_tokens.set(tokens, last_updated));
+        }
+        Ok(_) => {}
+        Err(error) => error!(?error, "Failed to get token updates from the registry"),
+    }
+}
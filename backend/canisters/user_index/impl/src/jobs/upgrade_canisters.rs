@@ -4,8 +4,8 @@ use ic_cdk_timers::TimerId;
 use std::cell::Cell;
 use std::time::Duration;
 use tracing::trace;
-use types::{BuildVersion, CanisterId};
-use utils::canister::{install, FailedUpgrade};
+use types::{BuildVersion, CanisterId, Milliseconds};
+use utils::canister::{install, install_trapped, FailedUpgrade, RolloutProgress};
 
 type CanisterToUpgrade = utils::canister::CanisterToInstall<local_user_index_canister::post_upgrade::Args>;
 
@@ -31,25 +31,47 @@ fn run() {
     match mutate_state(try_get_next) {
         GetNextResult::Success(canister_to_upgrade) => ic_cdk::spawn(perform_upgrade(canister_to_upgrade)),
         GetNextResult::Continue => {}
-        GetNextResult::QueueEmpty => {
-            if let Some(timer_id) = TIMER_ID.with(|t| t.take()) {
-                ic_cdk_timers::clear_timer(timer_id);
-                trace!("'upgrade_canisters' job stopped");
+        GetNextResult::QueueEmpty => stop_job(),
+        GetNextResult::RolloutPaused(resume_in) => {
+            stop_job();
+            if let Some(resume_in) = resume_in {
+                ic_cdk_timers::set_timer(Duration::from_millis(resume_in), resume_rollout);
             }
         }
     }
 }
 
+fn stop_job() {
+    if let Some(timer_id) = TIMER_ID.with(|t| t.take()) {
+        ic_cdk_timers::clear_timer(timer_id);
+        trace!("'upgrade_canisters' job stopped");
+    }
+}
+
+fn resume_rollout() {
+    mutate_state(|state| {
+        state.data.canisters_requiring_upgrade.update_rollout(state.env.now());
+        start_job_if_required(state);
+    });
+}
+
 enum GetNextResult {
     Success(CanisterToUpgrade),
     Continue,
     QueueEmpty,
+    RolloutPaused(Option<Milliseconds>),
 }
 
 fn try_get_next(state: &mut RuntimeState) -> GetNextResult {
     if state.data.canisters_requiring_upgrade.count_in_progress() > 0 {
         return GetNextResult::Continue;
     }
+    let now = state.env.now();
+    match state.data.canisters_requiring_upgrade.update_rollout(now) {
+        RolloutProgress::Proceed => {}
+        RolloutProgress::Soaking(until) => return GetNextResult::RolloutPaused(Some(until.saturating_sub(now))),
+        RolloutProgress::Halted => return GetNextResult::RolloutPaused(None),
+    }
     if state.data.canisters_requiring_upgrade.count_pending() == 0 {
         return GetNextResult::QueueEmpty;
     }
@@ -98,8 +120,9 @@ async fn perform_upgrade(canister_to_upgrade: CanisterToUpgrade) {
         Ok(_) => {
             mutate_state(|state| on_success(canister_id, to_version, state));
         }
-        Err(_) => {
-            mutate_state(|state| on_failure(canister_id, from_version, to_version, state));
+        Err(error) => {
+            let trapped = install_trapped(&error);
+            mutate_state(|state| on_failure(canister_id, from_version, to_version, trapped, state));
         }
     }
 }
@@ -112,10 +135,21 @@ fn on_success(canister_id: CanisterId, to_version: BuildVersion, state: &mut Run
     }
 }
 
-fn on_failure(canister_id: CanisterId, from_version: BuildVersion, to_version: BuildVersion, state: &mut RuntimeState) {
-    state.data.canisters_requiring_upgrade.mark_failure(FailedUpgrade {
-        canister_id,
-        from_version,
-        to_version,
-    });
+fn on_failure(
+    canister_id: CanisterId,
+    from_version: BuildVersion,
+    to_version: BuildVersion,
+    trapped: bool,
+    state: &mut RuntimeState,
+) {
+    let now = state.env.now();
+    state.data.canisters_requiring_upgrade.mark_failure(
+        FailedUpgrade {
+            canister_id,
+            from_version,
+            to_version,
+            trapped,
+        },
+        now,
+    );
 }
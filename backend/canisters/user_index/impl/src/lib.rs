@@ -1,4 +1,8 @@
+use crate::model::diamond_membership_gifts::{DiamondMembershipGiftMetrics, DiamondMembershipGifts};
+use crate::model::diamond_membership_pricing::DiamondMembershipPricing;
 use crate::model::local_user_index_map::LocalUserIndex;
+use crate::model::referral_campaigns::{CampaignRefund, CampaignReward, ReferralCampaigns};
+use crate::model::registry_tokens::RegistryTokens;
 use crate::model::storage_index_user_sync_queue::OpenStorageUserSyncQueue;
 use crate::model::user_map::UserMap;
 use crate::model::user_principal_migration_queue::UserPrincipalMigrationQueue;
@@ -9,14 +13,16 @@ use canister_state_macros::canister_state;
 use canister_timer_jobs::TimerJobs;
 use local_user_index_canister::Event as LocalUserIndexEvent;
 use model::local_user_index_map::LocalUserIndexMap;
-use model::pending_payments_queue::{PendingPayment, PendingPaymentsQueue};
+use model::pending_payments_queue::{PendingPayment, PendingPaymentReason, PendingPaymentsQueue};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use types::{
-    BuildVersion, CanisterId, CanisterWasm, ChatId, Cryptocurrency, Cycles, Milliseconds, TimestampMillis, Timestamped, UserId,
+    BuildVersion, CanisterId, CanisterWasm, ChatId, Cryptocurrency, Cycles, Milliseconds, TimestampMillis, Timestamped,
+    TokenInfo, UserId,
 };
-use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount};
+use utils::canister::{CanistersRequiringUpgrade, FailedUpgradeCount, PreviousWasms, WasmUploads};
 use utils::canister_event_sync_queue::CanisterEventSyncQueue;
 use utils::consts::DEV_TEAM_DFX_PRINCIPAL;
 use utils::env::Environment;
@@ -37,6 +43,8 @@ const USER_CANISTER_TOP_UP_AMOUNT: Cycles = 100_000_000_000; // 0.1T cycles
 const TIME_UNTIL_SUSPENDED_ACCOUNT_IS_DELETED_MILLIS: Milliseconds = DAY_IN_MS * 90; // 90 days
 const ONE_MB: u64 = 1024 * 1024;
 const ONE_GB: u64 = 1024 * ONE_MB;
+const STANDARD_STORAGE_ALLOWANCE: u64 = 100 * ONE_MB;
+const DIAMOND_STORAGE_ALLOWANCE: u64 = ONE_GB;
 
 thread_local! {
     static WASM_VERSION: RefCell<Timestamped<BuildVersion>> = RefCell::default();
@@ -118,6 +126,36 @@ impl RuntimeState {
         jobs::make_pending_payments::start_job_if_required(self);
     }
 
+    pub fn queue_referral_campaign_rewards(&mut self, rewards: Vec<CampaignReward>) {
+        let now_nanos = self.env.now_nanos();
+
+        for reward in rewards {
+            let payment = PendingPayment {
+                amount: reward.amount_e8s,
+                ledger: reward.token.ledger_canister_id(),
+                currency: reward.token,
+                timestamp: now_nanos,
+                recipient: reward.recipient.into(),
+                memo: self.env.rng().gen(),
+                reason: PendingPaymentReason::ReferralCampaignReward(reward.campaign),
+            };
+            self.queue_payment(payment);
+        }
+    }
+
+    pub fn queue_referral_campaign_refund(&mut self, refund: CampaignRefund) {
+        let payment = PendingPayment {
+            amount: refund.amount_e8s,
+            ledger: refund.token.ledger_canister_id(),
+            currency: refund.token,
+            timestamp: self.env.now_nanos(),
+            recipient: refund.recipient.into(),
+            memo: self.env.rng().gen(),
+            reason: PendingPaymentReason::Refund,
+        };
+        self.queue_payment(payment);
+    }
+
     pub fn metrics(&self) -> Metrics {
         let now = self.env.now();
         let canister_upgrades_metrics = self.data.canisters_requiring_upgrade.metrics();
@@ -132,6 +170,7 @@ impl RuntimeState {
             diamond_members: DiamondMembershipMetrics {
                 users: self.data.users.diamond_metrics(now),
                 payments: self.data.diamond_membership_payment_metrics.clone(),
+                gifts: self.data.diamond_membership_gifts.metrics(),
             },
             canister_upgrades_completed: canister_upgrades_metrics.completed,
             canister_upgrades_failed: canister_upgrades_metrics.failed,
@@ -164,10 +203,18 @@ struct Data {
     pub user_canister_wasm: CanisterWasm,
     pub local_user_index_canister_wasm_for_new_canisters: CanisterWasm,
     pub local_user_index_canister_wasm_for_upgrades: CanisterWasm,
+    #[serde(default)]
+    pub previous_user_canister_wasms: PreviousWasms,
+    #[serde(default)]
+    pub previous_local_user_index_canister_wasms: PreviousWasms,
+    #[serde(default)]
+    pub wasm_uploads: WasmUploads,
     pub group_index_canister_id: CanisterId,
     pub notifications_index_canister_id: CanisterId,
     #[serde(default = "proposals_bot_canister_id")]
     pub proposals_bot_canister_id: CanisterId,
+    #[serde(default = "online_users_canister_id")]
+    pub online_users_canister_id: CanisterId,
     pub canisters_requiring_upgrade: CanistersRequiringUpgrade,
     pub total_cycles_spent_on_canisters: Cycles,
     pub cycles_dispenser_canister_id: CanisterId,
@@ -187,12 +234,32 @@ struct Data {
     pub internet_identity_canister_id: CanisterId,
     pub user_referral_leaderboards: UserReferralLeaderboards,
     pub platform_moderators_group: Option<ChatId>,
+    #[serde(default)]
+    pub referral_campaigns: ReferralCampaigns,
+    #[serde(default = "registry_canister_id")]
+    pub registry_canister_id: CanisterId,
+    #[serde(default)]
+    pub registry_tokens: RegistryTokens,
+    #[serde(default)]
+    pub diamond_membership_pricing: DiamondMembershipPricing,
+    #[serde(default)]
+    pub diamond_membership_gifts: DiamondMembershipGifts,
+    #[serde(default)]
+    pub skip_internet_identity_check: bool,
 }
 
 fn proposals_bot_canister_id() -> CanisterId {
     CanisterId::from_text("iywa7-ayaaa-aaaaf-aemga-cai").unwrap()
 }
 
+fn online_users_canister_id() -> CanisterId {
+    CanisterId::from_text("3vlw6-fiaaa-aaaaf-aaa3a-cai").unwrap()
+}
+
+fn registry_canister_id() -> CanisterId {
+    CanisterId::from_text("cpi5u-yiaaa-aaaar-aqw5a-cai").unwrap()
+}
+
 impl Data {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -202,10 +269,13 @@ impl Data {
         group_index_canister_id: CanisterId,
         notifications_index_canister_id: CanisterId,
         proposals_bot_canister_id: CanisterId,
+        online_users_canister_id: CanisterId,
         cycles_dispenser_canister_id: CanisterId,
         storage_index_canister_id: CanisterId,
         internet_identity_canister_id: CanisterId,
+        registry_canister_id: CanisterId,
         test_mode: bool,
+        skip_internet_identity_check: bool,
     ) -> Self {
         let mut data = Data {
             users: UserMap::default(),
@@ -213,9 +283,13 @@ impl Data {
             user_canister_wasm,
             local_user_index_canister_wasm_for_new_canisters: local_user_index_canister_wasm.clone(),
             local_user_index_canister_wasm_for_upgrades: local_user_index_canister_wasm,
+            previous_user_canister_wasms: PreviousWasms::default(),
+            previous_local_user_index_canister_wasms: PreviousWasms::default(),
+            wasm_uploads: WasmUploads::default(),
             group_index_canister_id,
             notifications_index_canister_id,
             proposals_bot_canister_id,
+            online_users_canister_id,
             cycles_dispenser_canister_id,
             canisters_requiring_upgrade: CanistersRequiringUpgrade::default(),
             total_cycles_spent_on_canisters: 0,
@@ -234,7 +308,13 @@ impl Data {
             neuron_controllers_for_initial_airdrop: HashMap::new(),
             internet_identity_canister_id,
             user_referral_leaderboards: UserReferralLeaderboards::default(),
+            referral_campaigns: ReferralCampaigns::default(),
             platform_moderators_group: None,
+            registry_canister_id,
+            registry_tokens: RegistryTokens::default(),
+            diamond_membership_pricing: DiamondMembershipPricing::default(),
+            diamond_membership_gifts: DiamondMembershipGifts::default(),
+            skip_internet_identity_check,
         };
 
         // Register the ProposalsBot
@@ -250,6 +330,19 @@ impl Data {
 
         data
     }
+
+    pub fn token_info(&self, token: &Cryptocurrency, ledger: Option<CanisterId>) -> Option<TokenInfo> {
+        // Fall back to the hardcoded details so that tokens with their own `Cryptocurrency` variant
+        // can be used even before the tokens have been synced from the registry
+        self.registry_tokens.get(token, ledger).or_else(|| {
+            Some(TokenInfo {
+                token: token.clone(),
+                ledger: token.ledger_canister_id()?,
+                decimals: token.decimals()?,
+                fee: token.fee()?,
+            })
+        })
+    }
 }
 
 #[cfg(test)]
@@ -261,9 +354,13 @@ impl Default for Data {
             user_canister_wasm: CanisterWasm::default(),
             local_user_index_canister_wasm_for_new_canisters: CanisterWasm::default(),
             local_user_index_canister_wasm_for_upgrades: CanisterWasm::default(),
+            previous_user_canister_wasms: PreviousWasms::default(),
+            previous_local_user_index_canister_wasms: PreviousWasms::default(),
+            wasm_uploads: WasmUploads::default(),
             group_index_canister_id: Principal::anonymous(),
             notifications_index_canister_id: Principal::anonymous(),
             proposals_bot_canister_id: Principal::anonymous(),
+            online_users_canister_id: Principal::anonymous(),
             canisters_requiring_upgrade: CanistersRequiringUpgrade::default(),
             cycles_dispenser_canister_id: Principal::anonymous(),
             total_cycles_spent_on_canisters: 0,
@@ -282,7 +379,13 @@ impl Default for Data {
             neuron_controllers_for_initial_airdrop: HashMap::new(),
             internet_identity_canister_id: Principal::anonymous(),
             user_referral_leaderboards: UserReferralLeaderboards::default(),
+            referral_campaigns: ReferralCampaigns::default(),
             platform_moderators_group: None,
+            registry_canister_id: Principal::anonymous(),
+            registry_tokens: RegistryTokens::default(),
+            diamond_membership_pricing: DiamondMembershipPricing::default(),
+            diamond_membership_gifts: DiamondMembershipGifts::default(),
+            skip_internet_identity_check: false,
         }
     }
 }
@@ -317,6 +420,7 @@ pub struct Metrics {
 pub struct DiamondMembershipMetrics {
     pub users: DiamondMembershipUserMetrics,
     pub payments: DiamondMembershipPaymentMetrics,
+    pub gifts: DiamondMembershipGiftMetrics,
 }
 
 #[derive(Serialize, Debug, Default)]
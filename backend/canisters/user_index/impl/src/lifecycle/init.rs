@@ -1,4 +1,5 @@
 use crate::lifecycle::{init_env, init_state};
+use crate::memory::{get_log_overflow_data_memory, get_log_overflow_index_memory};
 use crate::Data;
 use canister_tracing_macros::trace;
 use ic_cdk_macros::init;
@@ -9,7 +10,13 @@ use utils::cycles::init_cycles_dispenser_client;
 #[init]
 #[trace]
 fn init(args: Args) {
+    assert!(
+        args.test_mode || !args.skip_internet_identity_check,
+        "The Internet Identity check can only be skipped in test mode"
+    );
+
     canister_logger::init(args.test_mode);
+    canister_logger::init_log_overflow(get_log_overflow_index_memory(), get_log_overflow_data_memory());
     init_cycles_dispenser_client(args.cycles_dispenser_canister_id);
 
     let env = init_env();
@@ -21,10 +28,13 @@ fn init(args: Args) {
         args.group_index_canister_id,
         args.notifications_index_canister_id,
         args.proposals_bot_canister_id,
+        args.online_users_canister_id,
         args.cycles_dispenser_canister_id,
         args.storage_index_canister_id,
         args.internet_identity_canister_id,
+        args.registry_canister_id,
         args.test_mode,
+        args.skip_internet_identity_check,
     );
 
     init_state(env, data, args.wasm_version);
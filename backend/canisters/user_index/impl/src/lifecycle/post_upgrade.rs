@@ -1,6 +1,7 @@
 use crate::lifecycle::{init_env, init_state, UPGRADE_BUFFER_SIZE};
-use crate::memory::get_upgrades_memory;
-use crate::Data;
+use crate::memory::{get_log_overflow_data_memory, get_log_overflow_index_memory, get_upgrades_memory};
+use crate::timer_job_types::{DiamondMembershipExpiry, TimerJob};
+use crate::{mutate_state, Data};
 use canister_logger::LogEntry;
 use canister_tracing_macros::trace;
 use ic_cdk_macros::post_upgrade;
@@ -19,10 +20,32 @@ fn post_upgrade(args: Args) {
 
     let (data, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>) = serializer::deserialize(reader).unwrap();
 
+    canister_logger::init_log_overflow(get_log_overflow_index_memory(), get_log_overflow_data_memory());
     canister_logger::init_with_logs(data.test_mode, logs, traces);
 
     init_cycles_dispenser_client(data.cycles_dispenser_canister_id);
     init_state(env, data, args.wasm_version);
 
     info!(version = %args.wasm_version, "Post-upgrade complete");
+
+    // One time job to schedule the storage allowance of existing Diamond members to be reverted once
+    // their membership expires, since previously this was only scheduled when paying for membership
+    mutate_state(|state| {
+        let now = state.env.now();
+        let expiries: Vec<_> = state
+            .data
+            .users
+            .iter()
+            .filter(|u| u.diamond_membership_details.is_active(now))
+            .filter_map(|u| u.diamond_membership_details.expires_at().map(|ts| (u.user_id, ts)))
+            .collect();
+
+        for (user_id, expires_at) in expiries {
+            state.data.timer_jobs.enqueue_job(
+                TimerJob::DiamondMembershipExpiry(DiamondMembershipExpiry { user_id }),
+                expires_at,
+                now,
+            );
+        }
+    })
 }
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::cmp::max;
-use types::{Cryptocurrency, DiamondMembershipDetails, DiamondMembershipPlanDuration, Milliseconds, TimestampMillis};
+use types::{
+    CanisterId, Cryptocurrency, DiamondMembershipDetails, DiamondMembershipPlanDuration, Milliseconds, TimestampMillis, UserId,
+};
 use user_index_canister::pay_for_diamond_membership::CannotExtendResult;
 use utils::time::DAY_IN_MS;
 
@@ -16,10 +18,14 @@ pub struct DiamondMembershipDetailsInternal {
 pub struct DiamondMembershipPayment {
     pub timestamp: TimestampMillis,
     pub token: Cryptocurrency,
+    #[serde(default)]
+    pub ledger: Option<CanisterId>,
     pub amount_e8s: u64,
     pub block_index: u64,
     pub duration: DiamondMembershipPlanDuration,
     pub manual_payment: bool,
+    #[serde(default)]
+    pub gifted_by: Option<UserId>,
 }
 
 const THREE_MONTHS: Milliseconds = DiamondMembershipPlanDuration::ThreeMonths.as_millis();
@@ -74,20 +80,24 @@ impl DiamondMembershipDetailsInternal {
     pub fn add_payment(
         &mut self,
         token: Cryptocurrency,
+        ledger: CanisterId,
         amount_e8s: u64,
         block_index: u64,
         duration: DiamondMembershipPlanDuration,
         recurring: bool,
         manual_payment: bool,
+        gifted_by: Option<UserId>,
         now: TimestampMillis,
     ) {
         let payment = DiamondMembershipPayment {
             timestamp: now,
             token,
+            ledger: Some(ledger),
             amount_e8s,
             block_index,
             duration,
             manual_payment,
+            gifted_by,
         };
 
         let duration_millis = duration.as_millis();
@@ -105,15 +115,23 @@ impl DiamondMembershipDetailsInternal {
         self.payment_in_progress = value;
     }
 
+    // Gifted payments are skipped so that recurring payments continue using the user's own plan
     pub fn latest_duration(&self) -> Option<DiamondMembershipPlanDuration> {
-        self.payments.last().map(|p| p.duration)
+        self.latest_own_payment().map(|p| p.duration)
+    }
+
+    pub fn latest_token(&self) -> Option<(Cryptocurrency, Option<CanisterId>)> {
+        self.latest_own_payment().map(|p| (p.token.clone(), p.ledger))
+    }
+
+    fn latest_own_payment(&self) -> Option<&DiamondMembershipPayment> {
+        self.payments.iter().rev().find(|p| p.gifted_by.is_none())
     }
 
     pub fn payments(&self) -> &[DiamondMembershipPayment] {
         &self.payments
     }
 
-    #[allow(dead_code)]
     pub fn set_recurring(&mut self, value: bool) {
         self.recurring = value;
     }
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry::Vacant;
+use std::collections::HashMap;
+use types::{DiamondMembershipPlanDuration, TimestampMillis, TokenInfo, UserId};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct DiamondMembershipGifts {
+    codes: HashMap<String, GiftCode>,
+    gifts_sent: u32,
+}
+
+// The payment for a gift code is held by the user_index until the code is redeemed, at which
+// point it is distributed in the same way as any other Diamond membership payment
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DiamondMembershipPurchase {
+    pub token: TokenInfo,
+    pub amount_e8s: u64,
+    pub block_index: u64,
+    pub duration: DiamondMembershipPlanDuration,
+    pub purchased_by: UserId,
+    pub purchased_at: TimestampMillis,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GiftCode {
+    purchase: DiamondMembershipPurchase,
+    redeemed: Option<GiftCodeRedemption>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GiftCodeRedemption {
+    redeemed_by: UserId,
+    timestamp: TimestampMillis,
+}
+
+pub enum RedeemGiftCodeResult {
+    Success(DiamondMembershipPurchase),
+    NotFound,
+    AlreadyRedeemed,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct DiamondMembershipGiftMetrics {
+    pub gifts_sent: u32,
+    pub codes_created: u32,
+    pub codes_redeemed: u32,
+}
+
+impl DiamondMembershipGifts {
+    pub fn record_gift_sent(&mut self) {
+        self.gifts_sent += 1;
+    }
+
+    pub fn add_code(&mut self, code: String, purchase: DiamondMembershipPurchase) -> bool {
+        if let Vacant(e) = self.codes.entry(code) {
+            e.insert(GiftCode {
+                purchase,
+                redeemed: None,
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn peek(&self, code: &str) -> Option<&DiamondMembershipPurchase> {
+        self.codes.get(code).filter(|c| c.redeemed.is_none()).map(|c| &c.purchase)
+    }
+
+    pub fn redeem(&mut self, code: &str, user_id: UserId, now: TimestampMillis) -> RedeemGiftCodeResult {
+        match self.codes.get_mut(code) {
+            Some(c) if c.redeemed.is_some() => RedeemGiftCodeResult::AlreadyRedeemed,
+            Some(c) => {
+                c.redeemed = Some(GiftCodeRedemption {
+                    redeemed_by: user_id,
+                    timestamp: now,
+                });
+                RedeemGiftCodeResult::Success(c.purchase.clone())
+            }
+            None => RedeemGiftCodeResult::NotFound,
+        }
+    }
+
+    pub fn metrics(&self) -> DiamondMembershipGiftMetrics {
+        DiamondMembershipGiftMetrics {
+            gifts_sent: self.gifts_sent,
+            codes_created: self.codes.len() as u32,
+            codes_redeemed: self.codes.values().filter(|c| c.redeemed.is_some()).count() as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{CanisterId, Cryptocurrency};
+
+    #[test]
+    fn gift_codes_can_only_be_redeemed_once() {
+        let buyer = UserId::new(CanisterId::from_slice(&[1]));
+        let recipient = UserId::new(CanisterId::from_slice(&[2]));
+        let token = Cryptocurrency::InternetComputer;
+        let mut gifts = DiamondMembershipGifts::default();
+
+        assert!(gifts.add_code(
+            "abc".to_string(),
+            DiamondMembershipPurchase {
+                token: TokenInfo {
+                    ledger: token.ledger_canister_id().unwrap(),
+                    decimals: 8,
+                    fee: token.fee().unwrap(),
+                    token,
+                },
+                amount_e8s: 20_000_000,
+                block_index: 1,
+                duration: DiamondMembershipPlanDuration::OneMonth,
+                purchased_by: buyer,
+                purchased_at: 0,
+            }
+        ));
+
+        assert!(matches!(gifts.redeem("xyz", recipient, 1), RedeemGiftCodeResult::NotFound));
+        assert!(matches!(gifts.redeem("abc", recipient, 1), RedeemGiftCodeResult::Success(_)));
+        assert!(matches!(
+            gifts.redeem("abc", recipient, 2),
+            RedeemGiftCodeResult::AlreadyRedeemed
+        ));
+        assert!(gifts.peek("abc").is_none());
+
+        let metrics = gifts.metrics();
+        assert_eq!(metrics.codes_created, 1);
+        assert_eq!(metrics.codes_redeemed, 1);
+    }
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::{CanisterId, DiamondMembershipPlanDuration, Milliseconds, TimestampMillis, TokenInfo, UserId};
+use user_index_canister::set_diamond_membership_pricing::{
+    DiamondMembershipPriceFeed, DiamondMembershipUsdPrices, PriceBounds, PriceSource,
+};
+use utils::time::MINUTE_IN_MS;
+
+const QUOTE_VALIDITY: Milliseconds = 5 * MINUTE_IN_MS;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct DiamondMembershipPricing {
+    usd_prices: Option<DiamondMembershipUsdPrices>,
+    price_feeds: HashMap<CanisterId, PriceSource>,
+    #[serde(default)]
+    price_bounds: HashMap<CanisterId, PriceBounds>,
+    locked_quotes: HashMap<UserId, LockedQuote>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LockedQuote {
+    ledger: CanisterId,
+    duration: DiamondMembershipPlanDuration,
+    price_e8s: u64,
+    expires_at: TimestampMillis,
+}
+
+impl DiamondMembershipPricing {
+    pub fn set(&mut self, usd_prices: Option<DiamondMembershipUsdPrices>, price_feeds: Vec<DiamondMembershipPriceFeed>) {
+        self.usd_prices = usd_prices;
+        self.price_bounds = price_feeds.iter().map(|f| (f.ledger_canister_id, f.bounds)).collect();
+        self.price_feeds = price_feeds.into_iter().map(|f| (f.ledger_canister_id, f.source)).collect();
+        self.locked_quotes.clear();
+    }
+
+    // Returns the USD target for the given duration along with the source to use to convert it
+    // into the token, or None if the token can only be paid for using the fixed ICP prices
+    pub fn usd_price_source(&self, ledger: CanisterId, duration: DiamondMembershipPlanDuration) -> Option<(u32, &PriceSource)> {
+        let usd_prices = self.usd_prices.as_ref()?;
+        let source = self.price_feeds.get(&ledger)?;
+
+        let cents = match duration {
+            DiamondMembershipPlanDuration::OneMonth => usd_prices.one_month_cents,
+            DiamondMembershipPlanDuration::ThreeMonths => usd_prices.three_months_cents,
+            DiamondMembershipPlanDuration::OneYear => usd_prices.one_year_cents,
+        };
+
+        Some((cents, source))
+    }
+
+    pub fn price_bounds(&self, ledger: CanisterId) -> Option<PriceBounds> {
+        self.price_bounds.get(&ledger).copied()
+    }
+
+    pub fn lock_quote(
+        &mut self,
+        user_id: UserId,
+        ledger: CanisterId,
+        duration: DiamondMembershipPlanDuration,
+        price_e8s: u64,
+        now: TimestampMillis,
+    ) -> TimestampMillis {
+        let expires_at = now + QUOTE_VALIDITY;
+        self.locked_quotes.retain(|_, q| q.expires_at > now);
+        self.locked_quotes.insert(
+            user_id,
+            LockedQuote {
+                ledger,
+                duration,
+                price_e8s,
+                expires_at,
+            },
+        );
+        expires_at
+    }
+
+    pub fn locked_price(
+        &self,
+        user_id: UserId,
+        ledger: CanisterId,
+        duration: DiamondMembershipPlanDuration,
+        now: TimestampMillis,
+    ) -> Option<u64> {
+        self.locked_quotes
+            .get(&user_id)
+            .filter(|q| q.ledger == ledger && q.duration == duration && q.expires_at > now)
+            .map(|q| q.price_e8s)
+    }
+
+    pub fn remove_quote(&mut self, user_id: &UserId) {
+        self.locked_quotes.remove(user_id);
+    }
+}
+
+pub fn usd_amount_in_usd_token_units(cents: u32, usd_token: &TokenInfo) -> u128 {
+    cents as u128 * 10u128.pow(usd_token.decimals as u32) / 100
+}
+
+// Checks that the number of token units quoted for `cents` falls within the feed's sanity bounds
+pub fn is_within_bounds(price: u64, cents: u32, bounds: &PriceBounds) -> bool {
+    if price == 0 || cents == 0 {
+        return false;
+    }
+    let units_per_usd = price as u128 * 100 / cents as u128;
+    bounds.min_units_per_usd <= units_per_usd && units_per_usd <= bounds.max_units_per_usd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use user_index_canister::set_diamond_membership_pricing::ICDexPriceSource;
+
+    #[test]
+    fn locked_quote_expires() {
+        let user_id = UserId::new(CanisterId::from_slice(&[1]));
+        let ledger = CanisterId::from_slice(&[2]);
+        let mut pricing = DiamondMembershipPricing::default();
+
+        let expires_at = pricing.lock_quote(user_id, ledger, DiamondMembershipPlanDuration::OneMonth, 123, 1000);
+
+        assert_eq!(
+            pricing.locked_price(user_id, ledger, DiamondMembershipPlanDuration::OneMonth, expires_at - 1),
+            Some(123)
+        );
+        assert!(pricing
+            .locked_price(user_id, ledger, DiamondMembershipPlanDuration::OneYear, 1000)
+            .is_none());
+        assert!(pricing
+            .locked_price(user_id, ledger, DiamondMembershipPlanDuration::OneMonth, expires_at)
+            .is_none());
+    }
+
+    #[test]
+    fn usd_price_requires_target_and_feed() {
+        let ledger = CanisterId::from_slice(&[2]);
+        let mut pricing = DiamondMembershipPricing::default();
+        let feed = DiamondMembershipPriceFeed {
+            ledger_canister_id: ledger,
+            source: PriceSource::ICDex(ICDexPriceSource {
+                dex_canister_id: CanisterId::from_slice(&[3]),
+                usd_token: TokenInfo {
+                    token: types::Cryptocurrency::Other("USD".to_string()),
+                    ledger: CanisterId::from_slice(&[4]),
+                    decimals: 6,
+                    fee: 10,
+                },
+            }),
+            bounds: PriceBounds {
+                min_units_per_usd: 1,
+                max_units_per_usd: 100,
+            },
+        };
+
+        pricing.set(None, vec![feed.clone()]);
+        assert!(pricing
+            .usd_price_source(ledger, DiamondMembershipPlanDuration::OneMonth)
+            .is_none());
+
+        pricing.set(
+            Some(DiamondMembershipUsdPrices {
+                one_month_cents: 500,
+                three_months_cents: 1200,
+                one_year_cents: 3500,
+            }),
+            vec![feed],
+        );
+        let (cents, _) = pricing
+            .usd_price_source(ledger, DiamondMembershipPlanDuration::ThreeMonths)
+            .unwrap();
+        assert_eq!(cents, 1200);
+    }
+
+    #[test]
+    fn quotes_outside_bounds_rejected() {
+        // Between 2 and 10 tokens (with 8 decimals) per USD
+        let bounds = PriceBounds {
+            min_units_per_usd: 200_000_000,
+            max_units_per_usd: 1_000_000_000,
+        };
+
+        assert!(is_within_bounds(2_500_000_000, 500, &bounds));
+        assert!(!is_within_bounds(500_000_000, 500, &bounds));
+        assert!(!is_within_bounds(6_000_000_000, 500, &bounds));
+        assert!(!is_within_bounds(2_500_000_000, 0, &bounds));
+    }
+}
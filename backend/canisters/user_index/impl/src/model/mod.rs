@@ -1,7 +1,11 @@
 pub mod account_billing;
 pub mod diamond_membership_details;
+pub mod diamond_membership_gifts;
+pub mod diamond_membership_pricing;
 pub mod local_user_index_map;
 pub mod pending_payments_queue;
+pub mod referral_campaigns;
+pub mod registry_tokens;
 pub mod storage_index_user_sync_queue;
 pub mod user;
 pub mod user_map;
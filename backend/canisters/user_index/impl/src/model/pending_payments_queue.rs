@@ -1,7 +1,7 @@
 use candid::Principal;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
-use types::{Cryptocurrency, TimestampNanos};
+use types::{CanisterId, Cryptocurrency, TimestampNanos};
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct PendingPaymentsQueue {
@@ -26,6 +26,9 @@ impl PendingPaymentsQueue {
 pub struct PendingPayment {
     pub amount: u64,
     pub currency: Cryptocurrency,
+    // Only None for payments queued before the ledger was recorded
+    #[serde(default)]
+    pub ledger: Option<CanisterId>,
     pub timestamp: TimestampNanos,
     pub recipient: Principal,
     pub memo: [u8; 32],
@@ -36,4 +39,6 @@ pub struct PendingPayment {
 pub enum PendingPaymentReason {
     Treasury,
     ReferralReward,
+    ReferralCampaignReward(String),
+    Refund,
 }
@@ -0,0 +1,486 @@
+use crate::model::user_referral_leaderboards::{MonthKey, ReferralStats, UserReferralLeaderboards};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use types::{
+    CommunityId, Cryptocurrency, Milliseconds, ReferralCampaignCode, ReferralCampaignRewards, TimestampMillis, UserId,
+};
+use utils::time::DAY_IN_MS;
+
+// Users referred while a campaign is running still earn their referrer the conversion reward if they
+// become Diamond members within this period after the campaign ends
+pub const CONVERSION_GRACE_PERIOD: Milliseconds = 30 * DAY_IN_MS;
+
+// Campaigns are keyed by `campaign_key` so that each community has its own namespace of names
+#[derive(Serialize, Deserialize, Default)]
+pub struct ReferralCampaigns {
+    campaigns: HashMap<String, ReferralCampaign>,
+    code_to_campaign: HashMap<String, String>,
+    user_to_campaign: HashMap<UserId, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReferralCampaign {
+    pub name: String,
+    pub sponsor_community: Option<CommunityId>,
+    pub start: TimestampMillis,
+    pub end: TimestampMillis,
+    pub token: Cryptocurrency,
+    pub rewards: ReferralCampaignRewards,
+    pub second_tier_rewards: Option<ReferralCampaignRewards>,
+    pub budget_e8s: u64,
+    pub spent_e8s: u64,
+    // Set for campaigns funded by a community owner, who is refunded whatever remains of the budget
+    #[serde(default)]
+    pub funded_by: Option<UserId>,
+    #[serde(default)]
+    settled: bool,
+    signups: HashMap<UserId, CampaignSignup>,
+    per_month: BTreeMap<MonthKey, CampaignStats>,
+    total: CampaignStats,
+    leaderboards: UserReferralLeaderboards,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CampaignSignup {
+    referrer: UserId,
+    second_tier_referrer: Option<UserId>,
+    converted: bool,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+pub struct CampaignStats {
+    pub signups: u32,
+    pub diamond_conversions: u32,
+    pub rewards_e8s: u64,
+}
+
+pub struct CampaignReward {
+    pub campaign: String,
+    pub recipient: UserId,
+    pub amount_e8s: u64,
+    pub token: Cryptocurrency,
+}
+
+pub struct CampaignRefund {
+    pub recipient: UserId,
+    pub amount_e8s: u64,
+    pub token: Cryptocurrency,
+}
+
+pub enum AddCampaignResult {
+    Success,
+    AlreadyExists,
+    CodeAlreadyInUse(String),
+}
+
+pub enum AddCodesResult {
+    Success,
+    CampaignNotFound,
+    CampaignEnded,
+    CodeAlreadyInUse(String),
+}
+
+// Campaigns run by OpenChat are keyed by their name, whereas those funded by a community are
+// prefixed with the community's id, so different communities can use the same campaign names
+pub fn campaign_key(community: Option<CommunityId>, name: &str) -> String {
+    match community {
+        Some(community_id) => format!("{community_id}/{name}"),
+        None => name.to_string(),
+    }
+}
+
+impl ReferralCampaigns {
+    pub fn add(&mut self, key: String, campaign: ReferralCampaign, codes: &[ReferralCampaignCode]) -> AddCampaignResult {
+        if self.campaigns.contains_key(&key) {
+            return AddCampaignResult::AlreadyExists;
+        }
+        if let Some(code) = self.first_code_in_use(codes) {
+            return AddCampaignResult::CodeAlreadyInUse(code);
+        }
+
+        for code in codes {
+            self.code_to_campaign.insert(code.code.clone(), key.clone());
+        }
+        self.campaigns.insert(key, campaign);
+        AddCampaignResult::Success
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ReferralCampaign> {
+        self.campaigns.get(name)
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        self.campaigns.contains_key(name)
+    }
+
+    pub fn first_code_in_use(&self, codes: &[ReferralCampaignCode]) -> Option<String> {
+        codes
+            .iter()
+            .find(|c| self.code_to_campaign.contains_key(&c.code))
+            .map(|c| c.code.clone())
+    }
+
+    pub fn add_codes(&mut self, name: &str, codes: &[ReferralCampaignCode], now: TimestampMillis) -> AddCodesResult {
+        let campaign = match self.campaigns.get(name) {
+            Some(c) => c,
+            None => return AddCodesResult::CampaignNotFound,
+        };
+
+        if campaign.end <= now || campaign.settled {
+            return AddCodesResult::CampaignEnded;
+        }
+
+        if let Some(code) = self.first_code_in_use(codes) {
+            return AddCodesResult::CodeAlreadyInUse(code);
+        }
+
+        for code in codes {
+            self.code_to_campaign.insert(code.code.clone(), name.to_string());
+        }
+
+        AddCodesResult::Success
+    }
+
+    // Called when a local user index rejects codes which clash with its one-off referral codes. If
+    // the campaign is left without any codes it is ended early, and if nobody has signed up using its
+    // codes the unspent budget is refunded straight away.
+    pub fn remove_codes(&mut self, name: &str, codes: &[String], now: TimestampMillis) -> Option<CampaignRefund> {
+        for code in codes {
+            if self.code_to_campaign.get(code).map_or(false, |c| c == name) {
+                self.code_to_campaign.remove(code);
+            }
+        }
+
+        if !self.code_to_campaign.values().any(|c| c == name) {
+            if let Some(campaign) = self.campaigns.get_mut(name) {
+                campaign.end = campaign.end.min(now);
+            }
+        }
+        self.settle(name, now)
+    }
+
+    // Once a campaign can no longer pay out any rewards, whatever remains of its budget is refunded
+    // to the community owner who funded it. Returns None if the campaign isn't ready to be settled.
+    pub fn settle(&mut self, name: &str, now: TimestampMillis) -> Option<CampaignRefund> {
+        let campaign = self.campaigns.get_mut(name).filter(|c| !c.settled && c.can_settle(now))?;
+        campaign.settled = true;
+
+        let amount_e8s = campaign.budget_e8s.saturating_sub(campaign.spent_e8s);
+        campaign.spent_e8s = campaign.budget_e8s;
+
+        campaign.funded_by.filter(|_| amount_e8s > 0).map(|recipient| CampaignRefund {
+            recipient,
+            amount_e8s,
+            token: campaign.token.clone(),
+        })
+    }
+
+    // The signup rewards are paid as soon as the referred user registers
+    pub fn record_signup(
+        &mut self,
+        name: &str,
+        user_id: UserId,
+        referrer: UserId,
+        second_tier_referrer: Option<UserId>,
+        now: TimestampMillis,
+    ) -> Vec<CampaignReward> {
+        let campaign = match self.campaigns.get_mut(name) {
+            Some(c) if c.is_active(now) && !c.settled => c,
+            _ => return Vec::new(),
+        };
+
+        campaign.signups.insert(
+            user_id,
+            CampaignSignup {
+                referrer,
+                second_tier_referrer,
+                converted: false,
+            },
+        );
+        campaign.stats_mut(now, |s| s.signups += 1);
+        campaign.leaderboards.add_referral(referrer, now);
+        self.user_to_campaign.insert(user_id, name.to_string());
+
+        let second_tier = campaign.second_tier_rewards.map(|r| r.signup_e8s).unwrap_or_default();
+        campaign.allocate_rewards(referrer, campaign.rewards.signup_e8s, second_tier_referrer, second_tier, now)
+    }
+
+    // Users who signed up while the campaign was running are honoured until the grace period after
+    // the campaign ends has passed
+    pub fn record_diamond_conversion(&mut self, user_id: UserId, now: TimestampMillis) -> Vec<CampaignReward> {
+        let campaign = match self.user_to_campaign.get(&user_id).and_then(|n| self.campaigns.get_mut(n)) {
+            Some(c) if !c.settled && now <= c.end + CONVERSION_GRACE_PERIOD => c,
+            _ => return Vec::new(),
+        };
+
+        let (referrer, second_tier_referrer) = match campaign.signups.get_mut(&user_id) {
+            Some(signup) if !signup.converted => {
+                signup.converted = true;
+                (signup.referrer, signup.second_tier_referrer)
+            }
+            _ => return Vec::new(),
+        };
+
+        campaign.stats_mut(now, |s| s.diamond_conversions += 1);
+
+        let second_tier = campaign
+            .second_tier_rewards
+            .map(|r| r.diamond_conversion_e8s)
+            .unwrap_or_default();
+        campaign.allocate_rewards(
+            referrer,
+            campaign.rewards.diamond_conversion_e8s,
+            second_tier_referrer,
+            second_tier,
+            now,
+        )
+    }
+}
+
+impl ReferralCampaign {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: String,
+        sponsor_community: Option<CommunityId>,
+        start: TimestampMillis,
+        end: TimestampMillis,
+        token: Cryptocurrency,
+        rewards: ReferralCampaignRewards,
+        second_tier_rewards: Option<ReferralCampaignRewards>,
+        budget_e8s: u64,
+        funded_by: Option<UserId>,
+    ) -> ReferralCampaign {
+        ReferralCampaign {
+            name,
+            sponsor_community,
+            start,
+            end,
+            token,
+            rewards,
+            second_tier_rewards,
+            budget_e8s,
+            spent_e8s: 0,
+            funded_by,
+            settled: false,
+            signups: HashMap::new(),
+            per_month: BTreeMap::new(),
+            total: CampaignStats::default(),
+            leaderboards: UserReferralLeaderboards::default(),
+        }
+    }
+
+    pub fn is_active(&self, now: TimestampMillis) -> bool {
+        self.start <= now && now < self.end
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.settled
+    }
+
+    // A campaign can be settled once it has ended and there are no referred users left who could
+    // still earn their referrer a conversion reward
+    fn can_settle(&self, now: TimestampMillis) -> bool {
+        now >= self.end && (now > self.end + CONVERSION_GRACE_PERIOD || self.signups.values().all(|s| s.converted))
+    }
+
+    pub fn total(&self) -> CampaignStats {
+        self.total
+    }
+
+    pub fn months(&self) -> impl Iterator<Item = (MonthKey, CampaignStats)> + '_ {
+        self.per_month.iter().map(|(m, s)| (*m, *s))
+    }
+
+    pub fn top_referrers_for_month(&self, month: MonthKey, count: usize) -> Vec<ReferralStats> {
+        self.leaderboards.top_for_month(month, count)
+    }
+
+    pub fn top_referrers(&self, count: usize) -> Vec<ReferralStats> {
+        self.leaderboards.top_all_time(count)
+    }
+
+    fn stats_mut<F: Fn(&mut CampaignStats)>(&mut self, now: TimestampMillis, f: F) {
+        f(self.per_month.entry(MonthKey::from_timestamp(now)).or_default());
+        f(&mut self.total);
+    }
+
+    // Rewards are only allocated while they fit within the remaining budget (including the
+    // ledger fee), with the first tier taking priority over the second
+    fn allocate_rewards(
+        &mut self,
+        referrer: UserId,
+        amount_e8s: u64,
+        second_tier_referrer: Option<UserId>,
+        second_tier_amount_e8s: u64,
+        now: TimestampMillis,
+    ) -> Vec<CampaignReward> {
+        let fee = self.token.fee().unwrap_or_default() as u64;
+        let mut rewards = Vec::new();
+
+        for (recipient, amount, is_first_tier) in [
+            (Some(referrer), amount_e8s, true),
+            (second_tier_referrer, second_tier_amount_e8s, false),
+        ] {
+            let recipient = match recipient {
+                Some(r) if amount > 0 => r,
+                _ => continue,
+            };
+
+            let cost = amount + fee;
+            if self.spent_e8s + cost > self.budget_e8s {
+                continue;
+            }
+
+            self.spent_e8s += cost;
+            self.stats_mut(now, |s| s.rewards_e8s += amount);
+            self.leaderboards.add_reward(recipient, is_first_tier, amount, now);
+
+            rewards.push(CampaignReward {
+                campaign: self.name.clone(),
+                recipient,
+                amount_e8s: amount,
+                token: self.token.clone(),
+            });
+        }
+
+        rewards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::CanisterId;
+
+    const NOW: TimestampMillis = 1_700_000_000_000;
+    const END: TimestampMillis = NOW + 1000;
+
+    fn user(id: u8) -> UserId {
+        UserId::new(CanisterId::from_slice(&[id]))
+    }
+
+    fn code(code: &str) -> ReferralCampaignCode {
+        ReferralCampaignCode {
+            code: code.to_string(),
+            referrer: user(2),
+        }
+    }
+
+    fn setup(budget_e8s: u64, funded_by: Option<UserId>) -> ReferralCampaigns {
+        let mut campaigns = ReferralCampaigns::default();
+        let campaign = ReferralCampaign::new(
+            "launch".to_string(),
+            None,
+            NOW - 1000,
+            END,
+            Cryptocurrency::CHAT,
+            ReferralCampaignRewards {
+                signup_e8s: 100_000_000,
+                diamond_conversion_e8s: 500_000_000,
+            },
+            Some(ReferralCampaignRewards {
+                signup_e8s: 10_000_000,
+                diamond_conversion_e8s: 50_000_000,
+            }),
+            budget_e8s,
+            funded_by,
+        );
+        campaigns.add("launch".to_string(), campaign, &[code("A"), code("B")]);
+        campaigns
+    }
+
+    fn paid(rewards: &[CampaignReward]) -> Vec<(UserId, u64)> {
+        rewards.iter().map(|r| (r.recipient, r.amount_e8s)).collect()
+    }
+
+    #[test]
+    fn rewards_paid_on_signup_and_on_conversion() {
+        let mut campaigns = setup(10_000_000_000, None);
+
+        let rewards = campaigns.record_signup("launch", user(1), user(2), Some(user(3)), NOW);
+        assert_eq!(paid(&rewards), vec![(user(2), 100_000_000), (user(3), 10_000_000)]);
+
+        let rewards = campaigns.record_diamond_conversion(user(1), NOW);
+        assert_eq!(paid(&rewards), vec![(user(2), 500_000_000), (user(3), 50_000_000)]);
+
+        // Conversions are only rewarded once
+        assert!(campaigns.record_diamond_conversion(user(1), NOW).is_empty());
+
+        let total = campaigns.get("launch").unwrap().total();
+        assert_eq!(total.signups, 1);
+        assert_eq!(total.diamond_conversions, 1);
+        assert_eq!(total.rewards_e8s, 660_000_000);
+    }
+
+    #[test]
+    fn rewards_limited_by_budget() {
+        let fee = Cryptocurrency::CHAT.fee().unwrap() as u64;
+        let mut campaigns = setup(600_000_000 + 2 * fee, None);
+
+        assert_eq!(campaigns.record_signup("launch", user(1), user(2), None, NOW).len(), 1);
+        assert_eq!(campaigns.record_diamond_conversion(user(1), NOW).len(), 1);
+
+        assert!(campaigns.record_signup("launch", user(4), user(2), None, NOW).is_empty());
+
+        let campaign = campaigns.get("launch").unwrap();
+        assert_eq!(campaign.spent_e8s, campaign.budget_e8s);
+        assert_eq!(campaign.total().signups, 2);
+    }
+
+    #[test]
+    fn no_signups_outside_campaign_window() {
+        let mut campaigns = setup(10_000_000_000, None);
+
+        assert!(campaigns.record_signup("launch", user(1), user(2), None, END).is_empty());
+        assert!(campaigns.record_diamond_conversion(user(1), END).is_empty());
+        assert_eq!(campaigns.get("launch").unwrap().total().signups, 0);
+    }
+
+    #[test]
+    fn conversions_honoured_after_end_then_remaining_budget_refunded() {
+        let mut campaigns = setup(10_000_000_000, Some(user(9)));
+        let fee = Cryptocurrency::CHAT.fee().unwrap() as u64;
+
+        campaigns.record_signup("launch", user(1), user(2), None, NOW);
+        campaigns.record_signup("launch", user(4), user(2), None, NOW);
+
+        // Referred users can still convert after the campaign has ended
+        assert!(campaigns.settle("launch", END).is_none());
+        assert_eq!(campaigns.record_diamond_conversion(user(1), END + DAY_IN_MS).len(), 1);
+
+        // Until the grace period has passed, since user(4) has yet to convert
+        assert!(campaigns.settle("launch", END + DAY_IN_MS).is_none());
+
+        let refund = campaigns.settle("launch", END + CONVERSION_GRACE_PERIOD + 1).unwrap();
+        assert_eq!(refund.recipient, user(9));
+        assert_eq!(refund.amount_e8s, 10_000_000_000 - 700_000_000 - 3 * fee);
+
+        assert!(campaigns.settle("launch", END + CONVERSION_GRACE_PERIOD + 1).is_none());
+        assert!(campaigns
+            .record_diamond_conversion(user(4), END + CONVERSION_GRACE_PERIOD + 1)
+            .is_empty());
+    }
+
+    #[test]
+    fn campaign_refunded_once_all_codes_rejected() {
+        let mut campaigns = setup(10_000_000_000, Some(user(9)));
+
+        assert!(campaigns.remove_codes("launch", &["A".to_string()], NOW).is_none());
+
+        let refund = campaigns.remove_codes("launch", &["B".to_string()], NOW).unwrap();
+        assert_eq!(refund.amount_e8s, 10_000_000_000);
+        assert!(campaigns.record_signup("launch", user(1), user(2), None, NOW).is_empty());
+    }
+
+    #[test]
+    fn campaign_names_are_scoped_per_community() {
+        let community1 = CommunityId::from(CanisterId::from_slice(&[10]));
+        let community2 = CommunityId::from(CanisterId::from_slice(&[11]));
+
+        assert_ne!(
+            campaign_key(Some(community1), "launch"),
+            campaign_key(Some(community2), "launch")
+        );
+        assert_ne!(campaign_key(Some(community1), "launch"), campaign_key(None, "launch"));
+    }
+}
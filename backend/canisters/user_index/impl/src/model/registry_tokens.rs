@@ -0,0 +1,39 @@
+use registry_canister::TokenDetails;
+use serde::{Deserialize, Serialize};
+use types::{CanisterId, Cryptocurrency, TimestampMillis, TokenInfo};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct RegistryTokens {
+    last_updated: Option<TimestampMillis>,
+    tokens: Vec<TokenDetails>,
+}
+
+impl RegistryTokens {
+    pub fn last_updated(&self) -> Option<TimestampMillis> {
+        self.last_updated
+    }
+
+    pub fn set(&mut self, tokens: Vec<TokenDetails>, last_updated: TimestampMillis) {
+        self.tokens = tokens;
+        self.last_updated = Some(last_updated);
+    }
+
+    // Tokens are always matched on their ledger. Tokens without their own `Cryptocurrency` variant
+    // must have their ledger passed in since symbols are not unique, and the symbol must match too.
+    pub fn get(&self, token: &Cryptocurrency, ledger: Option<CanisterId>) -> Option<TokenInfo> {
+        let (ledger, check_symbol) = match token.ledger_canister_id() {
+            Some(l) => (l, false),
+            None => (ledger?, true),
+        };
+
+        self.tokens
+            .iter()
+            .find(|t| t.ledger_canister_id == ledger && (!check_symbol || t.symbol == token.token_symbol()))
+            .map(|t| TokenInfo {
+                token: token.clone(),
+                ledger: t.ledger_canister_id,
+                decimals: t.decimals,
+                fee: t.fee,
+            })
+    }
+}
@@ -5,10 +5,12 @@ pub mod http_request;
 pub mod platform_moderators;
 pub mod platform_moderators_group;
 pub mod platform_operators;
+pub mod referral_campaign_performance;
 pub mod referral_leaderboard;
 pub mod referral_metrics;
 pub mod search;
 pub mod suspected_bots;
+pub mod upgrade_rollout_status;
 pub mod user;
 pub mod user_registration_canister;
 pub mod users;
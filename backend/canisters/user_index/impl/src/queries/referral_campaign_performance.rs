@@ -0,0 +1,65 @@
+use crate::model::referral_campaigns::{campaign_key, CampaignStats as CampaignStatsInternal};
+use crate::model::user_referral_leaderboards::ReferralStats as ReferralStatsInternal;
+use crate::{read_state, RuntimeState};
+use ic_cdk_macros::query;
+use user_index_canister::referral_campaign_performance::{Response::*, *};
+use user_index_canister::referral_leaderboard::ReferralStats;
+
+#[query]
+fn referral_campaign_performance(args: Args) -> Response {
+    read_state(|state| referral_campaign_performance_impl(args, state))
+}
+
+fn referral_campaign_performance_impl(args: Args, state: &RuntimeState) -> Response {
+    let campaign = match state
+        .data
+        .referral_campaigns
+        .get(&campaign_key(args.community, &args.campaign))
+    {
+        Some(c) => c,
+        None => return CampaignNotFound,
+    };
+
+    let count = args.top_referrers as usize;
+    let to_stats = |s: CampaignStatsInternal| CampaignStats {
+        signups: s.signups,
+        diamond_conversions: s.diamond_conversions,
+        rewards_e8s: s.rewards_e8s,
+    };
+
+    Success(SuccessResult {
+        name: campaign.name.clone(),
+        sponsor_community: campaign.sponsor_community,
+        start: campaign.start,
+        end: campaign.end,
+        token: campaign.token.clone(),
+        budget_e8s: campaign.budget_e8s,
+        spent_e8s: campaign.spent_e8s,
+        total: to_stats(campaign.total()),
+        top_referrers: with_usernames(campaign.top_referrers(count), state),
+        months: campaign
+            .months()
+            .map(|(m, s)| MonthPerformance {
+                year: m.year(),
+                month: m.month(),
+                stats: to_stats(s),
+                top_referrers: with_usernames(campaign.top_referrers_for_month(m, count), state),
+            })
+            .collect(),
+    })
+}
+
+fn with_usernames(stats: Vec<ReferralStatsInternal>, state: &RuntimeState) -> Vec<ReferralStats> {
+    stats
+        .into_iter()
+        .filter_map(|rs| {
+            state.data.users.get_by_user_id(&rs.user_id).map(|u| ReferralStats {
+                user_id: rs.user_id,
+                username: u.username.clone(),
+                total_rewards_e8s: rs.total_rewards_e8s,
+                diamond_members: rs.diamond_members,
+                total_users: rs.total_users,
+            })
+        })
+        .collect()
+}
@@ -1,14 +1,22 @@
+use crate::model::referral_campaigns::CONVERSION_GRACE_PERIOD;
+use crate::updates::diamond_membership_quote::{get_price_e8s, GetPriceError};
 use crate::updates::pay_for_diamond_membership::pay_for_diamond_membership_impl;
 use crate::updates::suspend_user::suspend_user_impl;
 use crate::updates::unsuspend_user::unsuspend_user_impl;
-use crate::{mutate_state, read_state};
+use crate::{mutate_state, read_state, STANDARD_STORAGE_ALLOWANCE};
 use canister_timer_jobs::Job;
-use ic_ledger_types::Tokens;
+use ledger_utils::format_crypto_amount;
 use local_user_index_canister::{Event as LocalUserIndexEvent, OpenChatBotMessage, UserJoinedGroup};
 use serde::{Deserialize, Serialize};
-use types::{ChatId, Cryptocurrency, DiamondMembershipPlanDuration, MessageContent, Milliseconds, TextContent, UserId};
+use storage_index_canister::add_or_update_users::UserConfig;
+use types::{
+    CanisterId, ChatId, Cryptocurrency, DiamondMembershipPlanDuration, MessageContent, Milliseconds, TextContent, UserId,
+};
 use utils::time::{MINUTE_IN_MS, SECOND_IN_MS};
 
+// Failed recurring payments are retried every 10 minutes for up to 2 hours before being cancelled
+const MAX_RECURRING_PAYMENT_ATTEMPTS: u32 = 12;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub enum TimerJob {
     RecurringDiamondMembershipPayment(RecurringDiamondMembershipPayment),
@@ -16,11 +24,15 @@ pub enum TimerJob {
     SetUserSuspendedInGroup(SetUserSuspendedInGroup),
     UnsuspendUser(UnsuspendUser),
     JoinUserToGroup(JoinUserToGroup),
+    DiamondMembershipExpiry(DiamondMembershipExpiry),
+    SettleReferralCampaign(SettleReferralCampaign),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct RecurringDiamondMembershipPayment {
     pub user_id: UserId,
+    #[serde(default)]
+    pub attempt: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -51,6 +63,16 @@ pub struct JoinUserToGroup {
     pub attempt: usize,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DiamondMembershipExpiry {
+    pub user_id: UserId,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SettleReferralCampaign {
+    pub campaign: String,
+}
+
 impl Job for TimerJob {
     fn execute(&self) {
         match self {
@@ -59,13 +81,15 @@ impl Job for TimerJob {
             TimerJob::SetUserSuspendedInGroup(job) => job.execute(),
             TimerJob::UnsuspendUser(job) => job.execute(),
             TimerJob::JoinUserToGroup(job) => job.execute(),
+            TimerJob::DiamondMembershipExpiry(job) => job.execute(),
+            TimerJob::SettleReferralCampaign(job) => job.execute(),
         }
     }
 }
 
 impl Job for RecurringDiamondMembershipPayment {
     fn execute(&self) {
-        if let Some(duration) = read_state(|state| {
+        if let Some((duration, (token, ledger))) = read_state(|state| {
             let now = state.env.now();
             state
                 .data
@@ -73,19 +97,49 @@ impl Job for RecurringDiamondMembershipPayment {
                 .get_by_user_id(&self.user_id)
                 .map(|u| &u.diamond_membership_details)
                 .filter(|d| d.is_recurring_payment_due(now))
-                .and_then(|d| d.latest_duration())
+                .and_then(|d| d.latest_duration().zip(d.latest_token()))
         }) {
-            ic_cdk::spawn(pay_for_diamond_membership(self.user_id, duration));
+            ic_cdk::spawn(pay_for_diamond_membership(
+                self.user_id,
+                duration,
+                token,
+                ledger,
+                self.attempt,
+            ));
         }
 
-        async fn pay_for_diamond_membership(user_id: UserId, duration: DiamondMembershipPlanDuration) {
+        async fn pay_for_diamond_membership(
+            user_id: UserId,
+            duration: DiamondMembershipPlanDuration,
+            token: Cryptocurrency,
+            ledger: Option<CanisterId>,
+            attempt: u32,
+        ) {
             use user_index_canister::pay_for_diamond_membership::*;
 
-            let price_e8s = duration.icp_price_e8s();
+            let (token, price_e8s) = match get_price_e8s(&token, ledger, duration).await {
+                Ok((token, price_e8s)) => (token, price_e8s),
+                Err(GetPriceError::CurrencyNotSupported) => {
+                    // Payments are never switched over to a different token without the user's consent
+                    cancel_recurring_payments(
+                        user_id,
+                        format!(
+                            "Your recurring Diamond membership payment has been cancelled because {} is no longer supported.\n\nIf you would like to extend your Diamond membership you will need to pay manually.",
+                            token.token_symbol()
+                        ),
+                    );
+                    return;
+                }
+                Err(_) => {
+                    retry_later(user_id, attempt);
+                    return;
+                }
+            };
 
             let args = Args {
                 duration,
-                token: Cryptocurrency::InternetComputer,
+                token: token.token.clone(),
+                ledger: Some(token.ledger),
                 expected_price_e8s: price_e8s,
                 recurring: true,
             };
@@ -93,6 +147,7 @@ impl Job for RecurringDiamondMembershipPayment {
             match pay_for_diamond_membership_impl(args, user_id, false).await {
                 Response::InsufficientFunds(balance) => {
                     mutate_state(|state| {
+                        let symbol = token.token.token_symbol();
                         state.push_event_to_local_user_index(
                             user_id,
                             LocalUserIndexEvent::OpenChatBotMessage(Box::new(OpenChatBotMessage {
@@ -100,12 +155,12 @@ impl Job for RecurringDiamondMembershipPayment {
                                 message: MessageContent::Text(TextContent {
                                     text: format!(
                                         "Failed to take payment for Diamond membership due to insufficient funds.\
-Payment amount: {}\
-Balance: {}\
+Payment amount: {} {symbol}\
+Balance: {} {symbol}\
 \
 If you would like to extend your Diamond membership you will need to top up your account and pay manually.",
-                                        Tokens::from_e8s(price_e8s),
-                                        Tokens::from_e8s(balance)
+                                        format_crypto_amount(price_e8s as u128, token.decimals),
+                                        format_crypto_amount(balance as u128, token.decimals)
                                     ),
                                 }),
                             })),
@@ -116,19 +171,50 @@ If you would like to extend your Diamond membership you will need to top up your
                             .recurring_payments_failed_due_to_insufficient_funds += 1;
                     });
                 }
-                Response::InternalError(_) => {
-                    mutate_state(|state| {
-                        let now = state.env.now();
-                        state.data.timer_jobs.enqueue_job(
-                            TimerJob::RecurringDiamondMembershipPayment(RecurringDiamondMembershipPayment { user_id }),
-                            now + 10 * MINUTE_IN_MS,
-                            now,
-                        )
-                    });
-                }
+                Response::InternalError(_) => retry_later(user_id, attempt),
                 _ => {}
             }
         }
+
+        fn retry_later(user_id: UserId, attempt: u32) {
+            if attempt + 1 >= MAX_RECURRING_PAYMENT_ATTEMPTS {
+                cancel_recurring_payments(
+                    user_id,
+                    "Your recurring Diamond membership payment has been cancelled because the payment failed repeatedly.\n\nIf you would like to extend your Diamond membership you will need to pay manually."
+                        .to_string(),
+                );
+                return;
+            }
+
+            mutate_state(|state| {
+                let now = state.env.now();
+                state.data.timer_jobs.enqueue_job(
+                    TimerJob::RecurringDiamondMembershipPayment(RecurringDiamondMembershipPayment {
+                        user_id,
+                        attempt: attempt + 1,
+                    }),
+                    now + 10 * MINUTE_IN_MS,
+                    now,
+                )
+            });
+        }
+
+        fn cancel_recurring_payments(user_id: UserId, text: String) {
+            mutate_state(|state| {
+                let now = state.env.now();
+                if let Some(details) = state.data.users.diamond_membership_details_mut(&user_id) {
+                    details.set_recurring(false);
+                    state.data.users.mark_updated(&user_id, now);
+                }
+                state.push_event_to_local_user_index(
+                    user_id,
+                    LocalUserIndexEvent::OpenChatBotMessage(Box::new(OpenChatBotMessage {
+                        user_id,
+                        message: MessageContent::Text(TextContent { text }),
+                    })),
+                );
+            });
+        }
     }
 }
 
@@ -245,3 +331,51 @@ impl Job for JoinUserToGroup {
         }
     }
 }
+
+impl Job for DiamondMembershipExpiry {
+    fn execute(&self) {
+        mutate_state(|state| {
+            let now = state.env.now();
+            if let Some(user) = state
+                .data
+                .users
+                .get_by_user_id(&self.user_id)
+                .filter(|u| !u.diamond_membership_details.is_active(now))
+            {
+                state.data.storage_index_user_sync_queue.push(UserConfig {
+                    user_id: user.principal,
+                    byte_limit: STANDARD_STORAGE_ALLOWANCE,
+                });
+                crate::jobs::sync_users_to_storage_index::start_job_if_required(state);
+            }
+        });
+    }
+}
+
+impl Job for SettleReferralCampaign {
+    fn execute(&self) {
+        mutate_state(|state| {
+            let now = state.env.now();
+            if let Some(refund) = state.data.referral_campaigns.settle(&self.campaign, now) {
+                state.queue_referral_campaign_refund(refund);
+            }
+
+            // If some referred users could still convert, try again once the grace period has passed
+            if let Some(end) = state
+                .data
+                .referral_campaigns
+                .get(&self.campaign)
+                .filter(|c| !c.is_settled())
+                .map(|c| c.end)
+            {
+                let settle_at = end + CONVERSION_GRACE_PERIOD + 1;
+                if now < settle_at {
+                    state
+                        .data
+                        .timer_jobs
+                        .enqueue_job(TimerJob::SettleReferralCampaign(self.clone()), settle_at, now);
+                }
+            }
+        });
+    }
+}
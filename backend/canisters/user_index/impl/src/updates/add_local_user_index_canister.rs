@@ -54,9 +54,11 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response>
                 group_index_canister_id: state.data.group_index_canister_id,
                 notifications_canister_id: args.notifications_canister_id,
                 proposals_bot_canister_id: state.data.proposals_bot_canister_id,
+                online_users_canister_id: state.data.online_users_canister_id,
                 cycles_dispenser_canister_id: state.data.cycles_dispenser_canister_id,
                 internet_identity_canister_id: state.data.internet_identity_canister_id,
                 test_mode: state.data.test_mode,
+                skip_internet_identity_check: state.data.skip_internet_identity_check,
             },
         })
     } else {
@@ -0,0 +1,57 @@
+use crate::guards::caller_is_dev_team_dfx_principal;
+use crate::model::referral_campaigns::{AddCampaignResult, ReferralCampaign};
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use local_user_index_canister::{Event, ReferralCampaignCodesAdded};
+use user_index_canister::add_referral_campaign::{Response::*, *};
+
+#[update(guard = "caller_is_dev_team_dfx_principal")]
+#[trace]
+fn add_referral_campaign(args: Args) -> Response {
+    mutate_state(|state| add_referral_campaign_impl(args, state))
+}
+
+fn add_referral_campaign_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+
+    // '/' separates a community's id from its campaign names
+    if args.name.is_empty() || args.name.contains('/') {
+        return NameInvalid;
+    }
+    if args.start >= args.end || args.end < now {
+        return InvalidDates;
+    }
+    if args.token.ledger_canister_id().is_none() {
+        return TokenNotSupported;
+    }
+
+    let campaign = ReferralCampaign::new(
+        args.name.clone(),
+        args.sponsor_community,
+        args.start,
+        args.end,
+        args.token,
+        args.rewards,
+        args.second_tier_rewards,
+        args.budget_e8s,
+        None,
+    );
+
+    match state.data.referral_campaigns.add(args.name.clone(), campaign, &args.codes) {
+        AddCampaignResult::Success => {
+            state.push_event_to_all_local_user_indexes(
+                Event::ReferralCampaignCodesAdded(ReferralCampaignCodesAdded {
+                    campaign: args.name,
+                    codes: args.codes,
+                    start: args.start,
+                    end: args.end,
+                }),
+                None,
+            );
+            Success
+        }
+        AddCampaignResult::AlreadyExists => AlreadyExists,
+        AddCampaignResult::CodeAlreadyInUse(code) => CodeAlreadyInUse(code),
+    }
+}
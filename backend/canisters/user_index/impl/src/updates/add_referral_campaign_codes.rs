@@ -0,0 +1,34 @@
+use crate::guards::caller_is_dev_team_dfx_principal;
+use crate::model::referral_campaigns::AddCodesResult;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use local_user_index_canister::{Event, ReferralCampaignCodesAdded};
+use user_index_canister::add_referral_campaign_codes::{Response::*, *};
+
+#[update(guard = "caller_is_dev_team_dfx_principal")]
+#[trace]
+fn add_referral_campaign_codes(args: Args) -> Response {
+    mutate_state(|state| add_referral_campaign_codes_impl(args, state))
+}
+
+fn add_referral_campaign_codes_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let now = state.env.now();
+
+    match state.data.referral_campaigns.add_codes(&args.campaign, &args.codes, now) {
+        AddCodesResult::Success => {
+            let campaign = state.data.referral_campaigns.get(&args.campaign).unwrap();
+            let event = Event::ReferralCampaignCodesAdded(ReferralCampaignCodesAdded {
+                campaign: args.campaign,
+                codes: args.codes,
+                start: campaign.start,
+                end: campaign.end,
+            });
+            state.push_event_to_all_local_user_indexes(event, None);
+            Success
+        }
+        AddCodesResult::CampaignNotFound => CampaignNotFound,
+        AddCodesResult::CampaignEnded => CampaignEnded,
+        AddCodesResult::CodeAlreadyInUse(code) => CodeAlreadyInUse(code),
+    }
+}
@@ -0,0 +1,141 @@
+use crate::model::pending_payments_queue::{PendingPayment, PendingPaymentReason};
+use crate::model::referral_campaigns::{campaign_key, AddCampaignResult, ReferralCampaign};
+use crate::timer_job_types::{SettleReferralCampaign, TimerJob};
+use crate::{mutate_state, read_state, RuntimeState};
+use canister_api_macros::update_msgpack;
+use canister_tracing_macros::trace;
+use local_user_index_canister::{Event, ReferralCampaignCodesAdded};
+use rand::Rng;
+use types::{CanisterId, CommunityId};
+use user_index_canister::c2c_add_referral_campaign::{Response::*, *};
+
+// Lets partner communities run their own referral campaigns. The community canister checks that the
+// caller is its owner, and the campaign's budget is then taken from the owner's account. Each
+// community has its own namespace of campaign names, and once the campaign is settled whatever
+// remains of the budget is refunded to the owner.
+#[update_msgpack]
+#[trace]
+async fn c2c_add_referral_campaign(args: Args) -> Response {
+    let PrepareResult {
+        community_id,
+        group_index_canister_id,
+        ledger_canister_id,
+        fee,
+    } = match read_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    match group_index_canister_c2c_client::is_community(group_index_canister_id, community_id).await {
+        Ok(true) => {}
+        Ok(false) => return NotAuthorized,
+        Err(error) => return InternalError(error),
+    }
+
+    let charge_args = user_canister::c2c_charge_user_account_v2::Args {
+        ledger_canister_id,
+        amount: args.budget_e8s as u128 + fee,
+        fee,
+    };
+
+    match user_canister_c2c_client::c2c_charge_user_account_v2(args.owner.into(), &charge_args).await {
+        Ok(user_canister::c2c_charge_user_account_v2::Response::Success(_)) => {
+            mutate_state(|state| commit(args, community_id, ledger_canister_id, state))
+        }
+        Ok(user_canister::c2c_charge_user_account_v2::Response::InsufficientFunds(balance)) => InsufficientFunds(balance),
+        Ok(user_canister::c2c_charge_user_account_v2::Response::TransferFailed(error)) => TransferFailed(error),
+        Ok(user_canister::c2c_charge_user_account_v2::Response::InternalError(error)) => InternalError(error),
+        Err(error) => InternalError(format!("{error:?}")),
+    }
+}
+
+struct PrepareResult {
+    community_id: CommunityId,
+    group_index_canister_id: CanisterId,
+    ledger_canister_id: CanisterId,
+    fee: u128,
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    let now = state.env.now();
+
+    if state.data.users.get_by_user_id(&args.owner).is_none() {
+        return Err(NotAuthorized);
+    }
+    if args.start >= args.end || args.end < now {
+        return Err(InvalidDates);
+    }
+    let community_id: CommunityId = state.env.caller().into();
+
+    if state
+        .data
+        .referral_campaigns
+        .exists(&campaign_key(Some(community_id), &args.name))
+    {
+        return Err(AlreadyExists);
+    }
+    if let Some(code) = state.data.referral_campaigns.first_code_in_use(&args.codes) {
+        return Err(CodeAlreadyInUse(code));
+    }
+
+    match (args.token.ledger_canister_id(), args.token.fee()) {
+        (Some(ledger_canister_id), Some(fee)) => Ok(PrepareResult {
+            community_id,
+            group_index_canister_id: state.data.group_index_canister_id,
+            ledger_canister_id,
+            fee,
+        }),
+        _ => Err(TokenNotSupported),
+    }
+}
+
+fn commit(args: Args, community_id: CommunityId, ledger_canister_id: CanisterId, state: &mut RuntimeState) -> Response {
+    let campaign = ReferralCampaign::new(
+        args.name.clone(),
+        Some(community_id),
+        args.start,
+        args.end,
+        args.token.clone(),
+        args.rewards,
+        args.second_tier_rewards,
+        args.budget_e8s,
+        Some(args.owner),
+    );
+
+    let key = campaign_key(Some(community_id), &args.name);
+    let response = match state.data.referral_campaigns.add(key.clone(), campaign, &args.codes) {
+        AddCampaignResult::Success => {
+            let now = state.env.now();
+            state.data.timer_jobs.enqueue_job(
+                TimerJob::SettleReferralCampaign(SettleReferralCampaign { campaign: key.clone() }),
+                args.end,
+                now,
+            );
+            state.push_event_to_all_local_user_indexes(
+                Event::ReferralCampaignCodesAdded(ReferralCampaignCodesAdded {
+                    campaign: key,
+                    codes: args.codes,
+                    start: args.start,
+                    end: args.end,
+                }),
+                None,
+            );
+            return Success;
+        }
+        AddCampaignResult::AlreadyExists => AlreadyExists,
+        AddCampaignResult::CodeAlreadyInUse(code) => CodeAlreadyInUse(code),
+    };
+
+    // Another campaign claimed the name or codes while the budget was being taken, so refund it
+    let refund = PendingPayment {
+        amount: args.budget_e8s,
+        currency: args.token,
+        ledger: Some(ledger_canister_id),
+        timestamp: state.env.now_nanos(),
+        recipient: args.owner.into(),
+        memo: state.env.rng().gen(),
+        reason: PendingPaymentReason::Refund,
+    };
+    state.queue_payment(refund);
+    response
+}
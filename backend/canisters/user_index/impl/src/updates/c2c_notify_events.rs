@@ -1,6 +1,6 @@
 use crate::guards::caller_is_local_user_index_canister;
 use crate::timer_job_types::{JoinUserToGroup, TimerJob};
-use crate::{mutate_state, RuntimeState, ONE_MB};
+use crate::{mutate_state, RuntimeState, STANDARD_STORAGE_ALLOWANCE};
 use candid::Principal;
 use canister_api_macros::update_msgpack;
 use canister_tracing_macros::trace;
@@ -37,6 +37,7 @@ fn handle_event(event: Event, state: &mut RuntimeState) {
             ev.display_name,
             ev.user_id,
             ev.referred_by,
+            ev.referral_campaign,
             caller,
             state,
         ),
@@ -81,6 +82,12 @@ fn handle_event(event: Event, state: &mut RuntimeState) {
                 })),
             );
         }
+        Event::ReferralCampaignCodesRejected(ev) => {
+            let now = state.env.now();
+            if let Some(refund) = state.data.referral_campaigns.remove_codes(&ev.campaign, &ev.codes, now) {
+                state.queue_referral_campaign_refund(refund);
+            }
+        }
     }
 }
 
@@ -90,6 +97,7 @@ fn process_new_user(
     display_name: Option<String>,
     user_id: UserId,
     referred_by: Option<UserId>,
+    referral_campaign: Option<String>,
     local_user_index_canister_id: CanisterId,
     state: &mut RuntimeState,
 ) {
@@ -149,12 +157,21 @@ You can change your username at any time by clicking \"Profile settings\" from t
 
     state.data.storage_index_user_sync_queue.push(UserConfig {
         user_id: caller,
-        byte_limit: 100 * ONE_MB,
+        byte_limit: STANDARD_STORAGE_ALLOWANCE,
     });
 
     crate::jobs::sync_users_to_storage_index::start_job_if_required(state);
 
     if let Some(referrer) = referred_by {
         state.data.user_referral_leaderboards.add_referral(referrer, now);
+
+        if let Some(campaign) = referral_campaign {
+            let second_tier_referrer = state.data.users.get_by_user_id(&referrer).and_then(|u| u.referred_by);
+            let rewards = state
+                .data
+                .referral_campaigns
+                .record_signup(&campaign, user_id, referrer, second_tier_referrer, now);
+            state.queue_referral_campaign_rewards(rewards);
+        }
     }
 }
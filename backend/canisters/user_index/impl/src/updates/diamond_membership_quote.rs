@@ -0,0 +1,118 @@
+use crate::guards::caller_is_openchat_user;
+use crate::model::diamond_membership_pricing::{is_within_bounds, usd_amount_in_usd_token_units};
+use crate::{mutate_state, read_state};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use icdex_client::ICDexClient;
+use icpswap_client::ICPSwapClient;
+use types::{CancelOrderRequest, CanisterId, Cryptocurrency, DiamondMembershipPlanDuration, MakeOrderRequest, TokenInfo};
+use user_index_canister::diamond_membership_quote::{Response::*, *};
+use user_index_canister::set_diamond_membership_pricing::PriceSource;
+
+#[update(guard = "caller_is_openchat_user")]
+#[trace]
+async fn diamond_membership_quote(args: Args) -> Response {
+    let user_id = match read_state(|state| {
+        let caller = state.env.caller();
+        state.data.users.get_by_principal(&caller).map(|u| u.user_id)
+    }) {
+        Some(u) => u,
+        _ => return UserNotFound,
+    };
+
+    match get_price_e8s(&args.token, args.ledger, args.duration).await {
+        Ok((token, price_e8s)) => mutate_state(|state| {
+            let now = state.env.now();
+            let expires_at =
+                state
+                    .data
+                    .diamond_membership_pricing
+                    .lock_quote(user_id, token.ledger, args.duration, price_e8s, now);
+
+            Success(SuccessResult { price_e8s, expires_at })
+        }),
+        Err(GetPriceError::CurrencyNotSupported) => CurrencyNotSupported,
+        Err(GetPriceError::InternalError(error)) => InternalError(error),
+    }
+}
+
+pub(crate) enum GetPriceError {
+    CurrencyNotSupported,
+    InternalError(String),
+}
+
+// Converts the USD target price into the token using its configured price feed. If there is no
+// USD pricing for ICP then the fixed ICP prices are used. Quotes outside of the feed's sanity bounds
+// are rejected.
+pub(crate) async fn get_price_e8s(
+    token: &Cryptocurrency,
+    ledger: Option<CanisterId>,
+    duration: DiamondMembershipPlanDuration,
+) -> Result<(TokenInfo, u64), GetPriceError> {
+    let (this_canister_id, token_info, usd_price) = read_state(|state| {
+        let pricing = &state.data.diamond_membership_pricing;
+        let token_info = state.data.token_info(token, ledger)?;
+        let usd_price = pricing
+            .usd_price_source(token_info.ledger, duration)
+            .map(|(cents, source)| (cents, source.clone(), pricing.price_bounds(token_info.ledger)));
+
+        Some((state.env.canister_id(), token_info, usd_price))
+    })
+    .ok_or(GetPriceError::CurrencyNotSupported)?;
+
+    match usd_price {
+        Some((_, _, None)) => Err(GetPriceError::InternalError("No price bounds configured".to_string())),
+        Some((cents, source, Some(bounds))) => match quote(this_canister_id, cents, source, token_info.clone()).await {
+            Ok(price) if is_within_bounds(price, cents, &bounds) => Ok((token_info, price)),
+            Ok(price) => Err(GetPriceError::InternalError(format!(
+                "Quoted price is outside of the configured bounds: {price}"
+            ))),
+            Err(error) => Err(GetPriceError::InternalError(error)),
+        },
+        None if token_info.token == Cryptocurrency::InternetComputer => Ok((token_info, duration.icp_price_e8s())),
+        None => Err(GetPriceError::CurrencyNotSupported),
+    }
+}
+
+async fn quote(this_canister_id: CanisterId, cents: u32, source: PriceSource, token: TokenInfo) -> Result<u64, String> {
+    match source {
+        PriceSource::ICPSwap(s) => {
+            let usd_amount = usd_amount_in_usd_token_units(cents, &s.usd_token);
+            let client = if s.usd_token_is_token0 {
+                ICPSwapClient::new(this_canister_id, s.swap_canister_id, s.usd_token, token, true)
+            } else {
+                ICPSwapClient::new(this_canister_id, s.swap_canister_id, token, s.usd_token, false)
+            };
+
+            client
+                .quote(usd_amount)
+                .await
+                .map_err(|error| format!("{error:?}"))
+                .and_then(to_u64)
+        }
+        PriceSource::ICDex(s) => {
+            let usd_amount = usd_amount_in_usd_token_units(cents, &s.usd_token);
+            let token_units_per_whole = 10u128.pow(token.decimals as u32);
+            let client = ICDexClient::new(
+                this_canister_id,
+                s.dex_canister_id,
+                s.usd_token,
+                token,
+                1,
+                |_: MakeOrderRequest| {},
+                |_: CancelOrderRequest| {},
+            );
+
+            // The latest price is the number of USD token units per whole token
+            match client.latest_price().await {
+                Ok(0) => Ok(0),
+                Ok(price) => to_u64(usd_amount * token_units_per_whole / price as u128),
+                Err(error) => Err(format!("{error:?}")),
+            }
+        }
+    }
+}
+
+fn to_u64(amount: u128) -> Result<u64, String> {
+    u64::try_from(amount).map_err(|_| format!("Quoted amount is too large: {amount}"))
+}
@@ -0,0 +1,166 @@
+use crate::guards::caller_is_openchat_user;
+use crate::model::diamond_membership_gifts::DiamondMembershipPurchase;
+use crate::model::pending_payments_queue::{PendingPayment, PendingPaymentReason};
+use crate::updates::pay_for_diamond_membership::{apply_payment, charge_user_account, is_expected_price};
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use ic_ledger_types::BlockIndex;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use types::{TokenInfo, UserId};
+use user_index_canister::gift_diamond_membership::{Response::*, *};
+
+const GIFT_CODE_LENGTH: usize = 16;
+
+#[update(guard = "caller_is_openchat_user")]
+#[trace]
+async fn gift_diamond_membership(args: Args) -> Response {
+    let (buyer, token) = match mutate_state(|state| prepare(&args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    let response = match charge_user_account(buyer, &token, args.expected_price_e8s).await {
+        Ok(block_index) => mutate_state(|state| process_charge(&args, buyer, token, block_index, state)),
+        Err(response) => convert_charge_error(response),
+    };
+
+    mutate_state(|state| {
+        let recipient = if let GiftRecipient::User(recipient) = args.recipient { Some(recipient) } else { None };
+        for user_id in [Some(buyer), recipient].into_iter().flatten() {
+            if let Some(diamond_membership) = state.data.users.diamond_membership_details_mut(&user_id) {
+                diamond_membership.set_payment_in_progress(false);
+            }
+        }
+    });
+    response
+}
+
+fn prepare(args: &Args, state: &mut RuntimeState) -> Result<(UserId, TokenInfo), Response> {
+    let caller = state.env.caller();
+    let buyer = match state.data.users.get_by_principal(&caller) {
+        Some(u) => u.user_id,
+        None => return Err(UserNotFound),
+    };
+
+    let now = state.env.now();
+
+    if let GiftRecipient::User(recipient) = args.recipient {
+        if recipient == buyer {
+            return Err(CannotGiftToSelf);
+        }
+        match state.data.users.get_by_user_id(&recipient) {
+            Some(user) if !user.is_bot => {
+                if user.diamond_membership_details.payment_in_progress() {
+                    return Err(PaymentAlreadyInProgress);
+                }
+                if let Err(result) = user.diamond_membership_details.can_extend(now) {
+                    return Err(CannotExtend(result));
+                }
+            }
+            _ => return Err(RecipientNotFound),
+        }
+    }
+
+    let token = match state.data.token_info(&args.token, args.ledger) {
+        Some(t) => t,
+        None => return Err(CurrencyNotSupported),
+    };
+
+    if !is_expected_price(buyer, &token, args.duration, args.expected_price_e8s, state) {
+        return Err(PriceMismatch);
+    }
+
+    let diamond_membership = state.data.users.diamond_membership_details_mut(&buyer).unwrap();
+    if diamond_membership.payment_in_progress() {
+        return Err(PaymentAlreadyInProgress);
+    }
+    diamond_membership.set_payment_in_progress(true);
+
+    // Lock the recipient too so that their membership can't change while the buyer is being charged
+    if let GiftRecipient::User(recipient) = args.recipient {
+        if let Some(diamond_membership) = state.data.users.diamond_membership_details_mut(&recipient) {
+            diamond_membership.set_payment_in_progress(true);
+        }
+    }
+
+    Ok((buyer, token))
+}
+
+fn process_charge(args: &Args, buyer: UserId, token: TokenInfo, block_index: BlockIndex, state: &mut RuntimeState) -> Response {
+    state.data.diamond_membership_pricing.remove_quote(&buyer);
+
+    let purchase = DiamondMembershipPurchase {
+        token,
+        amount_e8s: args.expected_price_e8s,
+        block_index,
+        duration: args.duration,
+        purchased_by: buyer,
+        purchased_at: state.env.now(),
+    };
+
+    match args.recipient {
+        GiftRecipient::User(recipient) => {
+            let now = state.env.now();
+            let can_extend = state
+                .data
+                .users
+                .get_by_user_id(&recipient)
+                .map(|u| u.diamond_membership_details.can_extend(now));
+
+            let response = match can_extend {
+                Some(Ok(_)) => match apply_payment(recipient, &purchase, false, true, state) {
+                    Some(result) => {
+                        state.data.diamond_membership_gifts.record_gift_sent();
+                        return Success(result.expires_at);
+                    }
+                    None => RecipientNotFound,
+                },
+                Some(Err(result)) => CannotExtend(result),
+                None => RecipientNotFound,
+            };
+            refund(buyer, &purchase, state);
+            response
+        }
+        GiftRecipient::GiftCode => loop {
+            let code: String = state
+                .env
+                .rng()
+                .sample_iter(&Alphanumeric)
+                .take(GIFT_CODE_LENGTH)
+                .map(char::from)
+                .collect();
+
+            if state.data.diamond_membership_gifts.add_code(code.clone(), purchase.clone()) {
+                return GiftCode(code);
+            }
+        },
+    }
+}
+
+// The gift couldn't be applied after the buyer was charged, so return the payment minus the fees
+fn refund(buyer: UserId, purchase: &DiamondMembershipPurchase, state: &mut RuntimeState) {
+    let fee = purchase.token.fee as u64;
+    let payment = PendingPayment {
+        amount: purchase.amount_e8s.saturating_sub(2 * fee),
+        currency: purchase.token.token.clone(),
+        ledger: Some(purchase.token.ledger),
+        timestamp: state.env.now_nanos(),
+        recipient: buyer.into(),
+        memo: state.env.rng().gen(),
+        reason: PendingPaymentReason::Refund,
+    };
+    state.queue_payment(payment);
+}
+
+fn convert_charge_error(response: user_index_canister::pay_for_diamond_membership::Response) -> Response {
+    use user_index_canister::pay_for_diamond_membership::Response as R;
+
+    match response {
+        R::InsufficientFunds(balance) => InsufficientFunds(balance),
+        R::TransferFailed(error) => TransferFailed(error),
+        R::InternalError(error) => InternalError(error),
+        response => InternalError(format!("{response:?}")),
+    }
+}
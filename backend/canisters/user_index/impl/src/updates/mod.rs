@@ -1,25 +1,37 @@
 pub mod add_local_user_index_canister;
 pub mod add_platform_moderator;
 pub mod add_platform_operator;
+pub mod add_referral_campaign;
+pub mod add_referral_campaign_codes;
 pub mod add_referral_codes;
 pub mod assign_platform_moderators_group;
+pub mod c2c_add_referral_campaign;
 pub mod c2c_migrate_user_principal;
 pub mod c2c_notify_events;
 pub mod c2c_notify_low_balance;
 pub mod c2c_register_bot;
 pub mod c2c_set_avatar;
 pub mod c2c_suspend_users;
+pub mod commit_wasm_upload;
 pub mod create_challenge;
+pub mod diamond_membership_quote;
+pub mod gift_diamond_membership;
 pub mod mark_local_user_index_full;
 pub mod mark_suspected_bot;
 pub mod pay_for_diamond_membership;
+pub mod push_wasm_chunk;
+pub mod redeem_diamond_gift_code;
 pub mod remove_platform_moderator;
 pub mod remove_platform_operator;
+pub mod rollback_local_user_index_canister_wasm;
+pub mod rollback_user_canister_wasm;
+pub mod set_diamond_membership_pricing;
 pub mod set_display_name;
 pub mod set_max_concurrent_user_canister_upgrades;
 pub mod set_moderation_flags;
 pub mod set_user_upgrade_concurrency;
 pub mod set_username;
+pub mod start_wasm_upload;
 pub mod suspend_user;
 pub mod unsuspend_user;
 pub mod upgrade_local_user_index_canister_wasm;
@@ -1,15 +1,19 @@
 use crate::guards::caller_is_openchat_user;
+use crate::model::diamond_membership_gifts::DiamondMembershipPurchase;
 use crate::model::pending_payments_queue::{PendingPayment, PendingPaymentReason};
-use crate::timer_job_types::{RecurringDiamondMembershipPayment, TimerJob};
-use crate::{mutate_state, read_state, RuntimeState, ONE_GB};
+use crate::timer_job_types::{DiamondMembershipExpiry, RecurringDiamondMembershipPayment, TimerJob};
+use crate::{mutate_state, read_state, RuntimeState, DIAMOND_STORAGE_ALLOWANCE};
 use canister_tracing_macros::trace;
 use ic_cdk_macros::update;
 use ic_ledger_types::{BlockIndex, TransferError};
-use local_user_index_canister::{DiamondMembershipPaymentReceived, Event};
+use local_user_index_canister::{DiamondMembershipPaymentReceived, Event, OpenChatBotMessage};
 use rand::Rng;
 use storage_index_canister::add_or_update_users::UserConfig;
 use tracing::error;
-use types::{Cryptocurrency, DiamondMembershipPlanDuration, UserId, ICP};
+use types::{
+    Cryptocurrency, DiamondMembershipDetails, DiamondMembershipPlanDuration, MessageContent, TextContent, TimestampMillis,
+    TokenInfo, UserId, ICP,
+};
 use user_index_canister::pay_for_diamond_membership::{Response::*, *};
 use utils::consts::SNS_GOVERNANCE_CANISTER_ID;
 use utils::time::DAY_IN_MS;
@@ -29,23 +33,14 @@ async fn pay_for_diamond_membership(args: Args) -> Response {
 }
 
 pub(crate) async fn pay_for_diamond_membership_impl(args: Args, user_id: UserId, manual_payment: bool) -> Response {
-    if let Err(response) = mutate_state(|state| prepare(&args, user_id, state)) {
-        return response;
+    let token = match mutate_state(|state| prepare(&args, user_id, manual_payment, state)) {
+        Ok(t) => t,
+        Err(response) => return response,
     };
 
-    let c2c_args = user_canister::c2c_charge_user_account::Args {
-        amount: ICP::from_e8s(args.expected_price_e8s),
-    };
-
-    let response = match user_canister_c2c_client::c2c_charge_user_account(user_id.into(), &c2c_args).await {
-        Ok(result) => match result {
-            user_canister::c2c_charge_user_account::Response::Success(block_index) => {
-                mutate_state(|state| process_charge(args, user_id, block_index, manual_payment, state))
-            }
-            user_canister::c2c_charge_user_account::Response::TransferError(error) => process_error(error),
-            user_canister::c2c_charge_user_account::Response::InternalError(error) => InternalError(error),
-        },
-        Err(error) => InternalError(format!("{error:?}")),
+    let response = match charge_user_account(user_id, &token, args.expected_price_e8s).await {
+        Ok(block_index) => mutate_state(|state| process_charge(args, user_id, &token, block_index, manual_payment, state)),
+        Err(response) => response,
     };
     if !matches!(response, Success(_)) {
         mutate_state(|state| {
@@ -57,42 +52,141 @@ pub(crate) async fn pay_for_diamond_membership_impl(args: Args, user_id: UserId,
     response
 }
 
-fn prepare(args: &Args, user_id: UserId, state: &mut RuntimeState) -> Result<(), Response> {
+fn prepare(args: &Args, user_id: UserId, manual_payment: bool, state: &mut RuntimeState) -> Result<TokenInfo, Response> {
+    let now = state.env.now();
+    let token = state.data.token_info(&args.token, args.ledger);
+
+    // Recurring payments are priced immediately before being taken, so only manual payments need
+    // to be checked against the quoted price
+    let price_matches = token.as_ref().map_or(false, |t| {
+        !manual_payment || is_expected_price(user_id, t, args.duration, args.expected_price_e8s, state)
+    });
+
     let diamond_membership = state.data.users.diamond_membership_details_mut(&user_id).unwrap();
     if diamond_membership.payment_in_progress() {
         Err(PaymentAlreadyInProgress)
-    } else if let Err(result) = diamond_membership.can_extend(state.env.now()) {
+    } else if let Err(result) = diamond_membership.can_extend(now) {
         Err(CannotExtend(result))
-    } else if args.token != Cryptocurrency::InternetComputer {
+    } else if let Some(token) = token {
+        if price_matches {
+            diamond_membership.set_payment_in_progress(true);
+            Ok(token)
+        } else {
+            Err(PriceMismatch)
+        }
+    } else {
         Err(CurrencyNotSupported)
-    } else if args.expected_price_e8s != args.duration.icp_price_e8s() {
-        Err(PriceMismatch)
+    }
+}
+
+// The price must match the quote locked in by the user, or the fixed ICP price if there is no USD
+// pricing for ICP
+pub(crate) fn is_expected_price(
+    user_id: UserId,
+    token: &TokenInfo,
+    duration: DiamondMembershipPlanDuration,
+    expected_price_e8s: u64,
+    state: &RuntimeState,
+) -> bool {
+    let pricing = &state.data.diamond_membership_pricing;
+
+    match pricing.locked_price(user_id, token.ledger, duration, state.env.now()) {
+        Some(price) => price == expected_price_e8s,
+        None => {
+            token.token == Cryptocurrency::InternetComputer
+                && pricing.usd_price_source(token.ledger, duration).is_none()
+                && expected_price_e8s == duration.icp_price_e8s()
+        }
+    }
+}
+
+// ICP payments go via `c2c_charge_user_account` so that they continue to work for user canisters
+// which have not yet been upgraded to support `c2c_charge_user_account_v2`
+pub(crate) async fn charge_user_account(user_id: UserId, token: &TokenInfo, amount_e8s: u64) -> Result<BlockIndex, Response> {
+    if token.token == Cryptocurrency::InternetComputer {
+        let c2c_args = user_canister::c2c_charge_user_account::Args {
+            amount: ICP::from_e8s(amount_e8s),
+        };
+
+        match user_canister_c2c_client::c2c_charge_user_account(user_id.into(), &c2c_args).await {
+            Ok(user_canister::c2c_charge_user_account::Response::Success(block_index)) => Ok(block_index),
+            Ok(user_canister::c2c_charge_user_account::Response::TransferError(error)) => Err(process_error(error)),
+            Ok(user_canister::c2c_charge_user_account::Response::InternalError(error)) => Err(InternalError(error)),
+            Err(error) => Err(InternalError(format!("{error:?}"))),
+        }
     } else {
-        diamond_membership.set_payment_in_progress(true);
-        Ok(())
+        let c2c_args = user_canister::c2c_charge_user_account_v2::Args {
+            ledger_canister_id: token.ledger,
+            amount: amount_e8s as u128,
+            fee: token.fee,
+        };
+
+        match user_canister_c2c_client::c2c_charge_user_account_v2(user_id.into(), &c2c_args).await {
+            Ok(user_canister::c2c_charge_user_account_v2::Response::Success(block_index)) => Ok(block_index),
+            Ok(user_canister::c2c_charge_user_account_v2::Response::InsufficientFunds(balance)) => {
+                Err(InsufficientFunds(balance.try_into().unwrap_or(u64::MAX)))
+            }
+            Ok(user_canister::c2c_charge_user_account_v2::Response::TransferFailed(error)) => Err(TransferFailed(error)),
+            Ok(user_canister::c2c_charge_user_account_v2::Response::InternalError(error)) => Err(InternalError(error)),
+            Err(error) => Err(InternalError(format!("{error:?}"))),
+        }
     }
 }
 
 fn process_charge(
     args: Args,
     user_id: UserId,
+    token: &TokenInfo,
     block_index: BlockIndex,
     manual_payment: bool,
     state: &mut RuntimeState,
 ) -> Response {
+    state.data.diamond_membership_pricing.remove_quote(&user_id);
+
+    let payment = DiamondMembershipPurchase {
+        token: token.clone(),
+        amount_e8s: args.expected_price_e8s,
+        block_index,
+        duration: args.duration,
+        purchased_by: user_id,
+        purchased_at: state.env.now(),
+    };
+
+    match apply_payment(user_id, &payment, args.recurring, manual_payment, state) {
+        Some(result) => Success(result),
+        None => UserNotFound,
+    }
+}
+
+// Extends the user's Diamond membership and distributes the payment between the treasury and the
+// user's referrer. Payments purchased by another user are recorded as gifts.
+pub(crate) fn apply_payment(
+    user_id: UserId,
+    payment: &DiamondMembershipPurchase,
+    recurring: bool,
+    manual_payment: bool,
+    state: &mut RuntimeState,
+) -> Option<DiamondMembershipDetails> {
     let share_with = referrer_to_share_payment(user_id, state);
+    let gifted_by = (payment.purchased_by != user_id).then_some(payment.purchased_by);
+    let token = &payment.token.token;
 
     if let Some(diamond_membership) = state.data.users.diamond_membership_details_mut(&user_id) {
         let now = state.env.now();
         let has_ever_been_diamond_member = diamond_membership.has_ever_been_diamond_member();
 
+        // Gifts must not cancel the recipient's own recurring payments
+        let recurring = if gifted_by.is_some() { diamond_membership.is_recurring() } else { recurring };
+
         diamond_membership.add_payment(
-            args.token.clone(),
-            args.expected_price_e8s,
-            block_index,
-            args.duration,
-            args.recurring,
+            token.clone(),
+            payment.token.ledger,
+            payment.amount_e8s,
+            payment.block_index,
+            payment.duration,
+            recurring,
             manual_payment,
+            gifted_by,
             now,
         );
 
@@ -106,44 +200,67 @@ fn process_charge(
                 user_id,
                 timestamp: now,
                 expires_at,
-                token: args.token.clone(),
-                amount_e8s: args.expected_price_e8s,
-                block_index,
-                duration: args.duration,
-                recurring: args.recurring,
-                send_bot_message: true,
+                token: token.clone(),
+                amount_e8s: payment.amount_e8s,
+                block_index: payment.block_index,
+                duration: payment.duration,
+                recurring,
+                send_bot_message: gifted_by.is_none(),
             }),
         );
+        if let Some(gifted_by) = gifted_by {
+            state.push_event_to_local_user_index(
+                user_id,
+                Event::OpenChatBotMessage(Box::new(OpenChatBotMessage {
+                    user_id,
+                    message: MessageContent::Text(TextContent {
+                        text: format!(
+                            "@UserId({gifted_by}) has gifted you {} of Diamond membership! Your Diamond membership now expires on {}.",
+                            duration_text(payment.duration),
+                            human_readable_date(expires_at)
+                        ),
+                    }),
+                })),
+            );
+        }
         crate::jobs::sync_events_to_local_user_index_canisters::start_job_if_required(state);
 
         if let Some(user) = state.data.users.get_by_user_id(&user_id) {
             state.data.storage_index_user_sync_queue.push(UserConfig {
                 user_id: user.principal,
-                byte_limit: ONE_GB,
+                byte_limit: DIAMOND_STORAGE_ALLOWANCE,
             });
             crate::jobs::sync_users_to_storage_index::start_job_if_required(state);
         }
 
-        if args.recurring {
+        // Reverts the user's storage allowance if their membership isn't extended before it expires
+        state.data.timer_jobs.enqueue_job(
+            TimerJob::DiamondMembershipExpiry(DiamondMembershipExpiry { user_id }),
+            expires_at,
+            now,
+        );
+
+        if recurring {
             state.data.timer_jobs.enqueue_job(
-                TimerJob::RecurringDiamondMembershipPayment(RecurringDiamondMembershipPayment { user_id }),
+                TimerJob::RecurringDiamondMembershipPayment(RecurringDiamondMembershipPayment { user_id, attempt: 0 }),
                 expires_at.saturating_sub(DAY_IN_MS),
                 now,
             );
         }
 
-        let mut amount_to_treasury = args.expected_price_e8s - (2 * Cryptocurrency::InternetComputer.fee().unwrap() as u64);
+        let fee = payment.token.fee as u64;
+        let mut amount_to_treasury = payment.amount_e8s.saturating_sub(2 * fee);
 
         let now_nanos = state.env.now_nanos();
 
         if let Some(share_with) = share_with {
-            let amount_to_referrer = args.expected_price_e8s / 2;
-            amount_to_treasury -= amount_to_referrer;
-            amount_to_treasury -= Cryptocurrency::InternetComputer.fee().unwrap() as u64;
+            let amount_to_referrer = payment.amount_e8s / 2;
+            amount_to_treasury = amount_to_treasury.saturating_sub(amount_to_referrer + fee);
 
             let referral_payment = PendingPayment {
                 amount: amount_to_referrer,
-                currency: Cryptocurrency::InternetComputer,
+                currency: token.clone(),
+                ledger: Some(payment.token.ledger),
                 timestamp: now_nanos,
                 recipient: share_with.into(),
                 memo: state.env.rng().gen(),
@@ -151,17 +268,24 @@ fn process_charge(
             };
             state.queue_payment(referral_payment);
 
-            state.data.user_referral_leaderboards.add_reward(
-                share_with,
-                !has_ever_been_diamond_member,
-                amount_to_referrer,
-                now,
-            );
+            // The leaderboards are denominated in ICP so rewards paid in other tokens only count
+            // towards the number of Diamond members referred
+            let reward_e8s = if *token == Cryptocurrency::InternetComputer { amount_to_referrer } else { 0 };
+            state
+                .data
+                .user_referral_leaderboards
+                .add_reward(share_with, !has_ever_been_diamond_member, reward_e8s, now);
+        }
+
+        if !has_ever_been_diamond_member {
+            let rewards = state.data.referral_campaigns.record_diamond_conversion(user_id, now);
+            state.queue_referral_campaign_rewards(rewards);
         }
 
         let treasury_payment = PendingPayment {
             amount: amount_to_treasury,
-            currency: Cryptocurrency::InternetComputer,
+            currency: token.clone(),
+            ledger: Some(payment.token.ledger),
             timestamp: now_nanos,
             recipient: SNS_GOVERNANCE_CANISTER_ID,
             memo: state.env.rng().gen(),
@@ -179,25 +303,38 @@ fn process_charge(
             .diamond_membership_payment_metrics
             .amount_raised
             .iter_mut()
-            .find(|(t, _)| *t == args.token)
+            .find(|(t, _)| t == token)
             .map(|(_, amount)| amount)
         {
-            *amount += args.expected_price_e8s as u128;
+            *amount += payment.amount_e8s as u128;
         } else {
             state
                 .data
                 .diamond_membership_payment_metrics
                 .amount_raised
-                .push((args.token, args.expected_price_e8s as u128));
+                .push((token.clone(), payment.amount_e8s as u128));
         }
 
-        Success(result)
+        Some(result)
     } else {
         error!(%user_id, "Diamond membership payment taken, but user no longer exists");
-        UserNotFound
+        None
+    }
+}
+
+fn duration_text(duration: DiamondMembershipPlanDuration) -> &'static str {
+    match duration {
+        DiamondMembershipPlanDuration::OneMonth => "1 month",
+        DiamondMembershipPlanDuration::ThreeMonths => "3 months",
+        DiamondMembershipPlanDuration::OneYear => "1 year",
     }
 }
 
+fn human_readable_date(timestamp: TimestampMillis) -> String {
+    let date = time::OffsetDateTime::from_unix_timestamp((timestamp / 1000) as i64).unwrap();
+    format!("{} {} {}", date.day(), date.month(), date.year())
+}
+
 fn referrer_to_share_payment(user_id: UserId, state: &RuntimeState) -> Option<UserId> {
     if let Some(user) = state.data.users.get_by_user_id(&user_id) {
         let now = state.env.now();
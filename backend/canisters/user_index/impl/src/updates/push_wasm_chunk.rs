@@ -0,0 +1,22 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use user_index_canister::push_wasm_chunk::{Response::*, *};
+use utils::canister::PushChunkResult;
+
+#[update(guard = "caller_is_governance_principal")]
+#[trace]
+fn push_wasm_chunk(args: Args) -> Response {
+    mutate_state(|state| push_wasm_chunk_impl(args, state))
+}
+
+fn push_wasm_chunk_impl(args: Args, state: &mut RuntimeState) -> Response {
+    match state.data.wasm_uploads.push_chunk(&args.sha256, args.index, args.bytes) {
+        PushChunkResult::Success => Success,
+        PushChunkResult::NotFound => UploadNotFound,
+        PushChunkResult::AlreadyCommitted => UploadAlreadyCommitted,
+        PushChunkResult::UnexpectedChunkIndex(expected) => UnexpectedChunkIndex(expected),
+        PushChunkResult::ExceedsTotalSize => ExceedsTotalSize,
+    }
+}
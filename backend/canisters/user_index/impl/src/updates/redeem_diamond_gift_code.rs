@@ -0,0 +1,61 @@
+use crate::guards::caller_is_openchat_user;
+use crate::model::diamond_membership_gifts::RedeemGiftCodeResult;
+use crate::updates::pay_for_diamond_membership::apply_payment;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use types::UserId;
+use user_index_canister::redeem_diamond_gift_code::{Response::*, *};
+
+#[update(guard = "caller_is_openchat_user")]
+#[trace]
+fn redeem_diamond_gift_code(args: Args) -> Response {
+    mutate_state(|state| redeem_diamond_gift_code_impl(args, state))
+}
+
+fn redeem_diamond_gift_code_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let (user_id, recurring) = match prepare(state) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+
+    let now = state.env.now();
+    let response = match state.data.diamond_membership_gifts.redeem(&args.code, user_id, now) {
+        RedeemGiftCodeResult::Success(purchase) => match apply_payment(user_id, &purchase, recurring, true, state) {
+            Some(result) => Success(result),
+            None => UserNotFound,
+        },
+        RedeemGiftCodeResult::NotFound => CodeNotFound,
+        RedeemGiftCodeResult::AlreadyRedeemed => CodeAlreadyRedeemed,
+    };
+
+    // Applying the payment releases the guard, so it only needs releasing here if that didn't happen
+    if !matches!(response, Success(_)) {
+        if let Some(diamond_membership) = state.data.users.diamond_membership_details_mut(&user_id) {
+            diamond_membership.set_payment_in_progress(false);
+        }
+    }
+    response
+}
+
+// Takes the same guard as `pay_for_diamond_membership` so that a code can't be redeemed while a
+// payment is being taken, which could otherwise extend the membership beyond what's allowed
+fn prepare(state: &mut RuntimeState) -> Result<(UserId, bool), Response> {
+    let caller = state.env.caller();
+    let now = state.env.now();
+
+    let user_id = match state.data.users.get_by_principal(&caller) {
+        Some(user) => user.user_id,
+        None => return Err(UserNotFound),
+    };
+
+    let diamond_membership = state.data.users.diamond_membership_details_mut(&user_id).unwrap();
+    if diamond_membership.payment_in_progress() {
+        Err(PaymentAlreadyInProgress)
+    } else if let Err(result) = diamond_membership.can_extend(now) {
+        Err(CannotExtend(result))
+    } else {
+        diamond_membership.set_payment_in_progress(true);
+        Ok((user_id, diamond_membership.is_recurring()))
+    }
+}
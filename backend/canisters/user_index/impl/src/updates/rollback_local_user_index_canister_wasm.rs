@@ -0,0 +1,52 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, RuntimeState};
+use canister_api_macros::proposal;
+use canister_tracing_macros::trace;
+use std::collections::HashSet;
+use tracing::info;
+use user_index_canister::rollback_local_user_index_canister_wasm::{Response::*, *};
+
+#[proposal(guard = "caller_is_governance_principal")]
+#[trace]
+fn rollback_local_user_index_canister_wasm(args: Args) -> Response {
+    mutate_state(|state| rollback_local_user_index_canister_wasm_impl(args, state))
+}
+
+fn rollback_local_user_index_canister_wasm_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let previous_wasms = &mut state.data.previous_local_user_index_canister_wasms;
+    let wasm = match previous_wasms
+        .get(args.version)
+        .map(|w| w.version)
+        .and_then(|v| previous_wasms.take(v))
+    {
+        Some(w) => w,
+        None => return NoPreviousWasm,
+    };
+    let version = wasm.version;
+
+    state.data.canisters_requiring_upgrade.clear();
+    state.data.local_user_index_canister_wasm_for_new_canisters = wasm.clone();
+    state.data.local_user_index_canister_wasm_for_upgrades = wasm;
+
+    let filter = args.filter.unwrap_or_default();
+    let include: HashSet<_> = filter.include.into_iter().collect();
+    let include_all = include.is_empty();
+    let exclude: HashSet<_> = filter.exclude.into_iter().collect();
+
+    for canister_id in state
+        .data
+        .local_index_map
+        .iter()
+        .filter(|(_, i)| i.wasm_version() != version)
+        .map(|(c, _)| *c)
+        .filter(|c| include_all || include.contains(c))
+        .filter(|c| !exclude.contains(c))
+    {
+        state.data.canisters_requiring_upgrade.enqueue(canister_id, false);
+    }
+    crate::jobs::upgrade_canisters::start_job_if_required(state);
+
+    let canisters_queued_for_upgrade = state.data.canisters_requiring_upgrade.count_pending();
+    info!(%version, canisters_queued_for_upgrade, "Local user index canister wasm rolled back");
+    Success(version)
+}
@@ -0,0 +1,104 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, read_state, RuntimeState};
+use canister_api_macros::proposal;
+use canister_tracing_macros::trace;
+use tracing::info;
+use types::{CanisterId, CanisterWasm, UpgradeCanisterWasmArgs, UpgradesFilter};
+use user_index_canister::rollback_user_canister_wasm::{Response::*, *};
+use utils::canister::{args_for_uploaded_wasm, build_filter_map, requires_chunked_upload, upload_wasm_in_chunks};
+
+#[proposal(guard = "caller_is_governance_principal")]
+#[trace]
+async fn rollback_user_canister_wasm(args: Args) -> Response {
+    let PrepareResult {
+        wasm,
+        local_user_index_canisters,
+    } = match read_state(|state| prepare(args, state)) {
+        Ok(ok) => ok,
+        Err(response) => return response,
+    };
+    let version = wasm.version;
+
+    let futures: Vec<_> = local_user_index_canisters
+        .into_iter()
+        .map(|(canister_id, filter)| {
+            c2c_rollback_user_canister_wasm(
+                canister_id,
+                UpgradeCanisterWasmArgs {
+                    wasm: wasm.clone(),
+                    filter: Some(filter),
+                    use_for_new_canisters: Some(true),
+                    rollout: None,
+                    uploaded_wasm_hash: None,
+                },
+            )
+        })
+        .collect();
+
+    let result = futures::future::join_all(futures).await;
+
+    if let Some(first_error) = result.into_iter().filter_map(|res| res.err()).next() {
+        InternalError(first_error)
+    } else {
+        mutate_state(|state| {
+            state.data.previous_user_canister_wasms.take(version);
+            state.data.user_canister_wasm = wasm;
+        });
+
+        info!(%version, "User canister wasm rolled back");
+        Success(version)
+    }
+}
+
+struct PrepareResult {
+    wasm: CanisterWasm,
+    local_user_index_canisters: Vec<(CanisterId, UpgradesFilter)>,
+}
+
+fn prepare(args: Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    // Rolling back to the current wasm is allowed so that canisters can be reverted after an upgrade
+    // which wasn't used for new canisters
+    let wasm = match args.version {
+        Some(v) if v == state.data.user_canister_wasm.version => state.data.user_canister_wasm.clone(),
+        version => match state.data.previous_user_canister_wasms.get(version) {
+            Some(w) => w.clone(),
+            None => return Err(NoPreviousWasm),
+        },
+    };
+
+    let local_user_index_canister_ids: Vec<_> = state.data.local_index_map.canisters().copied().collect();
+
+    let local_user_index_canisters = build_filter_map(local_user_index_canister_ids, args.filter.unwrap_or_default(), |c| {
+        state.data.local_index_map.get_index_canister(&c.into())
+    });
+
+    Ok(PrepareResult {
+        wasm,
+        local_user_index_canisters,
+    })
+}
+
+// The wasm is pushed to the local index in chunks if it is too large to fit in a single cross-subnet
+// message, and the subsequent call then references the uploaded wasm by its hash
+async fn c2c_rollback_user_canister_wasm(
+    canister_id: CanisterId,
+    args: local_user_index_canister::c2c_rollback_user_canister_wasm::Args,
+) -> Result<(), String> {
+    let args = if requires_chunked_upload(&args.wasm) {
+        let sha256 = upload_wasm_in_chunks(&args.wasm, |chunk| {
+            local_user_index_canister_c2c_client::push_wasm_chunk(canister_id, chunk)
+        })
+        .await?;
+        args_for_uploaded_wasm(args, sha256)
+    } else {
+        args
+    };
+
+    match local_user_index_canister_c2c_client::c2c_rollback_user_canister_wasm(canister_id, &args).await {
+        Ok(local_user_index_canister::c2c_rollback_user_canister_wasm::Response::UploadedWasmNotFound) => {
+            Err(format!("Uploaded wasm not found. Canister: {canister_id}"))
+        }
+        Ok(_) => Ok(()),
+        Err(error) => Err(format!("{error:?}")),
+    }
+}
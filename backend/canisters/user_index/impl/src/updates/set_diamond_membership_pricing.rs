@@ -0,0 +1,17 @@
+use crate::guards::caller_is_governance_principal;
+use crate::{mutate_state, RuntimeState};
+use canister_tracing_macros::trace;
+use ic_cdk_macros::update;
+use user_index_canister::set_diamond_membership_pricing::{Response::*, *};
+
+#[update(guard = "caller_is_governance_principal")]
+#[trace]
+fn set_diamond_membership_pricing(args: Args) -> Response {
+    mutate_state(|state| set_diamond_membership_pricing_impl(args, state))
+}
+
+fn set_diamond_membership_pricing_impl(args: Args, state: &mut RuntimeState) -> Response {
+    state.data.diamond_membership_pricing.set(args.usd_prices, args.price_feeds);
+
+    Success
+}
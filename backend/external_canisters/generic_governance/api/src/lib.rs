@@ -0,0 +1,5 @@
+mod queries;
+
+pub use queries::*;
+
+pub mod types;
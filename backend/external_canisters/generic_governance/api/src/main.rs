@@ -0,0 +1,10 @@
+use candid_gen::generate_candid_method;
+
+#[allow(deprecated)]
+fn main() {
+    generate_candid_method!(generic_governance, get_proposal, query);
+    generate_candid_method!(generic_governance, list_proposals, query);
+
+    candid::export_service!();
+    std::print!("{}", __export_service());
+}
@@ -0,0 +1,15 @@
+use crate::types::GovernanceProposal;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::ProposalId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub proposal_id: ProposalId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(GovernanceProposal),
+    NotFound,
+}
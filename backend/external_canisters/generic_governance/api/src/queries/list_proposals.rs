@@ -0,0 +1,18 @@
+use crate::types::GovernanceProposal;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::ProposalId;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub limit: u32,
+    pub before_proposal: Option<ProposalId>,
+    // If true, only proposals which are still accepting votes are returned
+    pub active_only: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Response {
+    // Ordered by proposal Id descending
+    pub proposals: Vec<GovernanceProposal>,
+}
@@ -0,0 +1,2 @@
+pub mod get_proposal;
+pub mod list_proposals;
@@ -0,0 +1,59 @@
+// The interface which any governance canister must expose in order for the ProposalsBot to push its
+// proposals into OpenChat. All timestamps are in milliseconds.
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::{ProposalDecisionStatus, ProposalId, TimestampMillis};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct GovernanceProposal {
+    pub id: ProposalId,
+    pub proposer: String,
+    pub created: TimestampMillis,
+    pub title: String,
+    pub summary: String,
+    pub url: String,
+    pub status: ProposalStatus,
+    pub tally: Tally,
+    pub deadline: TimestampMillis,
+    pub payload_text_rendering: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalStatus {
+    Open,
+    Rejected,
+    Adopted,
+    Executed,
+    Failed,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Tally {
+    pub yes: u64,
+    pub no: u64,
+    pub total: u64,
+    pub timestamp: TimestampMillis,
+}
+
+impl From<ProposalStatus> for ProposalDecisionStatus {
+    fn from(value: ProposalStatus) -> Self {
+        match value {
+            ProposalStatus::Open => ProposalDecisionStatus::Open,
+            ProposalStatus::Rejected => ProposalDecisionStatus::Rejected,
+            ProposalStatus::Adopted => ProposalDecisionStatus::Adopted,
+            ProposalStatus::Executed => ProposalDecisionStatus::Executed,
+            ProposalStatus::Failed => ProposalDecisionStatus::Failed,
+        }
+    }
+}
+
+impl From<Tally> for types::Tally {
+    fn from(value: Tally) -> Self {
+        types::Tally {
+            yes: value.yes,
+            no: value.no,
+            total: value.total,
+            timestamp: value.timestamp,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+use canister_client::generate_candid_c2c_call;
+use generic_governance_canister::*;
+
+// Queries
+generate_candid_c2c_call!(get_proposal);
+generate_candid_c2c_call!(list_proposals);
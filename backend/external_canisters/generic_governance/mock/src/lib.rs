@@ -0,0 +1,44 @@
+// A minimal governance canister implementing the generic governance interface, used to test the
+// ProposalsBot against DAO frameworks other than the NNS and SNS. Proposals are set directly by
+// calling `upsert_proposal`.
+use generic_governance_canister::types::{GovernanceProposal, ProposalStatus};
+use generic_governance_canister::{get_proposal, list_proposals};
+use ic_cdk_macros::{query, update};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use types::ProposalId;
+
+thread_local! {
+    static PROPOSALS: RefCell<BTreeMap<ProposalId, GovernanceProposal>> = RefCell::default();
+}
+
+#[update]
+fn upsert_proposal(proposal: GovernanceProposal) {
+    PROPOSALS.with(|p| p.borrow_mut().insert(proposal.id, proposal));
+}
+
+#[query]
+fn get_proposal(args: get_proposal::Args) -> get_proposal::Response {
+    PROPOSALS.with(|p| {
+        p.borrow()
+            .get(&args.proposal_id)
+            .cloned()
+            .map_or(get_proposal::Response::NotFound, get_proposal::Response::Success)
+    })
+}
+
+#[query]
+fn list_proposals(args: list_proposals::Args) -> list_proposals::Response {
+    let proposals = PROPOSALS.with(|p| {
+        p.borrow()
+            .values()
+            .rev()
+            .filter(|p| args.before_proposal.map_or(true, |id| p.id < id))
+            .filter(|p| !args.active_only || p.status == ProposalStatus::Open)
+            .take(args.limit as usize)
+            .cloned()
+            .collect()
+    });
+
+    list_proposals::Response { proposals }
+}
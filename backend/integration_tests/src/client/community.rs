@@ -17,11 +17,13 @@ generate_update_call!(add_reaction);
 generate_update_call!(block_user);
 generate_update_call!(change_role);
 generate_update_call!(create_channel);
+generate_update_call!(create_custom_role);
 generate_update_call!(create_user_group);
 generate_update_call!(delete_messages);
 generate_update_call!(delete_user_groups);
 generate_update_call!(edit_message);
 generate_update_call!(enable_invite_code);
+generate_update_call!(follow_announcement_channel);
 generate_update_call!(import_group);
 generate_update_call!(leave_channel);
 generate_update_call!(remove_member);
@@ -32,6 +34,7 @@ generate_update_call!(unblock_user);
 generate_update_call!(undelete_messages);
 generate_update_call!(update_channel);
 generate_update_call!(update_community);
+generate_update_call!(update_custom_role);
 generate_update_call!(update_user_group);
 
 pub mod happy_path {
@@ -67,6 +70,8 @@ pub mod happy_path {
                 permissions: None,
                 events_ttl: None,
                 gate: None,
+                category_id: None,
+                is_announcement: None,
             },
         );
 
@@ -76,6 +81,30 @@ pub mod happy_path {
         }
     }
 
+    pub fn follow_announcement_channel(
+        env: &mut StateMachine,
+        sender: Principal,
+        community_id: CommunityId,
+        channel_id: ChannelId,
+        source_community_id: CommunityId,
+        source_channel_id: ChannelId,
+    ) {
+        let response = super::follow_announcement_channel(
+            env,
+            sender,
+            community_id.into(),
+            &community_canister::follow_announcement_channel::Args {
+                channel_id,
+                source_community_id,
+                source_channel_id,
+            },
+        );
+
+        if !matches!(response, community_canister::follow_announcement_channel::Response::Success) {
+            panic!("'follow_announcement_channel' error: {response:?}")
+        }
+    }
+
     pub fn leave_channel(env: &mut StateMachine, sender: Principal, community_id: CommunityId, channel_id: ChannelId) {
         let response = super::leave_channel(
             env,
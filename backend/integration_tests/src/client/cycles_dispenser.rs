@@ -4,4 +4,9 @@ use cycles_dispenser_canister::*;
 // Queries
 
 // Updates
+generate_update_call!(c2c_request_cycles);
+generate_update_call!(register_bot);
+generate_update_call!(set_external_canister_group);
+generate_update_call!(set_ops_group);
+generate_update_call!(set_top_up_budget);
 generate_update_call!(update_config);
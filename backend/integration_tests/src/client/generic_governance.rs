@@ -0,0 +1,16 @@
+use crate::generate_query_call;
+use candid::Principal;
+use generic_governance_canister::types::GovernanceProposal;
+use generic_governance_canister::*;
+use ic_test_state_machine_client::StateMachine;
+use types::CanisterId;
+
+// Queries
+generate_query_call!(list_proposals);
+
+// Updates
+
+// Only exposed by the mock governance canister used in tests
+pub fn upsert_proposal(env: &mut StateMachine, sender: Principal, canister_id: CanisterId, proposal: &GovernanceProposal) {
+    crate::client::execute_update_no_response(env, sender, canister_id, "upsert_proposal", proposal);
+}
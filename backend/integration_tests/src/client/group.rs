@@ -9,6 +9,7 @@ generate_query_call!(selected_initial);
 generate_query_call!(selected_updates_v2);
 generate_query_call!(summary);
 generate_query_call!(summary_updates);
+generate_query_call!(typing_indicators);
 
 // Updates
 generate_update_call!(add_reaction);
@@ -23,6 +24,7 @@ generate_update_call!(register_poll_vote);
 generate_update_call!(remove_participant);
 generate_update_call!(remove_reaction);
 generate_update_call!(send_message_v2);
+generate_update_call!(set_typing);
 generate_update_call!(unblock_user);
 generate_update_call!(undelete_messages);
 generate_update_call!(unpin_message);
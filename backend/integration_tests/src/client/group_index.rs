@@ -9,7 +9,11 @@ generate_query_call!(search);
 // Updates
 generate_update_call!(add_local_group_index_canister);
 generate_update_call!(delete_frozen_group);
+generate_update_call!(export_state_snapshot);
+generate_update_call!(finish_state_restore);
 generate_update_call!(freeze_group);
+generate_update_call!(push_state_restore_chunk);
+generate_update_call!(start_state_restore);
 generate_update_call!(unfreeze_group);
 
 pub mod happy_path {
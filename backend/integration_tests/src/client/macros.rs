@@ -31,3 +31,20 @@ macro_rules! generate_update_call {
         }
     };
 }
+
+#[macro_export]
+macro_rules! generate_msgpack_update_call {
+    ($method_name:ident) => {
+        #[allow(dead_code)]
+        pub fn $method_name(
+            env: &mut ic_test_state_machine_client::StateMachine,
+            sender: candid::Principal,
+            canister_id: candid::Principal,
+            args: &$method_name::Args,
+        ) -> $method_name::Response {
+            let method_name = concat!(stringify!($method_name), "_msgpack");
+
+            $crate::client::execute_msgpack_update(env, sender, canister_id, method_name, args)
+        }
+    };
+}
@@ -5,12 +5,15 @@ use candid::{CandidType, Principal};
 use ic_cdk::api::management_canister::main::{CanisterInstallMode, InstallCodeArgument};
 use ic_test_state_machine_client::{StateMachine, UserError, WasmResult};
 use serde::de::DeserializeOwned;
-use types::{CanisterId, CanisterWasm, DiamondMembershipPlanDuration};
+use serde::Serialize;
+use serde_bytes::ByteBuf;
+use types::{CanisterId, CanisterWasm, DiamondMembershipPlanDuration, HttpRequest, HttpResponse};
 
 mod macros;
 
 pub mod community;
 pub mod cycles_dispenser;
+pub mod generic_governance;
 pub mod group;
 pub mod group_index;
 pub mod icrc1;
@@ -18,6 +21,7 @@ pub mod local_user_index;
 pub mod notifications;
 pub mod notifications_index;
 pub mod online_users;
+pub mod proposals_bot;
 pub mod registry;
 pub mod storage_bucket;
 pub mod storage_index;
@@ -73,6 +77,27 @@ pub fn install_canister<P: CandidType>(
     )
 }
 
+pub fn upgrade_canister<P: CandidType>(
+    env: &mut StateMachine,
+    sender: Principal,
+    canister_id: CanisterId,
+    wasm: CanisterWasm,
+    payload: P,
+) {
+    execute_update_no_response(
+        env,
+        sender,
+        Principal::management_canister(),
+        "install_code",
+        &InstallCodeArgument {
+            mode: CanisterInstallMode::Upgrade,
+            canister_id,
+            wasm_module: wasm.module,
+            arg: candid::encode_one(&payload).unwrap(),
+        },
+    )
+}
+
 pub fn execute_query<P: CandidType, R: CandidType + DeserializeOwned>(
     env: &StateMachine,
     sender: Principal,
@@ -93,6 +118,23 @@ pub fn execute_update<P: CandidType, R: CandidType + DeserializeOwned>(
     unwrap_response(env.update_call(canister_id, sender, method_name, candid::encode_one(payload).unwrap()))
 }
 
+// For endpoints which are only exposed to other canisters, and therefore only accept msgpack
+pub fn execute_msgpack_update<P: Serialize, R: DeserializeOwned>(
+    env: &mut StateMachine,
+    sender: Principal,
+    canister_id: CanisterId,
+    method_name: &str,
+    payload: &P,
+) -> R {
+    match env
+        .update_call(canister_id, sender, method_name, msgpack::serialize_then_unwrap(payload))
+        .unwrap()
+    {
+        WasmResult::Reply(bytes) => msgpack::deserialize_then_unwrap(&bytes),
+        WasmResult::Reject(error) => panic!("{error}"),
+    }
+}
+
 pub fn execute_update_no_response<P: CandidType>(
     env: &mut StateMachine,
     sender: Principal,
@@ -104,6 +146,19 @@ pub fn execute_update_no_response<P: CandidType>(
         .unwrap();
 }
 
+// Reads the JSON served by a canister's `/metrics` route
+pub fn metrics<R: DeserializeOwned>(env: &StateMachine, canister_id: CanisterId) -> R {
+    let request = HttpRequest {
+        method: "GET".to_string(),
+        url: "/metrics".to_string(),
+        headers: Vec::new(),
+        body: ByteBuf::new(),
+    };
+    let response: HttpResponse = execute_query(env, Principal::anonymous(), canister_id, "http_request", &request);
+
+    serde_json::from_slice(&response.body).unwrap()
+}
+
 pub fn register_diamond_user(env: &mut StateMachine, canister_ids: &CanisterIds, controller: Principal) -> User {
     let user = local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
 
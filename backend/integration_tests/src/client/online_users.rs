@@ -1,8 +1,15 @@
-use crate::{generate_query_call, generate_update_call};
+use crate::{generate_msgpack_update_call, generate_query_call, generate_update_call};
 use online_users_canister::*;
 
 // Queries
+generate_query_call!(chat_presence);
 generate_query_call!(last_online);
+generate_query_call!(typing_indicators);
 
 // Updates
 generate_update_call!(mark_as_online);
+generate_update_call!(set_typing);
+
+// C2C updates
+generate_msgpack_update_call!(c2c_set_blocked_users);
+generate_msgpack_update_call!(c2c_set_last_seen_visibility);
@@ -0,0 +1,7 @@
+use crate::generate_update_call;
+use proposals_bot_canister::*;
+
+// Queries
+
+// Updates
+generate_update_call!(add_governance_canister);
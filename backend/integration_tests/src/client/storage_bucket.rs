@@ -4,10 +4,12 @@ use storage_bucket_canister::*;
 // Queries
 generate_query_call!(file_info);
 generate_query_call!(file_status);
+generate_query_call!(upload_progress);
 
 // Updates
 generate_update_call!(delete_file);
 generate_update_call!(delete_files);
+generate_update_call!(forward_blob);
 generate_update_call!(forward_file);
 generate_update_call!(upload_chunk_v2);
 
@@ -48,6 +50,7 @@ pub mod happy_path {
                     total_size,
                     bytes: ByteBuf::from(chunk),
                     expiry,
+                    chunk_hash: Some(hash_bytes(chunk)),
                 },
             );
 
@@ -5,9 +5,11 @@ use storage_index_canister::*;
 generate_query_call!(allocated_bucket_v2);
 generate_query_call!(can_forward);
 generate_query_call!(user);
+generate_query_call!(user_files);
 
 // Updates
 generate_update_call!(add_or_update_users);
+generate_update_call!(delete_user_files);
 generate_update_call!(remove_accessor);
 generate_update_call!(remove_user);
 generate_update_call!(update_user_id);
@@ -75,4 +77,18 @@ pub mod happy_path {
             panic!("'user' error: {response:?}");
         }
     }
+
+    pub fn user_files(
+        env: &StateMachine,
+        sender: Principal,
+        canister_id: CanisterId,
+    ) -> storage_index_canister::user_files::SuccessResult {
+        let response = super::user_files(env, sender, canister_id, &storage_index_canister::user_files::Args::default());
+
+        if let storage_index_canister::user_files::Response::Success(result) = response {
+            result
+        } else {
+            panic!("'user_files' error: {response:?}");
+        }
+    }
 }
@@ -27,6 +27,7 @@ generate_update_call!(save_crypto_account);
 generate_update_call!(send_message_v2);
 generate_update_call!(send_message_with_transfer_to_channel);
 generate_update_call!(send_message_with_transfer_to_group);
+generate_update_call!(set_last_seen_visibility);
 generate_update_call!(set_message_reminder_v2);
 generate_update_call!(tip_message);
 generate_update_call!(unblock_user);
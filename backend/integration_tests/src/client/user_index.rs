@@ -82,6 +82,7 @@ pub mod happy_path {
             &user_index_canister::pay_for_diamond_membership::Args {
                 duration,
                 token: Cryptocurrency::InternetComputer,
+                ledger: None,
                 expected_price_e8s: duration.icp_price_e8s(),
                 recurring,
             },
@@ -0,0 +1,225 @@
+use crate::env::ENV;
+use crate::rng::{random_message_id, random_string};
+use crate::utils::tick_many;
+use crate::{client, CanisterIds, TestEnv, User};
+use candid::Principal;
+use ic_test_state_machine_client::StateMachine;
+use std::ops::Deref;
+use types::{ChannelId, CommunityId, MessageContent, MessageContentInitial, MessageId, Rules, TextContent};
+
+#[test]
+fn messages_are_mirrored_into_following_channels() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        user,
+        source_community_id,
+        source_channel_id,
+        community_id,
+        channel_id,
+    } = init_test_data(env, canister_ids, *controller);
+
+    let message_id = random_message_id();
+    client::community::happy_path::send_text_message(
+        env,
+        &user,
+        source_community_id,
+        source_channel_id,
+        None,
+        "Announcement",
+        Some(message_id),
+    );
+    tick_many(env, 3);
+
+    assert_eq!(
+        latest_mirrored_text(env, &user, community_id, channel_id, message_id),
+        "Announcement"
+    );
+}
+
+#[test]
+fn edits_are_mirrored_into_following_channels() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        user,
+        source_community_id,
+        source_channel_id,
+        community_id,
+        channel_id,
+    } = init_test_data(env, canister_ids, *controller);
+
+    let message_id = random_message_id();
+    client::community::happy_path::send_text_message(
+        env,
+        &user,
+        source_community_id,
+        source_channel_id,
+        None,
+        "Announcement",
+        Some(message_id),
+    );
+    tick_many(env, 3);
+
+    let response = client::community::edit_message(
+        env,
+        user.principal,
+        source_community_id.into(),
+        &community_canister::edit_message::Args {
+            channel_id: source_channel_id,
+            thread_root_message_index: None,
+            message_id,
+            content: MessageContentInitial::Text(TextContent {
+                text: "Edited announcement".to_string(),
+            }),
+        },
+    );
+    assert!(matches!(response, community_canister::edit_message::Response::Success));
+    tick_many(env, 3);
+
+    assert_eq!(
+        latest_mirrored_text(env, &user, community_id, channel_id, message_id),
+        "Edited announcement"
+    );
+}
+
+#[test]
+fn deletions_are_mirrored_into_following_channels() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        user,
+        source_community_id,
+        source_channel_id,
+        community_id,
+        channel_id,
+    } = init_test_data(env, canister_ids, *controller);
+
+    let message_id = random_message_id();
+    client::community::happy_path::send_text_message(
+        env,
+        &user,
+        source_community_id,
+        source_channel_id,
+        None,
+        "Announcement",
+        Some(message_id),
+    );
+    tick_many(env, 3);
+
+    let response = client::community::delete_messages(
+        env,
+        user.principal,
+        source_community_id.into(),
+        &community_canister::delete_messages::Args {
+            channel_id: source_channel_id,
+            thread_root_message_index: None,
+            message_ids: vec![message_id],
+            as_platform_moderator: None,
+        },
+    );
+    assert!(matches!(response, community_canister::delete_messages::Response::Success));
+    tick_many(env, 3);
+
+    let summary = client::community::happy_path::channel_summary(env, &user, community_id, channel_id);
+    let message = summary.latest_message.expect("Expected a mirrored message").event;
+    assert_eq!(message.message_id, message_id);
+    assert!(matches!(message.content, MessageContent::Deleted(_)));
+}
+
+fn latest_mirrored_text(
+    env: &StateMachine,
+    user: &User,
+    community_id: CommunityId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> String {
+    let summary = client::community::happy_path::channel_summary(env, user, community_id, channel_id);
+    let message = summary.latest_message.expect("Expected a mirrored message").event;
+    assert_eq!(message.message_id, message_id);
+    assert!(message.forwarded);
+
+    if let MessageContent::Text(content) = message.content {
+        content.text
+    } else {
+        panic!("Expected a text message");
+    }
+}
+
+fn init_test_data(env: &mut StateMachine, canister_ids: &CanisterIds, controller: Principal) -> TestData {
+    let user = client::register_diamond_user(env, canister_ids, controller);
+
+    let source_community_id =
+        client::user::happy_path::create_community(env, &user, &random_string(), true, vec!["general".to_string()]);
+    let response = client::community::create_channel(
+        env,
+        user.principal,
+        source_community_id.into(),
+        &community_canister::create_channel::Args {
+            is_public: true,
+            name: "announcements".to_string(),
+            description: "announcements_description".to_string(),
+            rules: Rules::default(),
+            subtype: None,
+            avatar: None,
+            history_visible_to_new_joiners: true,
+            permissions: None,
+            events_ttl: None,
+            gate: None,
+            category_id: None,
+            is_announcement: Some(true),
+        },
+    );
+    let source_channel_id = match response {
+        community_canister::create_channel::Response::Success(result) => result.channel_id,
+        response => panic!("'create_channel' error: {response:?}"),
+    };
+
+    let community_id =
+        client::user::happy_path::create_community(env, &user, &random_string(), true, vec!["general".to_string()]);
+    let summary = client::community::happy_path::summary(env, &user, community_id);
+    let channel_id = summary.channels.iter().find(|c| c.name == "general").unwrap().channel_id;
+
+    client::community::happy_path::follow_announcement_channel(
+        env,
+        user.principal,
+        community_id,
+        channel_id,
+        source_community_id,
+        source_channel_id,
+    );
+
+    TestData {
+        user,
+        source_community_id,
+        source_channel_id,
+        community_id,
+        channel_id,
+    }
+}
+
+struct TestData {
+    user: User,
+    source_community_id: CommunityId,
+    source_channel_id: ChannelId,
+    community_id: CommunityId,
+    channel_id: ChannelId,
+}
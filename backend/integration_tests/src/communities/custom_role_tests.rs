@@ -0,0 +1,104 @@
+use crate::env::ENV;
+use crate::rng::random_string;
+use crate::{client, TestEnv, User};
+use ic_test_state_machine_client::StateMachine;
+use std::ops::Deref;
+use types::{CommunityId, CommunityRole, GroupPermission, UserId};
+
+#[test]
+fn admin_cannot_grant_channel_permissions_they_do_not_hold() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user1 = client::register_diamond_user(env, canister_ids, *controller);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let user3 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    let community_id =
+        client::user::happy_path::create_community(env, &user1, &random_string(), true, vec!["general".to_string()]);
+
+    client::local_user_index::happy_path::join_community(env, user2.principal, canister_ids.local_user_index, community_id);
+    client::local_user_index::happy_path::join_community(env, user3.principal, canister_ids.local_user_index, community_id);
+
+    // user2 becomes a community admin, so can assign custom roles, but is only a member of the channel
+    let change_role_response = client::community::change_role(
+        env,
+        user1.principal,
+        community_id.into(),
+        &community_canister::change_role::Args {
+            user_id: user2.user_id,
+            new_role: CommunityRole::Admin,
+        },
+    );
+    assert!(matches!(
+        change_role_response,
+        community_canister::change_role::Response::Success
+    ));
+
+    let moderator_role_id = create_custom_role(env, &user1, community_id, vec![GroupPermission::DeleteMessages]);
+    let poster_role_id = create_custom_role(env, &user1, community_id, vec![GroupPermission::SendMessages]);
+
+    let response = assign_custom_role(env, &user2, community_id, moderator_role_id, user3.user_id);
+    assert!(
+        matches!(response, community_canister::update_custom_role::Response::NotAuthorized),
+        "{response:?}"
+    );
+
+    let response = assign_custom_role(env, &user2, community_id, poster_role_id, user3.user_id);
+    assert!(
+        matches!(response, community_canister::update_custom_role::Response::Success),
+        "{response:?}"
+    );
+}
+
+fn create_custom_role(
+    env: &mut StateMachine,
+    owner: &User,
+    community_id: CommunityId,
+    channel_permissions: Vec<GroupPermission>,
+) -> u32 {
+    let response = client::community::create_custom_role(
+        env,
+        owner.principal,
+        community_id.into(),
+        &community_canister::create_custom_role::Args {
+            name: random_string(),
+            community_permissions: Vec::new(),
+            channel_permissions,
+        },
+    );
+
+    match response {
+        community_canister::create_custom_role::Response::Success(result) => result.role_id,
+        response => panic!("'create_custom_role' error: {response:?}"),
+    }
+}
+
+fn assign_custom_role(
+    env: &mut StateMachine,
+    sender: &User,
+    community_id: CommunityId,
+    role_id: u32,
+    user_id: UserId,
+) -> community_canister::update_custom_role::Response {
+    client::community::update_custom_role(
+        env,
+        sender.principal,
+        community_id.into(),
+        &community_canister::update_custom_role::Args {
+            role_id,
+            name: None,
+            community_permissions: None,
+            channel_permissions: None,
+            users_to_add: vec![user_id],
+            users_to_remove: Vec::new(),
+            user_groups_to_add: Vec::new(),
+            user_groups_to_remove: Vec::new(),
+        },
+    )
+}
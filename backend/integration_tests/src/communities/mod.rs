@@ -1,5 +1,7 @@
+mod announcement_channel_tests;
 mod convert_group_into_community_tests;
 mod create_channel_tests;
+mod custom_role_tests;
 mod disappearing_message_tests;
 mod import_group_tests;
 mod join_channel_tests;
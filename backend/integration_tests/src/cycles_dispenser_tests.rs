@@ -1,9 +1,13 @@
+use crate::client::create_canister;
 use crate::env::ENV;
+use crate::rng::random_string;
 use crate::utils::tick_many;
 use crate::{client, TestEnv, T};
+use cycles_dispenser_canister::{BudgetTarget, ExternalCanisterGroup, ExternalCanisters, TopUpBudget};
 use ic_ledger_types::Tokens;
 use std::ops::Deref;
 use std::time::Duration;
+use types::{ChatEvent, MessageContent};
 
 #[test]
 fn icp_is_burned_into_cycles() {
@@ -35,6 +39,8 @@ fn icp_is_burned_into_cycles() {
             min_interval: None,
             max_top_up_amount: None,
             icp_burn_amount: Some(Tokens::from_e8s(10_000_000_000)),
+            anomaly_multiplier: None,
+            min_days_until_empty: None,
         },
     );
 
@@ -53,3 +59,151 @@ fn icp_is_burned_into_cycles() {
         canister_ids.cycles_dispenser
     );
 }
+
+#[test]
+fn alerts_are_posted_to_ops_group() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let group_id = client::user::happy_path::create_group(env, &user, &random_string(), true, true);
+
+    let register_bot_response = client::cycles_dispenser::register_bot(
+        env,
+        *controller,
+        canister_ids.cycles_dispenser,
+        &cycles_dispenser_canister::register_bot::Args {
+            user_index_canister_id: canister_ids.user_index,
+            username: "CyclesDispenser".to_string(),
+        },
+    );
+    assert!(matches!(
+        register_bot_response,
+        cycles_dispenser_canister::register_bot::Response::Success
+            | cycles_dispenser_canister::register_bot::Response::AlreadyRegistered
+    ));
+
+    tick_many(env, 5);
+
+    let set_ops_group_response = client::cycles_dispenser::set_ops_group(
+        env,
+        *controller,
+        canister_ids.cycles_dispenser,
+        &cycles_dispenser_canister::set_ops_group::Args {
+            group_id: Some(group_id),
+            invite_code: None,
+        },
+    );
+    assert!(matches!(
+        set_ops_group_response,
+        cycles_dispenser_canister::set_ops_group::Response::Success
+    ));
+
+    // A daily cap of zero means the next request from the canister raises an alert
+    let target = BudgetTarget::Canister(canister_ids.online_users);
+    client::cycles_dispenser::set_top_up_budget(
+        env,
+        *controller,
+        canister_ids.cycles_dispenser,
+        &cycles_dispenser_canister::set_top_up_budget::Args {
+            target: target.clone(),
+            budget: Some(TopUpBudget {
+                max_top_up_amount: None,
+                daily_cap: Some(0),
+            }),
+        },
+    );
+
+    let request_cycles_response = client::cycles_dispenser::c2c_request_cycles(
+        env,
+        canister_ids.online_users,
+        canister_ids.cycles_dispenser,
+        &cycles_dispenser_canister::c2c_request_cycles::Args { amount: None },
+    );
+    assert!(matches!(
+        request_cycles_response,
+        cycles_dispenser_canister::c2c_request_cycles::Response::DailyCapReached
+    ));
+
+    tick_many(env, 5);
+
+    let events_response = client::group::events(
+        env,
+        user.principal,
+        group_id.into(),
+        &group_canister::events::Args {
+            thread_root_message_index: None,
+            start_index: 0.into(),
+            ascending: true,
+            max_messages: 100,
+            max_events: 100,
+            latest_client_event_index: None,
+        },
+    );
+    let group_canister::events::Response::Success(mut result) = events_response else {
+        panic!("'events' error: {events_response:?}");
+    };
+    let ChatEvent::Message(message) = result.events.pop().unwrap().event else {
+        panic!("Expected the alert to be the latest event");
+    };
+    let MessageContent::Text(text) = &message.content else {
+        panic!("Expected a text message: {:?}", message.content);
+    };
+    assert!(text.text.contains("daily top up cap"), "{}", text.text);
+
+    client::cycles_dispenser::set_top_up_budget(
+        env,
+        *controller,
+        canister_ids.cycles_dispenser,
+        &cycles_dispenser_canister::set_top_up_budget::Args { target, budget: None },
+    );
+}
+
+#[test]
+fn external_canister_groups_must_be_controlled_by_dispenser() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let controlled = create_canister(env, canister_ids.cycles_dispenser);
+    let not_controlled = create_canister(env, *controller);
+
+    let mut args = cycles_dispenser_canister::set_external_canister_group::Args {
+        name: random_string(),
+        group: Some(ExternalCanisterGroup {
+            canisters: ExternalCanisters::Canisters(vec![controlled, not_controlled]),
+            min_cycles_balance: T,
+            top_up_amount: T,
+        }),
+    };
+
+    let response =
+        client::cycles_dispenser::set_external_canister_group(env, *controller, canister_ids.cycles_dispenser, &args);
+    assert!(
+        matches!(
+            &response,
+            cycles_dispenser_canister::set_external_canister_group::Response::NotController(c) if *c == vec![not_controlled]
+        ),
+        "{response:?}"
+    );
+
+    args.group.as_mut().unwrap().canisters = ExternalCanisters::Canisters(vec![controlled]);
+    let response =
+        client::cycles_dispenser::set_external_canister_group(env, *controller, canister_ids.cycles_dispenser, &args);
+    assert!(
+        matches!(
+            response,
+            cycles_dispenser_canister::set_external_canister_group::Response::Success
+        ),
+        "{response:?}"
+    );
+}
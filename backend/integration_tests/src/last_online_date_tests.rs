@@ -1,8 +1,10 @@
 use crate::env::ENV;
-use crate::rng::random_principal;
+use crate::rng::{random_principal, random_string};
+use crate::utils::tick_many;
 use crate::{client, TestEnv};
 use std::ops::Deref;
 use std::time::Duration;
+use types::{Chat, LastSeenVisibility, TypingActivity};
 
 #[test]
 fn set_then_get_last_online_date_succeeds() {
@@ -38,3 +40,246 @@ fn set_then_get_last_online_date_succeeds() {
     assert_eq!(users[0].user_id, user1.user_id);
     assert_eq!(users[0].duration_since_last_online, 1000);
 }
+
+#[test]
+fn last_seen_visibility_and_typing_indicators_are_respected() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user1 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    for user in [&user1, &user2] {
+        client::online_users::mark_as_online(
+            env,
+            user.principal,
+            canister_ids.online_users,
+            &online_users_canister::mark_as_online::Args {},
+        );
+    }
+
+    client::user::set_last_seen_visibility(
+        env,
+        user1.principal,
+        user1.canister(),
+        &user_canister::set_last_seen_visibility::Args {
+            visibility: LastSeenVisibility::Nobody,
+        },
+    );
+    tick_many(env, 5);
+
+    let set_typing_response = client::online_users::set_typing(
+        env,
+        user1.principal,
+        canister_ids.online_users,
+        &online_users_canister::set_typing::Args {
+            recipient: user2.user_id,
+            thread_root_message_index: None,
+            activity: Some(TypingActivity::Typing),
+        },
+    );
+    assert!(matches!(
+        set_typing_response,
+        online_users_canister::set_typing::Response::Success
+    ));
+
+    let online_users_canister::chat_presence::Response::Success(presence) = client::online_users::chat_presence(
+        env,
+        user2.principal,
+        canister_ids.online_users,
+        &online_users_canister::chat_presence::Args {
+            chat: Chat::Direct(user1.user_id.into()),
+            user_ids: vec![user1.user_id],
+        },
+    );
+    assert_eq!(presence.len(), 1);
+    assert!(presence[0].duration_since_last_online.is_none());
+    assert_eq!(presence[0].activity, Some(TypingActivity::Typing));
+
+    // Indicators in direct chats are only visible to the recipient
+    let response = client::online_users::typing_indicators(
+        env,
+        user1.principal,
+        canister_ids.online_users,
+        &online_users_canister::typing_indicators::Args { updated_since: None },
+    );
+    assert!(matches!(response, online_users_canister::typing_indicators::Response::Success(r) if r.indicators.is_empty()));
+}
+
+#[test]
+fn blocked_users_cannot_set_typing_indicators() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user1 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    client::online_users::mark_as_online(
+        env,
+        user1.principal,
+        canister_ids.online_users,
+        &online_users_canister::mark_as_online::Args {},
+    );
+
+    client::user::block_user(
+        env,
+        user2.principal,
+        user2.canister(),
+        &user_canister::block_user::Args { user_id: user1.user_id },
+    );
+    tick_many(env, 5);
+
+    let set_typing_response = client::online_users::set_typing(
+        env,
+        user1.principal,
+        canister_ids.online_users,
+        &online_users_canister::set_typing::Args {
+            recipient: user2.user_id,
+            thread_root_message_index: None,
+            activity: Some(TypingActivity::Typing),
+        },
+    );
+    assert!(matches!(
+        set_typing_response,
+        online_users_canister::set_typing::Response::Blocked
+    ));
+}
+
+#[test]
+fn typing_indicators_are_hidden_once_user_is_blocked() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user1 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    for user in [&user1, &user2] {
+        client::online_users::mark_as_online(
+            env,
+            user.principal,
+            canister_ids.online_users,
+            &online_users_canister::mark_as_online::Args {},
+        );
+    }
+
+    client::online_users::set_typing(
+        env,
+        user1.principal,
+        canister_ids.online_users,
+        &online_users_canister::set_typing::Args {
+            recipient: user2.user_id,
+            thread_root_message_index: None,
+            activity: Some(TypingActivity::Typing),
+        },
+    );
+
+    let response = client::online_users::typing_indicators(
+        env,
+        user2.principal,
+        canister_ids.online_users,
+        &online_users_canister::typing_indicators::Args { updated_since: None },
+    );
+    assert!(matches!(response, online_users_canister::typing_indicators::Response::Success(r) if r.indicators.len() == 1));
+
+    client::user::block_user(
+        env,
+        user2.principal,
+        user2.canister(),
+        &user_canister::block_user::Args { user_id: user1.user_id },
+    );
+    tick_many(env, 5);
+
+    let response = client::online_users::typing_indicators(
+        env,
+        user2.principal,
+        canister_ids.online_users,
+        &online_users_canister::typing_indicators::Args { updated_since: None },
+    );
+    assert!(matches!(response, online_users_canister::typing_indicators::Response::Success(r) if r.indicators.is_empty()));
+}
+
+#[test]
+fn only_user_canisters_can_set_blocked_users_or_last_seen_visibility() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    // Neither the user's own principal nor an unknown principal can act on behalf of their canister
+    for caller in [user.principal, random_principal()] {
+        let response = client::online_users::c2c_set_blocked_users(
+            env,
+            caller,
+            canister_ids.online_users,
+            &online_users_canister::c2c_set_blocked_users::Args {
+                blocked_users: Vec::new(),
+            },
+        );
+        assert!(matches!(
+            response,
+            online_users_canister::c2c_set_blocked_users::Response::NotAuthorized
+        ));
+
+        let response = client::online_users::c2c_set_last_seen_visibility(
+            env,
+            caller,
+            canister_ids.online_users,
+            &online_users_canister::c2c_set_last_seen_visibility::Args {
+                visibility: LastSeenVisibility::Nobody,
+                contacts: Vec::new(),
+            },
+        );
+        assert!(matches!(
+            response,
+            online_users_canister::c2c_set_last_seen_visibility::Response::NotAuthorized
+        ));
+    }
+}
+
+#[test]
+fn group_typing_indicators_are_only_visible_to_members() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user1 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let user2 = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let group_id = client::user::happy_path::create_group(env, &user1, &random_string(), true, true);
+
+    let set_typing_response = client::group::set_typing(
+        env,
+        user1.principal,
+        group_id.into(),
+        &group_canister::set_typing::Args {
+            thread_root_message_index: None,
+            activity: Some(TypingActivity::RecordingAudio),
+        },
+    );
+    assert!(matches!(set_typing_response, group_canister::set_typing::Response::Success));
+
+    let response = client::group::typing_indicators(
+        env,
+        user2.principal,
+        group_id.into(),
+        &group_canister::typing_indicators::Args { updated_since: None },
+    );
+    assert!(matches!(
+        response,
+        group_canister::typing_indicators::Response::CallerNotInGroup
+    ));
+
+    client::local_user_index::happy_path::join_group(env, user2.principal, canister_ids.local_user_index, group_id);
+
+    let response = client::group::typing_indicators(
+        env,
+        user2.principal,
+        group_id.into(),
+        &group_canister::typing_indicators::Args { updated_since: None },
+    );
+    if let group_canister::typing_indicators::Response::Success(result) = response {
+        assert_eq!(result.indicators.len(), 1);
+        assert_eq!(result.indicators[0].user_id, user1.user_id);
+        assert_eq!(result.indicators[0].activity, TypingActivity::RecordingAudio);
+    } else {
+        panic!("'typing_indicators' error: {response:?}");
+    }
+}
@@ -19,9 +19,11 @@ mod freeze_group_tests;
 mod gated_group_tests;
 mod join_group_tests;
 mod last_online_date_tests;
+mod load_tests;
 mod notification_tests;
 mod platform_moderator_tests;
 mod poll_tests;
+mod proposals_bot_tests;
 mod register_user_tests;
 mod registry_tests;
 mod remove_from_group_tests;
@@ -31,11 +33,13 @@ mod send_crypto_tests;
 mod send_direct_message_tests;
 mod set_message_reminder_tests;
 mod setup;
+mod state_snapshot_tests;
 mod storage;
 mod suspend_user_tests;
 mod tip_message_tests;
 mod update_group_tests;
 mod update_profile_tests;
+mod upgrade_dry_run_tests;
 mod utils;
 mod wasms;
 
@@ -0,0 +1,416 @@
+// Load / soak tests. These are ignored by default since they take a long time to run, run them with eg.
+//
+// LOAD_TEST_USERS=2000 cargo test --package integration_tests load_tests -- --ignored --nocapture
+//
+// The report is written to `LOAD_TEST_REPORT` (defaults to `load_test_report.json`). If
+// `LOAD_TEST_BASELINE` is set, the report is compared against that baseline and the test fails if
+// instruction counts or memory growth have regressed by more than `LOAD_TEST_MAX_REGRESSION_PERCENT`.
+use crate::env::ENV;
+use crate::rng::random_message_id;
+use crate::utils::{now_millis, tick_many};
+use crate::{client, CanisterIds, TestEnv, User};
+use ic_test_state_machine_client::StateMachine;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::time::Duration;
+use storage_index_canister::add_or_update_users::UserConfig;
+use types::{CanisterId, ChannelId, ChatId, CommunityId, Reaction, TimestampMillis};
+
+#[test]
+#[ignore]
+fn soak_test() {
+    let config = LoadTestConfig::from_env();
+
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+    } = wrapper.env();
+
+    let started = now_millis(env);
+    let mut recorder = Recorder::default();
+
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let users: Vec<_> = (0..config.users)
+        .map(|_| client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index))
+        .collect();
+    println!("Registered {} users", users.len());
+
+    let group_ids: Vec<_> = (0..config.groups)
+        .map(|i| client::user::happy_path::create_group(env, &owner, &format!("load_test_group_{i}"), true, true))
+        .collect();
+    let community_id =
+        client::user::happy_path::create_community(env, &owner, "load_test_community", true, vec!["general".to_string()]);
+    let channel_id =
+        client::community::happy_path::create_channel(env, owner.principal, community_id, true, "load_test".to_string());
+
+    recorder.record_memory(env, canister_ids, &group_ids, community_id, true);
+
+    for (index, user) in users.iter().enumerate() {
+        let group_id = group_ids[index % group_ids.len()];
+        client::local_user_index::happy_path::join_group(env, user.principal, canister_ids.local_user_index, group_id);
+        client::local_user_index::happy_path::join_channel(
+            env,
+            user.principal,
+            canister_ids.local_user_index,
+            community_id,
+            channel_id,
+        );
+    }
+    println!("Users joined groups and channel");
+
+    for round in 0..config.rounds {
+        for (index, user) in users.iter().enumerate() {
+            let group_id = group_ids[index % group_ids.len()];
+            send_messages(env, user, group_id, community_id, channel_id, config.messages_per_round);
+
+            if config.file_every > 0 && index % config.file_every == 0 {
+                upload_file(env, canister_ids, user, round);
+            }
+        }
+
+        env.advance_time(Duration::from_secs(60));
+        env.tick();
+        recorder.record_backlogs(env, canister_ids, &group_ids, community_id);
+        println!("Completed round {}", round + 1);
+    }
+
+    tick_many(env, 10);
+    recorder.record_backlogs(env, canister_ids, &group_ids, community_id);
+    recorder.record_memory(env, canister_ids, &group_ids, community_id, false);
+    recorder.record_instruction_counts(env, &group_ids, community_id, started);
+
+    let report = recorder.into_report(config);
+    let report_path = std::env::var("LOAD_TEST_REPORT").unwrap_or_else(|_| "load_test_report.json".to_string());
+    std::fs::write(&report_path, serde_json::to_string_pretty(&report).unwrap()).unwrap();
+    println!("{}", report.summary());
+    println!("Report written to {report_path}");
+
+    if let Ok(baseline_path) = std::env::var("LOAD_TEST_BASELINE") {
+        let baseline: LoadTestReport = serde_json::from_slice(&std::fs::read(PathBuf::from(baseline_path)).unwrap()).unwrap();
+        let max_regression_percent = env_var_or("LOAD_TEST_MAX_REGRESSION_PERCENT", 10);
+
+        let regressions = report.regressions(&baseline, max_regression_percent);
+        assert!(
+            regressions.is_empty(),
+            "Regressions against baseline:\n{}",
+            regressions.join("\n")
+        );
+    }
+}
+
+fn send_messages(
+    env: &mut StateMachine,
+    user: &User,
+    group_id: ChatId,
+    community_id: CommunityId,
+    channel_id: ChannelId,
+    count: usize,
+) {
+    for i in 0..count {
+        let message_id = random_message_id();
+        client::group::happy_path::send_text_message(env, user, group_id, None, format!("Group message {i}"), Some(message_id));
+        client::group::add_reaction(
+            env,
+            user.principal,
+            group_id.into(),
+            &group_canister::add_reaction::Args {
+                thread_root_message_index: None,
+                message_id,
+                reaction: Reaction::new("👍".to_string()),
+                username: user.username(),
+                display_name: None,
+                correlation_id: 0,
+            },
+        );
+
+        let message_id = random_message_id();
+        client::community::happy_path::send_text_message(
+            env,
+            user,
+            community_id,
+            channel_id,
+            None,
+            format!("Channel message {i}"),
+            Some(message_id),
+        );
+        client::community::add_reaction(
+            env,
+            user.principal,
+            community_id.into(),
+            &community_canister::add_reaction::Args {
+                channel_id,
+                thread_root_message_index: None,
+                message_id,
+                reaction: Reaction::new("👍".to_string()),
+                username: user.username(),
+                display_name: None,
+            },
+        );
+    }
+}
+
+fn upload_file(env: &mut StateMachine, canister_ids: &CanisterIds, user: &User, round: usize) {
+    if round == 0 {
+        client::storage_index::happy_path::add_or_update_users(
+            env,
+            canister_ids.user_index,
+            canister_ids.storage_index,
+            vec![UserConfig {
+                user_id: user.principal,
+                byte_limit: 100_000_000,
+            }],
+        );
+    }
+
+    let file = vec![round as u8; 10_000];
+    let allocated = client::storage_index::happy_path::allocated_bucket(env, user.principal, canister_ids.storage_index, &file);
+    client::storage_bucket::happy_path::upload_file(env, user.principal, allocated.canister_id, allocated.file_id, file, None);
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+struct LoadTestConfig {
+    users: usize,
+    groups: usize,
+    rounds: usize,
+    messages_per_round: usize,
+    file_every: usize,
+}
+
+impl LoadTestConfig {
+    fn from_env() -> LoadTestConfig {
+        LoadTestConfig {
+            users: env_var_or("LOAD_TEST_USERS", 1000),
+            groups: env_var_or("LOAD_TEST_GROUPS", 10),
+            rounds: env_var_or("LOAD_TEST_ROUNDS", 5),
+            messages_per_round: env_var_or("LOAD_TEST_MESSAGES_PER_ROUND", 2),
+            file_every: env_var_or("LOAD_TEST_FILE_EVERY", 10),
+        }
+    }
+}
+
+fn env_var_or(name: &str, default: usize) -> usize {
+    std::env::var(name).map_or(default, |v| v.parse().unwrap())
+}
+
+#[derive(Deserialize)]
+struct Metrics {
+    memory_used: u64,
+    #[serde(default)]
+    instruction_counts: Vec<InstructionCount>,
+    #[serde(default)]
+    timer_jobs: u32,
+    #[serde(default)]
+    user_events_queue_length: usize,
+}
+
+#[derive(Deserialize)]
+struct InstructionCount {
+    timestamp: TimestampMillis,
+    function_id: String,
+    instruction_count: u64,
+}
+
+#[derive(Default)]
+struct Recorder {
+    instruction_counts: BTreeMap<String, Vec<u64>>,
+    memory: BTreeMap<String, MemoryGrowth>,
+    max_backlogs: BTreeMap<String, u64>,
+}
+
+impl Recorder {
+    fn record_memory(
+        &mut self,
+        env: &StateMachine,
+        canister_ids: &CanisterIds,
+        group_ids: &[ChatId],
+        community_id: CommunityId,
+        before: bool,
+    ) {
+        let mut record = |name: &str, canisters: &[CanisterId]| {
+            let total: u64 = canisters
+                .iter()
+                .map(|c| client::metrics::<Metrics>(env, *c).memory_used)
+                .sum();
+            let entry = self.memory.entry(name.to_string()).or_default();
+            if before {
+                entry.before = total;
+            } else {
+                entry.after = total;
+            }
+        };
+
+        record("group", &group_ids.iter().map(|g| CanisterId::from(*g)).collect::<Vec<_>>());
+        record("community", &[community_id.into()]);
+        record("local_user_index", &[canister_ids.local_user_index]);
+    }
+
+    fn record_backlogs(
+        &mut self,
+        env: &StateMachine,
+        canister_ids: &CanisterIds,
+        group_ids: &[ChatId],
+        community_id: CommunityId,
+    ) {
+        let mut record_max = |name: &str, value: u64| {
+            let max = self.max_backlogs.entry(name.to_string()).or_default();
+            *max = (*max).max(value);
+        };
+
+        let group_timer_jobs = group_ids
+            .iter()
+            .map(|g| client::metrics::<Metrics>(env, (*g).into()).timer_jobs as u64)
+            .max()
+            .unwrap_or_default();
+        record_max("group_timer_jobs", group_timer_jobs);
+
+        let community_metrics: Metrics = client::metrics(env, community_id.into());
+        record_max("community_timer_jobs", community_metrics.timer_jobs as u64);
+
+        let local_user_index_metrics: Metrics = client::metrics(env, canister_ids.local_user_index);
+        record_max("local_user_index_timer_jobs", local_user_index_metrics.timer_jobs as u64);
+        record_max(
+            "local_user_index_user_events_queue",
+            local_user_index_metrics.user_events_queue_length as u64,
+        );
+    }
+
+    fn record_instruction_counts(
+        &mut self,
+        env: &StateMachine,
+        group_ids: &[ChatId],
+        community_id: CommunityId,
+        since: TimestampMillis,
+    ) {
+        let canisters = group_ids
+            .iter()
+            .map(|g| ("group", CanisterId::from(*g)))
+            .chain([("community", community_id.into())]);
+
+        for (name, canister_id) in canisters {
+            let metrics: Metrics = client::metrics(env, canister_id);
+            for entry in metrics.instruction_counts.into_iter().filter(|e| e.timestamp >= since) {
+                self.instruction_counts
+                    .entry(format!("{name}::{}", entry.function_id))
+                    .or_default()
+                    .push(entry.instruction_count);
+            }
+        }
+    }
+
+    fn into_report(self, config: LoadTestConfig) -> LoadTestReport {
+        LoadTestReport {
+            config,
+            instruction_counts: self
+                .instruction_counts
+                .into_iter()
+                .map(|(k, v)| (k, InstructionCountStats::new(v)))
+                .collect(),
+            memory: self.memory,
+            max_backlogs: self.max_backlogs,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LoadTestReport {
+    config: LoadTestConfig,
+    instruction_counts: BTreeMap<String, InstructionCountStats>,
+    memory: BTreeMap<String, MemoryGrowth>,
+    max_backlogs: BTreeMap<String, u64>,
+}
+
+impl LoadTestReport {
+    fn summary(&self) -> String {
+        let mut summary = String::new();
+        for (name, stats) in &self.instruction_counts {
+            writeln!(
+                summary,
+                "{name}: calls={} mean={} p95={} max={}",
+                stats.calls, stats.mean, stats.p95, stats.max
+            )
+            .unwrap();
+        }
+        for (name, memory) in &self.memory {
+            writeln!(summary, "{name} memory: {} -> {} bytes", memory.before, memory.after).unwrap();
+        }
+        for (name, max) in &self.max_backlogs {
+            writeln!(summary, "{name}: max {max}").unwrap();
+        }
+        summary
+    }
+
+    fn regressions(&self, baseline: &LoadTestReport, max_regression_percent: usize) -> Vec<String> {
+        assert_eq!(
+            self.config, baseline.config,
+            "The baseline was recorded using a different config"
+        );
+
+        let exceeds = |value: u64, baseline: u64| value * 100 > baseline * (100 + max_regression_percent as u64);
+        let mut regressions = Vec::new();
+
+        for (name, stats) in &self.instruction_counts {
+            if let Some(baseline_stats) = baseline.instruction_counts.get(name) {
+                if exceeds(stats.p95, baseline_stats.p95) {
+                    regressions.push(format!("{name} p95 instructions: {} -> {}", baseline_stats.p95, stats.p95));
+                }
+                if exceeds(stats.max, baseline_stats.max) {
+                    regressions.push(format!("{name} max instructions: {} -> {}", baseline_stats.max, stats.max));
+                }
+            }
+        }
+        for (name, memory) in &self.memory {
+            if let Some(baseline_memory) = baseline.memory.get(name) {
+                if exceeds(memory.growth(), baseline_memory.growth()) {
+                    regressions.push(format!(
+                        "{name} memory growth: {} -> {} bytes",
+                        baseline_memory.growth(),
+                        memory.growth()
+                    ));
+                }
+            }
+        }
+        regressions
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct InstructionCountStats {
+    calls: u64,
+    mean: u64,
+    p95: u64,
+    max: u64,
+}
+
+impl InstructionCountStats {
+    fn new(mut counts: Vec<u64>) -> InstructionCountStats {
+        counts.sort_unstable();
+        let calls = counts.len() as u64;
+
+        InstructionCountStats {
+            calls,
+            mean: counts.iter().sum::<u64>() / calls.max(1),
+            p95: counts
+                .get((counts.len() * 95 / 100).min(counts.len().saturating_sub(1)))
+                .copied()
+                .unwrap_or_default(),
+            max: counts.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct MemoryGrowth {
+    before: u64,
+    after: u64,
+}
+
+impl MemoryGrowth {
+    fn growth(&self) -> u64 {
+        self.after.saturating_sub(self.before)
+    }
+}
@@ -0,0 +1,85 @@
+use crate::client::{create_canister, install_canister};
+use crate::env::ENV;
+use crate::utils::{now_millis, tick_many};
+use crate::{client, wasms, TestEnv};
+use generic_governance_canister::types::{GovernanceProposal, ProposalStatus, Tally};
+use ic_test_state_machine_client::StateMachine;
+use std::ops::Deref;
+use std::time::Duration;
+use types::{CanisterId, ProposalId};
+use utils::time::DAY_IN_MS;
+
+#[test]
+fn proposals_from_generic_governance_canister_are_pushed_and_settled() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let governance_canister_id = create_canister(env, *controller);
+    install_canister(env, *controller, governance_canister_id, wasms::MOCK_GOVERNANCE.clone(), ());
+
+    let add_governance_canister_response = client::proposals_bot::add_governance_canister(
+        env,
+        *controller,
+        canister_ids.proposals_bot,
+        &proposals_bot_canister::add_governance_canister::Args {
+            governance_canister_id,
+            community_id: None,
+            name: "Mock DAO".to_string(),
+            description: None,
+            avatar: None,
+            is_generic: Some(true),
+        },
+    );
+    assert!(matches!(
+        add_governance_canister_response,
+        proposals_bot_canister::add_governance_canister::Response::Success
+    ));
+
+    let now = now_millis(env);
+    let mut proposal = GovernanceProposal {
+        id: 1,
+        proposer: controller.to_string(),
+        created: now,
+        title: "Test proposal".to_string(),
+        summary: "Test summary".to_string(),
+        url: String::new(),
+        status: ProposalStatus::Open,
+        tally: Tally::default(),
+        deadline: now + DAY_IN_MS,
+        payload_text_rendering: None,
+    };
+    client::generic_governance::upsert_proposal(env, *controller, governance_canister_id, &proposal);
+
+    env.advance_time(Duration::from_secs(120));
+    tick_many(env, 10);
+
+    assert_eq!(
+        active_proposals(env, canister_ids.proposals_bot, governance_canister_id),
+        vec![1]
+    );
+
+    proposal.status = ProposalStatus::Executed;
+    client::generic_governance::upsert_proposal(env, *controller, governance_canister_id, &proposal);
+
+    env.advance_time(Duration::from_secs(120));
+    tick_many(env, 10);
+
+    assert!(active_proposals(env, canister_ids.proposals_bot, governance_canister_id).is_empty());
+}
+
+fn active_proposals(env: &StateMachine, proposals_bot: CanisterId, governance_canister_id: CanisterId) -> Vec<ProposalId> {
+    let metrics: serde_json::Value = client::metrics(env, proposals_bot);
+
+    metrics["nervous_systems"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|ns| ns["governance_canister_id"] == governance_canister_id.to_string())
+        .map(|ns| serde_json::from_value(ns["active_proposals"].clone()).unwrap())
+        .unwrap()
+}
@@ -72,11 +72,14 @@ fn install_canisters(env: &mut StateMachine, controller: Principal) -> CanisterI
         group_index_canister_id,
         notifications_index_canister_id,
         proposals_bot_canister_id,
+        online_users_canister_id,
         cycles_dispenser_canister_id,
         storage_index_canister_id,
         internet_identity_canister_id: NNS_INTERNET_IDENTITY_CANISTER_ID,
+        registry_canister_id,
         wasm_version: BuildVersion::min(),
         test_mode: true,
+        skip_internet_identity_check: false,
     };
     install_canister(
         env,
@@ -0,0 +1,192 @@
+use crate::env::ENV;
+use crate::rng::random_string;
+use crate::{client, CanisterIds, TestEnv, User};
+use candid::Principal;
+use ic_test_state_machine_client::StateMachine;
+use serde_bytes::ByteBuf;
+use std::ops::Deref;
+use types::{ChatEvent, ChatId, MessageContent};
+
+#[test]
+fn export_then_restore_group_state_succeeds() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user, group_id, .. } = init_test_data(env, canister_ids);
+    let summary_before = client::group::happy_path::summary(env, &user, group_id);
+
+    let snapshot = export_state(env, *controller, canister_ids, group_id);
+    restore_state(env, *controller, canister_ids, group_id, snapshot);
+
+    let summary_after = client::group::happy_path::summary(env, &user, group_id);
+    assert_eq!(summary_after.name, summary_before.name);
+    assert_eq!(summary_after.latest_event_index, summary_before.latest_event_index);
+
+    // The restored group can still be used
+    client::group::happy_path::send_text_message(env, &user, group_id, None, random_string(), None);
+}
+
+#[test]
+fn members_can_still_reach_group_after_restore() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user, member, group_id } = init_test_data(env, canister_ids);
+
+    let snapshot = export_state(env, *controller, canister_ids, group_id);
+    restore_state(env, *controller, canister_ids, group_id, snapshot);
+
+    // The group is still listed in the member's user canister, under the same id
+    let initial_state = client::user::happy_path::initial_state(env, &member);
+    assert!(initial_state.group_chats.summaries.iter().any(|c| c.chat_id == group_id));
+
+    // And the member can still send messages which other members can see
+    let text = random_string();
+    let send_result = client::group::happy_path::send_text_message(env, &member, group_id, None, text.clone(), None);
+
+    let events = client::group::happy_path::events_by_index(env, &user, group_id, vec![send_result.event_index]);
+    if let Some(ChatEvent::Message(m)) = events.events.first().map(|e| &e.event) {
+        assert_eq!(m.sender, member.user_id);
+        assert!(matches!(&m.content, MessageContent::Text(t) if t.text == text));
+    } else {
+        panic!("Unexpected response from `events_by_index`: {events:?}");
+    }
+}
+
+#[test]
+fn restore_into_same_canister_without_export_fails() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { group_id, .. } = init_test_data(env, canister_ids);
+
+    let response = client::group_index::start_state_restore(
+        env,
+        *controller,
+        canister_ids.group_index,
+        &group_index_canister::start_state_restore::Args {
+            canister_id: group_id.into(),
+        },
+    );
+
+    assert!(
+        matches!(response, group_index_canister::start_state_restore::Response::ExportRequired),
+        "{response:?}"
+    );
+}
+
+fn export_state(env: &mut StateMachine, controller: Principal, canister_ids: &CanisterIds, group_id: ChatId) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let response = client::group_index::export_state_snapshot(
+            env,
+            controller,
+            canister_ids.group_index,
+            &group_index_canister::export_state_snapshot::Args {
+                canister_id: group_id.into(),
+                from: bytes.len() as u64,
+                page_size: 1000,
+            },
+        );
+
+        match response {
+            group_index_canister::export_state_snapshot::Response::Success(result) => {
+                bytes.extend(result.bytes.into_vec());
+                if bytes.len() as u64 >= result.total_bytes {
+                    return bytes;
+                }
+            }
+            response => panic!("'export_state_snapshot' error: {response:?}"),
+        }
+    }
+}
+
+fn restore_state(
+    env: &mut StateMachine,
+    controller: Principal,
+    canister_ids: &CanisterIds,
+    group_id: ChatId,
+    snapshot: Vec<u8>,
+) {
+    let canister_id = group_id.into();
+    let start_response = client::group_index::start_state_restore(
+        env,
+        controller,
+        canister_ids.group_index,
+        &group_index_canister::start_state_restore::Args { canister_id },
+    );
+    assert!(
+        matches!(start_response, group_index_canister::start_state_restore::Response::Success),
+        "{start_response:?}"
+    );
+
+    let mut from = 0;
+    for chunk in snapshot.chunks(1000) {
+        let push_response = client::group_index::push_state_restore_chunk(
+            env,
+            controller,
+            canister_ids.group_index,
+            &group_index_canister::push_state_restore_chunk::Args {
+                canister_id,
+                total_bytes: snapshot.len() as u64,
+                from,
+                bytes: ByteBuf::from(chunk.to_vec()),
+            },
+        );
+        assert!(
+            matches!(
+                push_response,
+                group_index_canister::push_state_restore_chunk::Response::Success
+            ),
+            "{push_response:?}"
+        );
+        from += chunk.len() as u64;
+    }
+
+    let finish_response = client::group_index::finish_state_restore(
+        env,
+        controller,
+        canister_ids.group_index,
+        &group_index_canister::finish_state_restore::Args { canister_id },
+    );
+    assert!(
+        matches!(finish_response, group_index_canister::finish_state_restore::Response::Success),
+        "{finish_response:?}"
+    );
+}
+
+fn init_test_data(env: &mut StateMachine, canister_ids: &CanisterIds) -> TestData {
+    let user = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let member = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+    let group_id = client::user::happy_path::create_group(env, &user, &random_string(), false, true);
+    client::local_user_index::happy_path::join_group(env, member.principal, canister_ids.local_user_index, group_id);
+
+    env.tick();
+
+    for _ in 0..5 {
+        client::group::happy_path::send_text_message(env, &user, group_id, None, random_string(), None);
+    }
+
+    TestData { user, member, group_id }
+}
+
+struct TestData {
+    user: User,
+    member: User,
+    group_id: ChatId,
+}
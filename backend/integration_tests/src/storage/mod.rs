@@ -1,3 +1,4 @@
 mod allocation_exceeded_tests;
 mod file_expiry_tests;
 mod upload_file_tests;
+mod user_files_tests;
@@ -1,9 +1,16 @@
 use crate::env::ENV;
 use crate::rng::random_principal;
+use crate::utils::tick_many;
 use crate::{client, TestEnv};
+use candid::Principal;
+use ic_test_state_machine_client::StateMachine;
+use serde_bytes::ByteBuf;
 use std::ops::Deref;
+use storage_bucket_canister::{forward_blob, upload_chunk_v2};
 use storage_index_canister::add_or_update_users::UserConfig;
-use utils::hasher::hash_bytes;
+use types::{FileId, Hash};
+use utils::file_id::generate_file_id;
+use utils::hasher::{hash_bytes, possession_proof};
 
 #[test]
 fn upload_file() {
@@ -42,3 +49,185 @@ fn upload_file() {
 
     assert_eq!(user_response.bytes_used, file_size);
 }
+
+#[test]
+fn upload_can_be_resumed() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let file: Vec<u8> = (0..3000).map(|i| (i % 101) as u8).collect();
+    let hash = hash_bytes(&file);
+    let chunks: Vec<_> = file.chunks(1000).collect();
+
+    let allocated_bucket_response =
+        client::storage_index::happy_path::allocated_bucket(env, user_id, canister_ids.storage_index, &file);
+    let bucket = allocated_bucket_response.canister_id;
+    let file_id = allocated_bucket_response.file_id;
+
+    let upload_chunk = |env: &mut StateMachine, chunk_index: usize, chunk_hash: Hash| {
+        client::storage_bucket::upload_chunk_v2(
+            env,
+            user_id,
+            bucket,
+            &upload_chunk_v2::Args {
+                file_id,
+                hash,
+                mime_type: "test_mime_type".to_string(),
+                accessors: vec![],
+                chunk_index: chunk_index as u32,
+                chunk_size: 1000,
+                total_size: file.len() as u64,
+                bytes: ByteBuf::from(chunks[chunk_index]),
+                expiry: None,
+                chunk_hash: Some(chunk_hash),
+            },
+        )
+    };
+
+    assert!(matches!(
+        upload_chunk(env, 0, hash_bytes(chunks[0])),
+        upload_chunk_v2::Response::Success
+    ));
+    assert!(matches!(
+        upload_chunk(env, 2, hash_bytes(chunks[2])),
+        upload_chunk_v2::Response::Success
+    ));
+    assert!(matches!(
+        upload_chunk(env, 1, hash_bytes(chunks[0])),
+        upload_chunk_v2::Response::ChunkHashMismatch
+    ));
+
+    let progress_response = client::storage_bucket::upload_progress(
+        env,
+        user_id,
+        bucket,
+        &storage_bucket_canister::upload_progress::Args { file_id },
+    );
+    if let storage_bucket_canister::upload_progress::Response::Success(result) = progress_response {
+        assert_eq!(result.chunks_received, vec![0, 2]);
+    } else {
+        panic!("'upload_progress' error: {progress_response:?}");
+    }
+
+    assert!(matches!(
+        upload_chunk(env, 1, hash_bytes(chunks[1])),
+        upload_chunk_v2::Response::Success
+    ));
+
+    let progress_response = client::storage_bucket::upload_progress(
+        env,
+        user_id,
+        bucket,
+        &storage_bucket_canister::upload_progress::Args { file_id },
+    );
+    assert!(matches!(
+        progress_response,
+        storage_bucket_canister::upload_progress::Response::Completed
+    ));
+}
+
+#[test]
+fn existing_blob_can_be_forwarded_with_possession_proof() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id1 = random_principal();
+    let user_id2 = random_principal();
+    let user_id3 = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![
+            UserConfig {
+                user_id: user_id1,
+                byte_limit: 10000,
+            },
+            UserConfig {
+                user_id: user_id2,
+                byte_limit: 10000,
+            },
+            UserConfig {
+                user_id: user_id3,
+                byte_limit: 500,
+            },
+        ],
+    );
+
+    let file = vec![2u8; 1000];
+    let hash = hash_bytes(&file);
+
+    let allocated_bucket_response1 =
+        client::storage_index::happy_path::allocated_bucket(env, user_id1, canister_ids.storage_index, &file);
+    assert!(!allocated_bucket_response1.blob_exists);
+
+    client::storage_bucket::happy_path::upload_file(
+        env,
+        user_id1,
+        allocated_bucket_response1.canister_id,
+        allocated_bucket_response1.file_id,
+        file.clone(),
+        None,
+    );
+
+    let allocated_bucket_response2 =
+        client::storage_index::happy_path::allocated_bucket(env, user_id2, canister_ids.storage_index, &file);
+    assert!(allocated_bucket_response2.blob_exists);
+
+    let bucket = allocated_bucket_response2.canister_id;
+    let file_id = allocated_bucket_response2.file_id;
+    assert_eq!(bucket, allocated_bucket_response1.canister_id);
+
+    let forward_blob = |env: &mut StateMachine, user_id: Principal, file_id: FileId, proof: Hash| {
+        client::storage_bucket::forward_blob(
+            env,
+            user_id,
+            bucket,
+            &forward_blob::Args {
+                file_id,
+                hash,
+                possession_proof: proof,
+                mime_type: "test_mime_type".to_string(),
+                accessors: vec![],
+                expiry: None,
+            },
+        )
+    };
+
+    // A proof generated by a different principal is rejected
+    assert!(matches!(
+        forward_blob(env, user_id2, file_id, possession_proof(user_id1, &file)),
+        forward_blob::Response::InvalidPossessionProof
+    ));
+    assert!(matches!(
+        forward_blob(env, user_id2, file_id, possession_proof(user_id2, &file)),
+        forward_blob::Response::Success
+    ));
+
+    // Forwarding is charged against the user's allowance in the same way as uploading
+    let user3_file_id = generate_file_id(bucket, user_id3, hash, 0, 0);
+    assert!(matches!(
+        forward_blob(env, user_id3, user3_file_id, possession_proof(user_id3, &file)),
+        forward_blob::Response::AllowanceExceeded
+    ));
+
+    tick_many(env, 10);
+
+    let file_info_response = client::storage_bucket::happy_path::file_info(env, user_id2, bucket, file_id);
+    assert!(file_info_response.is_owner);
+    assert_eq!(file_info_response.file_hash, hash);
+
+    let user_response = client::storage_index::happy_path::user(env, user_id2, canister_ids.storage_index);
+    assert_eq!(user_response.bytes_used, file.len() as u64);
+}
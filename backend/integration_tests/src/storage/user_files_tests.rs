@@ -0,0 +1,190 @@
+use crate::env::ENV;
+use crate::rng::random_principal;
+use crate::utils::tick_many;
+use crate::{client, TestEnv};
+use std::ops::Deref;
+use std::time::Duration;
+use storage_index_canister::add_or_update_users::UserConfig;
+
+#[test]
+fn user_files_lists_files_with_details() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let file1 = vec![1u8; 500];
+    let file2 = vec![2u8; 700];
+
+    let allocated_bucket_response1 =
+        client::storage_index::happy_path::allocated_bucket(env, user_id, canister_ids.storage_index, &file1);
+    let file_id1 = allocated_bucket_response1.file_id;
+    client::storage_bucket::happy_path::upload_file(
+        env,
+        user_id,
+        allocated_bucket_response1.canister_id,
+        file_id1,
+        file1,
+        None,
+    );
+
+    env.advance_time(Duration::from_millis(1));
+
+    let allocated_bucket_response2 =
+        client::storage_index::happy_path::allocated_bucket(env, user_id, canister_ids.storage_index, &file2);
+    let file_id2 = allocated_bucket_response2.file_id;
+    client::storage_bucket::happy_path::upload_file(
+        env,
+        user_id,
+        allocated_bucket_response2.canister_id,
+        file_id2,
+        file2,
+        None,
+    );
+
+    let result = client::storage_index::happy_path::user_files(env, user_id, canister_ids.storage_index);
+
+    assert_eq!(result.bytes_used, 1200);
+    assert_eq!(result.files.len(), 2);
+    assert_eq!(result.files[0].file_id, file_id1);
+    assert_eq!(result.files[0].size, 500);
+    assert_eq!(result.files[1].file_id, file_id2);
+    assert_eq!(result.files[1].size, 700);
+    assert!(result.files.iter().all(|f| f.mime_type.is_some()));
+}
+
+#[test]
+fn user_files_are_paged() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let mut file_ids = Vec::new();
+    for i in 0..5u8 {
+        let file = vec![i; 100];
+        let allocated_bucket_response =
+            client::storage_index::happy_path::allocated_bucket(env, user_id, canister_ids.storage_index, &file);
+        let file_id = allocated_bucket_response.file_id;
+        client::storage_bucket::happy_path::upload_file(
+            env,
+            user_id,
+            allocated_bucket_response.canister_id,
+            file_id,
+            file,
+            None,
+        );
+        file_ids.push(file_id);
+        env.advance_time(Duration::from_millis(1));
+    }
+
+    let mut pages = Vec::new();
+    let mut start_after = None;
+    loop {
+        let response = client::storage_index::user_files(
+            env,
+            user_id,
+            canister_ids.storage_index,
+            &storage_index_canister::user_files::Args {
+                start_after,
+                max_results: Some(2),
+            },
+        );
+
+        if let storage_index_canister::user_files::Response::Success(result) = response {
+            pages.push(result.files.iter().map(|f| f.file_id).collect::<Vec<_>>());
+            start_after = result.next;
+        } else {
+            panic!("'user_files' error: {response:?}");
+        }
+
+        if start_after.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(
+        pages,
+        vec![file_ids[0..2].to_vec(), file_ids[2..4].to_vec(), file_ids[4..].to_vec()]
+    );
+}
+
+#[test]
+fn delete_user_files_frees_up_space() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let mut files = Vec::new();
+    for i in 0..3u8 {
+        let file = vec![i; 500];
+        let allocated_bucket_response =
+            client::storage_index::happy_path::allocated_bucket(env, user_id, canister_ids.storage_index, &file);
+        let bucket = allocated_bucket_response.canister_id;
+        let file_id = allocated_bucket_response.file_id;
+        client::storage_bucket::happy_path::upload_file(env, user_id, bucket, file_id, file, None);
+        files.push((bucket, file_id));
+        env.advance_time(Duration::from_millis(1));
+    }
+
+    // Delete the newest file explicitly plus enough of the oldest files to free up 700 bytes in total
+    let response = client::storage_index::delete_user_files(
+        env,
+        user_id,
+        canister_ids.storage_index,
+        &storage_index_canister::delete_user_files::Args {
+            file_ids: vec![files[2].1],
+            free_up_bytes: Some(700),
+        },
+    );
+
+    if let storage_index_canister::delete_user_files::Response::Success(result) = response {
+        assert_eq!(result.files_deleted, vec![files[2].1, files[0].1]);
+        assert_eq!(result.bytes_freed, 1000);
+    } else {
+        panic!("'delete_user_files' error: {response:?}");
+    }
+
+    tick_many(env, 10);
+
+    assert!(!client::storage_bucket::happy_path::file_exists(
+        env, user_id, files[0].0, files[0].1
+    ));
+    assert!(client::storage_bucket::happy_path::file_exists(
+        env, user_id, files[1].0, files[1].1
+    ));
+    assert!(!client::storage_bucket::happy_path::file_exists(
+        env, user_id, files[2].0, files[2].1
+    ));
+
+    let user = client::storage_index::happy_path::user(env, user_id, canister_ids.storage_index);
+    assert_eq!(user.bytes_used, 500);
+}
@@ -0,0 +1,103 @@
+use crate::env::ENV;
+use crate::{client, wasms, TestEnv};
+use candid::Principal;
+use ic_test_state_machine_client::StateMachine;
+use std::ops::Deref;
+use types::{BuildVersion, CanisterId};
+use upgrade_dry_run::StableMemoryLayout;
+
+#[test]
+fn dry_run_with_same_wasm_is_compatible() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    let snapshot = take_user_index_snapshot(env, *controller, canister_ids.user_index);
+
+    let report = upgrade_dry_run::dry_run_upgrade(
+        env,
+        &snapshot,
+        StableMemoryLayout::MemoryManager,
+        &wasms::USER_INDEX.module,
+        BuildVersion::default(),
+    );
+
+    assert!(report.is_compatible(), "{report}");
+}
+
+#[test]
+fn dry_run_with_incompatible_wasm_reports_post_upgrade_error() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let snapshot = take_user_index_snapshot(env, *controller, canister_ids.user_index);
+
+    let report = upgrade_dry_run::dry_run_upgrade(
+        env,
+        &snapshot,
+        StableMemoryLayout::MemoryManager,
+        &wasms::GROUP_INDEX.module,
+        BuildVersion::default(),
+    );
+
+    assert!(report.post_upgrade_error.is_some());
+    assert!(!report.is_compatible());
+}
+
+#[test]
+fn dry_run_of_user_canister_with_same_wasm_is_compatible() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user = client::local_user_index::happy_path::register_user(env, canister_ids.local_user_index);
+
+    // User canisters are controlled by their local_user_index
+    client::upgrade_canister(
+        env,
+        canister_ids.local_user_index,
+        user.canister(),
+        wasms::USER.clone(),
+        user_canister::post_upgrade::Args {
+            wasm_version: BuildVersion::default(),
+        },
+    );
+    let stable_memory = env.stable_memory(user.canister());
+    assert_eq!(upgrade_dry_run::detect_layout(&stable_memory), StableMemoryLayout::Raw);
+
+    let snapshot = upgrade_dry_run::extract_snapshot(&stable_memory);
+
+    let report = upgrade_dry_run::dry_run_upgrade(
+        env,
+        &snapshot,
+        StableMemoryLayout::Raw,
+        &wasms::USER.module,
+        BuildVersion::default(),
+    );
+
+    assert!(report.is_compatible(), "{report}");
+}
+
+// Upgrades the user_index in place so that `pre_upgrade` writes its current state to stable memory
+fn take_user_index_snapshot(env: &mut StateMachine, controller: Principal, canister_id: CanisterId) -> Vec<u8> {
+    client::upgrade_canister(
+        env,
+        controller,
+        canister_id,
+        wasms::USER_INDEX.clone(),
+        user_index_canister::post_upgrade::Args {
+            wasm_version: BuildVersion::default(),
+        },
+    );
+    upgrade_dry_run::extract_snapshot(&env.stable_memory(canister_id))
+}
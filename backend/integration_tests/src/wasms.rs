@@ -14,6 +14,7 @@ lazy_static! {
     pub static ref ICRC1_LEDGER: CanisterWasm = get_canister_wasm("icrc1_ledger");
     pub static ref LOCAL_GROUP_INDEX: CanisterWasm = get_canister_wasm("local_group_index");
     pub static ref LOCAL_USER_INDEX: CanisterWasm = get_canister_wasm("local_user_index");
+    pub static ref MOCK_GOVERNANCE: CanisterWasm = get_canister_wasm("mock_governance");
     pub static ref NOTIFICATIONS: CanisterWasm = get_canister_wasm("notifications");
     pub static ref NOTIFICATIONS_INDEX: CanisterWasm = get_canister_wasm("notifications_index");
     pub static ref ONLINE_USERS: CanisterWasm = get_canister_wasm("online_users");
@@ -84,7 +84,7 @@ impl Display for CanisterName {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct CanisterIds {
     pub user_index: CanisterId,
     pub group_index: CanisterId,
@@ -1,7 +1,11 @@
 // Inspired by https://github.com/dfinity/ic/blob/master/rs/rust_canisters/canister_log/src/lib.rs
 
 use candid::CandidType;
+use ic_stable_structures::memory_manager::VirtualMemory;
+use ic_stable_structures::{DefaultMemoryImpl, Storable};
+use overflow::LogOverflow;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::io::Write;
@@ -14,10 +18,21 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::Registry;
 
+mod overflow;
+mod query;
+
+pub use query::*;
+
+pub type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+const MAX_OVERFLOW_ENTRIES_PER_GENERATION: u64 = 10_000;
+
 thread_local! {
     static INITIALIZED: Cell<bool> = Cell::default();
     static LOG: RefCell<LogBuffer> = RefCell::new(LogBuffer::default());
     static TRACE: RefCell<LogBuffer> = RefCell::new(LogBuffer::default());
+    static LOG_OVERFLOW: RefCell<Option<LogOverflow>> = RefCell::default();
+    static LOG_OVERFLOW_APPEND_FAILURES: Cell<u64> = Cell::default();
 }
 
 pub fn init(enable_trace: bool) {
@@ -54,16 +69,37 @@ pub fn init_with_logs(enable_trace: bool, logs: Vec<LogEntry>, traces: Vec<LogEn
     init(enable_trace);
 
     for log in logs {
-        LOG.with(|l| l.borrow_mut().append(log));
+        append_log(log);
     }
     for trace in traces {
-        TRACE.with(|t| t.borrow_mut().append(trace));
+        append_trace(trace);
     }
 }
 
+/// Enables the stable memory backed overflow for the log buffer. Once enabled, entries evicted
+/// from the in-memory buffer are appended to the overflow rather than being discarded, so they
+/// survive upgrades and can be paged through using cursors. Only the most recent entries are
+/// retained, see `MAX_OVERFLOW_ENTRIES_PER_GENERATION`.
+///
+/// On upgrade this must be called before `init_with_logs` so that any restored entries which are
+/// evicted from the buffer end up in the overflow.
+pub fn init_log_overflow(index_memory: Memory, data_memory: Memory) {
+    init_log_overflow_with_capacity(index_memory, data_memory, MAX_OVERFLOW_ENTRIES_PER_GENERATION);
+}
+
+fn init_log_overflow_with_capacity(index_memory: Memory, data_memory: Memory, max_entries_per_generation: u64) {
+    let overflow = LogOverflow::init(index_memory, data_memory, max_entries_per_generation);
+
+    // Entries in the overflow keep the index they were assigned when first logged, so the entries
+    // held in memory must follow on from the last entry in the overflow
+    LOG.with(|l| l.borrow_mut().reindex(overflow.next_index()));
+    LOG_OVERFLOW.with(|o| *o.borrow_mut() = Some(overflow));
+}
+
 /// A circular buffer for log messages.
 pub struct LogBuffer {
     max_capacity: usize,
+    next_index: u64,
     entries: VecDeque<LogEntry>,
 }
 
@@ -72,33 +108,75 @@ impl LogBuffer {
     pub fn with_capacity(max_capacity: usize) -> Self {
         Self {
             max_capacity,
+            next_index: 0,
             entries: VecDeque::with_capacity(max_capacity),
         }
     }
 
-    /// Adds a new entry to the buffer, potentially evicting older entries.
-    pub fn append(&mut self, entry: LogEntry) {
-        while self.entries.len() >= self.max_capacity {
-            self.entries.pop_front();
-        }
+    /// Adds a new entry to the buffer, potentially evicting the oldest entry, which is returned.
+    pub fn append(&mut self, mut entry: LogEntry) -> Option<LogEntry> {
+        let evicted = if self.entries.len() >= self.max_capacity { self.entries.pop_front() } else { None };
+
+        entry.index = self.next_index;
+        self.next_index += 1;
         self.entries.push_back(entry);
+        evicted
     }
 
     /// Returns an iterator over entries in the order of their insertion.
     pub fn iter(&self) -> impl Iterator<Item = &LogEntry> {
         self.entries.iter()
     }
+
+    /// Returns an iterator over the entries whose index is greater than or equal to `index`.
+    pub fn iter_from(&self, index: u64) -> impl Iterator<Item = &LogEntry> {
+        let skip = self.entries.front().map_or(0, |e| index.saturating_sub(e.index) as usize);
+
+        self.entries.iter().skip(skip)
+    }
+
+    fn reindex(&mut self, start: u64) {
+        for (offset, entry) in self.entries.iter_mut().enumerate() {
+            entry.index = start + offset as u64;
+        }
+        self.next_index = start + self.entries.len() as u64;
+    }
 }
 
 impl Default for LogBuffer {
     fn default() -> Self {
         LogBuffer {
             max_capacity: 100,
+            next_index: 0,
             entries: VecDeque::new(),
         }
     }
 }
 
+fn append_log(entry: LogEntry) {
+    if let Some(evicted) = LOG.with(|l| l.borrow_mut().append(entry)) {
+        LOG_OVERFLOW.with(|o| {
+            if let Some(overflow) = o.borrow_mut().as_mut() {
+                // Losing an old log entry is preferable to trapping the current call, so failures
+                // are counted (and surfaced by the logs endpoints) rather than propagated. They
+                // can't be logged since that would recurse back into the logger.
+                if !overflow.append(&evicted) {
+                    LOG_OVERFLOW_APPEND_FAILURES.with(|f| f.set(f.get() + 1));
+                }
+            }
+        });
+    }
+}
+
+/// Returns the number of evicted log entries which could not be appended to the overflow.
+pub fn log_overflow_append_failures() -> u64 {
+    LOG_OVERFLOW_APPEND_FAILURES.with(|f| f.get())
+}
+
+fn append_trace(entry: LogEntry) {
+    TRACE.with(|t| t.borrow_mut().append(entry));
+}
+
 pub fn export_logs() -> Vec<LogEntry> {
     LOG.with(|l| l.borrow().iter().cloned().collect())
 }
@@ -107,10 +185,90 @@ pub fn export_traces() -> Vec<LogEntry> {
     TRACE.with(|t| t.borrow().iter().cloned().collect())
 }
 
+/// Returns the log entries matching the query.
+///
+/// If a cursor or `since` is specified, the search starts from the entry after that point and
+/// continues forwards through the overflow (if enabled) and then the in-memory buffer. Otherwise
+/// the newest matching entries are returned.
+pub fn query_logs(query: &LogQuery) -> LogsPage {
+    LOG.with(|l| {
+        LOG_OVERFLOW.with(|o| {
+            let buffer = l.borrow();
+            let overflow = o.borrow();
+            let overflow_range = overflow.as_ref().map_or(0..0, |log| log.first_index()..log.next_index());
+
+            let mut page = if query.cursor.is_none() && query.since.is_none() {
+                let from_overflow = overflow
+                    .as_ref()
+                    .into_iter()
+                    .flat_map(|log| overflow_range.clone().rev().filter_map(|i| log.get(i)));
+
+                query.run_newest(buffer.iter().rev().cloned().chain(from_overflow))
+            } else {
+                let mut start = query.cursor.map_or(0, |c| c + 1).max(overflow_range.start);
+                if let (Some(since), Some(log)) = (query.since, overflow.as_ref()) {
+                    start = start.max(first_overflow_index_after(log, since));
+                }
+
+                let from_overflow = overflow
+                    .as_ref()
+                    .into_iter()
+                    .flat_map(|log| (start..overflow_range.end).filter_map(|i| log.get(i)));
+
+                query.run(from_overflow.chain(buffer.iter_from(start).cloned()))
+            };
+            page.overflow_append_failures = log_overflow_append_failures();
+            page
+        })
+    })
+}
+
+// Entries are appended in timestamp order, so the first entry logged after `since` can be found
+// using a binary search rather than by scanning the whole overflow
+fn first_overflow_index_after(log: &LogOverflow, since: u64) -> u64 {
+    let mut low = log.first_index();
+    let mut high = log.next_index();
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if log.get(mid).map_or(false, |e| e.timestamp <= since) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// Returns the trace entries matching the query. Traces are only held in memory.
+pub fn query_traces(query: &LogQuery) -> LogsPage {
+    TRACE.with(|t| {
+        let traces = t.borrow();
+        if query.cursor.is_none() && query.since.is_none() {
+            query.run_newest(traces.iter().rev().cloned())
+        } else {
+            let start = query.cursor.map_or(0, |c| c + 1);
+
+            query.run(traces.iter_from(start).cloned())
+        }
+    })
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone)]
 pub struct LogEntry {
     pub timestamp: u64,
     pub message: String,
+    #[serde(default)]
+    pub index: u64,
+}
+
+impl Storable for LogEntry {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(msgpack::serialize_then_unwrap(self))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        msgpack::deserialize_then_unwrap(bytes.as_ref())
+    }
 }
 
 struct LogWriter {
@@ -140,10 +298,14 @@ impl Write for LogWriter {
         let log_entry = LogEntry {
             timestamp: canister_time::timestamp_millis(),
             message: json,
+            index: 0,
         };
 
-        let sink = if self.trace { &TRACE } else { &LOG };
-        sink.with(|s| s.borrow_mut().append(log_entry));
+        if self.trace {
+            append_trace(log_entry);
+        } else {
+            append_log(log_entry);
+        }
         Ok(())
     }
 
@@ -161,3 +323,83 @@ impl FormatTime for Timer {
         w.write_str(&format!("{now}"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
+
+    fn append_entries(range: std::ops::Range<u64>) {
+        for i in range {
+            append_log(LogEntry {
+                timestamp: i,
+                message: format!("entry {i}"),
+                index: 0,
+            });
+        }
+    }
+
+    #[test]
+    fn queries_include_overflow() {
+        let memory_manager = MemoryManager::init(DefaultMemoryImpl::default());
+        init_log_overflow(memory_manager.get(MemoryId::new(0)), memory_manager.get(MemoryId::new(1)));
+
+        append_entries(0..150);
+
+        // With no cursor the newest entries are returned
+        let page = query_logs(&LogQuery::default());
+        assert_eq!(page.entries.len(), 100);
+        assert_eq!(page.entries.first().map(|e| e.index), Some(50));
+        assert_eq!(page.entries.last().map(|e| e.index), Some(149));
+        assert_eq!(page.next_cursor, Some(149));
+        assert_eq!(page.overflow_append_failures, 0);
+
+        let page = query_logs(&LogQuery {
+            since: Some(20),
+            limit: Some(5),
+            ..Default::default()
+        });
+        assert_eq!(page.entries.first().map(|e| e.index), Some(21));
+        assert_eq!(page.next_cursor, Some(25));
+
+        let page = query_logs(&LogQuery {
+            cursor: Some(25),
+            limit: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(page.entries[0].index, 26);
+    }
+
+    #[test]
+    fn overflow_is_bounded_and_survives_upgrades() {
+        let memory_manager = MemoryManager::init(DefaultMemoryImpl::default());
+        let init =
+            || init_log_overflow_with_capacity(memory_manager.get(MemoryId::new(0)), memory_manager.get(MemoryId::new(1)), 10);
+        init();
+
+        // 30 entries are evicted into the overflow, only the newest 2 generations of 10 are kept
+        append_entries(0..130);
+        let query = LogQuery {
+            cursor: Some(0),
+            limit: Some(1000),
+            ..Default::default()
+        };
+        let page = query_logs(&query);
+        assert_eq!(page.entries.first().map(|e| e.index), Some(10));
+        assert_eq!(page.entries.len(), 120);
+
+        // Simulate an upgrade, the overflow is initialized before the in-memory entries are restored
+        let logs = export_logs();
+        LOG.with(|l| *l.borrow_mut() = LogBuffer::default());
+        init();
+        for log in logs {
+            append_log(log);
+        }
+        append_entries(130..131);
+
+        let page = query_logs(&query);
+        assert_eq!(page.entries.first().map(|e| e.index), Some(20));
+        assert_eq!(page.entries.last().map(|e| e.index), Some(130));
+        assert!(page.entries.windows(2).all(|w| w[1].index == w[0].index + 1));
+    }
+}
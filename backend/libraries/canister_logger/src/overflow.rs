@@ -0,0 +1,102 @@
+use crate::{LogEntry, Memory};
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::StableLog;
+
+type GenerationMemory = VirtualMemory<Memory>;
+type Generation = StableLog<LogEntry, GenerationMemory, GenerationMemory>;
+
+// Holds the log entries evicted from the in-memory buffer.
+//
+// Entries can't be removed from a `StableLog`, so the overflow is split into two generations, each
+// backed by its own pair of memories. Once the current generation is full, the older generation is
+// cleared and takes over as the current one, so between `max_entries_per_generation` and twice that
+// many entries are retained.
+pub(crate) struct LogOverflow {
+    index_memory_manager: MemoryManager<Memory>,
+    data_memory_manager: MemoryManager<Memory>,
+    max_entries_per_generation: u64,
+    current_id: u8,
+    current: Generation,
+    previous: Option<Generation>,
+}
+
+impl LogOverflow {
+    pub fn init(index_memory: Memory, data_memory: Memory, max_entries_per_generation: u64) -> LogOverflow {
+        let index_memory_manager = MemoryManager::init_with_bucket_size(index_memory, 1);
+        let data_memory_manager = MemoryManager::init_with_bucket_size(data_memory, 1);
+
+        let mut generations: Vec<(u8, Generation)> = (0..2)
+            .map(|id| {
+                let memory_id = MemoryId::new(id);
+                let generation =
+                    StableLog::init(index_memory_manager.get(memory_id), data_memory_manager.get(memory_id)).unwrap();
+                (id, generation)
+            })
+            .collect();
+
+        // The current generation is the one holding the most recent entry
+        generations.sort_by_key(|(_, g)| last_index(g));
+        let (current_id, current) = generations.pop().unwrap();
+        let previous = generations.pop().map(|(_, g)| g).filter(|g| !g.is_empty());
+
+        LogOverflow {
+            index_memory_manager,
+            data_memory_manager,
+            max_entries_per_generation,
+            current_id,
+            current,
+            previous,
+        }
+    }
+
+    pub fn append(&mut self, entry: &LogEntry) -> bool {
+        if self.current.len() >= self.max_entries_per_generation {
+            self.rotate();
+        }
+        self.current.append(entry).is_ok()
+    }
+
+    // The index which will be assigned to the next entry logged
+    pub fn next_index(&self) -> u64 {
+        last_index(&self.current)
+            .or_else(|| self.previous.as_ref().and_then(last_index))
+            .map_or(0, |i| i + 1)
+    }
+
+    // The index of the oldest entry retained
+    pub fn first_index(&self) -> u64 {
+        self.previous
+            .as_ref()
+            .unwrap_or(&self.current)
+            .get(0)
+            .map_or_else(|| self.next_index(), |e| e.index)
+    }
+
+    pub fn get(&self, index: u64) -> Option<LogEntry> {
+        [Some(&self.current), self.previous.as_ref()]
+            .into_iter()
+            .flatten()
+            .find_map(|g| index.checked_sub(g.get(0)?.index).and_then(|offset| g.get(offset)))
+    }
+
+    fn rotate(&mut self) {
+        let id = 1 - self.current_id;
+        let memory_id = MemoryId::new(id);
+        let cleared = StableLog::new(
+            self.index_memory_manager.get(memory_id),
+            self.data_memory_manager.get(memory_id),
+        )
+        .unwrap();
+
+        self.previous = Some(std::mem::replace(&mut self.current, cleared));
+        self.current_id = id;
+    }
+}
+
+fn last_index(generation: &Generation) -> Option<u64> {
+    generation
+        .len()
+        .checked_sub(1)
+        .and_then(|i| generation.get(i))
+        .map(|e| e.index)
+}
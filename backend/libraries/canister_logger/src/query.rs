@@ -0,0 +1,251 @@
+use crate::LogEntry;
+use serde_json::Value;
+use std::str::FromStr;
+use tracing::Level;
+
+// Caps the number of entries inspected by a single query so that paging through a large overflow
+// doesn't exceed the instruction limit. If the cap is hit, the returned cursor continues the scan.
+const MAX_ENTRIES_SCANNED: usize = 10_000;
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1_000;
+
+#[derive(Default, Debug)]
+pub struct LogQuery {
+    pub since: Option<u64>,
+    pub level: Option<Level>,
+    pub target: Option<String>,
+    pub contains: Option<String>,
+    pub user_id: Option<String>,
+    pub correlation_id: Option<String>,
+    pub cursor: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+pub struct LogsPage {
+    pub entries: Vec<LogEntry>,
+    pub next_cursor: Option<u64>,
+    pub overflow_append_failures: u64,
+}
+
+impl LogQuery {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if self.since.map_or(false, |since| entry.timestamp <= since) {
+            return false;
+        }
+
+        if let Some(contains) = &self.contains {
+            if !entry.message.to_lowercase().contains(&contains.to_lowercase()) {
+                return false;
+            }
+        }
+
+        if self.level.is_none() && self.target.is_none() && self.user_id.is_none() && self.correlation_id.is_none() {
+            return true;
+        }
+
+        let Ok(json) = serde_json::from_str::<Value>(&entry.message) else {
+            return false;
+        };
+
+        if let Some(level) = self.level {
+            // Levels are ordered by verbosity, so this includes the specified level and anything more severe
+            match json
+                .get("level")
+                .and_then(|l| l.as_str())
+                .and_then(|l| Level::from_str(l).ok())
+            {
+                Some(l) if l <= level => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(target) = &self.target {
+            match json.get("target").and_then(|t| t.as_str()) {
+                Some(t) if t.to_lowercase().starts_with(&target.to_lowercase()) => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(user_id) = &self.user_id {
+            if !field_contains(&json, &["user_id", "caller"], user_id) {
+                return false;
+            }
+        }
+
+        if let Some(correlation_id) = &self.correlation_id {
+            if !field_contains(&json, &["correlation_id"], correlation_id) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT)
+    }
+
+    // Scans forwards through `entries`, returning a cursor to continue from if the page is cut short
+    pub(crate) fn run(&self, entries: impl Iterator<Item = LogEntry>) -> LogsPage {
+        let limit = self.limit();
+        let mut matches = Vec::new();
+        let mut last_scanned = None;
+
+        for (scanned, entry) in entries.enumerate() {
+            if matches.len() >= limit || scanned >= MAX_ENTRIES_SCANNED {
+                return LogsPage {
+                    entries: matches,
+                    next_cursor: last_scanned,
+                    overflow_append_failures: 0,
+                };
+            }
+
+            last_scanned = Some(entry.index);
+            if self.matches(&entry) {
+                matches.push(entry);
+            }
+        }
+
+        LogsPage {
+            entries: matches,
+            next_cursor: None,
+            overflow_append_failures: 0,
+        }
+    }
+
+    // Scans backwards through `entries` (which must be ordered newest first) and returns the newest
+    // matches in ascending order. The cursor points at the newest entry so that it can be used to
+    // poll for anything logged afterwards.
+    pub(crate) fn run_newest(&self, entries: impl Iterator<Item = LogEntry>) -> LogsPage {
+        let limit = self.limit();
+        let mut matches = Vec::new();
+        let mut next_cursor = None;
+
+        for entry in entries.take(MAX_ENTRIES_SCANNED) {
+            next_cursor.get_or_insert(entry.index);
+            if self.matches(&entry) {
+                matches.push(entry);
+                if matches.len() >= limit {
+                    break;
+                }
+            }
+        }
+        matches.reverse();
+
+        LogsPage {
+            entries: matches,
+            next_cursor,
+            overflow_append_failures: 0,
+        }
+    }
+}
+
+// Fields may be attached to the event itself or to any of the spans it was recorded within
+fn field_contains(json: &Value, keys: &[&str], value: &str) -> bool {
+    let value = value.to_lowercase();
+
+    let mut objects = vec![json.get("fields"), json.get("span")];
+    if let Some(Value::Array(spans)) = json.get("spans") {
+        objects.extend(spans.iter().map(Some));
+    }
+
+    objects.into_iter().flatten().any(|object| {
+        keys.iter().filter_map(|k| object.get(k)).any(|v| {
+            let text = match v {
+                Value::String(s) => s.to_lowercase(),
+                other => other.to_string(),
+            };
+            text.contains(&value)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(index: u64, timestamp: u64, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp,
+            message: message.to_string(),
+            index,
+        }
+    }
+
+    #[test]
+    fn level_includes_more_severe() {
+        let query = LogQuery {
+            level: Some(Level::WARN),
+            ..Default::default()
+        };
+
+        assert!(query.matches(&entry(0, 1, r#"{"level":"ERROR","target":"a"}"#)));
+        assert!(query.matches(&entry(0, 1, r#"{"level":"WARN","target":"a"}"#)));
+        assert!(!query.matches(&entry(0, 1, r#"{"level":"INFO","target":"a"}"#)));
+    }
+
+    #[test]
+    fn target_is_prefix_match() {
+        let query = LogQuery {
+            target: Some("group_canister_impl::updates".to_string()),
+            ..Default::default()
+        };
+
+        assert!(query.matches(&entry(0, 1, r#"{"target":"group_canister_impl::updates::send_message"}"#)));
+        assert!(!query.matches(&entry(0, 1, r#"{"target":"group_canister_impl::queries::events"}"#)));
+    }
+
+    #[test]
+    fn user_id_found_in_fields_or_spans() {
+        let query = LogQuery {
+            user_id: Some("3skqk-iqaaa-aaaaf-aaa3q-cai".to_string()),
+            ..Default::default()
+        };
+
+        assert!(query.matches(&entry(0, 1, r#"{"fields":{"user_id":"3skqk-iqaaa-aaaaf-aaa3q-cai"}}"#)));
+        assert!(query.matches(&entry(0, 1, r#"{"spans":[{"caller":"3skqk-iqaaa-aaaaf-aaa3q-cai"}]}"#)));
+        assert!(!query.matches(&entry(0, 1, r#"{"fields":{"message":"3skqk-iqaaa-aaaaf-aaa3q-cai"}}"#)));
+    }
+
+    #[test]
+    fn since_and_contains() {
+        let query = LogQuery {
+            since: Some(10),
+            contains: Some("timeout".to_string()),
+            ..Default::default()
+        };
+
+        assert!(query.matches(&entry(0, 11, "Call failed: Timeout")));
+        assert!(!query.matches(&entry(0, 10, "Call failed: Timeout")));
+        assert!(!query.matches(&entry(0, 11, "Call succeeded")));
+    }
+
+    #[test]
+    fn run_returns_cursor_when_limit_reached() {
+        let query = LogQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let page = query.run((0..5).map(|i| entry(i, i, "abc")));
+        assert_eq!(page.entries.len(), 2);
+        assert_eq!(page.next_cursor, Some(1));
+
+        let page = query.run((0..2).map(|i| entry(i, i, "abc")));
+        assert_eq!(page.entries.len(), 2);
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn run_newest_returns_latest_matches_in_order() {
+        let query = LogQuery {
+            contains: Some("abc".to_string()),
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let page = query.run_newest((0..10).rev().map(|i| entry(i, i, if i == 8 { "xyz" } else { "abc" })));
+        assert_eq!(page.entries.iter().map(|e| e.index).collect::<Vec<_>>(), vec![7, 9]);
+        assert_eq!(page.next_cursor, Some(9));
+    }
+}
@@ -30,6 +30,14 @@ impl<J: Job> TimerJobs<J> {
 }
 
 impl<J> TimerJobs<J> {
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
     pub fn cancel_jobs<F: Fn(&J) -> bool>(&mut self, filter: F) -> Vec<J> {
         #[allow(clippy::redundant_closure)]
         let to_remove: Vec<_> = self
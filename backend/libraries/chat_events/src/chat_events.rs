@@ -17,8 +17,9 @@ use types::{
     CanisterId, Chat, CompletedCryptoTransaction, Cryptocurrency, DirectChatCreated, EventIndex, EventWrapper,
     EventsTimeToLiveUpdated, GroupCanisterThreadDetails, GroupCreated, GroupFrozen, GroupUnfrozen, Hash, HydratedMention,
     Mention, Message, MessageContentInitial, MessageId, MessageIndex, MessageMatch, MessageReport, Milliseconds, MultiUserChat,
-    PendingCryptoTransaction, PollVotes, PrizeWinnerContent, ProposalUpdate, PushEventResult, PushIfNotContains, Reaction,
-    RegisterVoteResult, ReportedMessageInternal, TimestampMillis, TimestampNanos, Timestamped, Tips, UserId, VoteOperation,
+    PendingCryptoTransaction, PollVotes, PrizeWinnerContent, Proposal, ProposalDecisionStatus, ProposalUpdate, PushEventResult,
+    PushIfNotContains, Reaction, RegisterVoteResult, ReportedMessageInternal, TimestampMillis, TimestampNanos, Timestamped,
+    Tips, UserId, VoteOperation,
 };
 
 pub const OPENCHAT_BOT_USER_ID: UserId = UserId::new(Principal::from_slice(&[228, 104, 142, 9, 133, 211, 135, 217, 129, 1]));
@@ -461,20 +462,45 @@ impl ChatEvents {
         }
     }
 
-    pub fn update_proposals(&mut self, user_id: UserId, updates: Vec<ProposalUpdate>, now: TimestampMillis) {
+    // Returns the proposals which were closed by these updates
+    pub fn update_proposals(
+        &mut self,
+        user_id: UserId,
+        updates: Vec<ProposalUpdate>,
+        now: TimestampMillis,
+    ) -> Vec<ClosedProposal> {
+        let mut closed = Vec::new();
+
         for update in updates {
             if let Some((message, event_index)) =
                 self.message_internal_mut(EventIndex::default(), None, update.message_id.into())
             {
                 if message.sender == user_id {
                     if let MessageContentInternal::GovernanceProposal(p) = &mut message.content {
+                        let was_open = p.proposal.status() == ProposalDecisionStatus::Open;
                         p.proposal.update_status(update.into(), now);
                         message.last_updated = Some(now);
                         self.last_updated_timestamps.mark_updated(None, event_index, now);
+
+                        if was_open
+                            && !matches!(
+                                p.proposal.status(),
+                                ProposalDecisionStatus::Open | ProposalDecisionStatus::Unspecified
+                            )
+                        {
+                            closed.push(ClosedProposal {
+                                event_index,
+                                message_index: message.message_index,
+                                proposal: p.proposal.clone(),
+                                votes: p.votes.clone(),
+                            });
+                        }
                     }
                 }
             }
         }
+
+        closed
     }
 
     pub fn add_reaction(&mut self, args: AddRemoveReactionArgs) -> AddRemoveReactionResult {
@@ -1408,6 +1434,36 @@ pub enum EndPollResult {
     UnableToEndPoll,
 }
 
+pub struct ClosedProposal {
+    pub event_index: EventIndex,
+    pub message_index: MessageIndex,
+    pub proposal: Proposal,
+    pub votes: HashMap<UserId, bool>,
+}
+
+impl ClosedProposal {
+    pub fn adopted(&self) -> bool {
+        matches!(
+            self.proposal.status(),
+            ProposalDecisionStatus::Adopted | ProposalDecisionStatus::Executed | ProposalDecisionStatus::Failed
+        )
+    }
+
+    // Summarises how the chat's members voted compared to the final decision
+    pub fn vote_summary(&self) -> String {
+        let adopt_votes = self.votes.values().filter(|v| **v).count();
+        let reject_votes = self.votes.len() - adopt_votes;
+        let (decision, agreed) = if self.adopted() { ("adopted", adopt_votes) } else { ("rejected", reject_votes) };
+
+        format!(
+            "Proposal {} \"{}\" has been {decision}.\n\nMembers voted {adopt_votes} to adopt and {reject_votes} to reject, {agreed} of {} in line with the final decision.",
+            self.proposal.id(),
+            self.proposal.title(),
+            self.votes.len(),
+        )
+    }
+}
+
 pub enum RecordProposalVoteResult {
     Success,
     AlreadyVoted(bool),
@@ -14,8 +14,10 @@ use types::{
     InvalidPollReason, MemberLeft, MembersRemoved, Message, MessageContent, MessageContentInitial, MessageId, MessageIndex,
     MessageMatch, MessagePinned, MessageUnpinned, MessagesResponse, Milliseconds, OptionUpdate, OptionalGroupPermissions,
     PermissionsChanged, PushEventResult, PushIfNotContains, Reaction, RoleChanged, Rules, SelectedGroupUpdates, ThreadPreview,
-    TimestampMillis, Timestamped, UpdatedRules, UserId, UsersBlocked, UsersInvited, Version, Versioned, VersionedRules,
+    TimestampMillis, Timestamped, TypingActivity, TypingIndicator, UpdatedRules, UserId, UsersBlocked, UsersInvited, Version,
+    Versioned, VersionedRules,
 };
+use typing_indicators::TypingIndicators;
 use utils::document_validation::validate_avatar;
 use utils::text_validation::{
     validate_description, validate_group_name, validate_rules, NameValidationError, RulesValidationError,
@@ -24,11 +26,13 @@ use utils::text_validation::{
 mod invited_users;
 mod members;
 mod mentions;
+mod proposal_vote_follows;
 mod roles;
 
 pub use invited_users::*;
 pub use members::*;
 pub use mentions::*;
+pub use proposal_vote_follows::*;
 pub use roles::*;
 
 #[derive(Serialize, Deserialize)]
@@ -50,6 +54,8 @@ pub struct GroupChatCore {
     pub gate: Timestamped<Option<AccessGate>>,
     pub invited_users: InvitedUsers,
     pub min_visible_indexes_for_new_members: Option<(EventIndex, MessageIndex)>,
+    #[serde(skip)]
+    pub typing_indicators: TypingIndicators,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -90,6 +96,7 @@ impl GroupChatCore {
             gate: Timestamped::new(gate, now),
             invited_users: InvitedUsers::default(),
             min_visible_indexes_for_new_members: None,
+            typing_indicators: TypingIndicators::default(),
         }
     }
 
@@ -532,7 +539,7 @@ impl GroupChatCore {
                         if matches!(message.content, MessageContentInternal::Deleted(_)) {
                             MessageHardDeleted
                         } else if user_id == message.sender
-                            || (deleted_by.deleted_by != message.sender && member.role.can_delete_messages(&self.permissions))
+                            || (deleted_by.deleted_by != message.sender && member.can_delete_messages(&self.permissions))
                         {
                             Success(Box::new(message.content.hydrate(Some(user_id))))
                         } else {
@@ -688,14 +695,14 @@ impl GroupChatCore {
         let permissions = &self.permissions;
 
         if thread_root_message_index.is_some() {
-            if !member.role.can_reply_in_thread(permissions) {
+            if !member.can_reply_in_thread(permissions) {
                 return NotAuthorized;
             }
-        } else if !member.role.can_send_messages(permissions) {
+        } else if !member.can_send_messages(permissions) {
             return NotAuthorized;
         }
 
-        if matches!(content, MessageContentInitial::Poll(_)) && !member.role.can_create_polls(permissions) {
+        if matches!(content, MessageContentInitial::Poll(_)) && !member.can_create_polls(permissions) {
             return NotAuthorized;
         }
 
@@ -713,7 +720,7 @@ impl GroupChatCore {
             .as_ref()
             .and_then(|r| self.get_user_being_replied_to(r, min_visible_event_index, thread_root_message_index));
 
-        let everyone_mentioned = member.role.can_mention_everyone(permissions) && is_everyone_mentioned(&content);
+        let everyone_mentioned = member.can_mention_everyone(permissions) && is_everyone_mentioned(&content);
 
         let push_message_args = PushMessageArgs {
             sender,
@@ -798,7 +805,7 @@ impl GroupChatCore {
             if member.suspended.value {
                 return UserSuspended;
             }
-            if !member.role.can_react_to_messages(&self.permissions) {
+            if !member.can_react_to_messages(&self.permissions) {
                 return NotAuthorized;
             }
 
@@ -833,7 +840,7 @@ impl GroupChatCore {
             if member.suspended.value {
                 return UserSuspended;
             }
-            if !member.role.can_react_to_messages(&self.permissions) {
+            if !member.can_react_to_messages(&self.permissions) {
                 return NotAuthorized;
             }
 
@@ -861,7 +868,7 @@ impl GroupChatCore {
             if member.suspended.value {
                 return UserSuspended;
             }
-            if !member.role.can_react_to_messages(&self.permissions) {
+            if !member.can_react_to_messages(&self.permissions) {
                 return NotAuthorized;
             }
 
@@ -889,7 +896,7 @@ impl GroupChatCore {
             }
 
             let min_visible_event_index = member.min_visible_event_index();
-            let is_admin = member.role.can_delete_messages(&self.permissions) || as_platform_moderator;
+            let is_admin = member.can_delete_messages(&self.permissions) || as_platform_moderator;
 
             let results = self.events.delete_messages(DeleteUndeleteMessagesArgs {
                 caller: user_id,
@@ -954,7 +961,7 @@ impl GroupChatCore {
 
             let results = self.events.undelete_messages(DeleteUndeleteMessagesArgs {
                 caller: user_id,
-                is_admin: member.role.can_delete_messages(&self.permissions),
+                is_admin: member.can_delete_messages(&self.permissions),
                 min_visible_event_index,
                 thread_root_message_index,
                 message_ids,
@@ -1023,7 +1030,7 @@ impl GroupChatCore {
             if member.suspended.value {
                 return UserSuspended;
             }
-            if !member.role.can_pin_messages(&self.permissions) {
+            if !member.can_pin_messages(&self.permissions) {
                 return NotAuthorized;
             }
 
@@ -1068,7 +1075,7 @@ impl GroupChatCore {
             if member.suspended.value {
                 return UserSuspended;
             }
-            if !member.role.can_pin_messages(&self.permissions) {
+            if !member.can_pin_messages(&self.permissions) {
                 return NotAuthorized;
             }
 
@@ -1118,7 +1125,7 @@ impl GroupChatCore {
             }
 
             // The original caller must be authorized to invite other users
-            if !self.is_public && !member.role.can_invite_users(&self.permissions) {
+            if !self.is_public && !member.can_invite_users(&self.permissions) {
                 return NotAuthorized;
             }
 
@@ -1348,7 +1355,7 @@ impl GroupChatCore {
             }
 
             let group_permissions = &self.permissions;
-            if !member.role.can_update_group(group_permissions)
+            if !member.can_update_group(group_permissions)
                 || (permissions.is_some() && !member.role.can_change_permissions())
                 || (public.is_some() && !member.role.can_change_group_visibility())
             {
@@ -1515,6 +1522,22 @@ impl GroupChatCore {
         result
     }
 
+    // Used when permissions are applied to the chat by its parent (eg. a community applying a channel
+    // category's default permissions) rather than by a member of the chat
+    pub fn set_permissions(&mut self, permissions: GroupPermissions, changed_by: UserId, now: TimestampMillis) {
+        let old_permissions = std::mem::replace(&mut self.permissions, permissions.clone());
+
+        self.events.push_main_event(
+            ChatEventInternal::PermissionsChanged(Box::new(PermissionsChanged {
+                old_permissions,
+                new_permissions: permissions,
+                changed_by,
+            })),
+            0,
+            now,
+        );
+    }
+
     pub fn check_rules(&self, member: &GroupMemberInternal) -> bool {
         !self.rules.enabled
             || member.is_bot
@@ -1524,6 +1547,63 @@ impl GroupChatCore {
                 .map_or(false, |accepted| accepted.value >= self.rules.text.version))
     }
 
+    // The members who haven't voted on the proposal in the given message but have voted on earlier
+    // proposals in this chat, so that they can be reminded before voting closes. Members who have
+    // never voted are only reminded if they follow the proposal's thread. Returns `None` if the
+    // message isn't a proposal.
+    pub fn proposal_reminder_recipients(&self, message_index: MessageIndex) -> Option<Vec<UserId>> {
+        let message = self.events.main_events_reader().message_internal(message_index.into())?;
+        let MessageContentInternal::GovernanceProposal(proposal) = &message.content else {
+            return None;
+        };
+
+        Some(
+            self.members
+                .iter()
+                .filter(|m| !m.is_bot && !m.suspended.value && !m.notifications_muted.value)
+                .filter(|m| !m.proposal_votes.is_empty() && !proposal.votes.contains_key(&m.user_id))
+                .map(|m| m.user_id)
+                .collect(),
+        )
+    }
+
+    pub fn set_typing(
+        &mut self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        activity: Option<TypingActivity>,
+        now: TimestampMillis,
+    ) -> SetTypingResult {
+        use SetTypingResult::*;
+
+        match self.members.get(&user_id) {
+            Some(member) if member.suspended.value => UserSuspended,
+            Some(_) => {
+                self.typing_indicators.set(user_id, thread_root_message_index, activity, now);
+                Success
+            }
+            None => UserNotInGroup,
+        }
+    }
+
+    // Typing indicators are only visible to members, even in public groups
+    pub fn typing_indicators(
+        &self,
+        user_id: UserId,
+        updated_since: Option<TimestampMillis>,
+        now: TimestampMillis,
+    ) -> TypingIndicatorsResult {
+        use TypingIndicatorsResult::*;
+
+        if self.members.get(&user_id).is_none() {
+            UserNotInGroup
+        } else if let Some(indicators) = self.typing_indicators.get(updated_since, now) {
+            Success(indicators)
+        } else {
+            SuccessNoUpdates
+        }
+    }
+
     pub fn follow_thread(
         &mut self,
         user_id: UserId,
@@ -1846,6 +1926,18 @@ pub struct InvitedUsersSuccess {
     pub group_name: String,
 }
 
+pub enum SetTypingResult {
+    Success,
+    UserNotInGroup,
+    UserSuspended,
+}
+
+pub enum TypingIndicatorsResult {
+    Success(Vec<TypingIndicator>),
+    SuccessNoUpdates,
+    UserNotInGroup,
+}
+
 pub enum FollowThreadResult {
     Success,
     AlreadyFollowing,
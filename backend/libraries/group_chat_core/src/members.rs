@@ -8,8 +8,8 @@ use std::collections::hash_map::Entry::Vacant;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Formatter;
 use types::{
-    is_default, is_empty_btreemap, is_empty_hashset, is_empty_slice, EventIndex, GroupMember, GroupPermissions,
-    HydratedMention, MessageIndex, TimestampMillis, Timestamped, UserId, Version, MAX_RETURNED_MENTIONS,
+    is_default, is_empty_btreemap, is_empty_hashset, is_empty_slice, EventIndex, GroupMember, GroupPermission,
+    GroupPermissions, HydratedMention, MessageIndex, TimestampMillis, Timestamped, UserId, Version, MAX_RETURNED_MENTIONS,
 };
 
 const MAX_MEMBERS_PER_GROUP: u32 = 100_000;
@@ -41,6 +41,8 @@ impl GroupMembers {
             suspended: Timestamped::default(),
             rules_accepted: Some(Timestamped::new(Version::zero(), now)),
             is_bot,
+            granted_permissions: HashSet::new(),
+            denied_permissions: HashSet::new(),
         };
 
         GroupMembers {
@@ -82,6 +84,8 @@ impl GroupMembers {
                         suspended: Timestamped::default(),
                         rules_accepted: None,
                         is_bot,
+                        granted_permissions: HashSet::new(),
+                        denied_permissions: HashSet::new(),
                     };
                     e.insert(member.clone());
                     AddResult::Success(member)
@@ -260,6 +264,18 @@ impl GroupMembers {
             p.threads.insert(root_message_index);
         }
     }
+
+    pub fn set_permission_overrides(
+        &mut self,
+        user_id: &UserId,
+        granted: HashSet<GroupPermission>,
+        denied: HashSet<GroupPermission>,
+    ) {
+        if let Some(p) = self.get_mut(user_id) {
+            p.granted_permissions = granted;
+            p.denied_permissions = denied;
+        }
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -308,6 +324,14 @@ pub struct GroupMemberInternal {
     pub rules_accepted: Option<Timestamped<Version>>,
     #[serde(rename = "b", default, skip_serializing_if = "is_default")]
     pub is_bot: bool,
+    // Permissions granted by the custom roles the member has been assigned within the community or
+    // by the channel's user group overrides
+    #[serde(rename = "gp", default, skip_serializing_if = "is_empty_hashset")]
+    pub granted_permissions: HashSet<GroupPermission>,
+    // Permissions denied by the channel's user group overrides, these take precedence over both the
+    // member's role and their granted permissions but never apply to owners
+    #[serde(rename = "dp", default, skip_serializing_if = "is_empty_hashset")]
+    pub denied_permissions: HashSet<GroupPermission>,
 
     #[serde(rename = "me", default, skip_serializing_if = "is_default")]
     min_visible_event_index: EventIndex,
@@ -352,6 +376,72 @@ impl GroupMemberInternal {
             self.rules_accepted = Some(Timestamped::new(version, now));
         }
     }
+
+    pub fn can_update_group(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(self.role.can_update_group(permissions), GroupPermission::UpdateGroup)
+    }
+
+    pub fn can_add_members(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(self.role.can_add_members(permissions), GroupPermission::AddMembers)
+    }
+
+    pub fn can_invite_users(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(self.role.can_invite_users(permissions), GroupPermission::InviteUsers)
+    }
+
+    pub fn can_delete_messages(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(self.role.can_delete_messages(permissions), GroupPermission::DeleteMessages)
+    }
+
+    pub fn can_pin_messages(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(self.role.can_pin_messages(permissions), GroupPermission::PinMessages)
+    }
+
+    pub fn can_create_polls(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(self.role.can_create_polls(permissions), GroupPermission::CreatePolls)
+    }
+
+    pub fn can_send_messages(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(self.role.can_send_messages(permissions), GroupPermission::SendMessages)
+    }
+
+    pub fn can_react_to_messages(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(self.role.can_react_to_messages(permissions), GroupPermission::ReactToMessages)
+    }
+
+    pub fn can_reply_in_thread(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(self.role.can_reply_in_thread(permissions), GroupPermission::ReplyInThread)
+    }
+
+    pub fn can_mention_everyone(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(
+            self.role.can_mention_everyone(permissions),
+            GroupPermission::MentionAllMembers,
+        )
+    }
+
+    pub fn has_permission(&self, permission: GroupPermission, permissions: &GroupPermissions) -> bool {
+        match permission {
+            GroupPermission::UpdateGroup => self.can_update_group(permissions),
+            GroupPermission::AddMembers => self.can_add_members(permissions),
+            GroupPermission::InviteUsers => self.can_invite_users(permissions),
+            GroupPermission::DeleteMessages => self.can_delete_messages(permissions),
+            GroupPermission::PinMessages => self.can_pin_messages(permissions),
+            GroupPermission::CreatePolls => self.can_create_polls(permissions),
+            GroupPermission::SendMessages => self.can_send_messages(permissions),
+            GroupPermission::ReactToMessages => self.can_react_to_messages(permissions),
+            GroupPermission::ReplyInThread => self.can_reply_in_thread(permissions),
+            GroupPermission::MentionAllMembers => self.can_mention_everyone(permissions),
+        }
+    }
+
+    fn is_permitted(&self, permitted_by_role: bool, permission: GroupPermission) -> bool {
+        if self.denied_permissions.contains(&permission) && !self.role.is_owner() {
+            false
+        } else {
+            permitted_by_role || self.granted_permissions.contains(&permission)
+        }
+    }
 }
 
 impl From<GroupMemberInternal> for GroupMember {
@@ -417,7 +507,36 @@ mod tests {
     use crate::{GroupMemberInternal, Mentions};
     use candid::Principal;
     use std::collections::{BTreeMap, HashSet};
-    use types::{Timestamped, Version};
+    use types::{GroupPermission, GroupPermissions, Timestamped, Version};
+
+    #[test]
+    fn denied_permissions_take_precedence_except_for_owners() {
+        let permissions = GroupPermissions::default();
+        let mut member = GroupMemberInternal {
+            user_id: Principal::from_text("4bkt6-4aaaa-aaaaf-aaaiq-cai").unwrap().into(),
+            date_added: 1,
+            role: GroupRoleInternal::Member,
+            notifications_muted: Timestamped::new(false, 1),
+            mentions: Mentions::default(),
+            threads: HashSet::new(),
+            unfollowed_threads: Vec::new(),
+            proposal_votes: BTreeMap::new(),
+            suspended: Timestamped::default(),
+            min_visible_event_index: 0.into(),
+            min_visible_message_index: 0.into(),
+            rules_accepted: None,
+            is_bot: false,
+            granted_permissions: HashSet::from([GroupPermission::PinMessages, GroupPermission::SendMessages]),
+            denied_permissions: HashSet::from([GroupPermission::SendMessages]),
+        };
+
+        assert!(member.can_pin_messages(&permissions));
+        assert!(!member.can_send_messages(&permissions));
+        assert!(member.can_react_to_messages(&permissions));
+
+        member.role = GroupRoleInternal::Owner;
+        assert!(member.can_send_messages(&permissions));
+    }
 
     #[test]
     fn serialize_with_max_defaults() {
@@ -435,6 +554,8 @@ mod tests {
             min_visible_message_index: 0.into(),
             rules_accepted: Some(Timestamped::new(Version::zero(), 1)),
             is_bot: false,
+            granted_permissions: HashSet::new(),
+            denied_permissions: HashSet::new(),
         };
 
         let member_bytes = msgpack::serialize_then_unwrap(&member);
@@ -466,6 +587,8 @@ mod tests {
             min_visible_message_index: 1.into(),
             rules_accepted: Some(Timestamped::new(Version::zero(), 1)),
             is_bot: true,
+            granted_permissions: HashSet::new(),
+            denied_permissions: HashSet::new(),
         };
 
         let member_bytes = msgpack::serialize_then_unwrap(&member);
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use types::UserId;
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(from = "ProposalVoteFollowsTrimmed")]
+pub struct ProposalVoteFollows {
+    // Maps each follower to the member whose proposal votes they copy
+    followees: HashMap<UserId, UserId>,
+    // The reverse of `followees`, mapping each member to their direct followers
+    #[serde(skip)]
+    followers: HashMap<UserId, HashSet<UserId>>,
+}
+
+pub enum SetFolloweeResult {
+    Success,
+    CannotFollowSelf,
+    CircularFollow,
+}
+
+impl ProposalVoteFollows {
+    pub fn set_followee(&mut self, follower: UserId, followee: Option<UserId>) -> SetFolloweeResult {
+        if let Some(followee) = followee {
+            if followee == follower {
+                return SetFolloweeResult::CannotFollowSelf;
+            }
+
+            // Walk up the chain of followees to ensure the follower isn't already being followed by them
+            let mut next = Some(followee);
+            while let Some(user_id) = next {
+                if user_id == follower {
+                    return SetFolloweeResult::CircularFollow;
+                }
+                next = self.followees.get(&user_id).copied();
+            }
+
+            if let Some(previous) = self.followees.insert(follower, followee) {
+                self.remove_from_followers(previous, follower);
+            }
+            self.followers.entry(followee).or_default().insert(follower);
+        } else if let Some(previous) = self.followees.remove(&follower) {
+            self.remove_from_followers(previous, follower);
+        }
+        SetFolloweeResult::Success
+    }
+
+    // Returns everyone who follows the user's votes, either directly or via other followers
+    pub fn followers_of(&self, user_id: UserId) -> Vec<UserId> {
+        let mut followers = Vec::new();
+        let mut visited = HashSet::from([user_id]);
+        let mut queue = VecDeque::from([user_id]);
+
+        while let Some(next) = queue.pop_front() {
+            for follower in self.followers.get(&next).into_iter().flatten() {
+                if visited.insert(*follower) {
+                    followers.push(*follower);
+                    queue.push_back(*follower);
+                }
+            }
+        }
+
+        followers
+    }
+
+    fn remove_from_followers(&mut self, followee: UserId, follower: UserId) {
+        if let Some(followers) = self.followers.get_mut(&followee) {
+            followers.remove(&follower);
+            if followers.is_empty() {
+                self.followers.remove(&followee);
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ProposalVoteFollowsTrimmed {
+    followees: HashMap<UserId, UserId>,
+}
+
+impl From<ProposalVoteFollowsTrimmed> for ProposalVoteFollows {
+    fn from(value: ProposalVoteFollowsTrimmed) -> Self {
+        let mut follows = ProposalVoteFollows {
+            followees: value.followees,
+            ..Default::default()
+        };
+
+        for (follower, followee) in follows.followees.iter() {
+            follows.followers.entry(*followee).or_default().insert(*follower);
+        }
+
+        follows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::CanisterId;
+
+    fn user(id: u8) -> UserId {
+        UserId::new(CanisterId::from_slice(&[id]))
+    }
+
+    #[test]
+    fn followers_are_resolved_transitively() {
+        let mut follows = ProposalVoteFollows::default();
+
+        assert!(matches!(
+            follows.set_followee(user(1), Some(user(1))),
+            SetFolloweeResult::CannotFollowSelf
+        ));
+        assert!(matches!(
+            follows.set_followee(user(2), Some(user(1))),
+            SetFolloweeResult::Success
+        ));
+        assert!(matches!(
+            follows.set_followee(user(3), Some(user(2))),
+            SetFolloweeResult::Success
+        ));
+        assert!(matches!(
+            follows.set_followee(user(1), Some(user(3))),
+            SetFolloweeResult::CircularFollow
+        ));
+
+        let mut followers = follows.followers_of(user(1));
+        followers.sort();
+        assert_eq!(followers, vec![user(2), user(3)]);
+
+        assert!(matches!(follows.set_followee(user(2), None), SetFolloweeResult::Success));
+        assert!(follows.followers_of(user(1)).is_empty());
+        assert_eq!(follows.followers_of(user(2)), vec![user(3)]);
+
+        assert!(matches!(
+            follows.set_followee(user(3), Some(user(1))),
+            SetFolloweeResult::Success
+        ));
+        assert!(follows.followers_of(user(2)).is_empty());
+        assert_eq!(follows.followers_of(user(1)), vec![user(3)]);
+    }
+}
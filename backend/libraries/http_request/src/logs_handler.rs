@@ -1,14 +1,26 @@
 use crate::build_response;
-use canister_logger::LogEntry;
+use canister_logger::LogsPage;
 use std::io::Write;
-use types::{HttpResponse, TimestampMillis};
+use types::{HeaderField, HttpResponse};
 
-pub fn encode_logs(entries: Vec<LogEntry>, since: TimestampMillis) -> HttpResponse {
+pub fn encode_logs(page: LogsPage) -> HttpResponse {
     let mut body = Vec::new();
 
-    for entry in entries.into_iter().filter(|e| e.timestamp > since) {
+    for entry in page.entries {
         writeln!(&mut body, "{}", entry.message).unwrap();
     }
 
-    build_response(body, "text/plain")
+    let mut response = build_response(body, "text/plain");
+    if let Some(next_cursor) = page.next_cursor {
+        response
+            .headers
+            .push(HeaderField("X-Next-Cursor".to_string(), next_cursor.to_string()));
+    }
+    if page.overflow_append_failures > 0 {
+        response.headers.push(HeaderField(
+            "X-Log-Overflow-Append-Failures".to_string(),
+            page.overflow_append_failures.to_string(),
+        ));
+    }
+    response
 }
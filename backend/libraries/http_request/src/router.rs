@@ -1,13 +1,15 @@
+use canister_logger::LogQuery;
 use std::str::FromStr;
-use types::{ChannelId, FileId, TimestampMillis};
+use tracing::Level;
+use types::{ChannelId, FileId};
 
 pub enum Route {
     Avatar(Option<u128>),
     Banner(Option<u128>),
     ChannelAvatar((ChannelId, Option<u128>)),
     File(u128),
-    Logs(Option<TimestampMillis>),
-    Traces(Option<TimestampMillis>),
+    Logs(LogQuery),
+    Traces(LogQuery),
     Metrics,
     Other(String, String),
 }
@@ -47,14 +49,8 @@ pub fn extract_route(path: &str) -> Route {
                 }
             }
         }
-        "logs" => {
-            let since = parts.get(1).and_then(|p| u64::from_str(p).ok());
-            return Route::Logs(since);
-        }
-        "trace" => {
-            let since = parts.get(1).and_then(|p| u64::from_str(p).ok());
-            return Route::Traces(since);
-        }
+        "logs" => return Route::Logs(extract_log_query(parts.get(1).copied(), qs)),
+        "trace" => return Route::Traces(extract_log_query(parts.get(1).copied(), qs)),
         "metrics" => return Route::Metrics,
         _ => (),
     }
@@ -62,6 +58,63 @@ pub fn extract_route(path: &str) -> Route {
     Route::Other(path.to_string(), qs.to_string())
 }
 
+// Supports `/logs/{since}` as well as filtering via the querystring, eg.
+// `/logs?level=warn&target=group_canister_impl&contains=timeout&user_id=..&correlation_id=..&cursor=..&limit=..`
+fn extract_log_query(since: Option<&str>, qs: &str) -> LogQuery {
+    let mut query = LogQuery {
+        since: since.and_then(|p| u64::from_str(p).ok()),
+        ..Default::default()
+    };
+
+    for (key, value) in qs.split('&').filter_map(|p| p.split_once('=')) {
+        let value = decode_query_value(value);
+        if value.is_empty() {
+            continue;
+        }
+        match key {
+            "since" => query.since = u64::from_str(&value).ok(),
+            "level" => query.level = Level::from_str(&value).ok(),
+            "target" => query.target = Some(value),
+            "contains" => query.contains = Some(value),
+            "user_id" => query.user_id = Some(value),
+            "correlation_id" => query.correlation_id = Some(value),
+            "cursor" => query.cursor = u64::from_str(&value).ok(),
+            "limit" => query.limit = usize::from_str(&value).ok(),
+            _ => {}
+        }
+    }
+
+    query
+}
+
+fn decode_query_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                match std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+                {
+                    Some(b) => {
+                        decoded.push(b);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +133,31 @@ mod tests {
         assert!(matches!(extract_route("/logs/1633649663014109000"), Route::Logs(_)));
     }
 
+    #[test]
+    fn logs_with_filters() {
+        match extract_route("/logs?level=warn&contains=call%20failed&cursor=25&limit=10") {
+            Route::Logs(query) => {
+                assert_eq!(query.level, Some(Level::WARN));
+                assert_eq!(query.contains.as_deref(), Some("call failed"));
+                assert_eq!(query.cursor, Some(25));
+                assert_eq!(query.limit, Some(10));
+                assert!(query.since.is_none());
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn trace_since_from_path() {
+        match extract_route("/trace/1633649663014?user_id=3skqk-iqaaa-aaaaf-aaa3q-cai") {
+            Route::Traces(query) => {
+                assert_eq!(query.since, Some(1633649663014));
+                assert_eq!(query.user_id.as_deref(), Some("3skqk-iqaaa-aaaaf-aaa3q-cai"));
+            }
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn other() {
         assert!(matches!(extract_route("blah"), Route::Other(_, _)));
@@ -52,6 +52,11 @@ pub enum InstructionCountFunctionId {
     Unknown = 0,
     PreUpgrade = 1,
     PostUpgrade = 2,
+    SendMessage = 3,
+    AddReaction = 4,
+    RegisterPollVote = 5,
+    JoinGroup = 6,
+    JoinChannel = 7,
 }
 
 fn init_log(index_memory: Memory, data_memory: Memory) -> StableLog<InstructionCountEntry, Memory, Memory> {
@@ -1,4 +1,5 @@
-use serde::{de::DeserializeOwned, Serialize};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::io::{Read, Write};
 
@@ -19,3 +20,38 @@ where
     let mut deserializer = rmp_serde::Deserializer::new(reader);
     T::deserialize(&mut deserializer)
 }
+
+// Returns the number of bytes taken up by the first value in `reader`, without deserializing it
+pub fn serialized_length<R: Read>(reader: R) -> Result<u64, impl Error> {
+    let mut reader = CountingReader { inner: reader, count: 0 };
+    let mut deserializer = rmp_serde::Deserializer::new(&mut reader);
+    IgnoredAny::deserialize(&mut deserializer).map(|_| reader.count)
+}
+
+struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.count += count as u64;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_length_ignores_trailing_bytes() {
+        let mut bytes = Vec::new();
+        serialize((vec![1u32, 2, 3], "abc"), &mut bytes).unwrap();
+        let length = bytes.len() as u64;
+        bytes.extend([0; 10]);
+
+        assert_eq!(serialized_length(bytes.as_slice()).unwrap(), length);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{BuildVersion, CanisterId};
+use crate::{BuildVersion, CanisterId, Hash, UpgradeRolloutPolicy};
 use candid::CandidType;
 use human_readable::{HumanReadablePrincipal, ToHumanReadable};
 use serde::{Deserialize, Serialize};
@@ -10,6 +10,74 @@ pub struct UpgradeCanisterWasmArgs {
     pub wasm: CanisterWasm,
     pub filter: Option<UpgradesFilter>,
     pub use_for_new_canisters: Option<bool>,
+    #[serde(default)]
+    pub rollout: Option<UpgradeRolloutPolicy>,
+    // If set, `wasm.module` must be empty and the module is taken from the wasm which was uploaded in
+    // chunks and committed with this hash
+    #[serde(default)]
+    pub uploaded_wasm_hash: Option<Hash>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RollbackCanisterWasmArgs {
+    // If not set, the most recent previous version is used
+    pub version: Option<BuildVersion>,
+    pub filter: Option<UpgradesFilter>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct StartWasmUploadArgs {
+    pub version: BuildVersion,
+    pub total_size: u64,
+    pub sha256: Hash,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone)]
+pub struct PushWasmChunkArgs {
+    pub sha256: Hash,
+    pub index: u32,
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CommitWasmUploadArgs {
+    pub sha256: Hash,
+}
+
+// Used by index canisters to pass wasms on to their local indexes, since a whole wasm may exceed the
+// limit on the size of cross-subnet messages. Chunk 0 starts the upload and the upload is committed
+// once the final chunk is received.
+#[derive(CandidType, Serialize, Deserialize, Clone)]
+pub struct WasmChunk {
+    pub version: BuildVersion,
+    pub total_size: u64,
+    pub sha256: Hash,
+    pub index: u32,
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+impl Debug for WasmChunk {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmChunk")
+            .field("version", &self.version)
+            .field("total_size", &self.total_size)
+            .field("sha256", &self.sha256)
+            .field("index", &self.index)
+            .field("byte_length", &self.bytes.len())
+            .finish()
+    }
+}
+
+impl Debug for PushWasmChunkArgs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PushWasmChunkArgs")
+            .field("sha256", &self.sha256)
+            .field("index", &self.index)
+            .field("byte_length", &self.bytes.len())
+            .finish()
+    }
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone)]
@@ -48,6 +116,14 @@ pub struct HumanReadableUpgradeCanisterWasmArgs {
     wasm: CanisterWasmTrimmed,
     filter: Option<HumanReadableUpgradesFilter>,
     use_for_new_canisters: Option<bool>,
+    rollout: Option<HumanReadableUpgradeRolloutPolicy>,
+    uploaded_wasm_hash: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct HumanReadableRollbackCanisterWasmArgs {
+    version: Option<BuildVersion>,
+    filter: Option<HumanReadableUpgradesFilter>,
 }
 
 #[derive(Serialize)]
@@ -65,10 +141,27 @@ impl ToHumanReadable for UpgradeCanisterWasmArgs {
             wasm: (&self.wasm).into(),
             filter: self.filter.as_ref().map(|f| f.into()),
             use_for_new_canisters: self.use_for_new_canisters,
+            rollout: self.rollout.as_ref().map(|r| r.into()),
+            uploaded_wasm_hash: self.uploaded_wasm_hash.map(|h| hex(&h)),
         }
     }
 }
 
+impl ToHumanReadable for RollbackCanisterWasmArgs {
+    type Target = HumanReadableRollbackCanisterWasmArgs;
+
+    fn to_human_readable(&self) -> Self::Target {
+        HumanReadableRollbackCanisterWasmArgs {
+            version: self.version,
+            filter: self.filter.as_ref().map(|f| f.into()),
+        }
+    }
+}
+
+fn hex(hash: &Hash) -> String {
+    hash.iter().map(|b| format!("{b:02x}")).collect()
+}
+
 impl From<&CanisterWasm> for CanisterWasmTrimmed {
     fn from(value: &CanisterWasm) -> Self {
         CanisterWasmTrimmed {
@@ -93,3 +186,24 @@ impl From<&UpgradesFilter> for HumanReadableUpgradesFilter {
         }
     }
 }
+
+#[derive(Serialize)]
+struct HumanReadableUpgradeRolloutPolicy {
+    canaries: Vec<HumanReadablePrincipal>,
+    stage_percentages: Vec<u8>,
+    soak_duration: u64,
+    max_failures_per_stage: u32,
+    max_low_cycles_notifications_per_stage: u32,
+}
+
+impl From<&UpgradeRolloutPolicy> for HumanReadableUpgradeRolloutPolicy {
+    fn from(value: &UpgradeRolloutPolicy) -> Self {
+        HumanReadableUpgradeRolloutPolicy {
+            canaries: value.canaries.iter().copied().map(|c| c.into()).collect(),
+            stage_percentages: value.stage_percentages.clone(),
+            soak_duration: value.soak_duration,
+            max_failures_per_stage: value.max_failures_per_stage,
+            max_low_cycles_notifications_per_stage: value.max_low_cycles_notifications_per_stage,
+        }
+    }
+}
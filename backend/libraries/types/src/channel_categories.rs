@@ -0,0 +1,12 @@
+use crate::{ChannelId, GroupPermissions};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChannelCategory {
+    pub category_id: u32,
+    pub name: String,
+    pub collapsed_by_default: bool,
+    pub channels: Vec<ChannelId>,
+    pub default_permissions: Option<GroupPermissions>,
+}
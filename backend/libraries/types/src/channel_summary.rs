@@ -1,6 +1,7 @@
 use crate::{
     AccessGate, ChannelId, ChatMetrics, EventIndex, EventWrapper, GroupCanisterThreadDetails, GroupPermissions, GroupRole,
     GroupSubtype, HydratedMention, Message, MessageIndex, Milliseconds, OptionUpdate, TimestampMillis,
+    UserGroupPermissionOverride,
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,10 @@ pub struct CommunityCanisterChannelSummary {
     pub date_last_pinned: Option<TimestampMillis>,
     pub events_ttl: Option<Milliseconds>,
     pub gate: Option<AccessGate>,
+    #[serde(default)]
+    pub user_group_permission_overrides: Vec<UserGroupPermissionOverride>,
+    #[serde(default)]
+    pub is_announcement: bool,
     pub membership: Option<ChannelMembership>,
 }
 
@@ -59,6 +64,8 @@ pub struct CommunityCanisterChannelSummaryUpdates {
     pub date_last_pinned: Option<TimestampMillis>,
     pub events_ttl: OptionUpdate<Milliseconds>,
     pub gate: OptionUpdate<AccessGate>,
+    #[serde(default)]
+    pub user_group_permission_overrides: Option<Vec<UserGroupPermissionOverride>>,
     pub membership: Option<ChannelMembershipUpdates>,
 }
 
@@ -1,3 +1,4 @@
+use crate::{GroupPermission, UserId};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +19,8 @@ pub struct CommunityPermissions {
     pub create_private_channel: CommunityPermissionRole,
     #[serde(default = "admins")]
     pub manage_user_groups: CommunityPermissionRole,
+    #[serde(default = "admins")]
+    pub manage_channel_categories: CommunityPermissionRole,
 }
 
 fn admins() -> CommunityPermissionRole {
@@ -33,6 +36,7 @@ pub struct OptionalCommunityPermissions {
     pub create_public_channel: Option<CommunityPermissionRole>,
     pub create_private_channel: Option<CommunityPermissionRole>,
     pub manage_user_groups: Option<CommunityPermissionRole>,
+    pub manage_channel_categories: Option<CommunityPermissionRole>,
 }
 
 impl Default for CommunityPermissions {
@@ -45,6 +49,7 @@ impl Default for CommunityPermissions {
             create_public_channel: CommunityPermissionRole::Admins,
             create_private_channel: CommunityPermissionRole::Admins,
             manage_user_groups: CommunityPermissionRole::Admins,
+            manage_channel_categories: CommunityPermissionRole::Admins,
         }
     }
 }
@@ -56,6 +61,39 @@ pub enum CommunityPermissionRole {
     Members,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CommunityPermission {
+    UpdateDetails,
+    InviteUsers,
+    CreatePublicChannel,
+    CreatePrivateChannel,
+    ManageUserGroups,
+    ManageChannelCategories,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CustomRoleDetails {
+    pub role_id: u32,
+    pub name: String,
+    pub community_permissions: Vec<CommunityPermission>,
+    pub channel_permissions: Vec<GroupPermission>,
+    pub users: Vec<UserId>,
+    pub user_groups: Vec<u32>,
+}
+
+impl CommunityPermissions {
+    pub fn role_for(&self, permission: CommunityPermission) -> CommunityPermissionRole {
+        match permission {
+            CommunityPermission::UpdateDetails => self.update_details,
+            CommunityPermission::InviteUsers => self.invite_users,
+            CommunityPermission::CreatePublicChannel => self.create_public_channel,
+            CommunityPermission::CreatePrivateChannel => self.create_private_channel,
+            CommunityPermission::ManageUserGroups => self.manage_user_groups,
+            CommunityPermission::ManageChannelCategories => self.manage_channel_categories,
+        }
+    }
+}
+
 impl CommunityRole {
     pub fn is_owner(&self) -> bool {
         matches!(self, CommunityRole::Owner)
@@ -109,6 +147,10 @@ impl CommunityRole {
         self.is_permitted(permissions.manage_user_groups)
     }
 
+    pub fn can_manage_channel_categories(&self, permissions: &CommunityPermissions) -> bool {
+        self.is_permitted(permissions.manage_channel_categories)
+    }
+
     pub fn can_delete_community(&self) -> bool {
         self.has_owner_rights()
     }
@@ -1,7 +1,8 @@
 use crate::user_groups::UserGroupSummary;
 use crate::{
-    AccessGate, ChannelId, ChatMetrics, CommunityCanisterChannelSummary, CommunityCanisterChannelSummaryUpdates, CommunityId,
-    CommunityPermissions, CommunityRole, EventIndex, FrozenGroupInfo, OptionUpdate, TimestampMillis,
+    AccessGate, ChannelCategory, ChannelId, ChatMetrics, CommunityCanisterChannelSummary,
+    CommunityCanisterChannelSummaryUpdates, CommunityId, CommunityPermissions, CommunityRole, CustomRoleDetails, EventIndex,
+    FrozenGroupInfo, OptionUpdate, TimestampMillis,
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -24,6 +25,10 @@ pub struct CommunityCanisterCommunitySummary {
     pub channels: Vec<CommunityCanisterChannelSummary>,
     pub membership: Option<CommunityMembership>,
     pub user_groups: Vec<UserGroupSummary>,
+    #[serde(default)]
+    pub custom_roles: Vec<CustomRoleDetails>,
+    #[serde(default)]
+    pub channel_categories: Vec<ChannelCategory>,
     pub metrics: ChatMetrics,
 }
 
@@ -33,6 +38,8 @@ pub struct CommunityMembership {
     pub role: CommunityRole,
     pub rules_accepted: bool,
     pub display_name: Option<String>,
+    #[serde(default)]
+    pub custom_roles: Vec<u32>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -56,6 +63,16 @@ pub struct CommunityCanisterCommunitySummaryUpdates {
     pub membership: Option<CommunityMembershipUpdates>,
     pub user_groups: Vec<UserGroupSummary>,
     pub user_groups_deleted: Vec<u32>,
+    #[serde(default)]
+    pub custom_roles: Vec<CustomRoleDetails>,
+    #[serde(default)]
+    pub custom_roles_deleted: Vec<u32>,
+    #[serde(default)]
+    pub channel_categories: Vec<ChannelCategory>,
+    #[serde(default)]
+    pub channel_categories_deleted: Vec<u32>,
+    #[serde(default)]
+    pub channel_category_order: Option<Vec<u32>>,
     pub metrics: Option<ChatMetrics>,
 }
 
@@ -64,4 +81,6 @@ pub struct CommunityMembershipUpdates {
     pub role: Option<CommunityRole>,
     pub rules_accepted: Option<bool>,
     pub display_name: OptionUpdate<String>,
+    #[serde(default)]
+    pub custom_roles: Option<Vec<u32>>,
 }
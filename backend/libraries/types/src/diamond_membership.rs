@@ -8,7 +8,7 @@ pub struct DiamondMembershipDetails {
     pub recurring: Option<DiamondMembershipPlanDuration>,
 }
 
-#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
 #[repr(u8)]
 pub enum DiamondMembershipPlanDuration {
     OneMonth = 1,
@@ -1,4 +1,4 @@
-use crate::{FileId, Hash, TimestampMillis};
+use crate::{AccessorId, FileId, Hash, TimestampMillis};
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +8,11 @@ pub struct FileAdded {
     pub hash: Hash,
     pub size: u64,
     pub meta_data: FileMetaData,
+    // These are optional so that buckets and the index can be upgraded independently
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    #[serde(default)]
+    pub accessors: Option<Vec<AccessorId>>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -83,6 +83,22 @@ pub enum GroupPermissionRole {
     Members,
 }
 
+// The individual permissions which can be granted to community members via custom roles, on top of
+// those granted by their role within each channel
+#[derive(CandidType, Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum GroupPermission {
+    UpdateGroup,
+    AddMembers,
+    InviteUsers,
+    DeleteMessages,
+    PinMessages,
+    CreatePolls,
+    SendMessages,
+    ReactToMessages,
+    ReplyInThread,
+    MentionAllMembers,
+}
+
 fn group_permission_role_owner() -> GroupPermissionRole {
     GroupPermissionRole::Owner
 }
@@ -8,6 +8,7 @@ mod bots;
 mod build_version;
 mod canister_upgrade_status;
 mod canister_wasm;
+mod channel_categories;
 mod channel_summary;
 mod chat;
 mod chat_id;
@@ -47,6 +48,7 @@ mod notifications;
 mod option;
 mod phone_number;
 mod polls;
+mod presence;
 mod proposals;
 mod range_set;
 mod reactions;
@@ -58,6 +60,7 @@ mod suspension_duration;
 mod thread_preview;
 mod thread_summary;
 mod timestamped;
+mod upgrade_rollout;
 mod user;
 mod user_groups;
 mod user_summary;
@@ -70,6 +73,7 @@ pub use bots::*;
 pub use build_version::*;
 pub use canister_upgrade_status::*;
 pub use canister_wasm::*;
+pub use channel_categories::*;
 pub use channel_summary::*;
 pub use chat::*;
 pub use chat_id::*;
@@ -109,6 +113,7 @@ pub use notifications::*;
 pub use option::*;
 pub use phone_number::*;
 pub use polls::*;
+pub use presence::*;
 pub use proposals::*;
 pub use reactions::*;
 pub use referral_codes::*;
@@ -119,6 +124,7 @@ pub use suspension_duration::*;
 pub use thread_preview::*;
 pub use thread_summary::*;
 pub use timestamped::*;
+pub use upgrade_rollout::*;
 pub use user::*;
 pub use user_groups::*;
 pub use user_summary::*;
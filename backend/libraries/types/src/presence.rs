@@ -0,0 +1,27 @@
+use crate::{MessageIndex, Milliseconds, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LastSeenVisibility {
+    #[default]
+    Everyone,
+    // Only users in the user's contacts can see when they were last online
+    Contacts,
+    Nobody,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TypingActivity {
+    Typing,
+    RecordingAudio,
+    RecordingVideo,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TypingIndicator {
+    pub user_id: UserId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub activity: TypingActivity,
+    pub expires_in: Milliseconds,
+}
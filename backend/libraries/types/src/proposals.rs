@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 pub enum Proposal {
     NNS(NnsProposal),
     SNS(SnsProposal),
+    Generic(GenericProposal),
 }
 
 impl Proposal {
@@ -14,13 +15,14 @@ impl Proposal {
     }
 
     pub fn is_sns(&self) -> bool {
-        !self.is_nns()
+        matches!(self, Proposal::SNS(_))
     }
 
     pub fn id(&self) -> ProposalId {
         match self {
             Proposal::NNS(p) => p.id,
             Proposal::SNS(p) => p.id,
+            Proposal::Generic(p) => p.id,
         }
     }
 
@@ -28,6 +30,7 @@ impl Proposal {
         match self {
             Proposal::NNS(p) => p.created,
             Proposal::SNS(p) => p.created,
+            Proposal::Generic(p) => p.created,
         }
     }
 
@@ -35,6 +38,7 @@ impl Proposal {
         match self {
             Proposal::NNS(p) => &p.title,
             Proposal::SNS(p) => &p.title,
+            Proposal::Generic(p) => &p.title,
         }
     }
 
@@ -42,6 +46,7 @@ impl Proposal {
         match self {
             Proposal::NNS(p) => &p.summary,
             Proposal::SNS(p) => &p.summary,
+            Proposal::Generic(p) => &p.summary,
         }
     }
 
@@ -49,6 +54,7 @@ impl Proposal {
         match self {
             Proposal::NNS(p) => p.status,
             Proposal::SNS(p) => p.status,
+            Proposal::Generic(p) => p.status,
         }
     }
 
@@ -56,6 +62,7 @@ impl Proposal {
         match self {
             Proposal::NNS(p) => p.reward_status,
             Proposal::SNS(p) => p.reward_status,
+            Proposal::Generic(p) => p.reward_status,
         }
     }
 
@@ -63,6 +70,7 @@ impl Proposal {
         match self {
             Proposal::NNS(p) => p.tally.clone(),
             Proposal::SNS(p) => p.tally.clone(),
+            Proposal::Generic(p) => p.tally.clone(),
         }
     }
 
@@ -70,6 +78,7 @@ impl Proposal {
         match self {
             Proposal::NNS(p) => p.deadline,
             Proposal::SNS(p) => p.deadline,
+            Proposal::Generic(p) => p.deadline,
         }
     }
 
@@ -77,6 +86,7 @@ impl Proposal {
         match self {
             Proposal::NNS(p) => p.update_status(update, now),
             Proposal::SNS(p) => p.update_status(update, now),
+            Proposal::Generic(p) => p.update_status(update, now),
         }
     }
 }
@@ -147,6 +157,42 @@ impl SnsProposal {
     }
 }
 
+// A proposal from a governance canister which is neither the NNS nor an SNS, eg. one belonging to
+// a DAO framework built by a project hosted on OpenChat
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct GenericProposal {
+    pub id: ProposalId,
+    pub proposer: String,
+    pub created: TimestampMillis,
+    pub title: String,
+    pub summary: String,
+    pub url: String,
+    pub status: ProposalDecisionStatus,
+    pub reward_status: ProposalRewardStatus,
+    pub tally: Tally,
+    pub deadline: TimestampMillis,
+    pub payload_text_rendering: Option<String>,
+    pub last_updated: TimestampMillis,
+}
+
+impl GenericProposal {
+    pub fn update_status(&mut self, update: ProposalStatusUpdate, now: TimestampMillis) {
+        if let Some(status) = update.status {
+            self.status = status;
+        }
+        if let Some(reward_status) = update.reward_status {
+            self.reward_status = reward_status;
+        }
+        if let Some(latest_tally) = update.latest_tally {
+            self.tally = latest_tally;
+        }
+        if let Some(deadline) = update.deadline {
+            self.deadline = deadline;
+        }
+        self.last_updated = now;
+    }
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct ProposalContent {
     pub governance_canister_id: CanisterId,
@@ -1,3 +1,4 @@
+use crate::UserId;
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
@@ -6,3 +7,15 @@ pub enum ReferralType {
     BtcMiami,
     User,
 }
+
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct ReferralCampaignCode {
+    pub code: String,
+    pub referrer: UserId,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub struct ReferralCampaignRewards {
+    pub signup_e8s: u64,
+    pub diamond_conversion_e8s: u64,
+}
@@ -0,0 +1,62 @@
+use crate::{BuildVersion, CanisterId, Milliseconds, TimestampMillis};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct UpgradeRolloutPolicy {
+    // If any of these canisters are queued for upgrade they form the first stage of the rollout
+    pub canaries: Vec<CanisterId>,
+    // The cumulative percentages of the remaining canisters to upgrade at the end of each stage,
+    // eg. [1, 10, 50]. A final stage covering all remaining canisters is always added.
+    pub stage_percentages: Vec<u8>,
+    pub soak_duration: Milliseconds,
+    pub max_failures_per_stage: u32,
+    pub max_low_cycles_notifications_per_stage: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UpgradeRolloutStatus {
+    pub policy: UpgradeRolloutPolicy,
+    pub to_version: BuildVersion,
+    pub started: TimestampMillis,
+    pub state: UpgradeRolloutState,
+    pub current_stage: u32,
+    pub stages: Vec<UpgradeRolloutStageProgress>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpgradeRolloutState {
+    Upgrading,
+    Soaking(TimestampMillis),
+    Halted(UpgradeRolloutHalted),
+    Completed(TimestampMillis),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UpgradeRolloutHalted {
+    pub timestamp: TimestampMillis,
+    pub reason: UpgradeRolloutHaltReason,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpgradeRolloutHaltReason {
+    TooManyFailedUpgrades(u32),
+    UpgradeTrapped(CanisterId),
+    TooManyLowCyclesNotifications(u32),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct UpgradeRolloutStageProgress {
+    pub canaries: bool,
+    // For the final stage this is the number of canisters queued when the rollout began, the
+    // stage itself will also pick up any canisters queued afterwards
+    pub canisters: u32,
+    pub upgrades_started: u32,
+    pub succeeded: u32,
+    pub failed: u32,
+    pub trapped: u32,
+    pub low_cycles_notifications: u32,
+    pub started: Option<TimestampMillis>,
+    pub soak_until: Option<TimestampMillis>,
+    pub completed: Option<TimestampMillis>,
+}
@@ -1,4 +1,4 @@
-use crate::UserId;
+use crate::{GroupPermission, UserId};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
@@ -15,3 +15,12 @@ pub struct UserGroupDetails {
     pub name: String,
     pub members: Vec<UserId>,
 }
+
+// Overrides the permissions of a channel for the members of a user group. Denied permissions take
+// precedence over those allowed, either by the override or by the member's role.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UserGroupPermissionOverride {
+    pub user_group_id: u32,
+    pub allow: Vec<GroupPermission>,
+    pub deny: Vec<GroupPermission>,
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use types::{MessageIndex, Milliseconds, TimestampMillis, TypingActivity, TypingIndicator, UserId};
+use utils::time::SECOND_IN_MS;
+
+const INDICATOR_DURATION: Milliseconds = 10 * SECOND_IN_MS;
+const MIN_REFRESH_INTERVAL: Milliseconds = INDICATOR_DURATION / 2;
+
+// The typing and recording indicators within a single chat. Indicators are only ever needed for a
+// few seconds so they are held in heap memory and are dropped rather than persisted across upgrades.
+#[derive(Default)]
+pub struct TypingIndicators {
+    last_updated: TimestampMillis,
+    users: HashMap<(UserId, Option<MessageIndex>), Indicator>,
+}
+
+struct Indicator {
+    activity: TypingActivity,
+    expires: TimestampMillis,
+}
+
+impl TypingIndicators {
+    pub fn set(
+        &mut self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        activity: Option<TypingActivity>,
+        now: TimestampMillis,
+    ) {
+        self.prune(now);
+
+        let key = (user_id, thread_root_message_index);
+        if let Some(activity) = activity {
+            // Clients refresh their indicator every few seconds, so refreshes which would barely
+            // extend an unchanged indicator are ignored rather than making every poller refetch
+            if self
+                .users
+                .get(&key)
+                .map_or(false, |i| i.activity == activity && i.expires > now + MIN_REFRESH_INTERVAL)
+            {
+                return;
+            }
+            self.users.insert(
+                key,
+                Indicator {
+                    activity,
+                    expires: now + INDICATOR_DURATION,
+                },
+            );
+            self.last_updated = now;
+        } else if self.users.remove(&key).is_some() {
+            self.last_updated = now;
+        }
+    }
+
+    // Returns None if nothing has changed since `updated_since`
+    pub fn get(&self, updated_since: Option<TimestampMillis>, now: TimestampMillis) -> Option<Vec<TypingIndicator>> {
+        if updated_since.map_or(false, |since| self.last_updated <= since) {
+            return None;
+        }
+
+        Some(
+            self.users
+                .iter()
+                .filter(|(_, i)| i.expires > now)
+                .map(|((user_id, thread_root_message_index), i)| TypingIndicator {
+                    user_id: *user_id,
+                    thread_root_message_index: *thread_root_message_index,
+                    activity: i.activity,
+                    expires_in: i.expires - now,
+                })
+                .collect(),
+        )
+    }
+
+    // Returns the user's current activity in the chat, ignoring threads
+    pub fn activity(&self, user_id: UserId, now: TimestampMillis) -> Option<TypingActivity> {
+        self.users
+            .get(&(user_id, None))
+            .filter(|i| i.expires > now)
+            .map(|i| i.activity)
+    }
+
+    pub fn remove_user(&mut self, user_id: UserId, now: TimestampMillis) {
+        let count_before = self.users.len();
+        self.users.retain(|(u, _), _| *u != user_id);
+        if self.users.len() != count_before {
+            self.last_updated = now;
+        }
+    }
+
+    pub fn last_updated(&self) -> TimestampMillis {
+        self.last_updated
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    pub fn prune(&mut self, now: TimestampMillis) {
+        self.users.retain(|_, i| i.expires > now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::CanisterId;
+
+    fn user(id: u8) -> UserId {
+        UserId::new(CanisterId::from_slice(&[id]))
+    }
+
+    #[test]
+    fn indicators_expire_and_only_changes_are_returned() {
+        let mut indicators = TypingIndicators::default();
+
+        indicators.set(user(1), None, Some(TypingActivity::Typing), 1000);
+        assert_eq!(indicators.get(None, 1000).unwrap().len(), 1);
+        assert_eq!(indicators.activity(user(1), 1000), Some(TypingActivity::Typing));
+
+        assert!(indicators.get(Some(1000), 1500).is_none());
+
+        // Refreshing an unchanged indicator is ignored until half of its duration has passed
+        indicators.set(user(1), None, Some(TypingActivity::Typing), 2000);
+        assert!(indicators.get(Some(1000), 2000).is_none());
+
+        // Extending the indicator counts as an update so that pollers receive the new expiry
+        let extended_at = 1000 + MIN_REFRESH_INTERVAL;
+        indicators.set(user(1), None, Some(TypingActivity::Typing), extended_at);
+        assert_eq!(
+            indicators.get(Some(1000), extended_at).unwrap()[0].expires_in,
+            INDICATOR_DURATION
+        );
+
+        indicators.set(user(2), None, Some(TypingActivity::RecordingAudio), 7000);
+        assert_eq!(indicators.get(Some(extended_at), 7000).unwrap().len(), 2);
+
+        // user(1)'s indicator expires 10 seconds after it was last extended
+        let active = indicators.get(None, extended_at + INDICATOR_DURATION).unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].user_id, user(2));
+
+        indicators.set(user(2), None, None, 8000);
+        let active = indicators.get(Some(7000), 8000).unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].user_id, user(1));
+        assert!(indicators.activity(user(2), 8000).is_none());
+    }
+}
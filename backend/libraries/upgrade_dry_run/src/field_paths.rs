@@ -0,0 +1,263 @@
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fmt::Formatter;
+
+/// Returns the paths of the fields which are in `before` but not in `after`. If a field has been
+/// dropped, the fields nested within it are not reported separately.
+pub fn dropped(before: &[u8], after: &[u8]) -> Vec<String> {
+    let before = field_paths(before);
+    let after = field_paths(after);
+
+    let mut dropped: Vec<String> = Vec::new();
+    for path in before.difference(&after) {
+        if !dropped.iter().any(|d| is_nested_within(path, d)) {
+            dropped.push(path.clone());
+        }
+    }
+    dropped
+}
+
+// Collects the path of every field (map key which is a string) within a msgpack value. Elements of
+// sequences are merged under `[]` and map keys which aren't strings are merged under `{}`, other
+// than at the top level where the elements of the `(data, logs, traces)` tuple are numbered.
+fn field_paths(bytes: &[u8]) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    let mut deserializer = rmp_serde::Deserializer::new(bytes);
+    PathSeed {
+        path: String::new(),
+        paths: &mut paths,
+    }
+    .deserialize(&mut deserializer)
+    .expect("Failed to read snapshot");
+    paths
+}
+
+fn is_nested_within(path: &str, parent: &str) -> bool {
+    path.strip_prefix(parent)
+        .map_or(false, |rest| rest.starts_with(['.', '[', '{']))
+}
+
+struct PathSeed<'a> {
+    path: String,
+    paths: &'a mut BTreeSet<String>,
+}
+
+impl<'a> PathSeed<'a> {
+    fn child(&mut self, path: String) -> PathSeed<'_> {
+        PathSeed {
+            path,
+            paths: &mut *self.paths,
+        }
+    }
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for PathSeed<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for PathSeed<'a> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("any msgpack value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_bytes<E>(self, _: &[u8]) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_none<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        let top_level = self.path.is_empty();
+        let mut index = 0;
+        loop {
+            let path = if top_level { format!("[{index}]") } else { format!("{}[]", self.path) };
+            if seq.next_element_seed(self.child(path.clone()))?.is_none() {
+                break;
+            }
+            self.paths.insert(path);
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<MapKey>()? {
+            let path = match key {
+                MapKey::Field(name) => format!("{}.{name}", self.path),
+                MapKey::Other => format!("{}{{}}", self.path),
+            };
+            self.paths.insert(path.clone());
+            map.next_value_seed(self.child(path))?;
+        }
+        Ok(())
+    }
+}
+
+enum MapKey {
+    Field(String),
+    Other,
+}
+
+impl<'de> Deserialize<'de> for MapKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MapKeyVisitor;
+
+        impl<'de> Visitor<'de> for MapKeyVisitor {
+            type Value = MapKey;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a map key")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<MapKey, E> {
+                Ok(MapKey::Field(value.to_string()))
+            }
+
+            fn visit_bool<E>(self, _: bool) -> Result<MapKey, E> {
+                Ok(MapKey::Other)
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<MapKey, E> {
+                Ok(MapKey::Other)
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<MapKey, E> {
+                Ok(MapKey::Other)
+            }
+
+            fn visit_f64<E>(self, _: f64) -> Result<MapKey, E> {
+                Ok(MapKey::Other)
+            }
+
+            fn visit_bytes<E>(self, _: &[u8]) -> Result<MapKey, E> {
+                Ok(MapKey::Other)
+            }
+
+            fn visit_none<E>(self) -> Result<MapKey, E> {
+                Ok(MapKey::Other)
+            }
+
+            fn visit_unit<E>(self) -> Result<MapKey, E> {
+                Ok(MapKey::Other)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<MapKey, A::Error> {
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+                Ok(MapKey::Other)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<MapKey, A::Error> {
+                while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+                Ok(MapKey::Other)
+            }
+        }
+
+        deserializer.deserialize_any(MapKeyVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use std::collections::HashMap;
+
+    #[derive(Serialize)]
+    struct Before {
+        users: HashMap<u32, User>,
+        removed: Vec<User>,
+        kept: u32,
+    }
+
+    #[derive(Serialize)]
+    struct After {
+        users: HashMap<u32, UserAfter>,
+        kept: u32,
+    }
+
+    #[derive(Serialize)]
+    struct User {
+        name: String,
+        age: u32,
+    }
+
+    #[derive(Serialize)]
+    struct UserAfter {
+        name: String,
+    }
+
+    #[test]
+    fn dropped_fields_are_reported() {
+        let before = Before {
+            users: [(
+                1,
+                User {
+                    name: "a".to_string(),
+                    age: 10,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            removed: vec![User {
+                name: "b".to_string(),
+                age: 20,
+            }],
+            kept: 1,
+        };
+        let after = After {
+            users: [(1, UserAfter { name: "a".to_string() })].into_iter().collect(),
+            kept: 1,
+        };
+
+        let dropped = dropped(&serialize(&(before, 1)), &serialize(&(after, 1)));
+
+        assert_eq!(dropped, vec!["[0].removed".to_string(), "[0].users{}.age".to_string()]);
+    }
+
+    fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        rmp_serde::encode::write_named(&mut bytes, value).unwrap();
+        bytes
+    }
+}
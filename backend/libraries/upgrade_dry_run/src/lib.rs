@@ -0,0 +1,127 @@
+use candid::{CandidType, Principal};
+use ic_cdk::api::management_canister::main::{CanisterInstallMode, InstallCodeArgument};
+use ic_test_state_machine_client::{StateMachine, WasmResult};
+use serde_bytes::ByteBuf;
+use std::fmt::{Display, Formatter};
+use types::{BuildVersion, CanisterId};
+
+mod field_paths;
+mod stable_memory;
+
+pub use stable_memory::{build_stable_memory, detect_layout, extract_snapshot, StableMemoryLayout};
+
+// The smallest valid wasm module. It is installed first so that the scratch canister can then be
+// *upgraded* to the new build, causing its `post_upgrade` to run against the snapshot.
+const EMPTY_WASM: &[u8] = b"\x00asm\x01\x00\x00\x00";
+const SCRATCH_CANISTER_CYCLES: u128 = 100_000_000_000_000;
+
+#[derive(Debug, Default)]
+pub struct DryRunReport {
+    // Set if the new build trapped while deserializing the snapshot in `post_upgrade`
+    pub post_upgrade_error: Option<String>,
+    // Fields which were present in the snapshot but which are no longer present once the state
+    // has been deserialized by the new build and then serialized again
+    pub dropped_fields: Vec<String>,
+}
+
+impl DryRunReport {
+    pub fn is_compatible(&self) -> bool {
+        self.post_upgrade_error.is_none() && self.dropped_fields.is_empty()
+    }
+}
+
+impl Display for DryRunReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(error) = &self.post_upgrade_error {
+            writeln!(f, "Post upgrade failed: {error}")?;
+        } else {
+            writeln!(f, "Post upgrade succeeded")?;
+        }
+        if !self.dropped_fields.is_empty() {
+            writeln!(f, "Dropped fields:")?;
+            for field in self.dropped_fields.iter() {
+                writeln!(f, "  {field}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(CandidType)]
+struct PostUpgradeArgs {
+    wasm_version: BuildVersion,
+}
+
+/// Installs the new build into a scratch canister whose stable memory is seeded with `snapshot`
+/// (the state serialized by `pre_upgrade`), laid out according to `layout`, then reports whether
+/// `post_upgrade` was able to deserialize it and whether any fields were lost in the process.
+pub fn dry_run_upgrade(
+    env: &mut StateMachine,
+    snapshot: &[u8],
+    layout: StableMemoryLayout,
+    wasm: &[u8],
+    wasm_version: BuildVersion,
+) -> DryRunReport {
+    let controller = Principal::anonymous();
+    let canister_id = env.create_canister_with_settings(None, Some(controller));
+    env.add_cycles(canister_id, SCRATCH_CANISTER_CYCLES);
+
+    if let Err(error) = install_code(
+        env,
+        controller,
+        canister_id,
+        CanisterInstallMode::Install,
+        EMPTY_WASM,
+        Vec::new(),
+    ) {
+        panic!("Failed to create scratch canister: {error}");
+    }
+    env.set_stable_memory(canister_id, ByteBuf::from(build_stable_memory(snapshot, layout)));
+
+    let args = candid::encode_one(PostUpgradeArgs { wasm_version }).unwrap();
+    if let Err(error) = install_code(env, controller, canister_id, CanisterInstallMode::Upgrade, wasm, args.clone()) {
+        return DryRunReport {
+            post_upgrade_error: Some(error),
+            dropped_fields: Vec::new(),
+        };
+    }
+
+    // Upgrade again so that the new build serializes the state it loaded, which can then be
+    // compared with the original snapshot to find any fields which were silently dropped
+    if let Err(error) = install_code(env, controller, canister_id, CanisterInstallMode::Upgrade, wasm, args) {
+        panic!("Failed to re-serialize state using the new build: {error}");
+    }
+    let reserialized = extract_snapshot(&env.stable_memory(canister_id));
+
+    DryRunReport {
+        post_upgrade_error: None,
+        dropped_fields: field_paths::dropped(snapshot, &reserialized),
+    }
+}
+
+fn install_code(
+    env: &mut StateMachine,
+    sender: Principal,
+    canister_id: CanisterId,
+    mode: CanisterInstallMode,
+    wasm: &[u8],
+    arg: Vec<u8>,
+) -> Result<(), String> {
+    let args = InstallCodeArgument {
+        mode,
+        canister_id,
+        wasm_module: wasm.to_vec(),
+        arg,
+    };
+
+    match env.update_call(
+        Principal::management_canister(),
+        sender,
+        "install_code",
+        candid::encode_one(args).unwrap(),
+    ) {
+        Ok(WasmResult::Reply(_)) => Ok(()),
+        Ok(WasmResult::Reject(error)) => Err(error),
+        Err(error) => Err(error.to_string()),
+    }
+}
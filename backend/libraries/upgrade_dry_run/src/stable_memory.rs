@@ -0,0 +1,122 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
+use ic_stable_structures::reader::Reader;
+use ic_stable_structures::writer::Writer;
+use ic_stable_structures::{DefaultMemoryImpl, Memory};
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::io::{Read, Write};
+use std::rc::Rc;
+
+// Canisters using a MemoryManager write their state to this memory in `pre_upgrade`
+const UPGRADES: MemoryId = MemoryId::new(0);
+// The MemoryManager writes this magic value to the start of stable memory
+const MEMORY_MANAGER_MAGIC: &[u8; 3] = b"MGR";
+const WASM_PAGE_SIZE_BYTES: usize = 64 * 1024;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StableMemoryLayout {
+    // The state is written to the upgrades memory of a MemoryManager
+    MemoryManager,
+    // The state is written directly to the start of stable memory by `serialize_to_stable_memory`
+    Raw,
+}
+
+/// Builds a stable memory image, laid out the same way as the canister's stable memory, containing
+/// `snapshot` as though it had just been written by `pre_upgrade`
+pub fn build_stable_memory(snapshot: &[u8], layout: StableMemoryLayout) -> Vec<u8> {
+    match layout {
+        StableMemoryLayout::MemoryManager => {
+            let memory = DefaultMemoryImpl::default();
+            let memory_manager = MemoryManager::init(memory.clone());
+            let mut upgrades_memory = memory_manager.get(UPGRADES);
+
+            Writer::new(&mut upgrades_memory, 0).write_all(snapshot).unwrap();
+
+            let bytes = memory.borrow().clone();
+            bytes
+        }
+        StableMemoryLayout::Raw => {
+            let pages = (snapshot.len() + WASM_PAGE_SIZE_BYTES - 1) / WASM_PAGE_SIZE_BYTES;
+            let mut bytes = snapshot.to_vec();
+            bytes.resize(pages.max(1) * WASM_PAGE_SIZE_BYTES, 0);
+            bytes
+        }
+    }
+}
+
+/// Detects the layout of a canister's stable memory
+pub fn detect_layout(stable_memory: &[u8]) -> StableMemoryLayout {
+    if stable_memory.starts_with(MEMORY_MANAGER_MAGIC) {
+        StableMemoryLayout::MemoryManager
+    } else {
+        StableMemoryLayout::Raw
+    }
+}
+
+/// Extracts the state written by `pre_upgrade` from a canister's stable memory
+pub fn extract_snapshot(stable_memory: &[u8]) -> Vec<u8> {
+    match detect_layout(stable_memory) {
+        StableMemoryLayout::MemoryManager => {
+            let memory: DefaultMemoryImpl = Rc::new(RefCell::new(stable_memory.to_vec()));
+            let memory_manager = MemoryManager::init(memory);
+            let upgrades_memory = memory_manager.get(UPGRADES);
+            let size = upgrades_memory.size() as usize * WASM_PAGE_SIZE_BYTES;
+
+            read_snapshot(Reader::new(&upgrades_memory, 0).take(size as u64))
+        }
+        StableMemoryLayout::Raw => read_snapshot(stable_memory),
+    }
+}
+
+fn read_snapshot<R: Read>(reader: R) -> Vec<u8> {
+    let mut reader = RecordingReader::new(reader);
+    IgnoredAny::deserialize(&mut rmp_serde::Deserializer::new(&mut reader)).expect("Failed to read snapshot");
+    reader.bytes
+}
+
+// Records the bytes which are read so that exactly the bytes making up the snapshot are returned
+struct RecordingReader<R> {
+    inner: R,
+    bytes: Vec<u8>,
+}
+
+impl<R> RecordingReader<R> {
+    fn new(inner: R) -> RecordingReader<R> {
+        RecordingReader {
+            inner,
+            bytes: Vec::new(),
+        }
+    }
+}
+
+impl<R: Read> Read for RecordingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.bytes.extend_from_slice(&buf[..count]);
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_round_trips_through_stable_memory() {
+        let snapshot = test_snapshot();
+
+        for layout in [StableMemoryLayout::MemoryManager, StableMemoryLayout::Raw] {
+            let stable_memory = build_stable_memory(&snapshot, layout);
+
+            assert_eq!(detect_layout(&stable_memory), layout);
+            assert_eq!(extract_snapshot(&stable_memory), snapshot);
+        }
+    }
+
+    fn test_snapshot() -> Vec<u8> {
+        let mut snapshot = Vec::new();
+        rmp_serde::encode::write_named(&mut snapshot, &(vec![1u32, 2, 3], "logs", "traces")).unwrap();
+        snapshot
+    }
+}
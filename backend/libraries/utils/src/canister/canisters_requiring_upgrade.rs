@@ -1,23 +1,28 @@
+use crate::canister::{RolloutProgress, UpgradeRollout};
 use candid::CandidType;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashSet, VecDeque};
 use tracing::info;
-use types::{BuildVersion, CanisterId};
+use types::{BuildVersion, CanisterId, TimestampMillis, UpgradeRolloutPolicy, UpgradeRolloutStatus};
 
 #[derive(CandidType, Serialize, Deserialize)]
 pub struct FailedUpgrade {
     pub canister_id: CanisterId,
     pub from_version: BuildVersion,
     pub to_version: BuildVersion,
+    #[serde(default)]
+    pub trapped: bool,
 }
 
-#[derive(CandidType, Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, Default)]
 pub struct CanistersRequiringUpgrade {
     pending: VecDeque<(CanisterId, bool)>,
     in_progress: HashSet<CanisterId>,
     failed: VecDeque<FailedUpgrade>,
     completed: u64,
+    #[serde(default)]
+    rollout: Option<UpgradeRollout>,
 }
 
 impl CanistersRequiringUpgrade {
@@ -25,26 +30,84 @@ impl CanistersRequiringUpgrade {
         self.pending.push_back((canister_id, force));
     }
 
+    // Moves any canaries to the front of the queue and then releases the queued canisters in stages
+    pub fn start_rollout(&mut self, policy: UpgradeRolloutPolicy, to_version: BuildVersion, now: TimestampMillis) {
+        let canaries: HashSet<_> = policy.canaries.iter().copied().collect();
+        let (mut queue, rest): (VecDeque<_>, VecDeque<_>) = self
+            .pending
+            .drain(..)
+            .partition(|(canister_id, _)| canaries.contains(canister_id));
+
+        let canaries_queued = queue.len();
+        let canisters_queued = rest.len();
+        queue.extend(rest);
+        self.pending = queue;
+
+        if canaries_queued + canisters_queued > 0 {
+            info!(canaries_queued, canisters_queued, "Upgrade rollout started");
+            self.rollout = Some(UpgradeRollout::new(
+                policy,
+                to_version,
+                canaries_queued,
+                canisters_queued,
+                now,
+            ));
+        }
+    }
+
+    // Should be called by the upgrade job before taking the next batch. If this returns `Soaking`
+    // or `Halted` then no more upgrades will be released until the rollout is able to proceed.
+    pub fn update_rollout(&mut self, now: TimestampMillis) -> RolloutProgress {
+        match self.rollout.as_mut() {
+            Some(rollout) if self.in_progress.is_empty() => rollout.update(self.pending.is_empty(), now),
+            Some(rollout) if rollout.is_halted() => RolloutProgress::Halted,
+            _ => RolloutProgress::Proceed,
+        }
+    }
+
+    pub fn rollout_status(&self) -> Option<UpgradeRolloutStatus> {
+        self.rollout.as_ref().map(|r| r.status())
+    }
+
     pub fn try_take_next(&mut self) -> Option<(CanisterId, bool)> {
+        if self.rollout.as_ref().map_or(false, |r| !r.can_take_next()) {
+            return None;
+        }
         let (canister_id, force) = self.pending.pop_front()?;
         self.in_progress.insert(canister_id);
+        if let Some(rollout) = self.rollout.as_mut() {
+            rollout.on_taken(canister_id);
+        }
         Some((canister_id, force))
     }
 
     pub fn clear(&mut self) {
         self.pending.clear();
+        self.rollout = None;
     }
 
     pub fn mark_success(&mut self, canister_id: &CanisterId) {
         self.mark_upgrade_no_longer_in_progress(canister_id);
         self.completed += 1;
+        if let Some(rollout) = self.rollout.as_mut() {
+            rollout.on_success(canister_id);
+        }
     }
 
-    pub fn mark_failure(&mut self, failed_upgrade: FailedUpgrade) {
+    pub fn mark_failure(&mut self, failed_upgrade: FailedUpgrade, now: TimestampMillis) {
         self.mark_upgrade_no_longer_in_progress(&failed_upgrade.canister_id);
+        if let Some(rollout) = self.rollout.as_mut() {
+            rollout.on_failure(failed_upgrade.canister_id, failed_upgrade.trapped, now);
+        }
         self.failed.push_back(failed_upgrade);
     }
 
+    pub fn mark_low_cycles_notification(&mut self, canister_id: &CanisterId, now: TimestampMillis) {
+        if let Some(rollout) = self.rollout.as_mut() {
+            rollout.on_low_cycles_notification(canister_id, now);
+        }
+    }
+
     pub fn mark_skipped(&mut self, canister_id: &CanisterId) {
         self.mark_upgrade_no_longer_in_progress(canister_id);
     }
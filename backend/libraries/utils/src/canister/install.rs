@@ -100,3 +100,9 @@ fn should_deposit_cycles_and_retry(
     }
     ShouldDepositAndRetry::No
 }
+
+// If the canister itself rejected the install (ie. it trapped in `pre_upgrade` or `post_upgrade`)
+// then retrying is unlikely to help, so upgrade rollouts treat this as a reason to halt
+pub fn install_trapped(error: &(RejectionCode, String)) -> bool {
+    matches!(error.0, RejectionCode::CanisterError) && !error.1.contains("out of cycles")
+}
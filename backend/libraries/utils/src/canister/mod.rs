@@ -8,10 +8,14 @@ mod deposit_cycles;
 mod filtered_upgrades;
 mod install;
 mod pool;
+mod previous_wasms;
 mod raw_rand;
 mod start;
+mod state_snapshots;
 mod stop;
 mod update_settings;
+mod upgrade_rollout;
+mod wasm_uploads;
 
 pub use canisters_requiring_upgrade::*;
 pub use create::*;
@@ -20,10 +24,14 @@ pub use deposit_cycles::*;
 pub use filtered_upgrades::*;
 pub use install::*;
 pub use pool::*;
+pub use previous_wasms::*;
 pub use raw_rand::*;
 pub use start::*;
+pub use state_snapshots::*;
 pub use stop::*;
 pub use update_settings::*;
+pub use upgrade_rollout::*;
+pub use wasm_uploads::*;
 
 pub fn should_perform_upgrade(current: BuildVersion, next: BuildVersion, test_mode: bool) -> bool {
     match current.cmp(&next) {
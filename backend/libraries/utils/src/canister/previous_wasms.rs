@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use types::{BuildVersion, CanisterWasm};
+
+const MAX_PREVIOUS_WASMS: usize = 2;
+
+// Holds the wasms which were most recently replaced so that canisters can be rolled back to them
+#[derive(Serialize, Deserialize, Default)]
+pub struct PreviousWasms {
+    wasms: VecDeque<CanisterWasm>,
+}
+
+impl PreviousWasms {
+    pub fn push(&mut self, wasm: CanisterWasm) {
+        if wasm.module.is_empty() {
+            return;
+        }
+
+        self.wasms.retain(|w| w.version != wasm.version);
+        self.wasms.push_back(wasm);
+
+        while self.wasms.len() > MAX_PREVIOUS_WASMS {
+            self.wasms.pop_front();
+        }
+    }
+
+    pub fn get(&self, version: Option<BuildVersion>) -> Option<&CanisterWasm> {
+        match version {
+            Some(v) => self.wasms.iter().find(|w| w.version == v),
+            None => self.wasms.back(),
+        }
+    }
+
+    // Removes the wasm being rolled back to along with any which are newer than it
+    pub fn take(&mut self, version: BuildVersion) -> Option<CanisterWasm> {
+        let index = self.wasms.iter().position(|w| w.version == version)?;
+        let wasm = self.wasms.remove(index);
+        self.wasms.truncate(index);
+        wasm
+    }
+
+    pub fn versions(&self) -> Vec<BuildVersion> {
+        self.wasms.iter().map(|w| w.version).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_wasms_are_dropped() {
+        let mut previous_wasms = PreviousWasms::default();
+        for i in 1..=3 {
+            previous_wasms.push(wasm(i));
+        }
+
+        assert_eq!(previous_wasms.versions(), vec![version(2), version(3)]);
+    }
+
+    #[test]
+    fn take_removes_newer_wasms() {
+        let mut previous_wasms = PreviousWasms::default();
+        previous_wasms.push(wasm(1));
+        previous_wasms.push(wasm(2));
+
+        let taken = previous_wasms.take(version(1)).unwrap();
+
+        assert_eq!(taken.version, version(1));
+        assert!(previous_wasms.versions().is_empty());
+    }
+
+    #[test]
+    fn empty_wasms_are_ignored() {
+        let mut previous_wasms = PreviousWasms::default();
+        previous_wasms.push(CanisterWasm::default());
+
+        assert!(previous_wasms.get(None).is_none());
+    }
+
+    fn wasm(patch: u32) -> CanisterWasm {
+        CanisterWasm {
+            version: version(patch),
+            module: vec![1, 2, 3],
+        }
+    }
+
+    fn version(patch: u32) -> BuildVersion {
+        BuildVersion::new(0, 0, patch)
+    }
+}
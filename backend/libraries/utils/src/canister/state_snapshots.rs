@@ -0,0 +1,149 @@
+use serde_bytes::ByteBuf;
+use std::cmp::min;
+
+// Holds the details of a canister's state while it is being exported or restored. This is never
+// persisted across upgrades, so an export or restore which is interrupted by an upgrade must be
+// started again.
+//
+// Serializing the whole state within a single update call could exceed the instruction limit, so
+// exports instead read the state written to stable memory by `pre_upgrade`, which has a far higher
+// limit. The canister is upgraded in place by its local index immediately before an export starts
+// so that the snapshot is current.
+#[derive(Default)]
+pub struct StateSnapshots {
+    export_length: Option<u64>,
+    restore: Option<PendingRestore>,
+}
+
+struct PendingRestore {
+    total_bytes: u64,
+    bytes: Vec<u8>,
+}
+
+pub enum PushRestoreChunkResult {
+    Success,
+    UnexpectedOffset(u64),
+    ExceedsTotalSize,
+}
+
+pub enum TakeRestoreResult {
+    Success(Vec<u8>),
+    NotStarted,
+    Incomplete(u64),
+}
+
+impl StateSnapshots {
+    // The length of the snapshot is measured whenever the first page is requested, and every
+    // subsequent page is then read from the same snapshot. Returns the page along with the total
+    // size, or None if no snapshot is available.
+    pub fn export_page<L: FnOnce() -> Option<u64>, R: FnOnce(u64, &mut [u8])>(
+        &mut self,
+        from: u64,
+        page_size: u32,
+        snapshot_length: L,
+        read: R,
+    ) -> Option<(ByteBuf, u64)> {
+        if from == 0 {
+            self.export_length = snapshot_length();
+        }
+
+        let total_bytes = self.export_length?;
+        let from = min(from, total_bytes);
+        let to = min(from + page_size as u64, total_bytes);
+
+        let mut bytes = vec![0; (to - from) as usize];
+        read(from, &mut bytes);
+
+        Some((ByteBuf::from(bytes), total_bytes))
+    }
+
+    // Pushing a chunk at offset 0 discards any chunks received so far
+    pub fn push_restore_chunk(&mut self, total_bytes: u64, from: u64, bytes: Vec<u8>) -> PushRestoreChunkResult {
+        if from == 0 {
+            self.restore = Some(PendingRestore {
+                total_bytes,
+                bytes: Vec::with_capacity(total_bytes as usize),
+            });
+        }
+
+        let restore = match self.restore.as_mut() {
+            Some(r) => r,
+            None => return PushRestoreChunkResult::UnexpectedOffset(0),
+        };
+
+        let received = restore.bytes.len() as u64;
+        if from != received || total_bytes != restore.total_bytes {
+            PushRestoreChunkResult::UnexpectedOffset(received)
+        } else if received + bytes.len() as u64 > restore.total_bytes {
+            PushRestoreChunkResult::ExceedsTotalSize
+        } else {
+            restore.bytes.extend(bytes);
+            PushRestoreChunkResult::Success
+        }
+    }
+
+    pub fn take_restore(&mut self) -> TakeRestoreResult {
+        match self.restore.take() {
+            Some(restore) if restore.bytes.len() as u64 == restore.total_bytes => TakeRestoreResult::Success(restore.bytes),
+            Some(restore) => {
+                let received = restore.bytes.len() as u64;
+                self.restore = Some(restore);
+                TakeRestoreResult::Incomplete(received)
+            }
+            None => TakeRestoreResult::NotStarted,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_pages_come_from_a_single_snapshot() {
+        let mut snapshots = StateSnapshots::default();
+        let memory = [1, 2, 3, 4, 5, 0, 0, 0];
+        let read = |from: u64, buf: &mut [u8]| buf.copy_from_slice(&memory[from as usize..from as usize + buf.len()]);
+
+        assert!(snapshots.export_page(3, 3, || Some(5), read).is_none());
+
+        let (page1, total) = snapshots.export_page(0, 3, || Some(5), read).unwrap();
+        let (page2, _) = snapshots.export_page(3, 3, || Some(8), read).unwrap();
+        let (page3, _) = snapshots.export_page(6, 3, || Some(8), read).unwrap();
+
+        assert_eq!(total, 5);
+        assert_eq!(page1.into_vec(), vec![1, 2, 3]);
+        assert_eq!(page2.into_vec(), vec![4, 5]);
+        assert!(page3.is_empty());
+        assert!(snapshots.export_page(0, 3, || None, read).is_none());
+    }
+
+    #[test]
+    fn restore_chunks_must_be_pushed_in_order() {
+        let mut snapshots = StateSnapshots::default();
+
+        assert!(matches!(
+            snapshots.push_restore_chunk(5, 2, vec![3, 4]),
+            PushRestoreChunkResult::UnexpectedOffset(0)
+        ));
+        assert!(matches!(
+            snapshots.push_restore_chunk(5, 0, vec![1, 2]),
+            PushRestoreChunkResult::Success
+        ));
+        assert!(matches!(snapshots.take_restore(), TakeRestoreResult::Incomplete(2)));
+        assert!(matches!(
+            snapshots.push_restore_chunk(5, 2, vec![3, 4, 5, 6]),
+            PushRestoreChunkResult::ExceedsTotalSize
+        ));
+        assert!(matches!(
+            snapshots.push_restore_chunk(5, 2, vec![3, 4, 5]),
+            PushRestoreChunkResult::Success
+        ));
+
+        match snapshots.take_restore() {
+            TakeRestoreResult::Success(bytes) => assert_eq!(bytes, vec![1, 2, 3, 4, 5]),
+            _ => panic!(),
+        }
+        assert!(matches!(snapshots.take_restore(), TakeRestoreResult::NotStarted));
+    }
+}
@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::{error, info};
+use types::{
+    BuildVersion, CanisterId, TimestampMillis, UpgradeRolloutHaltReason, UpgradeRolloutHalted, UpgradeRolloutPolicy,
+    UpgradeRolloutStageProgress, UpgradeRolloutState, UpgradeRolloutStatus,
+};
+
+pub enum RolloutProgress {
+    Proceed,
+    Soaking(TimestampMillis),
+    Halted,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UpgradeRollout {
+    policy: UpgradeRolloutPolicy,
+    to_version: BuildVersion,
+    started: TimestampMillis,
+    state: UpgradeRolloutState,
+    current_stage: usize,
+    stages: Vec<UpgradeRolloutStageProgress>,
+    upgraded_in_current_stage: HashSet<CanisterId>,
+}
+
+impl UpgradeRollout {
+    // `canisters_queued` excludes the canaries, which are always upgraded in a stage of their own
+    pub fn new(
+        policy: UpgradeRolloutPolicy,
+        to_version: BuildVersion,
+        canaries_queued: usize,
+        canisters_queued: usize,
+        now: TimestampMillis,
+    ) -> UpgradeRollout {
+        let mut stages = Vec::new();
+        if canaries_queued > 0 {
+            stages.push(UpgradeRolloutStageProgress {
+                canaries: true,
+                canisters: canaries_queued as u32,
+                ..Default::default()
+            });
+        }
+
+        let mut percentages: Vec<_> = policy.stage_percentages.iter().copied().filter(|p| *p < 100).collect();
+        percentages.sort_unstable();
+        percentages.dedup();
+
+        let mut previous_total = 0;
+        for percentage in percentages {
+            let total = (canisters_queued * percentage as usize + 99) / 100;
+            if total > previous_total {
+                stages.push(UpgradeRolloutStageProgress {
+                    canisters: (total - previous_total) as u32,
+                    ..Default::default()
+                });
+                previous_total = total;
+            }
+        }
+        stages.push(UpgradeRolloutStageProgress {
+            canisters: (canisters_queued - previous_total) as u32,
+            ..Default::default()
+        });
+        stages[0].started = Some(now);
+
+        UpgradeRollout {
+            policy,
+            to_version,
+            started: now,
+            state: UpgradeRolloutState::Upgrading,
+            current_stage: 0,
+            stages,
+            upgraded_in_current_stage: HashSet::new(),
+        }
+    }
+
+    pub fn can_take_next(&self) -> bool {
+        matches!(self.state, UpgradeRolloutState::Upgrading)
+            && (self.is_final_stage() || self.current_stage().upgrades_started < self.current_stage().canisters)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        matches!(self.state, UpgradeRolloutState::Halted(_))
+    }
+
+    pub fn on_taken(&mut self, canister_id: CanisterId) {
+        self.current_stage_mut().upgrades_started += 1;
+        self.upgraded_in_current_stage.insert(canister_id);
+    }
+
+    pub fn on_success(&mut self, canister_id: &CanisterId) {
+        if self.upgraded_in_current_stage.contains(canister_id) {
+            self.current_stage_mut().succeeded += 1;
+        }
+    }
+
+    pub fn on_failure(&mut self, canister_id: CanisterId, trapped: bool, now: TimestampMillis) {
+        if !self.upgraded_in_current_stage.contains(&canister_id) {
+            return;
+        }
+
+        let stage = self.current_stage_mut();
+        stage.failed += 1;
+        let failed = stage.failed;
+
+        if trapped {
+            stage.trapped += 1;
+            self.halt(UpgradeRolloutHaltReason::UpgradeTrapped(canister_id), now);
+        } else if failed > self.policy.max_failures_per_stage {
+            self.halt(UpgradeRolloutHaltReason::TooManyFailedUpgrades(failed), now);
+        }
+    }
+
+    pub fn on_low_cycles_notification(&mut self, canister_id: &CanisterId, now: TimestampMillis) {
+        if !self.upgraded_in_current_stage.contains(canister_id) {
+            return;
+        }
+
+        let stage = self.current_stage_mut();
+        stage.low_cycles_notifications += 1;
+        let count = stage.low_cycles_notifications;
+
+        if count > self.policy.max_low_cycles_notifications_per_stage {
+            self.halt(UpgradeRolloutHaltReason::TooManyLowCyclesNotifications(count), now);
+        }
+    }
+
+    // Must only be called once there are no upgrades in progress
+    pub fn update(&mut self, queue_empty: bool, now: TimestampMillis) -> RolloutProgress {
+        match self.state {
+            UpgradeRolloutState::Upgrading
+                if queue_empty && (self.is_final_stage() || self.current_stage().upgrades_started == 0) =>
+            {
+                self.current_stage_mut().completed = Some(now);
+                self.state = UpgradeRolloutState::Completed(now);
+                info!(to_version = %self.to_version, "Upgrade rollout completed");
+                RolloutProgress::Proceed
+            }
+            UpgradeRolloutState::Upgrading if !self.can_take_next() || queue_empty => {
+                let soak_until = now + self.policy.soak_duration;
+                self.current_stage_mut().soak_until = Some(soak_until);
+                self.state = UpgradeRolloutState::Soaking(soak_until);
+                info!(stage = self.current_stage, soak_until, "Upgrade rollout stage soaking");
+                RolloutProgress::Soaking(soak_until)
+            }
+            UpgradeRolloutState::Soaking(until) if now < until => RolloutProgress::Soaking(until),
+            UpgradeRolloutState::Soaking(_) => {
+                self.current_stage_mut().completed = Some(now);
+                self.current_stage += 1;
+                self.current_stage_mut().started = Some(now);
+                self.upgraded_in_current_stage.clear();
+                self.state = UpgradeRolloutState::Upgrading;
+                info!(stage = self.current_stage, "Upgrade rollout proceeding to next stage");
+                RolloutProgress::Proceed
+            }
+            UpgradeRolloutState::Halted(_) => RolloutProgress::Halted,
+            UpgradeRolloutState::Upgrading | UpgradeRolloutState::Completed(_) => RolloutProgress::Proceed,
+        }
+    }
+
+    pub fn status(&self) -> UpgradeRolloutStatus {
+        UpgradeRolloutStatus {
+            policy: self.policy.clone(),
+            to_version: self.to_version,
+            started: self.started,
+            state: self.state,
+            current_stage: self.current_stage as u32,
+            stages: self.stages.clone(),
+        }
+    }
+
+    fn halt(&mut self, reason: UpgradeRolloutHaltReason, now: TimestampMillis) {
+        if !self.is_halted() {
+            error!(stage = self.current_stage, ?reason, "Upgrade rollout halted");
+            self.state = UpgradeRolloutState::Halted(UpgradeRolloutHalted { timestamp: now, reason });
+        }
+    }
+
+    fn is_final_stage(&self) -> bool {
+        self.current_stage + 1 == self.stages.len()
+    }
+
+    fn current_stage(&self) -> &UpgradeRolloutStageProgress {
+        &self.stages[self.current_stage]
+    }
+
+    fn current_stage_mut(&mut self) -> &mut UpgradeRolloutStageProgress {
+        &mut self.stages[self.current_stage]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stages_are_built_from_percentages() {
+        let rollout = UpgradeRollout::new(policy(vec![50, 1, 10, 100]), BuildVersion::default(), 2, 200, 0);
+
+        let sizes: Vec<_> = rollout.stages.iter().map(|s| (s.canaries, s.canisters)).collect();
+
+        assert_eq!(sizes, vec![(true, 2), (false, 2), (false, 18), (false, 80), (false, 100)]);
+    }
+
+    #[test]
+    fn stage_soaks_then_proceeds() {
+        let mut rollout = UpgradeRollout::new(policy(vec![50]), BuildVersion::default(), 0, 2, 0);
+
+        assert!(rollout.can_take_next());
+        rollout.on_taken(canister_id(1));
+        rollout.on_success(&canister_id(1));
+        assert!(!rollout.can_take_next());
+
+        assert!(matches!(rollout.update(false, 10), RolloutProgress::Soaking(1010)));
+        assert!(matches!(rollout.update(false, 500), RolloutProgress::Soaking(1010)));
+        assert!(matches!(rollout.update(false, 1010), RolloutProgress::Proceed));
+        assert!(rollout.can_take_next());
+
+        rollout.on_taken(canister_id(2));
+        rollout.on_success(&canister_id(2));
+        rollout.update(true, 2000);
+        assert_eq!(rollout.state, UpgradeRolloutState::Completed(2000));
+    }
+
+    #[test]
+    fn too_many_failures_halts_rollout() {
+        let mut rollout = UpgradeRollout::new(policy(vec![50]), BuildVersion::default(), 0, 10, 0);
+
+        for i in 0..2 {
+            rollout.on_taken(canister_id(i));
+            rollout.on_failure(canister_id(i), false, 5);
+        }
+
+        assert!(matches!(rollout.state, UpgradeRolloutState::Halted(_)));
+        assert!(!rollout.can_take_next());
+        assert!(matches!(rollout.update(false, 10), RolloutProgress::Halted));
+    }
+
+    #[test]
+    fn trap_halts_rollout_immediately() {
+        let mut rollout = UpgradeRollout::new(policy(vec![50]), BuildVersion::default(), 0, 10, 0);
+
+        rollout.on_taken(canister_id(1));
+        rollout.on_failure(canister_id(1), true, 5);
+
+        assert_eq!(
+            rollout.state,
+            UpgradeRolloutState::Halted(UpgradeRolloutHalted {
+                timestamp: 5,
+                reason: UpgradeRolloutHaltReason::UpgradeTrapped(canister_id(1))
+            })
+        );
+    }
+
+    fn policy(stage_percentages: Vec<u8>) -> UpgradeRolloutPolicy {
+        UpgradeRolloutPolicy {
+            canaries: Vec::new(),
+            stage_percentages,
+            soak_duration: 1000,
+            max_failures_per_stage: 1,
+            max_low_cycles_notifications_per_stage: 1,
+        }
+    }
+
+    fn canister_id(i: u8) -> CanisterId {
+        CanisterId::from_slice(&[i])
+    }
+}
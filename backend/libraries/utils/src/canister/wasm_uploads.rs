@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+use sha256::sha256;
+use std::future::Future;
+use types::{BuildVersion, CanisterWasm, Hash, TimestampMillis, UpgradeCanisterWasmArgs, WasmChunk};
+
+// Install code messages are limited to 10MB so there is no point accepting anything larger
+pub const MAX_UPLOADED_WASM_SIZE: u64 = 10 * 1024 * 1024;
+// Comfortably below the 2MB limit on the size of cross-subnet messages
+const WASM_CHUNK_SIZE_BYTES: usize = 1024 * 1024;
+const MAX_UPLOADS: usize = 3;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct WasmUploads {
+    uploads: Vec<WasmUpload>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WasmUpload {
+    sha256: Hash,
+    version: BuildVersion,
+    total_size: u64,
+    next_chunk_index: u32,
+    #[serde(with = "serde_bytes")]
+    module: Vec<u8>,
+    started: TimestampMillis,
+    committed: bool,
+}
+
+pub enum StartUploadResult {
+    Success,
+    TooLarge(u64),
+}
+
+pub enum PushChunkResult {
+    Success,
+    NotFound,
+    AlreadyCommitted,
+    UnexpectedChunkIndex(u32),
+    ExceedsTotalSize,
+}
+
+pub enum CommitUploadResult {
+    Success(BuildVersion),
+    NotFound,
+    Incomplete(u64),
+    HashMismatch(Hash),
+}
+
+pub enum PushWasmChunkResult {
+    Success,
+    Committed,
+    TooLarge(u64),
+    NotFound,
+    UnexpectedChunkIndex(u32),
+    ExceedsTotalSize,
+    HashMismatch(Hash),
+}
+
+impl WasmUploads {
+    // Starting an upload which already exists discards any chunks received so far. If the maximum
+    // number of uploads are being held, the oldest is discarded.
+    pub fn start(&mut self, version: BuildVersion, total_size: u64, hash: Hash, now: TimestampMillis) -> StartUploadResult {
+        if total_size > MAX_UPLOADED_WASM_SIZE {
+            return StartUploadResult::TooLarge(MAX_UPLOADED_WASM_SIZE);
+        }
+
+        self.uploads.retain(|u| u.sha256 != hash);
+        if self.uploads.len() >= MAX_UPLOADS {
+            // Uploads are held in the order they were started
+            self.uploads.remove(0);
+        }
+
+        self.uploads.push(WasmUpload {
+            sha256: hash,
+            version,
+            total_size,
+            next_chunk_index: 0,
+            module: Vec::with_capacity(total_size as usize),
+            started: now,
+            committed: false,
+        });
+        StartUploadResult::Success
+    }
+
+    pub fn push_chunk(&mut self, hash: &Hash, index: u32, bytes: Vec<u8>) -> PushChunkResult {
+        let upload = match self.uploads.iter_mut().find(|u| u.sha256 == *hash) {
+            Some(u) => u,
+            None => return PushChunkResult::NotFound,
+        };
+
+        if upload.committed {
+            PushChunkResult::AlreadyCommitted
+        } else if index != upload.next_chunk_index {
+            PushChunkResult::UnexpectedChunkIndex(upload.next_chunk_index)
+        } else if (upload.module.len() + bytes.len()) as u64 > upload.total_size {
+            PushChunkResult::ExceedsTotalSize
+        } else {
+            upload.module.extend(bytes);
+            upload.next_chunk_index += 1;
+            PushChunkResult::Success
+        }
+    }
+
+    pub fn commit(&mut self, hash: &Hash) -> CommitUploadResult {
+        let index = match self.uploads.iter().position(|u| u.sha256 == *hash) {
+            Some(i) => i,
+            None => return CommitUploadResult::NotFound,
+        };
+
+        let upload = &mut self.uploads[index];
+        if upload.committed {
+            return CommitUploadResult::Success(upload.version);
+        }
+
+        let received = upload.module.len() as u64;
+        if received != upload.total_size {
+            return CommitUploadResult::Incomplete(received);
+        }
+
+        let actual = sha256(&upload.module);
+        if actual != *hash {
+            self.uploads.remove(index);
+            return CommitUploadResult::HashMismatch(actual);
+        }
+
+        upload.committed = true;
+        CommitUploadResult::Success(upload.version)
+    }
+
+    // Handles a chunk of a wasm being passed on from an index canister to a local index
+    pub fn push_wasm_chunk(&mut self, chunk: WasmChunk, now: TimestampMillis) -> PushWasmChunkResult {
+        if chunk.index == 0 {
+            if let StartUploadResult::TooLarge(max) = self.start(chunk.version, chunk.total_size, chunk.sha256, now) {
+                return PushWasmChunkResult::TooLarge(max);
+            }
+        }
+
+        match self.push_chunk(&chunk.sha256, chunk.index, chunk.bytes) {
+            PushChunkResult::Success => {}
+            PushChunkResult::NotFound => return PushWasmChunkResult::NotFound,
+            PushChunkResult::AlreadyCommitted => return PushWasmChunkResult::Committed,
+            PushChunkResult::UnexpectedChunkIndex(expected) => return PushWasmChunkResult::UnexpectedChunkIndex(expected),
+            PushChunkResult::ExceedsTotalSize => return PushWasmChunkResult::ExceedsTotalSize,
+        }
+
+        match self.commit(&chunk.sha256) {
+            CommitUploadResult::Success(_) => PushWasmChunkResult::Committed,
+            CommitUploadResult::Incomplete(_) => PushWasmChunkResult::Success,
+            CommitUploadResult::NotFound => PushWasmChunkResult::NotFound,
+            CommitUploadResult::HashMismatch(actual) => PushWasmChunkResult::HashMismatch(actual),
+        }
+    }
+
+    pub fn get_committed(&self, hash: &Hash, version: BuildVersion) -> Option<CanisterWasm> {
+        self.uploads
+            .iter()
+            .find(|u| u.committed && u.sha256 == *hash && u.version == version)
+            .map(|u| CanisterWasm {
+                version: u.version,
+                module: u.module.clone(),
+            })
+    }
+
+    // If the args reference an uploaded wasm, `args.wasm` is replaced by that wasm. Returns false if
+    // the referenced wasm hasn't been committed or its version doesn't match.
+    pub fn resolve(&self, args: &mut UpgradeCanisterWasmArgs) -> bool {
+        if let Some(hash) = args.uploaded_wasm_hash {
+            match self.get_committed(&hash, args.wasm.version) {
+                Some(wasm) if args.wasm.module.is_empty() => args.wasm = wasm,
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    pub fn remove(&mut self, hash: &Hash) {
+        self.uploads.retain(|u| u.sha256 != *hash);
+    }
+}
+
+pub fn requires_chunked_upload(wasm: &CanisterWasm) -> bool {
+    wasm.module.len() > WASM_CHUNK_SIZE_BYTES
+}
+
+// Splits a wasm into chunks which can each be sent in a cross-subnet message
+fn split_into_chunks(wasm: &CanisterWasm) -> (Hash, Vec<WasmChunk>) {
+    let sha256 = sha256(&wasm.module);
+    let chunks = wasm
+        .module
+        .chunks(WASM_CHUNK_SIZE_BYTES)
+        .enumerate()
+        .map(|(index, bytes)| WasmChunk {
+            version: wasm.version,
+            total_size: wasm.module.len() as u64,
+            sha256,
+            index: index as u32,
+            bytes: bytes.to_vec(),
+        })
+        .collect();
+
+    (sha256, chunks)
+}
+
+// Sends a wasm to a local index in chunks, returning the hash which the subsequent upgrade should
+// reference via `UpgradeCanisterWasmArgs::uploaded_wasm_hash`. `push_chunk` should return whether
+// the upload has been committed.
+pub async fn upload_wasm_in_chunks<F, Fut>(wasm: &CanisterWasm, push_chunk: F) -> Result<Hash, String>
+where
+    F: Fn(WasmChunk) -> Fut,
+    Fut: Future<Output = Result<bool, String>>,
+{
+    let (sha256, chunks) = split_into_chunks(wasm);
+
+    for chunk in chunks {
+        if push_chunk(chunk).await? {
+            return Ok(sha256);
+        }
+    }
+    Err("Wasm upload not committed after all chunks were pushed".to_string())
+}
+
+// Builds the args to upgrade a local index to a wasm which has already been uploaded to it in chunks
+pub fn args_for_uploaded_wasm(mut args: UpgradeCanisterWasmArgs, sha256: Hash) -> UpgradeCanisterWasmArgs {
+    args.wasm = CanisterWasm {
+        version: args.wasm.version,
+        module: Vec::new(),
+    };
+    args.uploaded_wasm_hash = Some(sha256);
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upload_in_chunks_then_commit() {
+        let module: Vec<u8> = (0..=255).collect();
+        let hash = sha256(&module);
+        let mut uploads = WasmUploads::default();
+
+        uploads.start(BuildVersion::new(1, 0, 0), module.len() as u64, hash, 0);
+        for (index, chunk) in module.chunks(100).enumerate() {
+            assert!(matches!(
+                uploads.push_chunk(&hash, index as u32, chunk.to_vec()),
+                PushChunkResult::Success
+            ));
+        }
+
+        assert!(uploads.get_committed(&hash, BuildVersion::new(1, 0, 0)).is_none());
+        assert!(matches!(uploads.commit(&hash), CommitUploadResult::Success(_)));
+
+        let wasm = uploads.get_committed(&hash, BuildVersion::new(1, 0, 0)).unwrap();
+        assert_eq!(wasm.module, module);
+        assert!(uploads.get_committed(&hash, BuildVersion::new(1, 0, 1)).is_none());
+    }
+
+    #[test]
+    fn chunks_must_be_pushed_in_order() {
+        let hash = [1; 32];
+        let mut uploads = WasmUploads::default();
+
+        uploads.start(BuildVersion::default(), 10, hash, 0);
+
+        assert!(matches!(
+            uploads.push_chunk(&hash, 1, vec![0; 5]),
+            PushChunkResult::UnexpectedChunkIndex(0)
+        ));
+        assert!(matches!(
+            uploads.push_chunk(&hash, 0, vec![0; 11]),
+            PushChunkResult::ExceedsTotalSize
+        ));
+        assert!(matches!(uploads.commit(&hash), CommitUploadResult::Incomplete(0)));
+    }
+
+    #[test]
+    fn wasm_chunks_start_and_commit_upload() {
+        let wasm = CanisterWasm {
+            version: BuildVersion::new(1, 2, 3),
+            module: (0..(2 * WASM_CHUNK_SIZE_BYTES + 10)).map(|i| (i % 251) as u8).collect(),
+        };
+        let (hash, chunks) = split_into_chunks(&wasm);
+        assert_eq!(chunks.len(), 3);
+
+        let mut uploads = WasmUploads::default();
+        let results: Vec<_> = chunks.into_iter().map(|c| uploads.push_wasm_chunk(c, 0)).collect();
+
+        assert!(matches!(
+            results.as_slice(),
+            [
+                PushWasmChunkResult::Success,
+                PushWasmChunkResult::Success,
+                PushWasmChunkResult::Committed
+            ]
+        ));
+
+        let mut args = args_for_uploaded_wasm(
+            UpgradeCanisterWasmArgs {
+                wasm: wasm.clone(),
+                filter: None,
+                use_for_new_canisters: None,
+                rollout: None,
+                uploaded_wasm_hash: None,
+            },
+            hash,
+        );
+        assert!(uploads.resolve(&mut args));
+        assert_eq!(args.wasm.module, wasm.module);
+    }
+
+    #[test]
+    fn hash_mismatch_discards_upload() {
+        let hash = [1; 32];
+        let mut uploads = WasmUploads::default();
+
+        uploads.start(BuildVersion::default(), 3, hash, 0);
+        uploads.push_chunk(&hash, 0, vec![1, 2, 3]);
+
+        assert!(matches!(uploads.commit(&hash), CommitUploadResult::HashMismatch(_)));
+        assert!(matches!(uploads.commit(&hash), CommitUploadResult::NotFound));
+    }
+}
@@ -1,3 +1,4 @@
+use candid::Principal;
 use sha3::{Digest, Sha3_256};
 use types::Hash;
 
@@ -7,6 +8,23 @@ pub fn hash_bytes(value: impl AsRef<[u8]>) -> Hash {
     hasher.finalize().into()
 }
 
+// Proves that a principal holds the bytes of a blob. The proof is specific to the principal so that it
+// can't be reused by anyone else to claim the blob.
+pub fn possession_proof(principal: Principal, bytes: &[u8]) -> Hash {
+    possession_proof_from_chunks(principal, [bytes].into_iter())
+}
+
+// Calculates the same proof as `possession_proof` while only holding one chunk of the blob in memory
+// at a time
+pub fn possession_proof_from_chunks<T: AsRef<[u8]>>(principal: Principal, chunks: impl Iterator<Item = T>) -> Hash {
+    let mut hasher = Sha3_256::new();
+    hasher.update(principal.as_slice());
+    for chunk in chunks {
+        hasher.update(chunk.as_ref());
+    }
+    hasher.finalize().into()
+}
+
 pub fn hash_stream<'s>(stream: impl Iterator<Item = &'s [u8]>) -> Hash {
     let mut hasher = Sha3_256::new();
     for chunk in stream {
@@ -10,6 +10,21 @@ const MAX_GROUP_DESCRIPTION_LENGTH: u32 = 1024;
 const MAX_GROUP_RULES_LENGTH: u32 = 1024;
 const MIN_USER_GROUP_NAME_LENGTH: u32 = 3;
 const MAX_USER_GROUP_NAME_LENGTH: u32 = 25;
+const MIN_CUSTOM_ROLE_NAME_LENGTH: u32 = 3;
+const MAX_CUSTOM_ROLE_NAME_LENGTH: u32 = 25;
+const MIN_CHANNEL_CATEGORY_NAME_LENGTH: u32 = 1;
+const MAX_CHANNEL_CATEGORY_NAME_LENGTH: u32 = 30;
+
+const RESERVED_ROLE_NAMES: [&str; 8] = [
+    "owner",
+    "owners",
+    "admin",
+    "admins",
+    "moderator",
+    "moderators",
+    "member",
+    "members",
+];
 
 const RESERVED_GROUP_NAMES: [&str; 8] = [
     "channel",
@@ -123,6 +138,37 @@ pub fn validate_user_group_name(name: &str) -> Result<(), UsernameValidationErro
     }
 }
 
+pub fn validate_custom_role_name(name: &str) -> Result<(), UsernameValidationError> {
+    match validate_string_length(name, MIN_CUSTOM_ROLE_NAME_LENGTH, MAX_CUSTOM_ROLE_NAME_LENGTH) {
+        Ok(()) => {
+            if has_invalid_spacing(name) || RESERVED_ROLE_NAMES.contains(&name.to_lowercase().as_str()) {
+                Err(UsernameValidationError::Invalid)
+            } else {
+                Ok(())
+            }
+        }
+        Err(StringLengthValidationError::TooShort(s)) => Err(UsernameValidationError::TooShort(s)),
+        Err(StringLengthValidationError::TooLong(l)) => Err(UsernameValidationError::TooLong(l)),
+    }
+}
+
+pub fn validate_channel_category_name(name: &str) -> Result<(), UsernameValidationError> {
+    match validate_string_length(name, MIN_CHANNEL_CATEGORY_NAME_LENGTH, MAX_CHANNEL_CATEGORY_NAME_LENGTH) {
+        Ok(()) if has_invalid_spacing(name) => Err(UsernameValidationError::Invalid),
+        Ok(()) => Ok(()),
+        Err(StringLengthValidationError::TooShort(s)) => Err(UsernameValidationError::TooShort(s)),
+        Err(StringLengthValidationError::TooLong(l)) => Err(UsernameValidationError::TooLong(l)),
+    }
+}
+
+// Names may contain single spaces between words but no other whitespace
+fn has_invalid_spacing(name: &str) -> bool {
+    name.starts_with(' ')
+        || name.ends_with(' ')
+        || name.contains(|c: char| c.is_ascii_whitespace() && c != ' ')
+        || name.contains("  ")
+}
+
 pub fn validate_description(description: &str) -> Result<(), FieldTooLongResult> {
     validate_string_length(description, 0, MAX_GROUP_DESCRIPTION_LENGTH).map_err(|e| match e {
         StringLengthValidationError::TooLong(f) => f,
@@ -251,4 +297,27 @@ mod tests {
         assert!(validate_user_group_name("The_fox_jumps_over_John_Smith").is_err());
         assert!(validate_user_group_name("John Smith").is_err());
     }
+
+    #[test]
+    fn valid_custom_role_names() {
+        assert!(validate_custom_role_name("Event host").is_ok());
+        assert!(validate_custom_role_name("Translator").is_ok());
+    }
+
+    #[test]
+    fn invalid_custom_role_names() {
+        assert!(validate_custom_role_name("EH").is_err());
+        assert!(validate_custom_role_name(" Event host").is_err());
+        assert!(validate_custom_role_name("Event  host").is_err());
+        assert!(validate_custom_role_name("Admins").is_err());
+    }
+
+    #[test]
+    fn channel_category_names() {
+        assert!(validate_channel_category_name("Governance").is_ok());
+        assert!(validate_channel_category_name("Off topic").is_ok());
+        assert!(validate_channel_category_name("").is_err());
+        assert!(validate_channel_category_name("Off topic ").is_err());
+        assert!(validate_channel_category_name("Off\ttopic").is_err());
+    }
 }
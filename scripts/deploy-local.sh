@@ -4,6 +4,7 @@
 
 IDENTITY=${1:-default}
 WASM_SRC=${2:-latest} # WASM_SRC is either empty, "build", "latest", "local", prod" or the commit Id
+SEED=$3 # If set, the canisters are seeded with fixture data generated from this seed
 
 SCRIPT=$(readlink -f "$0")
 SCRIPT_DIR=$(dirname "$SCRIPT")
@@ -46,3 +47,4 @@ dfx --identity $IDENTITY canister create --no-wallet --with-cycles 1000000000000
     $NNS_CMC_CANISTER_ID \
     $NNS_SNS_WASM_CANISTER_ID \
     true \
+    ${SEED:+--skip-internet-identity-check --seed $SEED}
@@ -14,6 +14,7 @@ NNS_LEDGER_CANISTER_ID=$8
 NNS_CMC_CANISTER_ID=$9
 NNS_SNS_WASM_CANISTER_ID=${10}
 TEST_MODE=${11}
+shift 11 # Any remaining args are passed through to the canister installer (eg. "--seed 42")
 
 SCRIPT=$(readlink -f "$0")
 SCRIPT_DIR=$(dirname "$SCRIPT")
@@ -64,4 +65,5 @@ cargo run \
   --nns-internet-identity $NNS_INTERNET_IDENTITY_CANISTER_ID \
   --nns-ledger $NNS_LEDGER_CANISTER_ID \
   --nns-cmc $NNS_CMC_CANISTER_ID \
-  --nns-sns-wasm $NNS_SNS_WASM_CANISTER_ID
\ No newline at end of file
+  --nns-sns-wasm $NNS_SNS_WASM_CANISTER_ID \
+  "$@"
\ No newline at end of file
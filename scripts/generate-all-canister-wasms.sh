@@ -12,6 +12,7 @@ cd $SCRIPT_DIR/..
 ./scripts/generate-wasm.sh local_group_index
 ./scripts/generate-wasm.sh local_user_index
 ./scripts/generate-wasm.sh market_maker
+./scripts/generate-wasm.sh mock_governance
 ./scripts/generate-wasm.sh notifications
 ./scripts/generate-wasm.sh notifications_index
 ./scripts/generate-wasm.sh online_users
@@ -33,7 +33,7 @@ if [ "$FUNCTION_ID" -ge "1000" ] ; then
 
     # Build the proposal file
     cd $PROPOSAL_BUILDER_FOLDER
-    cargo run --quiet -- --title "$TITLE" --summary "$SUMMARY" --url "$URL" --function-id $FUNCTION_ID --wasm-path "$WASM_PATH" --version $VERSION > $PROPOSAL_FILE
+    cargo run --quiet -- upgrade --title "$TITLE" --summary "$SUMMARY" --url "$URL" --function-id $FUNCTION_ID --wasm-path "$WASM_PATH" --version $VERSION > $PROPOSAL_FILE
 
     # cd back into root of OpenChat repo
     cd $SCRIPT_DIR/..
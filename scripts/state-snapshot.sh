@@ -0,0 +1,24 @@
+#!/bin/sh
+
+# Pass in network name, IC url, identity name, then either "export" or "restore" followed by its args
+# eg './state-snapshot.sh local http://127.0.0.1:8080/ openchat export --canister-id <id> --output group.bin'
+# eg './state-snapshot.sh local http://127.0.0.1:8080/ openchat restore --canister-id <id> --snapshot group.bin'
+
+NETWORK=$1
+IC_URL=$2
+IDENTITY=$3
+shift 3
+
+SCRIPT=$(readlink -f "$0")
+SCRIPT_DIR=$(dirname "$SCRIPT")
+cd $SCRIPT_DIR/..
+
+GROUP_INDEX_CANISTER_ID=$(dfx canister --network $NETWORK id group_index)
+
+cargo run \
+  --manifest-path backend/canister_installer/Cargo.toml \
+  --bin state_snapshot -- \
+  --url $IC_URL \
+  --controller $IDENTITY \
+  --group-index $GROUP_INDEX_CANISTER_ID \
+  "$@"